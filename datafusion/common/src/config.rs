@@ -235,6 +235,12 @@ config_namespace! {
         ///
         /// Defaults to the number of CPU cores on the system
         pub planning_concurrency: usize, default = num_cpus::get()
+
+        /// Maximum number of times the recursive term of a recursive common
+        /// table expression (`WITH RECURSIVE`) is evaluated. A query that
+        /// needs more iterations fails, which guards against recursive
+        /// queries that never terminate
+        pub max_recursive_iterations: usize, default = 1000
     }
 }
 
@@ -270,6 +276,52 @@ config_namespace! {
         /// will be reordered heuristically to minimize the cost of evaluation. If false,
         /// the filters are applied in the same order as written in the query
         pub reorder_filters: bool, default = false
+
+        /// If true, the parquet reader uses the bloom filters stored in the parquet
+        /// file, if any, to skip entire row groups that cannot contain the values of
+        /// `col = literal` and `col IN (...)` predicates in the query
+        pub bloom_filter_on_read: bool, default = true
+
+        // The following map to parquet::file::properties::WriterProperties
+
+        /// Sets best effort maximum size of data page in bytes
+        pub data_pagesize_limit: usize, default = 1024 * 1024
+
+        /// Sets write_batch_size in bytes
+        pub write_batch_size: usize, default = 1024
+
+        /// Sets parquet writer version
+        /// valid values are "1.0" and "2.0"
+        pub writer_version: String, default = "1.0".into()
+
+        /// Sets default parquet compression codec
+        /// Valid values are: uncompressed, snappy, gzip(level),
+        /// lzo, brotli(level), lz4, zstd(level), and lz4_raw.
+        /// These values are not case sensitive.
+        pub compression: String, default = "zstd(3)".into()
+
+        /// Sets if dictionary encoding is enabled
+        pub dictionary_enabled: bool, default = true
+
+        /// Sets best effort maximum dictionary page size, in bytes
+        pub dictionary_page_size_limit: usize, default = 1024 * 1024
+
+        /// Sets if statistics are enabled for any column
+        /// Valid values are: "none", "chunk", and "page"
+        /// These values are not case sensitive.
+        pub statistics_enabled: String, default = "page".into()
+
+        /// Sets max statistics size for any column
+        pub max_statistics_size: usize, default = 4096
+
+        /// Sets maximum number of rows in a row group
+        pub max_row_group_size: usize, default = 1024 * 1024
+
+        /// Sets "created by" property
+        pub created_by: String, default = concat!("datafusion version ", env!("CARGO_PKG_VERSION")).into()
+
+        /// Sets best effort maximum number of rows in data page
+        pub data_page_row_count_limit: usize, default = usize::MAX
     }
 }
 
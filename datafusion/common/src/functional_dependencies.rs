@@ -110,6 +110,30 @@ impl Constraints {
     pub fn is_empty(&self) -> bool {
         self.inner.is_empty()
     }
+
+    /// Projects the constraints onto the columns with the given indices,
+    /// renumbering the columns by their position in `proj_indices`.
+    /// Returns `None` if a constraint refers to a column that is not
+    /// projected.
+    pub fn project(&self, proj_indices: &[usize]) -> Option<Self> {
+        let project = |indices: &Vec<usize>| {
+            indices
+                .iter()
+                .map(|idx| proj_indices.iter().position(|proj_idx| proj_idx == idx))
+                .collect::<Option<Vec<_>>>()
+        };
+        let constraints = self
+            .inner
+            .iter()
+            .map(|constraint| match constraint {
+                Constraint::PrimaryKey(indices) => {
+                    project(indices).map(Constraint::PrimaryKey)
+                }
+                Constraint::Unique(indices) => project(indices).map(Constraint::Unique),
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Constraints::new(constraints))
+    }
 }
 
 impl Display for Constraints {
@@ -343,6 +367,16 @@ impl FunctionalDependencies {
                 left_func_dependencies.extend(right_func_dependencies);
                 left_func_dependencies
             }
+            JoinType::AsOf => {
+                // Every left row appears exactly once, but a right row may be
+                // matched many times or not at all (padded with NULL values):
+                right_func_dependencies.add_offset(left_cols_len);
+                right_func_dependencies =
+                    right_func_dependencies.with_dependency(Dependency::Multi);
+                right_func_dependencies.downgrade_dependencies();
+                left_func_dependencies.extend(right_func_dependencies);
+                left_func_dependencies
+            }
             JoinType::LeftSemi | JoinType::LeftAnti => {
                 // These joins preserve functional dependencies of the left side:
                 left_func_dependencies
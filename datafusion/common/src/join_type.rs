@@ -43,6 +43,10 @@ pub enum JoinType {
     LeftAnti,
     /// Right Anti Join
     RightAnti,
+    /// ASOF Join: every left row is joined with at most one right row, the
+    /// closest one satisfying the match condition of the join filter. Left
+    /// rows without such a match are padded with nulls, as in a left join.
+    AsOf,
 }
 
 impl JoinType {
@@ -62,6 +66,7 @@ impl Display for JoinType {
             JoinType::RightSemi => "RightSemi",
             JoinType::LeftAnti => "LeftAnti",
             JoinType::RightAnti => "RightAnti",
+            JoinType::AsOf => "AsOf",
         };
         write!(f, "{join_type}")
     }
@@ -81,6 +86,7 @@ impl FromStr for JoinType {
             "RIGHTSEMI" => Ok(JoinType::RightSemi),
             "LEFTANTI" => Ok(JoinType::LeftAnti),
             "RIGHTANTI" => Ok(JoinType::RightAnti),
+            "ASOF" => Ok(JoinType::AsOf),
             _ => Err(DataFusionError::NotImplemented(format!(
                 "The join type {s} does not exist or is not implemented"
             ))),
@@ -27,11 +27,13 @@ use std::{convert::TryFrom, fmt, iter::repeat, sync::Arc};
 
 use crate::cast::{
     as_decimal128_array, as_decimal256_array, as_dictionary_array,
-    as_fixed_size_binary_array, as_fixed_size_list_array, as_list_array, as_struct_array,
+    as_fixed_size_binary_array, as_fixed_size_list_array, as_list_array, as_map_array,
+    as_struct_array,
 };
 use crate::delta::shift_months;
 use crate::error::{DataFusionError, Result};
-use arrow::buffer::NullBuffer;
+use crate::utils::map_entries_field;
+use arrow::buffer::{NullBuffer, OffsetBuffer};
 use arrow::compute::nullif;
 use arrow::datatypes::{i256, FieldRef, Fields, SchemaBuilder};
 use arrow::{
@@ -108,6 +110,9 @@ pub enum ScalarValue {
     Fixedsizelist(Option<Vec<ScalarValue>>, FieldRef, i32),
     /// List of nested ScalarValue
     List(Option<Vec<ScalarValue>>, FieldRef),
+    /// Map of key and value pairs, with the field of its entries and whether
+    /// its keys are sorted
+    Map(Option<Vec<(ScalarValue, ScalarValue)>>, FieldRef, bool),
     /// Date stored as a signed 32bit int days since UNIX epoch 1970-01-01
     Date32(Option<i32>),
     /// Date stored as a signed 64bit int milliseconds since UNIX epoch 1970-01-01
@@ -211,6 +216,8 @@ impl PartialEq for ScalarValue {
             (Fixedsizelist(_, _, _), _) => false,
             (List(v1, t1), List(v2, t2)) => v1.eq(v2) && t1.eq(t2),
             (List(_, _), _) => false,
+            (Map(v1, t1, s1), Map(v2, t2, s2)) => v1.eq(v2) && t1.eq(t2) && s1.eq(s2),
+            (Map(_, _, _), _) => false,
             (Date32(v1), Date32(v2)) => v1.eq(v2),
             (Date32(_), _) => false,
             (Date64(v1), Date64(v2)) => v1.eq(v2),
@@ -353,6 +360,14 @@ impl PartialOrd for ScalarValue {
                 }
             }
             (List(_, _), _) => None,
+            (Map(v1, t1, s1), Map(v2, t2, s2)) => {
+                if t1.eq(t2) && s1.eq(s2) {
+                    v1.partial_cmp(v2)
+                } else {
+                    None
+                }
+            }
+            (Map(_, _, _), _) => None,
             (Date32(v1), Date32(v2)) => v1.partial_cmp(v2),
             (Date32(_), _) => None,
             (Date64(v1), Date64(v2)) => v1.partial_cmp(v2),
@@ -1563,6 +1578,11 @@ impl std::hash::Hash for ScalarValue {
                 v.hash(state);
                 t.hash(state);
             }
+            Map(v, t, sorted) => {
+                v.hash(state);
+                t.hash(state);
+                sorted.hash(state);
+            }
             Date32(v) => v.hash(state),
             Date64(v) => v.hash(state),
             Time32Second(v) => v.hash(state),
@@ -1896,6 +1916,16 @@ impl ScalarValue {
         Self::List(scalars, Arc::new(Field::new("item", child_type, true)))
     }
 
+    /// Create a new nullable ScalarValue::Map with the specified key and value
+    /// types, whose keys are not sorted
+    pub fn new_map(
+        entries: Option<Vec<(Self, Self)>>,
+        key_type: DataType,
+        value_type: DataType,
+    ) -> Self {
+        Self::Map(entries, map_entries_field(key_type, value_type), false)
+    }
+
     /// Create a zero value in the given type.
     pub fn new_zero(datatype: &DataType) -> Result<ScalarValue> {
         assert!(datatype.is_primitive());
@@ -2070,6 +2100,7 @@ impl ScalarValue {
             ScalarValue::DurationNanosecond(_) => {
                 DataType::Duration(TimeUnit::Nanosecond)
             }
+            ScalarValue::Map(_, field, sorted) => DataType::Map(field.clone(), *sorted),
             ScalarValue::Struct(_, fields) => DataType::Struct(fields.clone()),
             ScalarValue::Dictionary(k, v) => {
                 DataType::Dictionary(k.clone(), Box::new(v.get_datatype()))
@@ -2198,6 +2229,7 @@ impl ScalarValue {
             ScalarValue::LargeBinary(v) => v.is_none(),
             ScalarValue::Fixedsizelist(v, ..) => v.is_none(),
             ScalarValue::List(v, _) => v.is_none(),
+            ScalarValue::Map(v, _, _) => v.is_none(),
             ScalarValue::Date32(v) => v.is_none(),
             ScalarValue::Date64(v) => v.is_none(),
             ScalarValue::Time32Second(v) => v.is_none(),
@@ -2553,6 +2585,9 @@ impl ScalarValue {
                 let list_array = ScalarValue::iter_to_array_list(scalars, &data_type)?;
                 Arc::new(list_array)
             }
+            DataType::Map(_, _) => {
+                Arc::new(ScalarValue::iter_to_map_array(scalars, &data_type)?)
+            }
             DataType::Struct(fields) => {
                 // Initialize a Vector to store the ScalarValues for each column
                 let mut columns: Vec<Vec<ScalarValue>> =
@@ -2669,7 +2704,6 @@ impl ScalarValue {
             | DataType::FixedSizeList(_, _)
             | DataType::LargeList(_)
             | DataType::Union(_, _)
-            | DataType::Map(_, _)
             | DataType::RunEndEncoded(_, _) => {
                 return Err(DataFusionError::Internal(format!(
                     "Unsupported creation of {:?} array from ScalarValue {:?}",
@@ -2725,6 +2759,71 @@ impl ScalarValue {
         Ok(array)
     }
 
+    /// This function builds a `MapArray` from the entries of `ScalarValue::Map`s
+    fn iter_to_map_array(
+        scalars: impl IntoIterator<Item = ScalarValue>,
+        data_type: &DataType,
+    ) -> Result<MapArray> {
+        let (entries_field, sorted) = match data_type {
+            DataType::Map(field, sorted) => (field, *sorted),
+            _ => {
+                return Err(DataFusionError::Internal(format!(
+                    "Expected Map data type. Received {data_type:?}"
+                )))
+            }
+        };
+        let entry_fields = match entries_field.data_type() {
+            DataType::Struct(fields) if fields.len() == 2 => fields,
+            other => {
+                return Err(DataFusionError::Internal(format!(
+                    "Expected Map entries to be a struct of key and value. Received {other:?}"
+                )))
+            }
+        };
+
+        let mut offsets = vec![0i32];
+        let mut keys = vec![];
+        let mut values = vec![];
+        let mut valid = BooleanBufferBuilder::new(0);
+        for scalar in scalars {
+            if let ScalarValue::Map(entries, _, _) = scalar {
+                valid.append(entries.is_some());
+                for (key, value) in entries.into_iter().flatten() {
+                    keys.push(key);
+                    values.push(value);
+                }
+                offsets.push(keys.len() as i32);
+            } else {
+                return Err(DataFusionError::Internal(format!(
+                    "Expected ScalarValue::Map element. Received {scalar:?}"
+                )));
+            }
+        }
+
+        let to_array = |scalars: Vec<ScalarValue>, field: &FieldRef| {
+            if scalars.is_empty() {
+                Ok(new_empty_array(field.data_type()))
+            } else {
+                ScalarValue::iter_to_array(scalars)
+            }
+        };
+        let entries = StructArray::try_new(
+            entry_fields.clone(),
+            vec![
+                to_array(keys, &entry_fields[0])?,
+                to_array(values, &entry_fields[1])?,
+            ],
+            None,
+        )?;
+        Ok(MapArray::try_new(
+            entries_field.clone(),
+            OffsetBuffer::new(offsets.into()),
+            entries,
+            Some(NullBuffer::new(valid.finish())),
+            sorted,
+        )?)
+    }
+
     fn iter_to_array_list(
         scalars: impl IntoIterator<Item = ScalarValue>,
         data_type: &DataType,
@@ -2769,12 +2868,15 @@ impl ScalarValue {
             }
         }
 
-        // Concatenate element arrays to create single flat array
-        let element_arrays: Vec<&dyn Array> =
-            elements.iter().map(|a| a.as_ref()).collect();
-        let flat_array = match arrow::compute::concat(&element_arrays) {
-            Ok(flat_array) => flat_array,
-            Err(err) => return Err(DataFusionError::ArrowError(err)),
+        // Concatenate element arrays to create single flat array, which is
+        // empty if all the lists are null
+        let flat_array = match (elements.is_empty(), data_type) {
+            (true, DataType::List(field)) => new_empty_array(field.data_type()),
+            _ => {
+                let element_arrays: Vec<&dyn Array> =
+                    elements.iter().map(|a| a.as_ref()).collect();
+                arrow::compute::concat(&element_arrays)?
+            }
         };
 
         // Build ListArray using ArrayData so we can specify a flat inner array, and offset indices
@@ -2973,6 +3075,13 @@ impl ScalarValue {
                 )
                 .unwrap(),
             }),
+            ScalarValue::Map(..) => Arc::new(
+                ScalarValue::iter_to_map_array(
+                    repeat(self.clone()).take(size),
+                    &self.get_datatype(),
+                )
+                .unwrap(),
+            ),
             ScalarValue::Date32(e) => {
                 build_array_from_option!(Date32, Date32Array, e, size)
             }
@@ -3257,6 +3366,25 @@ impl ScalarValue {
 
                 Self::Dictionary(key_type.clone(), Box::new(value))
             }
+            DataType::Map(field, sorted) => {
+                let map_array = as_map_array(array)?;
+                let value = match map_array.is_null(index) {
+                    true => None,
+                    false => {
+                        let entries = map_array.value(index);
+                        let entries = (0..entries.len())
+                            .map(|i| {
+                                Ok((
+                                    ScalarValue::try_from_array(entries.column(0), i)?,
+                                    ScalarValue::try_from_array(entries.column(1), i)?,
+                                ))
+                            })
+                            .collect::<Result<Vec<_>>>()?;
+                        Some(entries)
+                    }
+                };
+                ScalarValue::Map(value, field.clone(), *sorted)
+            }
             DataType::Struct(fields) => {
                 let array = as_struct_array(array)?;
                 let mut field_values: Vec<ScalarValue> = Vec::new();
@@ -3443,6 +3571,26 @@ impl ScalarValue {
             }
             ScalarValue::Fixedsizelist(..) => unimplemented!(),
             ScalarValue::List(_, _) => unimplemented!(),
+            ScalarValue::Map(entries, field, sorted) => {
+                if array.data_type() != &DataType::Map(field.clone(), *sorted) {
+                    return false;
+                }
+                let array = as_map_array(array).unwrap();
+                match entries {
+                    Some(entries) => {
+                        if array.is_null(index) {
+                            return false;
+                        }
+                        let array_entries = array.value(index);
+                        array_entries.len() == entries.len()
+                            && entries.iter().enumerate().all(|(i, (key, value))| {
+                                key.eq_array(array_entries.column(0), i)
+                                    && value.eq_array(array_entries.column(1), i)
+                            })
+                    }
+                    None => array.is_null(index),
+                }
+            }
             ScalarValue::Date32(val) => {
                 eq_array_primitive!(array, index, Date32Array, val)
             }
@@ -3571,6 +3719,21 @@ impl ScalarValue {
                         // `field` is boxed, so it is NOT already included in `self`
                         + field.size()
                 }
+                ScalarValue::Map(entries, field, _) => {
+                    entries
+                        .as_ref()
+                        .map(|entries| {
+                            entries
+                                .iter()
+                                .map(|(key, value)| key.size() + value.size())
+                                .sum::<usize>()
+                                + (std::mem::size_of::<(ScalarValue, ScalarValue)>()
+                                    * (entries.capacity() - entries.len()))
+                        })
+                        .unwrap_or_default()
+                        // `field` is boxed, so it is NOT already included in `self`
+                        + field.size()
+                }
                 ScalarValue::Struct(vals, fields) => {
                     vals.as_ref()
                         .map(|vals| {
@@ -3858,6 +4021,9 @@ impl TryFrom<&DataType> for ScalarValue {
             DataType::List(ref nested_type) => {
                 ScalarValue::new_list(None, nested_type.data_type().clone())
             }
+            DataType::Map(field, sorted) => {
+                ScalarValue::Map(None, field.clone(), *sorted)
+            }
             DataType::Struct(fields) => ScalarValue::Struct(None, fields.clone()),
             DataType::Null => ScalarValue::Null,
             _ => {
@@ -3928,6 +4094,17 @@ impl fmt::Display for ScalarValue {
                 )?,
                 None => write!(f, "NULL")?,
             },
+            ScalarValue::Map(e, _, _) => match e {
+                Some(l) => write!(
+                    f,
+                    "{{{}}}",
+                    l.iter()
+                        .map(|(key, value)| format!("{key}:{value}"))
+                        .collect::<Vec<_>>()
+                        .join(",")
+                )?,
+                None => write!(f, "NULL")?,
+            },
             ScalarValue::Date32(e) => format_option!(f, e)?,
             ScalarValue::Date64(e) => format_option!(f, e)?,
             ScalarValue::Time32Second(e) => format_option!(f, e)?,
@@ -4004,6 +4181,20 @@ impl fmt::Debug for ScalarValue {
             ScalarValue::LargeBinary(Some(_)) => write!(f, "LargeBinary(\"{self}\")"),
             ScalarValue::Fixedsizelist(..) => write!(f, "FixedSizeList([{self}])"),
             ScalarValue::List(_, _) => write!(f, "List([{self}])"),
+            ScalarValue::Map(e, _, _) => {
+                // Use Debug representation of keys and values
+                match e {
+                    Some(l) => write!(
+                        f,
+                        "Map({{{}}})",
+                        l.iter()
+                            .map(|(key, value)| format!("{key:?}:{value:?}"))
+                            .collect::<Vec<_>>()
+                            .join(",")
+                    ),
+                    None => write!(f, "Map(NULL)"),
+                }
+            }
             ScalarValue::Date32(_) => write!(f, "Date32(\"{self}\")"),
             ScalarValue::Date64(_) => write!(f, "Date64(\"{self}\")"),
             ScalarValue::Time32Second(_) => write!(f, "Time32Second(\"{self}\")"),
@@ -4397,6 +4588,23 @@ mod tests {
         assert_eq!(list_array.values().len(), 0);
     }
 
+    #[test]
+    fn scalar_decimal_list_null_to_array() {
+        let list_array_ref = ScalarValue::List(
+            None,
+            Arc::new(Field::new("item", DataType::Decimal128(20, 2), true)),
+        )
+        .to_array_of_size(2);
+        let list_array = as_list_array(&list_array_ref).unwrap();
+
+        assert_eq!(list_array.null_count(), 2);
+        assert_eq!(list_array.len(), 2);
+        assert_eq!(
+            list_array.values().data_type(),
+            &DataType::Decimal128(20, 2)
+        );
+    }
+
     #[test]
     fn scalar_list_to_array() -> Result<()> {
         let list_array_ref = ScalarValue::List(
@@ -5476,6 +5684,55 @@ mod tests {
         assert_eq!(array, &expected);
     }
 
+    #[test]
+    fn test_scalar_map() {
+        let m1 = ScalarValue::new_map(
+            Some(vec![
+                (ScalarValue::from("a"), ScalarValue::from(1i32)),
+                (ScalarValue::from("b"), ScalarValue::Int32(None)),
+            ]),
+            DataType::Utf8,
+            DataType::Int32,
+        );
+        let m2 = ScalarValue::new_map(None, DataType::Utf8, DataType::Int32);
+        let m3 = ScalarValue::new_map(Some(vec![]), DataType::Utf8, DataType::Int32);
+
+        assert_eq!(m1.to_string(), "{a:1,b:NULL}");
+        assert_eq!(
+            format!("{m1:?}"),
+            "Map({Utf8(\"a\"):Int32(1),Utf8(\"b\"):Int32(NULL)})"
+        );
+        assert!(m2.is_null());
+        assert_eq!(m3.to_string(), "{}");
+
+        let data_type = m1.get_datatype();
+        assert_eq!(data_type, m2.get_datatype());
+        assert_eq!(ScalarValue::try_from(&data_type).unwrap(), m2);
+
+        let array =
+            ScalarValue::iter_to_array(vec![m1.clone(), m2.clone(), m3.clone()]).unwrap();
+        assert_eq!(array.data_type(), &data_type);
+        let map_array = as_map_array(&array).unwrap();
+        assert_eq!(map_array.value_offsets(), &[0, 2, 2, 2]);
+        assert!(map_array.is_null(1));
+
+        for (i, expected) in [&m1, &m2, &m3].into_iter().enumerate() {
+            assert_eq!(&ScalarValue::try_from_array(&array, i).unwrap(), expected);
+        }
+
+        for (i, scalar) in [&m1, &m2, &m3].into_iter().enumerate() {
+            for j in 0..array.len() {
+                assert_eq!(scalar.eq_array(&array, j), i == j);
+            }
+        }
+        let other_type = ScalarValue::new_map(None, DataType::Utf8, DataType::Int64);
+        assert!(!other_type.eq_array(&array, 1));
+
+        let array = m1.to_array_of_size(2);
+        assert_eq!(array.len(), 2);
+        assert_eq!(ScalarValue::try_from_array(&array, 1).unwrap(), m1);
+    }
+
     #[test]
     fn scalar_timestamp_ns_utc_timezone() {
         let scalar = ScalarValue::TimestampNanosecond(
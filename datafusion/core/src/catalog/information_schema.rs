@@ -24,14 +24,14 @@ use std::{any::Any, sync::Arc};
 
 use arrow::{
     array::{StringBuilder, UInt64Builder},
-    datatypes::{DataType, Field, Schema, SchemaRef},
+    datatypes::{DataType, Field, IntervalUnit, Schema, SchemaRef, TimeUnit},
     record_batch::RecordBatch,
 };
 
 use crate::datasource::streaming::StreamingTable;
 use crate::datasource::TableProvider;
 use crate::execution::context::TaskContext;
-use crate::logical_expr::TableType;
+use crate::logical_expr::{ScalarMacro, TableType};
 use crate::physical_plan::stream::RecordBatchStreamAdapter;
 use crate::physical_plan::SendableRecordBatchStream;
 use crate::{
@@ -46,9 +46,11 @@ pub(crate) const TABLES: &str = "tables";
 pub(crate) const VIEWS: &str = "views";
 pub(crate) const COLUMNS: &str = "columns";
 pub(crate) const DF_SETTINGS: &str = "df_settings";
+pub(crate) const ROUTINES: &str = "routines";
 
 /// All information schema tables
-pub const INFORMATION_SCHEMA_TABLES: &[&str] = &[TABLES, VIEWS, COLUMNS, DF_SETTINGS];
+pub const INFORMATION_SCHEMA_TABLES: &[&str] =
+    &[TABLES, VIEWS, COLUMNS, DF_SETTINGS, ROUTINES];
 
 /// Implements the `information_schema` virtual schema and tables
 ///
@@ -64,14 +66,29 @@ impl InformationSchemaProvider {
     /// Creates a new [`InformationSchemaProvider`] for the provided `catalog_list`
     pub fn new(catalog_list: Arc<dyn CatalogList>) -> Self {
         Self {
-            config: InformationSchemaConfig { catalog_list },
+            config: InformationSchemaConfig {
+                catalog_list,
+                scalar_macros: vec![],
+            },
         }
     }
+
+    /// Lists `scalar_macros` in the `information_schema.routines` table
+    pub fn with_scalar_macros(
+        mut self,
+        scalar_macros: impl IntoIterator<Item = Arc<ScalarMacro>>,
+    ) -> Self {
+        let mut scalar_macros = scalar_macros.into_iter().collect::<Vec<_>>();
+        scalar_macros.sort_by(|a, b| a.name.cmp(&b.name));
+        self.config.scalar_macros = scalar_macros;
+        self
+    }
 }
 
 #[derive(Clone)]
 struct InformationSchemaConfig {
     catalog_list: Arc<dyn CatalogList>,
+    scalar_macros: Vec<Arc<ScalarMacro>>,
 }
 
 impl InformationSchemaConfig {
@@ -115,6 +132,12 @@ impl InformationSchemaConfig {
                 DF_SETTINGS,
                 TableType::View,
             );
+            builder.add_table(
+                &catalog_name,
+                INFORMATION_SCHEMA,
+                ROUTINES,
+                TableType::View,
+            );
         }
     }
 
@@ -182,6 +205,22 @@ impl InformationSchemaConfig {
             builder.add_setting(entry);
         }
     }
+
+    /// Construct the `information_schema.routines` virtual table
+    ///
+    /// The functions belong to the session rather than to a schema, and are
+    /// listed in the default schema
+    fn make_routines(
+        &self,
+        config_options: &ConfigOptions,
+        builder: &mut InformationSchemaRoutinesBuilder,
+    ) {
+        let catalog_name = &config_options.catalog.default_catalog;
+        let schema_name = &config_options.catalog.default_schema;
+        for scalar_macro in &self.scalar_macros {
+            builder.add_routine(catalog_name, schema_name, scalar_macro);
+        }
+    }
 }
 
 #[async_trait]
@@ -196,6 +235,7 @@ impl SchemaProvider for InformationSchemaProvider {
             VIEWS.to_string(),
             COLUMNS.to_string(),
             DF_SETTINGS.to_string(),
+            ROUTINES.to_string(),
         ]
     }
 
@@ -209,6 +249,8 @@ impl SchemaProvider for InformationSchemaProvider {
             Arc::new(InformationSchemaViews::new(config))
         } else if name.eq_ignore_ascii_case("df_settings") {
             Arc::new(InformationSchemaDfSettings::new(config))
+        } else if name.eq_ignore_ascii_case("routines") {
+            Arc::new(InformationSchemaRoutines::new(config))
         } else {
             return None;
         };
@@ -219,7 +261,10 @@ impl SchemaProvider for InformationSchemaProvider {
     }
 
     fn table_exist(&self, name: &str) -> bool {
-        matches!(name.to_ascii_lowercase().as_str(), TABLES | VIEWS | COLUMNS)
+        matches!(
+            name.to_ascii_lowercase().as_str(),
+            TABLES | VIEWS | COLUMNS | ROUTINES
+        )
     }
 }
 
@@ -684,3 +729,135 @@ impl InformationSchemaDfSettingsBuilder {
         .unwrap()
     }
 }
+
+struct InformationSchemaRoutines {
+    schema: SchemaRef,
+    config: InformationSchemaConfig,
+}
+
+impl InformationSchemaRoutines {
+    fn new(config: InformationSchemaConfig) -> Self {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("routine_catalog", DataType::Utf8, false),
+            Field::new("routine_schema", DataType::Utf8, false),
+            Field::new("routine_name", DataType::Utf8, false),
+            Field::new("routine_type", DataType::Utf8, false),
+            Field::new("data_type", DataType::Utf8, false),
+            Field::new("routine_body", DataType::Utf8, false),
+            Field::new("routine_definition", DataType::Utf8, true),
+        ]));
+
+        Self { schema, config }
+    }
+
+    fn builder(&self) -> InformationSchemaRoutinesBuilder {
+        InformationSchemaRoutinesBuilder {
+            catalog_names: StringBuilder::new(),
+            schema_names: StringBuilder::new(),
+            routine_names: StringBuilder::new(),
+            routine_types: StringBuilder::new(),
+            data_types: StringBuilder::new(),
+            routine_bodies: StringBuilder::new(),
+            definitions: StringBuilder::new(),
+            schema: self.schema.clone(),
+        }
+    }
+}
+
+impl PartitionStream for InformationSchemaRoutines {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        let config = self.config.clone();
+        let mut builder = self.builder();
+        Box::pin(RecordBatchStreamAdapter::new(
+            self.schema.clone(),
+            futures::stream::once(async move {
+                config.make_routines(ctx.session_config().options(), &mut builder);
+                Ok(builder.finish())
+            }),
+        ))
+    }
+}
+
+/// Returns the name of the SQL type that is planned as `data_type`, or the
+/// name of the arrow type if no SQL type is planned as it
+fn sql_type_name(data_type: &DataType) -> String {
+    match data_type {
+        DataType::Boolean => "BOOLEAN".to_owned(),
+        DataType::Int8 => "TINYINT".to_owned(),
+        DataType::Int16 => "SMALLINT".to_owned(),
+        DataType::Int32 => "INT".to_owned(),
+        DataType::Int64 => "BIGINT".to_owned(),
+        DataType::UInt8 => "TINYINT UNSIGNED".to_owned(),
+        DataType::UInt16 => "SMALLINT UNSIGNED".to_owned(),
+        DataType::UInt32 => "INT UNSIGNED".to_owned(),
+        DataType::UInt64 => "BIGINT UNSIGNED".to_owned(),
+        DataType::Float32 => "REAL".to_owned(),
+        DataType::Float64 => "DOUBLE".to_owned(),
+        DataType::Utf8 => "VARCHAR".to_owned(),
+        DataType::Binary => "BYTEA".to_owned(),
+        DataType::Date32 => "DATE".to_owned(),
+        DataType::Time64(TimeUnit::Nanosecond) => "TIME".to_owned(),
+        DataType::Timestamp(TimeUnit::Nanosecond, None) => "TIMESTAMP".to_owned(),
+        DataType::Timestamp(TimeUnit::Nanosecond, Some(_)) => {
+            "TIMESTAMP WITH TIME ZONE".to_owned()
+        }
+        DataType::Decimal128(precision, scale) => {
+            format!("DECIMAL({precision}, {scale})")
+        }
+        DataType::Interval(IntervalUnit::MonthDayNano) => "INTERVAL".to_owned(),
+        DataType::List(field) => format!("{}[]", sql_type_name(field.data_type())),
+        other => other.to_string(),
+    }
+}
+
+/// Builds the `information_schema.ROUTINES` table row by row
+///
+/// Columns are based on <https://www.postgresql.org/docs/current/infoschema-routines.html>
+struct InformationSchemaRoutinesBuilder {
+    schema: SchemaRef,
+    catalog_names: StringBuilder,
+    schema_names: StringBuilder,
+    routine_names: StringBuilder,
+    routine_types: StringBuilder,
+    data_types: StringBuilder,
+    routine_bodies: StringBuilder,
+    definitions: StringBuilder,
+}
+
+impl InformationSchemaRoutinesBuilder {
+    fn add_routine(
+        &mut self,
+        catalog_name: impl AsRef<str>,
+        schema_name: impl AsRef<str>,
+        scalar_macro: &ScalarMacro,
+    ) {
+        self.catalog_names.append_value(catalog_name.as_ref());
+        self.schema_names.append_value(schema_name.as_ref());
+        self.routine_names.append_value(&scalar_macro.name);
+        self.routine_types.append_value("FUNCTION");
+        self.data_types
+            .append_value(sql_type_name(&scalar_macro.return_type));
+        self.routine_bodies.append_value("SQL");
+        self.definitions.append_value(&scalar_macro.definition);
+    }
+
+    fn finish(&mut self) -> RecordBatch {
+        RecordBatch::try_new(
+            self.schema.clone(),
+            vec![
+                Arc::new(self.catalog_names.finish()),
+                Arc::new(self.schema_names.finish()),
+                Arc::new(self.routine_names.finish()),
+                Arc::new(self.routine_types.finish()),
+                Arc::new(self.data_types.finish()),
+                Arc::new(self.routine_bodies.finish()),
+                Arc::new(self.definitions.finish()),
+            ],
+        )
+        .unwrap()
+    }
+}
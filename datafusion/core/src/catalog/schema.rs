@@ -19,8 +19,10 @@
 //! representing collections of named tables.
 
 use async_trait::async_trait;
-use dashmap::DashMap;
+use parking_lot::RwLock;
 use std::any::Any;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::datasource::TableProvider;
@@ -61,6 +63,52 @@ pub trait SchemaProvider: Sync + Send {
         ))
     }
 
+    /// If supported by the implementation, replaces an existing table of this
+    /// schema, e.g. after its schema was changed by `ALTER TABLE`, and returns
+    /// the replaced table. If no table of that name exists, returns Ok(None)
+    /// without adding the table.
+    ///
+    /// The default implementation deregisters the existing table and registers
+    /// `table`, and registers the existing table again if that fails.
+    fn replace_table(
+        &self,
+        name: String,
+        table: Arc<dyn TableProvider>,
+    ) -> Result<Option<Arc<dyn TableProvider>>> {
+        let Some(old) = self.deregister_table(&name)? else {
+            return Ok(None);
+        };
+        match self.register_table(name.clone(), table) {
+            Ok(_) => Ok(Some(old)),
+            Err(e) => {
+                self.register_table(name, old)?;
+                Err(e)
+            }
+        }
+    }
+
+    /// If supported by the implementation, renames an existing table of this
+    /// schema. Returns an error if no table of that name exists, or if a table
+    /// with the new name already exists.
+    ///
+    /// The default implementation deregisters the table and registers it under
+    /// `new_name`, and registers it again under `name` if that fails.
+    /// Implementations should override it to rename tables atomically.
+    fn rename_table(&self, name: &str, new_name: String) -> Result<()> {
+        let Some(table) = self.deregister_table(name)? else {
+            return Err(DataFusionError::Execution(format!(
+                "Table '{name}' doesn't exist."
+            )));
+        };
+        match self.register_table(new_name, table.clone()) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                self.register_table(name.to_string(), table)?;
+                Err(e)
+            }
+        }
+    }
+
     /// If supported by the implementation, checks the table exist in the schema provider or not.
     /// If no matched table in the schema provider, return false.
     /// Otherwise, return true.
@@ -69,14 +117,14 @@ pub trait SchemaProvider: Sync + Send {
 
 /// Simple in-memory implementation of a schema.
 pub struct MemorySchemaProvider {
-    tables: DashMap<String, Arc<dyn TableProvider>>,
+    tables: RwLock<HashMap<String, Arc<dyn TableProvider>>>,
 }
 
 impl MemorySchemaProvider {
     /// Instantiates a new MemorySchemaProvider with an empty collection of tables.
     pub fn new() -> Self {
         Self {
-            tables: DashMap::new(),
+            tables: RwLock::new(HashMap::new()),
         }
     }
 }
@@ -94,14 +142,11 @@ impl SchemaProvider for MemorySchemaProvider {
     }
 
     fn table_names(&self) -> Vec<String> {
-        self.tables
-            .iter()
-            .map(|table| table.key().clone())
-            .collect()
+        self.tables.read().keys().cloned().collect()
     }
 
     async fn table(&self, name: &str) -> Option<Arc<dyn TableProvider>> {
-        self.tables.get(name).map(|table| table.value().clone())
+        self.tables.read().get(name).cloned()
     }
 
     fn register_table(
@@ -109,34 +154,68 @@ impl SchemaProvider for MemorySchemaProvider {
         name: String,
         table: Arc<dyn TableProvider>,
     ) -> Result<Option<Arc<dyn TableProvider>>> {
-        if self.table_exist(name.as_str()) {
-            return Err(DataFusionError::Execution(format!(
-                "The table {name} already exists"
-            )));
+        match self.tables.write().entry(name) {
+            Entry::Occupied(entry) => Err(DataFusionError::Execution(format!(
+                "The table {} already exists",
+                entry.key()
+            ))),
+            Entry::Vacant(entry) => {
+                entry.insert(table);
+                Ok(None)
+            }
         }
-        Ok(self.tables.insert(name, table))
     }
 
     fn deregister_table(&self, name: &str) -> Result<Option<Arc<dyn TableProvider>>> {
-        Ok(self.tables.remove(name).map(|(_, table)| table))
+        Ok(self.tables.write().remove(name))
+    }
+
+    fn replace_table(
+        &self,
+        name: String,
+        table: Arc<dyn TableProvider>,
+    ) -> Result<Option<Arc<dyn TableProvider>>> {
+        Ok(self
+            .tables
+            .write()
+            .get_mut(&name)
+            .map(|entry| std::mem::replace(entry, table)))
+    }
+
+    fn rename_table(&self, name: &str, new_name: String) -> Result<()> {
+        let mut tables = self.tables.write();
+        if tables.contains_key(&new_name) {
+            return Err(DataFusionError::Execution(format!(
+                "The table {new_name} already exists"
+            )));
+        }
+        let table = tables.remove(name).ok_or_else(|| {
+            DataFusionError::Execution(format!("Table '{name}' doesn't exist."))
+        })?;
+        tables.insert(new_name, table);
+        Ok(())
     }
 
     fn table_exist(&self, name: &str) -> bool {
-        self.tables.contains_key(name)
+        self.tables.read().contains_key(name)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::any::Any;
     use std::sync::Arc;
 
-    use arrow::datatypes::Schema;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use async_trait::async_trait;
 
     use crate::assert_batches_eq;
     use crate::catalog::schema::{MemorySchemaProvider, SchemaProvider};
     use crate::catalog::{CatalogProvider, MemoryCatalogProvider};
     use crate::datasource::empty::EmptyTable;
     use crate::datasource::listing::{ListingTable, ListingTableConfig, ListingTableUrl};
+    use crate::datasource::TableProvider;
+    use crate::error::{DataFusionError, Result};
     use crate::prelude::SessionContext;
 
     #[tokio::test]
@@ -158,6 +237,104 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_mem_provider_rename_table() {
+        let provider = MemorySchemaProvider::new();
+        let table = Arc::new(EmptyTable::new(Arc::new(Schema::empty())));
+        provider
+            .register_table("a".to_string(), table.clone())
+            .unwrap();
+        provider.register_table("b".to_string(), table).unwrap();
+
+        let err = provider.rename_table("a", "b".to_string()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: The table b already exists"
+        );
+        let err = provider.rename_table("c", "d".to_string()).unwrap_err();
+        assert_eq!(err.to_string(), "Execution error: Table 'c' doesn't exist.");
+
+        provider.rename_table("a", "c".to_string()).unwrap();
+        let mut names = provider.table_names();
+        names.sort();
+        assert_eq!(names, vec!["b", "c"]);
+    }
+
+    /// A schema provider that can't register the tables without columns, to
+    /// check the default implementations of the [`SchemaProvider`] methods
+    struct NoEmptyTablesSchemaProvider(MemorySchemaProvider);
+
+    #[async_trait]
+    impl SchemaProvider for NoEmptyTablesSchemaProvider {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn table_names(&self) -> Vec<String> {
+            self.0.table_names()
+        }
+
+        async fn table(&self, name: &str) -> Option<Arc<dyn TableProvider>> {
+            self.0.table(name).await
+        }
+
+        fn register_table(
+            &self,
+            name: String,
+            table: Arc<dyn TableProvider>,
+        ) -> Result<Option<Arc<dyn TableProvider>>> {
+            if table.schema().fields().is_empty() {
+                return Err(DataFusionError::Execution(format!(
+                    "The table {name} has no columns"
+                )));
+            }
+            self.0.register_table(name, table)
+        }
+
+        fn deregister_table(&self, name: &str) -> Result<Option<Arc<dyn TableProvider>>> {
+            self.0.deregister_table(name)
+        }
+
+        fn table_exist(&self, name: &str) -> bool {
+            self.0.table_exist(name)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_default_replace_and_rename_table_keep_table_on_failure() {
+        let provider = NoEmptyTablesSchemaProvider(MemorySchemaProvider::new());
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+        provider
+            .register_table("t".to_string(), Arc::new(EmptyTable::new(schema.clone())))
+            .unwrap();
+        provider
+            .register_table("u".to_string(), Arc::new(EmptyTable::new(schema.clone())))
+            .unwrap();
+
+        let empty_table = Arc::new(EmptyTable::new(Arc::new(Schema::empty())));
+        let err = provider
+            .replace_table("t".to_string(), empty_table)
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: The table t has no columns"
+        );
+        assert_eq!(provider.table("t").await.unwrap().schema(), schema);
+
+        let err = provider.rename_table("t", "u".to_string()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: The table u already exists"
+        );
+        assert!(provider.table_exist("t"));
+        assert!(provider.table_exist("u"));
+
+        provider.rename_table("t", "v".to_string()).unwrap();
+        assert!(!provider.table_exist("t"));
+        assert_eq!(provider.table("v").await.unwrap().schema(), schema);
+    }
+
     #[tokio::test]
     async fn test_schema_register_listing_table() {
         let testdata = crate::test_util::parquet_test_data();
@@ -30,7 +30,7 @@ use parquet::file::properties::WriterProperties;
 use datafusion_common::{Column, DFSchema, ScalarValue};
 use datafusion_expr::{
     avg, count, is_null, max, median, min, stddev, utils::COUNT_STAR_EXPANSION,
-    TableProviderFilterPushDown, UNNAMED_TABLE,
+    SampleMethod, SampleSize, TableProviderFilterPushDown, UNNAMED_TABLE,
 };
 
 use crate::arrow::datatypes::Schema;
@@ -218,6 +218,73 @@ impl DataFrame {
         Ok(DataFrame::new(self.session_state, plan))
     }
 
+    /// Rotate the values of `pivot_column` into columns, computing `aggr_expr`
+    /// for each of the `pivot_values` and grouping by the remaining columns.
+    ///
+    /// See [`LogicalPlanBuilder::pivot`] for details.
+    ///
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # use datafusion::scalar::ScalarValue;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.read_csv("tests/data/example.csv", CsvReadOptions::new()).await?;
+    ///
+    /// // The following use is the equivalent of
+    /// // "SELECT c, SUM(b) FILTER (WHERE a = 1) AS \"1\", SUM(b) FILTER (WHERE a = 2) AS \"2\" GROUP BY c"
+    /// let values = vec![ScalarValue::from(1i64), ScalarValue::from(2i64)];
+    /// let _ = df.pivot(sum(col("b")), "a", values)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pivot(
+        self,
+        aggr_expr: Expr,
+        pivot_column: &str,
+        pivot_values: Vec<ScalarValue>,
+    ) -> Result<DataFrame> {
+        let plan = LogicalPlanBuilder::from(self.plan)
+            .pivot(aggr_expr, pivot_column, pivot_values)?
+            .build()?;
+        Ok(DataFrame::new(self.session_state, plan))
+    }
+
+    /// Rotate `columns` into rows, with the name of each column in
+    /// `name_column` and its value in `value_column`. Rows with a `NULL` value
+    /// are skipped unless `include_nulls` is true.
+    ///
+    /// See [`LogicalPlanBuilder::unpivot`] for details.
+    ///
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.read_csv("tests/data/example.csv", CsvReadOptions::new()).await?;
+    ///
+    /// // The following use is the equivalent of
+    /// // "SELECT a, 'b' AS name, b AS value WHERE b IS NOT NULL
+    /// //  UNION ALL SELECT a, 'c' AS name, c AS value WHERE c IS NOT NULL"
+    /// let _ = df.unpivot("value", "name", vec!["b", "c"], false)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn unpivot(
+        self,
+        value_column: &str,
+        name_column: &str,
+        columns: Vec<&str>,
+        include_nulls: bool,
+    ) -> Result<DataFrame> {
+        let plan = LogicalPlanBuilder::from(self.plan)
+            .unpivot(value_column, name_column, columns, include_nulls)?
+            .build()?;
+        Ok(DataFrame::new(self.session_state, plan))
+    }
+
     /// Apply one or more window functions ([`Expr::WindowFunction`]) to extend the schema
     pub fn window(self, window_exprs: Vec<Expr>) -> Result<DataFrame> {
         let plan = LogicalPlanBuilder::from(self.plan)
@@ -249,6 +316,40 @@ impl DataFrame {
         Ok(DataFrame::new(self.session_state, plan))
     }
 
+    /// Return a random sample of the rows of this DataFrame, like
+    /// `TABLESAMPLE BERNOULLI` in SQL.
+    ///
+    /// `fraction` - Probability, between 0 and 1, with which each row is kept
+    ///
+    /// `seed` - Seed for the random number generator. The same seed returns
+    /// the same sample for the same input; `None` picks a random seed.
+    /// ```
+    /// # use datafusion::prelude::*;
+    /// # use datafusion::error::Result;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.read_csv("tests/data/example.csv", CsvReadOptions::new()).await?;
+    /// let df = df.sample(0.1, Some(42))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sample(self, fraction: f64, seed: Option<u64>) -> Result<DataFrame> {
+        if !(0.0..=1.0).contains(&fraction) {
+            return Err(DataFusionError::Plan(format!(
+                "Sample fraction must be between 0 and 1, got {fraction}"
+            )));
+        }
+        let plan = LogicalPlanBuilder::from(self.plan)
+            .sample(
+                SampleMethod::Bernoulli,
+                SampleSize::Percent(fraction * 100.0),
+                seed,
+            )?
+            .build()?;
+        Ok(DataFrame::new(self.session_state, plan))
+    }
+
     /// Calculate the union of two [`DataFrame`]s, preserving duplicate rows.The
     /// two [`DataFrame`]s must have exactly the same schema
     ///
@@ -513,6 +614,10 @@ impl DataFrame {
     /// down to any of join inputs.
     /// In case of outer join, filter applied to only matched rows.
     ///
+    /// For [`JoinType::AsOf`] the filter is required and is the match condition, a
+    /// comparison such as `left.ts >= right.ts` that selects the closest right row
+    /// for every left row with the same join keys.
+    ///
     /// ```
     /// # use datafusion::prelude::*;
     /// # use datafusion::error::Result;
@@ -1210,6 +1315,7 @@ mod tests {
             vec![col("aggregate_test_100.c2")],
             vec![],
             WindowFrame::new(false),
+            false,
         ));
         let t2 = t.select(vec![col("c1"), first_row])?;
         let plan = t2.plan.clone();
@@ -2197,6 +2303,7 @@ mod tests {
                         out_partitioning,
                     Partitioning::UnknownPartitioning(partition_count) if partition_count == default_partition_count));
                 }
+                JoinType::AsOf => unreachable!("ASOF joins require a match condition"),
             }
         }
 
@@ -0,0 +1,90 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`CteWorkTable`] implementation used for recursive queries
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::datatypes::SchemaRef;
+use async_trait::async_trait;
+
+use crate::datasource::{TableProvider, TableType};
+use crate::error::Result;
+use crate::execution::context::SessionState;
+use crate::logical_expr::Expr;
+use crate::physical_plan::project_schema;
+use crate::physical_plan::work_table::WorkTableExec;
+use crate::physical_plan::ExecutionPlan;
+
+/// The temporary working table where the previous iteration of a recursive
+/// query is stored.
+///
+/// Scanning it yields a [`WorkTableExec`], which is connected to the actual
+/// batches when the enclosing recursive query is planned.
+pub struct CteWorkTable {
+    /// The name of the CTE work table
+    name: String,
+    /// This schema must be shared across both the static and recursive terms
+    /// of a recursive query
+    table_schema: SchemaRef,
+}
+
+impl CteWorkTable {
+    /// Construct a new `CteWorkTable` with the given name and schema.
+    pub fn new(name: &str, table_schema: SchemaRef) -> Self {
+        Self {
+            name: name.to_owned(),
+            table_schema,
+        }
+    }
+
+    /// The name of the recursive query this work table belongs to
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[async_trait]
+impl TableProvider for CteWorkTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.table_schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Temporary
+    }
+
+    async fn scan(
+        &self,
+        _state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let projected_schema = project_schema(&self.table_schema, projection)?;
+        Ok(Arc::new(WorkTableExec::new(
+            self.name.clone(),
+            projected_schema,
+            projection.cloned(),
+        )))
+    }
+}
@@ -19,18 +19,32 @@
 //!
 //! Works with files following the [Arrow IPC format](https://arrow.apache.org/docs/format/Columnar.html#ipc-file-format)
 
-use crate::datasource::file_format::FileFormat;
-use crate::datasource::physical_plan::{ArrowExec, FileScanConfig};
+use crate::datasource::file_format::file_type::FileCompressionType;
+use crate::datasource::file_format::{
+    create_writer, output_file_locations, stateless_serialize_and_write_files,
+    write_hive_partitioned_files, BatchSerializer, FileFormat, FileWriterMode,
+};
+use crate::datasource::physical_plan::{
+    ArrowExec, FileGroupDisplay, FileScanConfig, FileSinkConfig,
+};
 use crate::error::Result;
 use crate::execution::context::SessionState;
-use crate::physical_plan::ExecutionPlan;
+use crate::physical_plan::insert::{DataSink, InsertExec};
+use crate::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, SendableRecordBatchStream,
+};
 use arrow::ipc::reader::FileReader;
+use arrow::ipc::writer::FileWriter;
+use arrow::record_batch::RecordBatch;
 use arrow_schema::{Schema, SchemaRef};
 use async_trait::async_trait;
-use datafusion_common::Statistics;
+use bytes::Bytes;
+use datafusion_common::{DataFusionError, Statistics};
+use datafusion_execution::TaskContext;
 use datafusion_physical_expr::PhysicalExpr;
 use object_store::{GetResult, ObjectMeta, ObjectStore};
 use std::any::Any;
+use std::fmt;
 use std::io::{Read, Seek};
 use std::sync::Arc;
 
@@ -88,9 +102,194 @@ impl FileFormat for ArrowFormat {
         let exec = ArrowExec::new(conf);
         Ok(Arc::new(exec))
     }
+
+    async fn create_writer_physical_plan(
+        &self,
+        input: Arc<dyn ExecutionPlan>,
+        _state: &SessionState,
+        conf: FileSinkConfig,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let sink_schema = conf.output_schema().clone();
+        let sink = Arc::new(ArrowSink::new(conf));
+
+        Ok(Arc::new(InsertExec::new(input, sink, sink_schema)) as _)
+    }
+}
+
+/// Serializes record batches into the [Arrow IPC file format].
+///
+/// Unlike the other serializers this one is stateful: the schema is written
+/// before the first batch, and [`BatchSerializer::finish`] returns the file
+/// footer, so each instance produces exactly one file.
+///
+/// [Arrow IPC file format]: https://arrow.apache.org/docs/format/Columnar.html#ipc-file-format
+pub struct ArrowSerializer {
+    writer: FileWriter<Vec<u8>>,
+}
+
+impl ArrowSerializer {
+    /// Creates a serializer for batches with the given `schema`
+    pub fn try_new(schema: &Schema) -> Result<Self> {
+        Ok(Self {
+            writer: FileWriter::try_new(Vec::with_capacity(4096), schema)?,
+        })
+    }
+
+    /// Takes the bytes encoded so far out of the inner buffer
+    fn take_bytes(&mut self) -> Bytes {
+        Bytes::from(std::mem::take(self.writer.get_mut()))
+    }
+}
+
+#[async_trait]
+impl BatchSerializer for ArrowSerializer {
+    async fn serialize(&mut self, batch: RecordBatch) -> Result<Bytes> {
+        self.writer.write(&batch)?;
+        Ok(self.take_bytes())
+    }
+
+    async fn finish(&mut self) -> Result<Bytes> {
+        self.writer.finish()?;
+        Ok(self.take_bytes())
+    }
+}
+
+/// Implements [`DataSink`] for writing to an Arrow IPC file.
+#[derive(Debug)]
+struct ArrowSink {
+    /// Config options for writing data
+    config: FileSinkConfig,
+}
+
+impl DisplayAs for ArrowSink {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "ArrowSink(writer_mode={:?}, file_groups=",
+                    self.config.writer_mode
+                )?;
+                FileGroupDisplay(&self.config.file_groups).fmt_as(t, f)?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+impl ArrowSink {
+    fn new(config: FileSinkConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl DataSink for ArrowSink {
+    async fn write_all(
+        &self,
+        data: SendableRecordBatchStream,
+        context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        // The footer of an IPC file can not be appended to
+        if matches!(self.config.writer_mode, FileWriterMode::Append) {
+            return Err(DataFusionError::NotImplemented(
+                "Appending to existing Arrow IPC files is not supported".to_owned(),
+            ));
+        }
+
+        let object_store = context
+            .runtime_env()
+            .object_store(&self.config.object_store_url)?;
+
+        if !self.config.table_partition_cols.is_empty() {
+            let writer_mode = self.config.writer_mode;
+            return write_hive_partitioned_files(
+                data,
+                &self.config,
+                DEFAULT_ARROW_EXTENSION,
+                |data, location| {
+                    let object_store = object_store.clone();
+                    async move {
+                        let serializer = ArrowSerializer::try_new(&data.schema())?;
+                        let writer = create_writer(
+                            writer_mode,
+                            FileCompressionType::UNCOMPRESSED,
+                            location,
+                            object_store,
+                        )
+                        .await?;
+                        stateless_serialize_and_write_files(
+                            data,
+                            vec![Box::new(serializer)],
+                            vec![writer],
+                        )
+                        .await
+                    }
+                },
+            )
+            .await;
+        }
+
+        let locations = output_file_locations(&self.config, DEFAULT_ARROW_EXTENSION)?;
+
+        // Construct serializer and writer for each output file
+        let mut serializers: Vec<Box<dyn BatchSerializer>> = vec![];
+        let mut writers = vec![];
+        for location in locations {
+            serializers.push(Box::new(ArrowSerializer::try_new(
+                &self.config.output_schema,
+            )?));
+            // IPC files are read with random access, so they are never compressed
+            let writer = create_writer(
+                self.config.writer_mode,
+                FileCompressionType::UNCOMPRESSED,
+                location,
+                object_store.clone(),
+            )
+            .await?;
+            writers.push(writer);
+        }
+
+        stateless_serialize_and_write_files(data, serializers, writers).await
+    }
 }
 
 fn read_arrow_schema_from_reader<R: Read + Seek>(reader: R) -> Result<SchemaRef> {
     let reader = FileReader::try_new(reader, None)?;
     Ok(reader.schema())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int32Array, StringArray};
+    use arrow_schema::{DataType, Field};
+    use std::io::Cursor;
+
+    #[tokio::test]
+    async fn serialize_ipc_file() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec![Some("foo"), None, Some("bar")])),
+            ],
+        )?;
+
+        let mut serializer = ArrowSerializer::try_new(&schema)?;
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&serializer.serialize(batch.clone()).await?);
+        bytes.extend_from_slice(&serializer.serialize(batch.clone()).await?);
+        bytes.extend_from_slice(&serializer.finish().await?);
+
+        let reader = FileReader::try_new(Cursor::new(bytes), None)?;
+        assert_eq!(reader.schema(), schema);
+        let batches = reader.collect::<std::result::Result<Vec<_>, _>>()?;
+        assert_eq!(batches, vec![batch.clone(), batch]);
+        Ok(())
+    }
+}
@@ -36,17 +36,16 @@ use bytes::{Buf, Bytes};
 use futures::stream::BoxStream;
 use futures::{pin_mut, Stream, StreamExt, TryStreamExt};
 use object_store::{delimited::newline_delimited_stream, ObjectMeta, ObjectStore};
-use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use super::FileFormat;
-use crate::datasource::file_format::file_type::FileCompressionType;
+use crate::datasource::file_format::file_type::{FileCompressionType, FileType};
 use crate::datasource::file_format::FileWriterMode;
 use crate::datasource::file_format::{
-    AbortMode, AbortableWrite, AsyncPutWriter, BatchSerializer, MultiPart,
-    DEFAULT_SCHEMA_INFER_MAX_RECORD,
+    create_writer, output_file_locations, stateless_serialize_and_write_files,
+    write_hive_partitioned_files, BatchSerializer, DEFAULT_SCHEMA_INFER_MAX_RECORD,
 };
 use crate::datasource::physical_plan::{
-    CsvExec, FileGroupDisplay, FileMeta, FileScanConfig, FileSinkConfig,
+    CsvExec, FileGroupDisplay, FileScanConfig, FileSinkConfig,
 };
 use crate::error::Result;
 use crate::execution::context::SessionState;
@@ -433,28 +432,6 @@ impl BatchSerializer for CsvSerializer {
     }
 }
 
-async fn check_for_errors<T, W: AsyncWrite + Unpin + Send>(
-    result: Result<T>,
-    writers: &mut [AbortableWrite<W>],
-) -> Result<T> {
-    match result {
-        Ok(value) => Ok(value),
-        Err(e) => {
-            // Abort all writers before returning the error:
-            for writer in writers {
-                let mut abort_future = writer.abort_writer();
-                if let Ok(abort_future) = &mut abort_future {
-                    let _ = abort_future.await;
-                }
-                // Ignore errors that occur during abortion,
-                // We do try to abort all writers before returning error.
-            }
-            // After aborting writers return original error.
-            Err(e)
-        }
-    }
-}
-
 /// Implements [`DataSink`] for writing to a CSV file.
 struct CsvSink {
     /// Config options for writing data
@@ -504,123 +481,84 @@ impl CsvSink {
             file_compression_type,
         }
     }
-
-    // Create a write for Csv files
-    async fn create_writer(
-        &self,
-        file_meta: FileMeta,
-        object_store: Arc<dyn ObjectStore>,
-    ) -> Result<AbortableWrite<Box<dyn AsyncWrite + Send + Unpin>>> {
-        let object = &file_meta.object_meta;
-        match self.config.writer_mode {
-            // If the mode is append, call the store's append method and return wrapped in
-            // a boxed trait object.
-            FileWriterMode::Append => {
-                let writer = object_store
-                    .append(&object.location)
-                    .await
-                    .map_err(DataFusionError::ObjectStore)?;
-                let writer = AbortableWrite::new(
-                    self.file_compression_type.convert_async_writer(writer)?,
-                    AbortMode::Append,
-                );
-                Ok(writer)
-            }
-            // If the mode is put, create a new AsyncPut writer and return it wrapped in
-            // a boxed trait object
-            FileWriterMode::Put => {
-                let writer = Box::new(AsyncPutWriter::new(object.clone(), object_store));
-                let writer = AbortableWrite::new(
-                    self.file_compression_type.convert_async_writer(writer)?,
-                    AbortMode::Put,
-                );
-                Ok(writer)
-            }
-            // If the mode is put multipart, call the store's put_multipart method and
-            // return the writer wrapped in a boxed trait object.
-            FileWriterMode::PutMultipart => {
-                let (multipart_id, writer) = object_store
-                    .put_multipart(&object.location)
-                    .await
-                    .map_err(DataFusionError::ObjectStore)?;
-                Ok(AbortableWrite::new(
-                    self.file_compression_type.convert_async_writer(writer)?,
-                    AbortMode::MultiPart(MultiPart::new(
-                        object_store,
-                        multipart_id,
-                        object.location.clone(),
-                    )),
-                ))
-            }
-        }
-    }
 }
 
 #[async_trait]
 impl DataSink for CsvSink {
     async fn write_all(
         &self,
-        mut data: SendableRecordBatchStream,
+        data: SendableRecordBatchStream,
         context: &Arc<TaskContext>,
     ) -> Result<u64> {
-        let num_partitions = self.config.file_groups.len();
-
         let object_store = context
             .runtime_env()
             .object_store(&self.config.object_store_url)?;
 
-        // Construct serializer and writer for each file group
-        let mut serializers = vec![];
+        let file_extension = FileType::CSV
+            .get_ext_with_compression(self.file_compression_type.to_owned())?;
+
+        if !self.config.table_partition_cols.is_empty() {
+            let writer_mode = self.config.writer_mode;
+            let (has_header, delimiter) = (self.has_header, self.delimiter);
+            return write_hive_partitioned_files(
+                data,
+                &self.config,
+                &file_extension,
+                |data, location| {
+                    let object_store = object_store.clone();
+                    let file_compression_type = self.file_compression_type.to_owned();
+                    async move {
+                        let builder = WriterBuilder::new().with_delimiter(delimiter);
+                        let serializer = CsvSerializer::new()
+                            .with_builder(builder)
+                            .with_header(has_header);
+                        let writer = create_writer(
+                            writer_mode,
+                            file_compression_type,
+                            location,
+                            object_store,
+                        )
+                        .await?;
+                        stateless_serialize_and_write_files(
+                            data,
+                            vec![Box::new(serializer)],
+                            vec![writer],
+                        )
+                        .await
+                    }
+                },
+            )
+            .await;
+        }
+
+        let locations = output_file_locations(&self.config, &file_extension)?;
+
+        // Construct serializer and writer for each output file
+        let mut serializers: Vec<Box<dyn BatchSerializer>> = vec![];
         let mut writers = vec![];
-        for file_group in &self.config.file_groups {
+        for (idx, location) in locations.into_iter().enumerate() {
             // In append mode, consider has_header flag only when file is empty (at the start).
             // For other modes, use has_header flag as is.
             let header = self.has_header
                 && (!matches!(&self.config.writer_mode, FileWriterMode::Append)
-                    || file_group.object_meta.size == 0);
+                    || self.config.file_groups[idx].object_meta.size == 0);
             let builder = WriterBuilder::new().with_delimiter(self.delimiter);
             let serializer = CsvSerializer::new()
                 .with_builder(builder)
                 .with_header(header);
-            serializers.push(serializer);
-
-            let file = file_group.clone();
-            let writer = self
-                .create_writer(file.object_meta.clone().into(), object_store.clone())
-                .await?;
-            writers.push(writer);
-        }
+            serializers.push(Box::new(serializer));
 
-        let mut idx = 0;
-        let mut row_count = 0;
-        // Map errors to DatafusionError.
-        let err_converter =
-            |_| DataFusionError::Internal("Unexpected FileSink Error".to_string());
-        while let Some(maybe_batch) = data.next().await {
-            // Write data to files in a round robin fashion:
-            idx = (idx + 1) % num_partitions;
-            let serializer = &mut serializers[idx];
-            let batch = check_for_errors(maybe_batch, &mut writers).await?;
-            row_count += batch.num_rows();
-            let bytes =
-                check_for_errors(serializer.serialize(batch).await, &mut writers).await?;
-            let writer = &mut writers[idx];
-            check_for_errors(
-                writer.write_all(&bytes).await.map_err(err_converter),
-                &mut writers,
-            )
-            .await?;
-        }
-        // Perform cleanup:
-        let n_writers = writers.len();
-        for idx in 0..n_writers {
-            check_for_errors(
-                writers[idx].shutdown().await.map_err(err_converter),
-                &mut writers,
+            let writer = create_writer(
+                self.config.writer_mode,
+                self.file_compression_type.to_owned(),
+                location,
+                object_store.clone(),
             )
             .await?;
+            writers.push(writer);
         }
-        Ok(row_count as u64)
+
+        stateless_serialize_and_write_files(data, serializers, writers).await
     }
 }
 
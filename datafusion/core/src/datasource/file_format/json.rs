@@ -18,29 +18,37 @@
 //! Line delimited JSON format abstractions
 
 use std::any::Any;
-
+use std::fmt;
+use std::fmt::Debug;
 use std::io::BufReader;
 use std::sync::Arc;
 
 use arrow::datatypes::Schema;
 use arrow::datatypes::SchemaRef;
+use arrow::json;
 use arrow::json::reader::infer_json_schema_from_iterator;
 use arrow::json::reader::ValueIter;
+use arrow_array::RecordBatch;
 use async_trait::async_trait;
-use bytes::Buf;
+use bytes::{Buf, Bytes};
 
+use datafusion_execution::TaskContext;
 use datafusion_physical_expr::PhysicalExpr;
 use object_store::{GetResult, ObjectMeta, ObjectStore};
 
 use super::FileFormat;
 use super::FileScanConfig;
-use crate::datasource::file_format::file_type::FileCompressionType;
-use crate::datasource::file_format::DEFAULT_SCHEMA_INFER_MAX_RECORD;
-use crate::datasource::physical_plan::NdJsonExec;
+use crate::datasource::file_format::file_type::{FileCompressionType, FileType};
+use crate::datasource::file_format::{
+    create_writer, output_file_locations, stateless_serialize_and_write_files,
+    write_hive_partitioned_files, BatchSerializer, DEFAULT_SCHEMA_INFER_MAX_RECORD,
+};
+use crate::datasource::physical_plan::{FileGroupDisplay, FileSinkConfig, NdJsonExec};
 use crate::error::Result;
 use crate::execution::context::SessionState;
-use crate::physical_plan::ExecutionPlan;
-use crate::physical_plan::Statistics;
+use crate::physical_plan::insert::{DataSink, InsertExec};
+use crate::physical_plan::{DisplayAs, DisplayFormatType, Statistics};
+use crate::physical_plan::{ExecutionPlan, SendableRecordBatchStream};
 
 /// The default file extension of json files
 pub const DEFAULT_JSON_EXTENSION: &str = ".json";
@@ -148,6 +156,153 @@ impl FileFormat for JsonFormat {
         let exec = NdJsonExec::new(conf, self.file_compression_type.to_owned());
         Ok(Arc::new(exec))
     }
+
+    async fn create_writer_physical_plan(
+        &self,
+        input: Arc<dyn ExecutionPlan>,
+        _state: &SessionState,
+        conf: FileSinkConfig,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let sink_schema = conf.output_schema().clone();
+        let sink = Arc::new(JsonSink::new(conf, self.file_compression_type.clone()));
+
+        Ok(Arc::new(InsertExec::new(input, sink, sink_schema)) as _)
+    }
+}
+
+impl Default for JsonSerializer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Define a struct for serializing Json records to a stream
+pub struct JsonSerializer {
+    // Inner buffer for avoiding reallocation
+    buffer: Vec<u8>,
+}
+
+impl JsonSerializer {
+    /// Constructor for the JsonSerializer object
+    pub fn new() -> Self {
+        Self {
+            buffer: Vec::with_capacity(4096),
+        }
+    }
+}
+
+#[async_trait]
+impl BatchSerializer for JsonSerializer {
+    async fn serialize(&mut self, batch: RecordBatch) -> Result<Bytes> {
+        let mut writer = json::LineDelimitedWriter::new(&mut self.buffer);
+        writer.write(&batch)?;
+        Ok(Bytes::from(self.buffer.drain(..).collect::<Vec<u8>>()))
+    }
+}
+
+/// Implements [`DataSink`] for writing to a Json file.
+struct JsonSink {
+    /// Config options for writing data
+    config: FileSinkConfig,
+    file_compression_type: FileCompressionType,
+}
+
+impl Debug for JsonSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonSink")
+            .field("file_compression_type", &self.file_compression_type)
+            .finish()
+    }
+}
+
+impl DisplayAs for JsonSink {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "JsonSink(writer_mode={:?}, file_groups=",
+                    self.config.writer_mode
+                )?;
+                FileGroupDisplay(&self.config.file_groups).fmt_as(t, f)?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+impl JsonSink {
+    fn new(config: FileSinkConfig, file_compression_type: FileCompressionType) -> Self {
+        Self {
+            config,
+            file_compression_type,
+        }
+    }
+}
+
+#[async_trait]
+impl DataSink for JsonSink {
+    async fn write_all(
+        &self,
+        data: SendableRecordBatchStream,
+        context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        let object_store = context
+            .runtime_env()
+            .object_store(&self.config.object_store_url)?;
+
+        let file_extension = FileType::JSON
+            .get_ext_with_compression(self.file_compression_type.to_owned())?;
+
+        if !self.config.table_partition_cols.is_empty() {
+            let writer_mode = self.config.writer_mode;
+            return write_hive_partitioned_files(
+                data,
+                &self.config,
+                &file_extension,
+                |data, location| {
+                    let object_store = object_store.clone();
+                    let file_compression_type = self.file_compression_type.to_owned();
+                    async move {
+                        let writer = create_writer(
+                            writer_mode,
+                            file_compression_type,
+                            location,
+                            object_store,
+                        )
+                        .await?;
+                        stateless_serialize_and_write_files(
+                            data,
+                            vec![Box::new(JsonSerializer::new())],
+                            vec![writer],
+                        )
+                        .await
+                    }
+                },
+            )
+            .await;
+        }
+
+        let locations = output_file_locations(&self.config, &file_extension)?;
+
+        // Construct serializer and writer for each output file
+        let mut serializers: Vec<Box<dyn BatchSerializer>> = vec![];
+        let mut writers = vec![];
+        for location in locations {
+            serializers.push(Box::new(JsonSerializer::new()));
+
+            let writer = create_writer(
+                self.config.writer_mode,
+                self.file_compression_type.to_owned(),
+                location,
+                object_store.clone(),
+            )
+            .await?;
+            writers.push(writer);
+        }
+
+        stateless_serialize_and_write_files(data, serializers, writers).await
+    }
 }
 
 #[cfg(test)]
@@ -29,30 +29,42 @@ pub mod options;
 pub mod parquet;
 
 use std::any::Any;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::future::Future;
 use std::io::Error;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 use std::{fmt, mem};
 
-use crate::arrow::datatypes::SchemaRef;
+use crate::arrow::datatypes::{DataType, SchemaRef};
+use crate::datasource::file_format::file_type::FileCompressionType;
+use crate::datasource::listing::{escape_partition_value, HIVE_DEFAULT_PARTITION};
 use crate::datasource::physical_plan::{FileScanConfig, FileSinkConfig};
 use crate::error::Result;
 use crate::execution::context::SessionState;
-use crate::physical_plan::{ExecutionPlan, Statistics};
+use crate::physical_plan::stream::RecordBatchStreamAdapter;
+use crate::physical_plan::{ExecutionPlan, SendableRecordBatchStream, Statistics};
 
-use arrow_array::RecordBatch;
+use crate::arrow::compute::{cast, take};
+use arrow_array::{Array, RecordBatch, UInt32Array};
+use datafusion_common::cast::as_string_array;
 use datafusion_common::DataFusionError;
 use datafusion_physical_expr::PhysicalExpr;
 
 use async_trait::async_trait;
 use bytes::Bytes;
+use chrono::Utc;
 use futures::future::BoxFuture;
 use futures::ready;
-use futures::FutureExt;
-use object_store::path::Path;
+use futures::{FutureExt, StreamExt};
+use object_store::path::{Path, PathPart};
 use object_store::{MultipartId, ObjectMeta, ObjectStore};
-use tokio::io::AsyncWrite;
+use rand::distributions::{Alphanumeric, DistString};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::mpsc::{self, Sender};
+use tokio::task::JoinSet;
 /// This trait abstracts all the file format specific implementations
 /// from the [`TableProvider`]. This helps code re-utilization across
 /// providers that support the the same file formats.
@@ -235,7 +247,7 @@ pub(crate) enum AbortMode {
 }
 
 /// A wrapper struct with abort method and writer
-struct AbortableWrite<W: AsyncWrite + Unpin + Send> {
+pub(crate) struct AbortableWrite<W: AsyncWrite + Unpin + Send> {
     writer: W,
     mode: AbortMode,
 }
@@ -311,6 +323,427 @@ pub enum FileWriterMode {
 pub trait BatchSerializer: Unpin + Send {
     /// Asynchronously serializes a `RecordBatch` and returns the serialized bytes.
     async fn serialize(&mut self, batch: RecordBatch) -> Result<Bytes>;
+
+    /// Returns any trailing bytes (e.g. a file footer) that must be written
+    /// after the last batch. Defaults to no bytes.
+    async fn finish(&mut self) -> Result<Bytes> {
+        Ok(Bytes::new())
+    }
+}
+
+/// Returns the locations of the files that a sink configured by `config`
+/// writes to.
+///
+/// In [`FileWriterMode::Append`] mode these are the existing files of the
+/// sink. Otherwise, new files are created: either the single file named by
+/// the (only) table path, or a file with a unique name and the given
+/// `file_extension` inside each table path.
+pub(crate) fn output_file_locations(
+    config: &FileSinkConfig,
+    file_extension: &str,
+) -> Result<Vec<Path>> {
+    match config.writer_mode {
+        FileWriterMode::Append => {
+            if config.file_groups.is_empty() {
+                return Err(DataFusionError::Plan(
+                    "Append mode requires at least one existing file".to_owned(),
+                ));
+            }
+            Ok(config
+                .file_groups
+                .iter()
+                .map(|file| file.object_meta.location.clone())
+                .collect())
+        }
+        FileWriterMode::Put | FileWriterMode::PutMultipart => {
+            if config.single_file_output {
+                if config.table_paths.len() != 1 {
+                    return Err(DataFusionError::Plan(
+                        "Single file output requires exactly one table path".to_owned(),
+                    ));
+                }
+                return Ok(vec![config.table_paths[0].prefix().clone()]);
+            }
+            if config.table_paths.is_empty() {
+                return Err(DataFusionError::Plan(
+                    "Writing new files requires at least one table path".to_owned(),
+                ));
+            }
+            // uniquely identify this batch of files with a random string,
+            // to prevent collisions with existing files
+            let write_id = Alphanumeric.sample_string(&mut rand::thread_rng(), 16);
+            Ok(config
+                .table_paths
+                .iter()
+                .map(|table_path| {
+                    table_path
+                        .prefix()
+                        .child(format!("{write_id}{file_extension}"))
+                })
+                .collect())
+        }
+    }
+}
+
+/// Returns an [`AbortableWrite`] which writes to `location` in `object_store`
+/// according to `writer_mode`, compressing its output with
+/// `file_compression_type`.
+pub(crate) async fn create_writer(
+    writer_mode: FileWriterMode,
+    file_compression_type: FileCompressionType,
+    location: Path,
+    object_store: Arc<dyn ObjectStore>,
+) -> Result<AbortableWrite<Box<dyn AsyncWrite + Send + Unpin>>> {
+    match writer_mode {
+        // If the mode is append, call the store's append method and return wrapped in
+        // a boxed trait object.
+        FileWriterMode::Append => {
+            let writer = object_store
+                .append(&location)
+                .await
+                .map_err(DataFusionError::ObjectStore)?;
+            let writer = AbortableWrite::new(
+                file_compression_type.convert_async_writer(writer)?,
+                AbortMode::Append,
+            );
+            Ok(writer)
+        }
+        // If the mode is put, create a new AsyncPut writer and return it wrapped in
+        // a boxed trait object
+        FileWriterMode::Put => {
+            let object_meta = ObjectMeta {
+                location,
+                last_modified: Utc::now(),
+                size: 0,
+                e_tag: None,
+            };
+            let writer = Box::new(AsyncPutWriter::new(object_meta, object_store));
+            let writer = AbortableWrite::new(
+                file_compression_type.convert_async_writer(writer)?,
+                AbortMode::Put,
+            );
+            Ok(writer)
+        }
+        // If the mode is put multipart, call the store's put_multipart method and
+        // return the writer wrapped in a boxed trait object.
+        FileWriterMode::PutMultipart => {
+            let (multipart_id, writer) = object_store
+                .put_multipart(&location)
+                .await
+                .map_err(DataFusionError::ObjectStore)?;
+            Ok(AbortableWrite::new(
+                file_compression_type.convert_async_writer(writer)?,
+                AbortMode::MultiPart(MultiPart::new(
+                    object_store,
+                    multipart_id,
+                    location,
+                )),
+            ))
+        }
+    }
+}
+
+/// Aborts all `writers` if `result` is an error, and returns `result`
+pub(crate) async fn check_for_errors<T, W: AsyncWrite + Unpin + Send>(
+    result: Result<T>,
+    writers: &mut [AbortableWrite<W>],
+) -> Result<T> {
+    match result {
+        Ok(value) => Ok(value),
+        Err(e) => {
+            // Abort all writers before returning the error:
+            for writer in writers {
+                let mut abort_future = writer.abort_writer();
+                if let Ok(abort_future) = &mut abort_future {
+                    let _ = abort_future.await;
+                }
+                // Ignore errors that occur during abortion,
+                // We do try to abort all writers before returning error.
+            }
+            // After aborting writers return original error.
+            Err(e)
+        }
+    }
+}
+
+/// Returns `result`, after running `aborts` if it is an error.
+///
+/// This aborts the writes of writers that are owned by another writer, such
+/// as a parquet writer, and so can not be aborted with [`check_for_errors`].
+pub(crate) async fn abort_on_error<T>(
+    result: Result<T>,
+    aborts: Vec<BoxFuture<'static, Result<()>>>,
+) -> Result<T> {
+    if result.is_err() {
+        for abort in aborts {
+            // Ignore errors that occur during abortion, as in check_for_errors
+            let _ = abort.await;
+        }
+    }
+    result
+}
+
+/// Serializes the batches of `data` with `serializers` and writes them to
+/// `writers` in a round robin fashion, returning the number of rows written.
+///
+/// `serializers` and `writers` must have the same length. Once `data` is
+/// exhausted, the bytes returned by [`BatchSerializer::finish`] are written
+/// and all writers are shut down. Writers are aborted if any error occurs.
+pub(crate) async fn stateless_serialize_and_write_files(
+    mut data: SendableRecordBatchStream,
+    mut serializers: Vec<Box<dyn BatchSerializer>>,
+    mut writers: Vec<AbortableWrite<Box<dyn AsyncWrite + Send + Unpin>>>,
+) -> Result<u64> {
+    let num_partitions = writers.len();
+    if num_partitions == 0 || serializers.len() != num_partitions {
+        return Err(DataFusionError::Internal(format!(
+            "Expected the same non-zero number of serializers and writers, got {} and {}",
+            serializers.len(),
+            num_partitions
+        )));
+    }
+
+    let mut idx = 0;
+    let mut row_count = 0;
+    // Map errors to DatafusionError.
+    let err_converter =
+        |_| DataFusionError::Internal("Unexpected FileSink Error".to_string());
+    while let Some(maybe_batch) = data.next().await {
+        // Write data to files in a round robin fashion:
+        idx = (idx + 1) % num_partitions;
+        let serializer = &mut serializers[idx];
+        let batch = check_for_errors(maybe_batch, &mut writers).await?;
+        row_count += batch.num_rows();
+        let bytes =
+            check_for_errors(serializer.serialize(batch).await, &mut writers).await?;
+        let writer = &mut writers[idx];
+        check_for_errors(
+            writer.write_all(&bytes).await.map_err(err_converter),
+            &mut writers,
+        )
+        .await?;
+    }
+    // Write any trailing bytes and perform cleanup:
+    let n_writers = writers.len();
+    for idx in 0..n_writers {
+        let bytes =
+            check_for_errors(serializers[idx].finish().await, &mut writers).await?;
+        if !bytes.is_empty() {
+            check_for_errors(
+                writers[idx].write_all(&bytes).await.map_err(err_converter),
+                &mut writers,
+            )
+            .await?;
+        }
+        check_for_errors(
+            writers[idx].shutdown().await.map_err(err_converter),
+            &mut writers,
+        )
+        .await?;
+    }
+    Ok(row_count as u64)
+}
+
+/// The number of batches buffered for each partition written by
+/// [`write_hive_partitioned_files`]
+const PARTITION_CHANNEL_CAPACITY: usize = 2;
+
+/// The error sent to the writers of the other partitions once writing one
+/// partition failed, so that they abort their files
+const PARTITION_WRITE_ABORTED: &str = "Writing partition aborted";
+
+/// Writes `data` into new files under the hive style partition directories
+/// (`col1=value1/col2=value2/...`) of the single table path of `config`,
+/// returning the number of rows written.
+///
+/// The rows of each batch are split by the values of the partition columns
+/// of `config`, which are dropped from the data written to the files. The
+/// first time a combination of values is seen, `write_file` is called with
+/// the stream of all the (projected) batches of that partition and the
+/// location of its file, which has a unique name and the given
+/// `file_extension`. The files are written concurrently.
+pub(crate) async fn write_hive_partitioned_files<F, Fut>(
+    mut data: SendableRecordBatchStream,
+    config: &FileSinkConfig,
+    file_extension: &str,
+    write_file: F,
+) -> Result<u64>
+where
+    F: Fn(SendableRecordBatchStream, Path) -> Fut,
+    Fut: Future<Output = Result<u64>> + Send + 'static,
+{
+    if config.table_paths.len() != 1 {
+        return Err(DataFusionError::Plan(
+            "Writing a partitioned table requires exactly one table path".to_owned(),
+        ));
+    }
+    if !matches!(config.writer_mode, FileWriterMode::PutMultipart) {
+        return Err(DataFusionError::NotImplemented(format!(
+            "Writing a partitioned table in {:?} mode is not supported",
+            config.writer_mode
+        )));
+    }
+
+    let schema = &config.output_schema;
+    let partition_indices = config
+        .table_partition_cols
+        .iter()
+        .map(|(name, _)| schema.index_of(name))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let file_indices = (0..schema.fields().len())
+        .filter(|idx| !partition_indices.contains(idx))
+        .collect::<Vec<_>>();
+    let file_schema = Arc::new(schema.project(&file_indices)?);
+
+    // uniquely identify this batch of files with a random string,
+    // to prevent collisions with existing files
+    let write_id = Alphanumeric.sample_string(&mut rand::thread_rng(), 16);
+    let table_prefix = config.table_paths[0].prefix();
+
+    let mut senders: HashMap<Vec<Option<String>>, Sender<Result<RecordBatch>>> =
+        HashMap::new();
+    let mut writer_tasks = JoinSet::new();
+    let mut writer_failed = false;
+    let demux_result: Result<()> = async {
+        while let Some(batch) = data.next().await {
+            for (values, batch) in split_batch_by_partition(
+                &batch?,
+                &partition_indices,
+                &file_indices,
+                &file_schema,
+            )? {
+                let sender = match senders.entry(values) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => {
+                        let mut location = table_prefix.clone();
+                        for ((name, _), value) in
+                            config.table_partition_cols.iter().zip(entry.key())
+                        {
+                            let value = match value {
+                                Some(value) => escape_partition_value(value),
+                                None => HIVE_DEFAULT_PARTITION.to_owned(),
+                            };
+                            let part =
+                                format!("{}={value}", escape_partition_value(name));
+                            // the part is escaped already, so it must not be
+                            // encoded again
+                            let part = PathPart::parse(&part)
+                                .map_err(|e| DataFusionError::Execution(e.to_string()))?;
+                            location = location.child(part);
+                        }
+                        let location =
+                            location.child(format!("{write_id}{file_extension}"));
+
+                        let (sender, receiver) =
+                            mpsc::channel(PARTITION_CHANNEL_CAPACITY);
+                        let stream = futures::stream::unfold(
+                            receiver,
+                            |mut receiver| async move {
+                                receiver.recv().await.map(|batch| (batch, receiver))
+                            },
+                        );
+                        let stream = Box::pin(RecordBatchStreamAdapter::new(
+                            file_schema.clone(),
+                            stream,
+                        ));
+                        writer_tasks.spawn(write_file(stream, location));
+                        entry.insert(sender)
+                    }
+                };
+                // The receiver is only dropped if writing the partition failed
+                if sender.send(Ok(batch)).await.is_err() {
+                    writer_failed = true;
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
+    }
+    .await;
+
+    // Make the writers of all partitions abort their files if anything failed
+    if demux_result.is_err() || writer_failed {
+        for sender in senders.values() {
+            let _ = sender
+                .send(Err(DataFusionError::Execution(
+                    PARTITION_WRITE_ABORTED.to_owned(),
+                )))
+                .await;
+        }
+    }
+    drop(senders);
+
+    let mut row_count = 0;
+    let mut writer_error = None;
+    while let Some(result) = writer_tasks.join_next().await {
+        match result {
+            Ok(Ok(rows)) => row_count += rows,
+            Ok(Err(e)) => match &e {
+                DataFusionError::Execution(msg) if msg == PARTITION_WRITE_ABORTED => {}
+                _ => {
+                    writer_error.get_or_insert(e);
+                }
+            },
+            Err(e) => {
+                writer_error.get_or_insert(DataFusionError::Execution(format!(
+                    "Writing partition panicked: {e}"
+                )));
+            }
+        }
+    }
+    demux_result?;
+    match writer_error {
+        Some(e) => Err(e),
+        None => Ok(row_count),
+    }
+}
+
+/// Splits the rows of `batch` by the values of its partition columns,
+/// returning for each distinct combination of values (in the order they
+/// first appear) the string representation of the values, which is `None`
+/// for null values, and the rows projected to the file columns.
+fn split_batch_by_partition(
+    batch: &RecordBatch,
+    partition_indices: &[usize],
+    file_indices: &[usize],
+    file_schema: &SchemaRef,
+) -> Result<Vec<(Vec<Option<String>>, RecordBatch)>> {
+    let partition_values = partition_indices
+        .iter()
+        .map(|idx| {
+            let values = cast(batch.column(*idx), &DataType::Utf8)?;
+            Ok(as_string_array(&values)?.clone())
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut partitions: Vec<(Vec<Option<String>>, Vec<u32>)> = vec![];
+    let mut partition_by_values: HashMap<Vec<Option<&str>>, usize> = HashMap::new();
+    for row in 0..batch.num_rows() {
+        let values = partition_values
+            .iter()
+            .map(|values| values.is_valid(row).then(|| values.value(row)))
+            .collect::<Vec<_>>();
+        let idx = *partition_by_values
+            .entry(values)
+            .or_insert_with_key(|values| {
+                let values = values.iter().map(|v| v.map(str::to_owned)).collect();
+                partitions.push((values, vec![]));
+                partitions.len() - 1
+            });
+        partitions[idx].1.push(row as u32);
+    }
+
+    partitions
+        .into_iter()
+        .map(|(values, rows)| {
+            let rows = UInt32Array::from(rows);
+            let columns = file_indices
+                .iter()
+                .map(|idx| take(batch.column(*idx), &rows, None))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            Ok((values, RecordBatch::try_new(file_schema.clone(), columns)?))
+        })
+        .collect()
 }
 
 #[cfg(test)]
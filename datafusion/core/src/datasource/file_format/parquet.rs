@@ -18,6 +18,8 @@
 //! Parquet format abstractions
 
 use std::any::Any;
+use std::fmt;
+use std::future::Future;
 use std::sync::Arc;
 
 use arrow::datatypes::SchemaRef;
@@ -26,28 +28,45 @@ use async_trait::async_trait;
 use bytes::{BufMut, BytesMut};
 use datafusion_common::DataFusionError;
 use datafusion_physical_expr::PhysicalExpr;
+use futures::future::BoxFuture;
 use futures::{StreamExt, TryStreamExt};
 use hashbrown::HashMap;
+use object_store::path::Path;
 use object_store::{ObjectMeta, ObjectStore};
-use parquet::arrow::parquet_to_arrow_schema;
+use parquet::arrow::{parquet_to_arrow_schema, AsyncArrowWriter};
+use parquet::basic::{BrotliLevel, Compression, GzipLevel, ZstdLevel};
 use parquet::file::footer::{decode_footer, decode_metadata};
 use parquet::file::metadata::ParquetMetaData;
+use parquet::file::properties::{
+    EnabledStatistics, WriterProperties, WriterPropertiesBuilder, WriterVersion,
+};
 use parquet::file::statistics::Statistics as ParquetStatistics;
 
-use super::FileFormat;
+use super::file_type::FileCompressionType;
 use super::FileScanConfig;
+use super::{
+    abort_on_error, create_writer, output_file_locations, write_hive_partitioned_files,
+    AbortableWrite, FileFormat, FileWriterMode,
+};
 use crate::arrow::array::{
     BooleanArray, Float32Array, Float64Array, Int32Array, Int64Array,
 };
 use crate::arrow::datatypes::DataType;
 use crate::config::ConfigOptions;
 
-use crate::datasource::physical_plan::{ParquetExec, SchemaAdapter};
+use crate::datasource::physical_plan::{
+    FileGroupDisplay, FileSinkConfig, ParquetExec, SchemaAdapter,
+};
 use crate::datasource::{create_max_min_accs, get_col_stats};
 use crate::error::Result;
 use crate::execution::context::SessionState;
 use crate::physical_plan::expressions::{MaxAccumulator, MinAccumulator};
-use crate::physical_plan::{Accumulator, ExecutionPlan, Statistics};
+use crate::physical_plan::insert::{DataSink, InsertExec};
+use crate::physical_plan::{
+    Accumulator, DisplayAs, DisplayFormatType, ExecutionPlan, SendableRecordBatchStream,
+    Statistics,
+};
+use datafusion_execution::TaskContext;
 
 /// The default file extension of parquet files
 pub const DEFAULT_PARQUET_EXTENSION: &str = ".parquet";
@@ -55,6 +74,10 @@ pub const DEFAULT_PARQUET_EXTENSION: &str = ".parquet";
 /// The number of files to read in parallel when inferring schema
 const SCHEMA_INFERENCE_CONCURRENCY: usize = 32;
 
+/// The size of the buffer in which [`ParquetSink`] accumulates encoded data
+/// before flushing it to the object store
+const PARQUET_WRITER_BUFFER_SIZE: usize = 10 * 1024 * 1024;
+
 /// The Apache Parquet `FileFormat` implementation
 ///
 /// Note it is recommended these are instead configured on the [`ConfigOptions`]
@@ -70,6 +93,8 @@ pub struct ParquetFormat {
     metadata_size_hint: Option<usize>,
     /// Override the global setting for `skip_metadata`
     skip_metadata: Option<bool>,
+    /// Properties used when writing parquet files
+    writer_properties: Option<WriterProperties>,
 }
 
 impl ParquetFormat {
@@ -124,6 +149,48 @@ impl ParquetFormat {
         self.skip_metadata
             .unwrap_or(config_options.execution.parquet.skip_metadata)
     }
+
+    /// Set the properties used when writing parquet files
+    ///
+    /// - If `None`, the properties are derived from `config_options`
+    pub fn with_writer_properties(
+        mut self,
+        writer_properties: Option<WriterProperties>,
+    ) -> Self {
+        self.writer_properties = writer_properties;
+        self
+    }
+
+    /// Returns the properties used when writing parquet files
+    pub fn writer_properties(
+        &self,
+        config_options: &ConfigOptions,
+    ) -> Result<WriterProperties> {
+        match &self.writer_properties {
+            Some(writer_properties) => Ok(writer_properties.clone()),
+            None => Ok(writer_properties_builder(config_options)?.build()),
+        }
+    }
+}
+
+/// Returns a [`WriterPropertiesBuilder`] configured with the parquet writer
+/// settings of `config_options` (`datafusion.execution.parquet.*`)
+pub(crate) fn writer_properties_builder(
+    config_options: &ConfigOptions,
+) -> Result<WriterPropertiesBuilder> {
+    let parquet = &config_options.execution.parquet;
+    Ok(WriterProperties::builder()
+        .set_data_page_size_limit(parquet.data_pagesize_limit)
+        .set_write_batch_size(parquet.write_batch_size)
+        .set_writer_version(parse_writer_version(&parquet.writer_version)?)
+        .set_compression(parse_compression_string(&parquet.compression)?)
+        .set_dictionary_enabled(parquet.dictionary_enabled)
+        .set_dictionary_page_size_limit(parquet.dictionary_page_size_limit)
+        .set_statistics_enabled(parse_statistics_string(&parquet.statistics_enabled)?)
+        .set_max_statistics_size(parquet.max_statistics_size)
+        .set_max_row_group_size(parquet.max_row_group_size)
+        .set_created_by(parquet.created_by.clone())
+        .set_data_page_row_count_limit(parquet.data_page_row_count_limit))
 }
 
 /// Clears all metadata (Schema level and field level) on an iterator
@@ -208,6 +275,155 @@ impl FileFormat for ParquetFormat {
             self.metadata_size_hint(state.config_options()),
         )))
     }
+
+    async fn create_writer_physical_plan(
+        &self,
+        input: Arc<dyn ExecutionPlan>,
+        state: &SessionState,
+        conf: FileSinkConfig,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let sink_schema = conf.output_schema().clone();
+        let writer_properties = self.writer_properties(state.config_options())?;
+        let sink = Arc::new(ParquetSink::new(conf, writer_properties));
+
+        Ok(Arc::new(InsertExec::new(input, sink, sink_schema)) as _)
+    }
+}
+
+/// Parses a parquet compression codec such as `snappy` or `zstd(3)`.
+///
+/// Codecs that support compression levels use the default level of the
+/// codec if none is given.
+pub(crate) fn parse_compression_string(value: &str) -> Result<Compression> {
+    let lowercase = value.trim().to_lowercase();
+    let (codec, level) = match lowercase.split_once('(') {
+        Some((codec, level)) => {
+            let level = level.strip_suffix(')').ok_or_else(|| {
+                DataFusionError::Plan(format!(
+                    "Invalid parquet compression \"{value}\": missing closing parenthesis"
+                ))
+            })?;
+            (codec.trim(), Some(level.trim()))
+        }
+        None => (lowercase.as_str(), None),
+    };
+    let parse_level = |level: &str| {
+        level.parse::<i64>().map_err(|_| {
+            DataFusionError::Plan(format!(
+                "Invalid parquet compression level \"{level}\" in \"{value}\""
+            ))
+        })
+    };
+    let compression = match (codec, level) {
+        ("uncompressed", None) => Compression::UNCOMPRESSED,
+        ("snappy", None) => Compression::SNAPPY,
+        ("lzo", None) => Compression::LZO,
+        ("lz4", None) => Compression::LZ4,
+        ("lz4_raw", None) => Compression::LZ4_RAW,
+        ("gzip", None) => Compression::GZIP(GzipLevel::default()),
+        ("gzip", Some(level)) => {
+            Compression::GZIP(GzipLevel::try_new(parse_level(level)? as u32)?)
+        }
+        ("brotli", None) => Compression::BROTLI(BrotliLevel::default()),
+        ("brotli", Some(level)) => {
+            Compression::BROTLI(BrotliLevel::try_new(parse_level(level)? as u32)?)
+        }
+        ("zstd", None) => Compression::ZSTD(ZstdLevel::default()),
+        ("zstd", Some(level)) => {
+            Compression::ZSTD(ZstdLevel::try_new(parse_level(level)? as i32)?)
+        }
+        _ => {
+            return Err(DataFusionError::Plan(format!(
+                "Unknown or unsupported parquet compression: {value}. Valid values are: \
+                uncompressed, snappy, gzip(level), lzo, brotli(level), lz4, zstd(level), and lz4_raw."
+            )))
+        }
+    };
+    Ok(compression)
+}
+
+/// Parses the parquet writer version, either `1.0` or `2.0`
+pub(crate) fn parse_writer_version(value: &str) -> Result<WriterVersion> {
+    match value.trim() {
+        "1.0" => Ok(WriterVersion::PARQUET_1_0),
+        "2.0" => Ok(WriterVersion::PARQUET_2_0),
+        _ => Err(DataFusionError::Plan(format!(
+            "Unknown or unsupported parquet writer version {value}. \
+            Valid values are 1.0 and 2.0"
+        ))),
+    }
+}
+
+/// Parses the level of statistics written to parquet files, one of
+/// `none`, `chunk` or `page`
+pub(crate) fn parse_statistics_string(value: &str) -> Result<EnabledStatistics> {
+    match value.trim().to_lowercase().as_str() {
+        "none" => Ok(EnabledStatistics::None),
+        "chunk" => Ok(EnabledStatistics::Chunk),
+        "page" => Ok(EnabledStatistics::Page),
+        _ => Err(DataFusionError::Plan(format!(
+            "Unknown or unsupported parquet statistics setting {value}. \
+            Valid values are none, chunk, and page"
+        ))),
+    }
+}
+
+/// Applies the user provided `options` (e.g. from `COPY ... TO ... (...)`)
+/// to a parquet [`WriterPropertiesBuilder`]
+pub(crate) fn apply_writer_options(
+    mut builder: WriterPropertiesBuilder,
+    options: &[(String, String)],
+) -> Result<WriterPropertiesBuilder> {
+    fn parse_usize(key: &str, value: &str) -> Result<usize> {
+        value.trim().parse().map_err(|_| {
+            DataFusionError::Plan(format!(
+                "Invalid value \"{value}\" for parquet option {key}, expected an unsigned integer"
+            ))
+        })
+    }
+    fn parse_bool(key: &str, value: &str) -> Result<bool> {
+        value.trim().to_lowercase().parse().map_err(|_| {
+            DataFusionError::Plan(format!(
+                "Invalid value \"{value}\" for parquet option {key}, expected true or false"
+            ))
+        })
+    }
+
+    for (key, value) in options {
+        builder = match key.as_str() {
+            "compression" => builder.set_compression(parse_compression_string(value)?),
+            "max_row_group_size" | "row_group_size" => {
+                builder.set_max_row_group_size(parse_usize(key, value)?)
+            }
+            "data_pagesize_limit" | "data_page_size_limit" => {
+                builder.set_data_page_size_limit(parse_usize(key, value)?)
+            }
+            "data_page_row_count_limit" => {
+                builder.set_data_page_row_count_limit(parse_usize(key, value)?)
+            }
+            "dictionary_page_size_limit" => {
+                builder.set_dictionary_page_size_limit(parse_usize(key, value)?)
+            }
+            "write_batch_size" => builder.set_write_batch_size(parse_usize(key, value)?),
+            "writer_version" => builder.set_writer_version(parse_writer_version(value)?),
+            "dictionary_enabled" => {
+                builder.set_dictionary_enabled(parse_bool(key, value)?)
+            }
+            "statistics_enabled" => {
+                builder.set_statistics_enabled(parse_statistics_string(value)?)
+            }
+            "max_statistics_size" => {
+                builder.set_max_statistics_size(parse_usize(key, value)?)
+            }
+            "created_by" => builder.set_created_by(value.clone()),
+            _ => {
+                return Err(DataFusionError::Plan(format!(
+                    "Unsupported option for parquet output: {key}"
+                )))
+            }
+        };
+    }
+    Ok(builder)
 }
 
 fn summarize_min_max(
@@ -543,6 +759,161 @@ async fn fetch_statistics(
     Ok(statistics)
 }
 
+/// Implements [`DataSink`] for writing to a parquet file.
+#[derive(Debug)]
+struct ParquetSink {
+    /// Config options for writing data
+    config: FileSinkConfig,
+    /// Properties of the written parquet files
+    writer_properties: WriterProperties,
+}
+
+impl DisplayAs for ParquetSink {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "ParquetSink(writer_mode={:?}, file_groups=",
+                    self.config.writer_mode
+                )?;
+                FileGroupDisplay(&self.config.file_groups).fmt_as(t, f)?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+impl ParquetSink {
+    fn new(config: FileSinkConfig, writer_properties: WriterProperties) -> Self {
+        Self {
+            config,
+            writer_properties,
+        }
+    }
+}
+
+#[async_trait]
+impl DataSink for ParquetSink {
+    async fn write_all(
+        &self,
+        mut data: SendableRecordBatchStream,
+        context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        if matches!(self.config.writer_mode, FileWriterMode::Append) {
+            return Err(DataFusionError::NotImplemented(
+                "Appending to existing parquet files is not supported".to_owned(),
+            ));
+        }
+
+        let object_store = context
+            .runtime_env()
+            .object_store(&self.config.object_store_url)?;
+
+        if !self.config.table_partition_cols.is_empty() {
+            return write_hive_partitioned_files(
+                data,
+                &self.config,
+                DEFAULT_PARQUET_EXTENSION,
+                |mut data, location| {
+                    let writer =
+                        self.create_writer(location, data.schema(), object_store.clone());
+                    async move {
+                        let (mut writer, abort) = writer.await?;
+                        let result = async {
+                            let mut row_count = 0;
+                            while let Some(batch) = data.next().await.transpose()? {
+                                row_count += batch.num_rows();
+                                writer.write(&batch).await?;
+                            }
+                            writer.close().await?;
+                            Ok(row_count as u64)
+                        }
+                        .await;
+                        abort_on_error(result, vec![abort]).await
+                    }
+                },
+            )
+            .await;
+        }
+
+        let locations = output_file_locations(&self.config, DEFAULT_PARQUET_EXTENSION)?;
+
+        let mut writers = vec![];
+        let mut aborts = vec![];
+        for location in locations {
+            let schema = self.config.output_schema.clone();
+            match self
+                .create_writer(location, schema, object_store.clone())
+                .await
+            {
+                Ok((writer, abort)) => {
+                    writers.push(writer);
+                    aborts.push(abort);
+                }
+                Err(e) => return abort_on_error(Err(e), aborts).await,
+            }
+        }
+
+        let result = async {
+            let num_partitions = writers.len();
+            let mut idx = 0;
+            let mut row_count = 0;
+            while let Some(batch) = data.next().await.transpose()? {
+                // Write data to files in a round robin fashion:
+                idx = (idx + 1) % num_partitions;
+                row_count += batch.num_rows();
+                writers[idx].write(&batch).await?;
+            }
+            // Write the footers and close the underlying writers:
+            for writer in writers {
+                writer.close().await?;
+            }
+            Ok(row_count as u64)
+        }
+        .await;
+        abort_on_error(result, aborts).await
+    }
+}
+
+impl ParquetSink {
+    /// Creates the parquet writer of the file at `location`, along with the
+    /// future aborting the write, as the parquet writer takes ownership of
+    /// the underlying writer
+    fn create_writer(
+        &self,
+        location: Path,
+        schema: SchemaRef,
+        object_store: Arc<dyn ObjectStore>,
+    ) -> impl Future<Output = Result<(ParquetFileWriter, BoxFuture<'static, Result<()>>)>>
+           + Send
+           + 'static {
+        let writer_mode = self.config.writer_mode;
+        let writer_properties = self.writer_properties.clone();
+        async move {
+            let writer = create_writer(
+                writer_mode,
+                FileCompressionType::UNCOMPRESSED,
+                location,
+                object_store,
+            )
+            .await?;
+            let abort = writer.abort_writer()?;
+            let writer = AsyncArrowWriter::try_new(
+                writer,
+                schema,
+                PARQUET_WRITER_BUFFER_SIZE,
+                Some(writer_properties),
+            )?;
+            Ok((writer, abort))
+        }
+    }
+}
+
+/// The parquet writer of one file written by [`ParquetSink`]
+type ParquetFileWriter =
+    AsyncArrowWriter<AbortableWrite<Box<dyn tokio::io::AsyncWrite + Send + Unpin>>>;
+
 #[cfg(test)]
 pub(crate) mod test_util {
     use super::*;
@@ -623,7 +994,9 @@ mod tests {
     use super::*;
 
     use crate::datasource::file_format::parquet::test_util::store_parquet;
+    use crate::datasource::listing::ListingTableUrl;
     use crate::datasource::physical_plan::get_scan_files;
+    use crate::physical_plan::memory::MemoryExec;
     use crate::physical_plan::metrics::MetricValue;
     use crate::prelude::{SessionConfig, SessionContext};
     use arrow::array::{Array, ArrayRef, StringArray};
@@ -632,7 +1005,7 @@ mod tests {
     use bytes::Bytes;
     use datafusion_common::cast::{
         as_binary_array, as_boolean_array, as_float32_array, as_float64_array,
-        as_int32_array, as_timestamp_nanosecond_array,
+        as_int32_array, as_timestamp_nanosecond_array, as_uint64_array,
     };
     use datafusion_common::ScalarValue;
     use futures::stream::BoxStream;
@@ -645,6 +1018,7 @@ mod tests {
     use parquet::arrow::ParquetRecordBatchStreamBuilder;
     use parquet::file::metadata::{ParquetColumnIndex, ParquetOffsetIndex};
     use parquet::file::page_index::index::Index;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
     use tokio::fs::File;
     use tokio::io::AsyncWrite;
 
@@ -1237,6 +1611,116 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn parse_compression() -> Result<()> {
+        assert_eq!(parse_compression_string("SNAPPY")?, Compression::SNAPPY);
+        assert_eq!(
+            parse_compression_string("zstd(10)")?,
+            Compression::ZSTD(ZstdLevel::try_new(10)?)
+        );
+        assert_eq!(
+            parse_compression_string("gzip")?,
+            Compression::GZIP(GzipLevel::default())
+        );
+
+        let err = parse_compression_string("zstd(100)").unwrap_err();
+        assert!(err.to_string().contains("valid compression range"), "{err}");
+        let err = parse_compression_string("snappy(1)").unwrap_err();
+        assert!(err.to_string().contains("unsupported parquet compression"));
+        let err = parse_compression_string("zstd(3").unwrap_err();
+        assert!(err.to_string().contains("missing closing parenthesis"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn write_parquet_with_session_config() -> Result<()> {
+        let config = SessionConfig::new()
+            .set_str("datafusion.execution.parquet.compression", "snappy")
+            .set_usize("datafusion.execution.parquet.max_row_group_size", 2)
+            .set_str(
+                "datafusion.execution.parquet.created_by",
+                "parquet sink test",
+            );
+        let session_ctx = SessionContext::with_config(config);
+        let state = session_ctx.state();
+
+        let c1: ArrayRef = Arc::new(Int64Array::from(vec![1, 2, 3, 4, 5]));
+        let batch = RecordBatch::try_from_iter(vec![("c1", c1)])?;
+        let schema = batch.schema();
+        let input = Arc::new(MemoryExec::try_new(&[vec![batch]], schema.clone(), None)?);
+
+        let tmp_dir = tempfile::TempDir::new()?;
+        let table_path = ListingTableUrl::parse(tmp_dir.path().to_str().unwrap())?;
+        let config = FileSinkConfig {
+            object_store_url: table_path.object_store(),
+            file_groups: vec![],
+            table_paths: vec![table_path],
+            output_schema: schema,
+            table_partition_cols: vec![],
+            writer_mode: FileWriterMode::PutMultipart,
+            single_file_output: false,
+        };
+        let plan = ParquetFormat::default()
+            .create_writer_physical_plan(input, &state, config)
+            .await?;
+        let result = collect(plan, session_ctx.task_ctx()).await?;
+        let count = as_uint64_array(result[0].column(0))?;
+        assert_eq!(count.value(0), 5);
+
+        // a single new file is written to the table directory
+        let files = std::fs::read_dir(tmp_dir.path())?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].extension().unwrap(), "parquet");
+
+        let reader = SerializedFileReader::new(std::fs::File::open(&files[0])?)?;
+        let metadata = reader.metadata();
+        assert_eq!(
+            metadata.file_metadata().created_by(),
+            Some("parquet sink test")
+        );
+        assert_eq!(metadata.file_metadata().num_rows(), 5);
+        assert_eq!(metadata.num_row_groups(), 3);
+        for row_group in metadata.row_groups() {
+            assert_eq!(row_group.column(0).compression(), Compression::SNAPPY);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn write_parquet_append_not_supported() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let state = session_ctx.state();
+
+        let c1: ArrayRef = Arc::new(Int64Array::from(vec![1, 2, 3]));
+        let batch = RecordBatch::try_from_iter(vec![("c1", c1)])?;
+        let schema = batch.schema();
+        let input = Arc::new(MemoryExec::try_new(&[vec![batch]], schema.clone(), None)?);
+
+        let tmp_dir = tempfile::TempDir::new()?;
+        let table_path = ListingTableUrl::parse(tmp_dir.path().to_str().unwrap())?;
+        let config = FileSinkConfig {
+            object_store_url: table_path.object_store(),
+            file_groups: vec![],
+            table_paths: vec![table_path],
+            output_schema: schema,
+            table_partition_cols: vec![],
+            writer_mode: FileWriterMode::Append,
+            single_file_output: false,
+        };
+        let plan = ParquetFormat::default()
+            .create_writer_physical_plan(input, &state, config)
+            .await?;
+        let err = collect(plan, session_ctx.task_ctx()).await.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Appending to existing parquet files is not supported"),
+            "{err}"
+        );
+        Ok(())
+    }
+
     fn check_page_index_validation(
         page_index: Option<&ParquetColumnIndex>,
         offset_index: Option<&ParquetOffsetIndex>,
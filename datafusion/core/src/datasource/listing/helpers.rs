@@ -17,6 +17,7 @@
 
 //! Helper functions for the table implementation
 
+use std::borrow::Cow;
 use std::sync::Arc;
 
 use arrow::compute::{and, cast, prep_null_mask_filter};
@@ -44,6 +45,48 @@ use datafusion_physical_expr::create_physical_expr;
 use datafusion_physical_expr::execution_props::ExecutionProps;
 use object_store::path::Path;
 use object_store::{ObjectMeta, ObjectStore};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
+
+/// The directory name of the partition holding the rows whose value of the
+/// partition column is null, as written by Hive
+pub(crate) const HIVE_DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// The characters escaped in the names and values of partition directories:
+/// the ones escaped by Hive, and the ones not allowed in object store paths
+const PARTITION_ESCAPED: &AsciiSet = &CONTROLS
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'\'')
+    .add(b'*')
+    .add(b'/')
+    .add(b':')
+    .add(b'=')
+    .add(b'?')
+    .add(b'\\')
+    .add(b'{')
+    .add(b'}')
+    .add(b'[')
+    .add(b']')
+    .add(b'^')
+    .add(b'`')
+    .add(b'<')
+    .add(b'>')
+    .add(b'|')
+    .add(b'~');
+
+/// Escapes a partition column name or value for use in the directory name
+/// of a partition, the way Hive does
+pub(crate) fn escape_partition_value(value: &str) -> String {
+    utf8_percent_encode(value, PARTITION_ESCAPED).to_string()
+}
+
+/// Returns the value of a partition directory named `{column}={value}`,
+/// which is `None` for the default partition
+fn unescape_partition_value(value: &str) -> Option<Cow<'_, str>> {
+    (value != HIVE_DEFAULT_PARTITION)
+        .then(|| percent_decode_str(value).decode_utf8_lossy())
+}
 
 /// Check whether the given expression can be resolved using only the columns `col_names`.
 /// This means that if this function returns true:
@@ -239,7 +282,7 @@ async fn prune_partitions(
 
         let mut builders = builders.iter_mut();
         for (p, b) in parsed.iter().zip(&mut builders) {
-            b.append_value(p);
+            b.append_option(unescape_partition_value(p));
         }
         builders.for_each(|b| b.append_null());
     }
@@ -345,9 +388,14 @@ pub async fn pruned_partition_list<'a>(
                 .into_iter()
                 .flatten()
                 .zip(partition_cols)
-                .map(|(parsed, (_, datatype))| {
-                    ScalarValue::try_from_string(parsed.to_string(), datatype)
-                })
+                .map(
+                    |(parsed, (_, datatype))| match unescape_partition_value(parsed) {
+                        Some(value) => {
+                            ScalarValue::try_from_string(value.into_owned(), datatype)
+                        }
+                        None => ScalarValue::try_from(datatype),
+                    },
+                )
                 .collect::<Result<Vec<_>>>()?;
 
             let files = match partition.files {
@@ -397,7 +445,9 @@ where
     let mut part_values = vec![];
     for (part, pn) in subpath.zip(table_partition_cols) {
         match part.split_once('=') {
-            Some((name, val)) if name == pn => part_values.push(val),
+            Some((name, val)) if percent_decode_str(name).decode_utf8_lossy() == pn => {
+                part_values.push(val)
+            }
             _ => {
                 debug!(
                     "Ignoring file: file_path='{}', table_path='{}', part='{}', partition_col='{}'",
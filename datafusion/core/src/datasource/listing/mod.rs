@@ -30,8 +30,11 @@ use object_store::{path::Path, ObjectMeta};
 use std::pin::Pin;
 use std::sync::Arc;
 
+pub(crate) use self::helpers::{escape_partition_value, HIVE_DEFAULT_PARTITION};
 pub use self::url::ListingTableUrl;
-pub use table::{ListingOptions, ListingTable, ListingTableConfig};
+pub use table::{
+    ListingOptions, ListingTable, ListingTableConfig, ListingTableInsertMode,
+};
 
 /// Stream of files get listed from object store
 pub type PartitionedFileStream =
@@ -17,6 +17,7 @@
 
 //! The table implementation.
 
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::str::FromStr;
 use std::{any::Any, sync::Arc};
 
@@ -25,15 +26,20 @@ use arrow::datatypes::{DataType, Field, SchemaBuilder, SchemaRef};
 use arrow_schema::Schema;
 use async_trait::async_trait;
 use dashmap::DashMap;
-use datafusion_common::{SchemaExt, ToDFSchema};
+use datafusion_common::{ScalarValue, SchemaExt, ToDFSchema};
 use datafusion_expr::expr::Sort;
+use datafusion_expr::SchemaChange;
 use datafusion_optimizer::utils::conjunction;
-use datafusion_physical_expr::{create_physical_expr, LexOrdering, PhysicalSortExpr};
+use datafusion_physical_expr::expressions::{Column, Literal};
+use datafusion_physical_expr::{
+    create_physical_expr, LexOrdering, PhysicalExpr, PhysicalSortExpr,
+};
 use futures::{future, stream, StreamExt, TryStreamExt};
 use object_store::path::Path;
 use object_store::ObjectMeta;
 
 use crate::datasource::file_format::file_type::{FileCompressionType, FileType};
+use crate::datasource::file_format::FileWriterMode;
 use crate::datasource::physical_plan::{FileScanConfig, FileSinkConfig};
 use crate::datasource::{
     file_format::{
@@ -50,7 +56,10 @@ use crate::{
     error::{DataFusionError, Result},
     execution::context::SessionState,
     logical_expr::Expr,
-    physical_plan::{empty::EmptyExec, project_schema, ExecutionPlan, Statistics},
+    physical_plan::{
+        empty::EmptyExec, project_schema, projection::ProjectionExec, union::UnionExec,
+        ExecutionPlan, Statistics,
+    },
 };
 
 use super::PartitionedFile;
@@ -207,6 +216,36 @@ impl ListingTableConfig {
     }
 }
 
+/// Defines how `INSERT INTO` writes to a [`ListingTable`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListingTableInsertMode {
+    /// Data is appended to the existing file of the table. Only supported
+    /// by formats that can be appended to, e.g. CSV and JSON
+    AppendToFile,
+    /// Data is written to new files in the table directory, leaving the
+    /// existing files untouched. For partitioned tables the rows are written
+    /// to the hive style `col=value/` directories of their partition values
+    AppendNewFiles,
+    /// Inserting into the table is not allowed
+    Error,
+}
+
+impl FromStr for ListingTableInsertMode {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "append_to_file" => Ok(ListingTableInsertMode::AppendToFile),
+            "append_new_files" => Ok(ListingTableInsertMode::AppendNewFiles),
+            "error" => Ok(ListingTableInsertMode::Error),
+            _ => Err(DataFusionError::Plan(format!(
+                "Unknown or unsupported insert mode {s}. Supported options are \
+                append_to_file, append_new_files, and error."
+            ))),
+        }
+    }
+}
+
 /// Options for creating a [`ListingTable`]
 #[derive(Clone, Debug)]
 pub struct ListingOptions {
@@ -245,6 +284,8 @@ pub struct ListingOptions {
     /// In order to support infinite inputs, DataFusion may adjust query
     /// plans (e.g. joins) to run the given query in full pipelining mode.
     pub infinite_source: bool,
+    /// This setting controls how inserts to the table are handled
+    pub insert_mode: ListingTableInsertMode,
 }
 
 impl ListingOptions {
@@ -254,7 +295,15 @@ impl ListingOptions {
     /// - no input partition to discover
     /// - one target partition
     /// - stat collection
+    /// - inserts append to the existing file for CSV and JSON, and write
+    ///   new files for the formats that can not be appended to
     pub fn new(format: Arc<dyn FileFormat>) -> Self {
+        let insert_mode =
+            if format.as_any().is::<CsvFormat>() || format.as_any().is::<JsonFormat>() {
+                ListingTableInsertMode::AppendToFile
+            } else {
+                ListingTableInsertMode::AppendNewFiles
+            };
         Self {
             file_extension: String::new(),
             format,
@@ -263,6 +312,7 @@ impl ListingOptions {
             target_partitions: 1,
             file_sort_order: vec![],
             infinite_source: false,
+            insert_mode,
         }
     }
 
@@ -431,6 +481,24 @@ impl ListingOptions {
         self
     }
 
+    /// Set how inserts to the table are handled on [`ListingOptions`] and returns self.
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use datafusion::datasource::{listing::{ListingOptions, ListingTableInsertMode}, file_format::parquet::ParquetFormat};
+    ///
+    /// let listing_options = ListingOptions::new(Arc::new(
+    ///     ParquetFormat::default()
+    ///   ))
+    ///   .with_insert_mode(ListingTableInsertMode::AppendNewFiles);
+    ///
+    /// assert_eq!(listing_options.insert_mode, ListingTableInsertMode::AppendNewFiles);
+    /// ```
+    pub fn with_insert_mode(mut self, insert_mode: ListingTableInsertMode) -> Self {
+        self.insert_mode = insert_mode;
+        self
+    }
+
     /// Infer the schema of the files at the given path on the provided object store.
     /// The inferred schema does not include the partitioning columns.
     ///
@@ -561,6 +629,9 @@ pub struct ListingTable {
     definition: Option<String>,
     collected_statistics: StatisticsCache,
     infinite_source: bool,
+    /// The files that contained each dropped column when it was dropped,
+    /// which read the column as null if it is added again
+    dropped_column_files: HashMap<String, HashSet<Path>>,
 }
 
 impl ListingTable {
@@ -583,10 +654,11 @@ impl ListingTable {
             DataFusionError::Internal("No ListingOptions provided".into())
         })?;
 
-        // Add the partition columns to the file schema
+        // Add the partition columns to the file schema, they are null for
+        // the files of the default partition
         let mut builder = SchemaBuilder::from(file_schema.fields());
         for (part_col_name, part_col_type) in &options.table_partition_cols {
-            builder.push(Field::new(part_col_name, part_col_type.clone(), false));
+            builder.push(Field::new(part_col_name, part_col_type.clone(), true));
         }
         let infinite_source = options.infinite_source;
 
@@ -598,6 +670,7 @@ impl ListingTable {
             definition: None,
             collected_statistics: Default::default(),
             infinite_source,
+            dropped_column_files: HashMap::new(),
         };
 
         Ok(table)
@@ -723,25 +796,59 @@ impl TableProvider for ListingTable {
         } else {
             return Ok(Arc::new(EmptyExec::new(false, Arc::new(Schema::empty()))));
         };
+        // Files that contained a dropped column which was added again read
+        // it as null, so they are scanned separately without it
+        let mut file_groups = BTreeMap::<Vec<usize>, Vec<Vec<PartitionedFile>>>::new();
+        for group in partitioned_file_lists {
+            let mut files_by_hidden_columns = BTreeMap::<_, Vec<_>>::new();
+            for file in group {
+                files_by_hidden_columns
+                    .entry(self.hidden_file_columns(&file))
+                    .or_default()
+                    .push(file);
+            }
+            for (hidden_columns, files) in files_by_hidden_columns {
+                file_groups.entry(hidden_columns).or_default().push(files);
+            }
+        }
+        let statistics = if file_groups
+            .keys()
+            .all(|hidden_columns| hidden_columns.is_empty())
+        {
+            statistics
+        } else {
+            Statistics::default()
+        };
+
         // create the execution plan
-        self.options
-            .format
-            .create_physical_plan(
-                state,
-                FileScanConfig {
-                    object_store_url,
-                    file_schema: Arc::clone(&self.file_schema),
-                    file_groups: partitioned_file_lists,
-                    statistics,
-                    projection: projection.cloned(),
-                    limit,
-                    output_ordering: self.try_create_output_ordering()?,
-                    table_partition_cols,
-                    infinite_source: self.infinite_source,
-                },
-                filters.as_ref(),
-            )
-            .await
+        let mut plans = vec![];
+        for (hidden_columns, file_groups) in file_groups {
+            let config = FileScanConfig {
+                object_store_url: object_store_url.clone(),
+                file_schema: Arc::clone(&self.file_schema),
+                file_groups,
+                statistics: statistics.clone(),
+                projection: projection.cloned(),
+                limit,
+                output_ordering: self.try_create_output_ordering()?,
+                table_partition_cols: table_partition_cols.clone(),
+                infinite_source: self.infinite_source,
+            };
+            let plan = if hidden_columns.is_empty() {
+                self.options
+                    .format
+                    .create_physical_plan(state, config, filters.as_ref())
+                    .await?
+            } else {
+                self.scan_hiding_columns(state, config, &hidden_columns)
+                    .await?
+            };
+            plans.push(plan);
+        }
+        if plans.len() == 1 {
+            return Ok(plans.remove(0));
+        }
+        Ok(Arc::new(UnionExec::new(plans)))
     }
 
     fn supports_filter_pushdown(
@@ -791,34 +898,66 @@ impl TableProvider for ListingTable {
         }
 
         let table_path = &self.table_paths()[0];
-        // Get the object store for the table path.
-        let store = state.runtime_env().object_store(table_path)?;
-
-        let file_list_stream = pruned_partition_list(
-            store.as_ref(),
-            table_path,
-            &[],
-            &self.options.file_extension,
-            &self.options.table_partition_cols,
-        )
-        .await?;
-
-        let file_groups = file_list_stream.try_collect::<Vec<_>>().await?;
 
-        if file_groups.len() > 1 {
-            return Err(DataFusionError::Plan(
-                "Datafusion currently supports tables from single partition and/or file."
-                    .to_owned(),
-            ));
-        }
+        let (file_groups, writer_mode) = match self.options.insert_mode {
+            // Each partition is a directory of its own, to which the rows
+            // are written as new files
+            ListingTableInsertMode::AppendToFile
+                if !self.options.table_partition_cols.is_empty() =>
+            {
+                return Err(DataFusionError::Plan(
+                    "Appending to existing files of a partitioned table is not supported, use insert mode append_new_files instead".to_owned(),
+                ))
+            }
+            ListingTableInsertMode::AppendToFile => {
+                // Get the object store for the table path.
+                let store = state.runtime_env().object_store(table_path)?;
+
+                let file_list_stream = pruned_partition_list(
+                    store.as_ref(),
+                    table_path,
+                    &[],
+                    &self.options.file_extension,
+                    &self.options.table_partition_cols,
+                )
+                .await?;
+
+                let file_groups = file_list_stream.try_collect::<Vec<_>>().await?;
+
+                if file_groups.len() > 1 {
+                    return Err(DataFusionError::Plan(
+                        "Datafusion currently supports tables from single partition and/or file."
+                            .to_owned(),
+                    ));
+                }
+                (file_groups, FileWriterMode::Append)
+            }
+            // New files are written next to the existing ones, so there
+            // is no need to list them
+            ListingTableInsertMode::AppendNewFiles => {
+                if !table_path.is_collection() {
+                    return Err(DataFusionError::Plan(format!(
+                        "Inserting new files requires the table location to be a directory, got {table_path}"
+                    )));
+                }
+                (vec![], FileWriterMode::PutMultipart)
+            }
+            ListingTableInsertMode::Error => {
+                return Err(DataFusionError::Plan(
+                    "Inserting into this table is not allowed".to_owned(),
+                ))
+            }
+        };
 
         // Sink related option, apart from format
         let config = FileSinkConfig {
-            object_store_url: self.table_paths()[0].object_store(),
+            object_store_url: table_path.object_store(),
             file_groups,
+            table_paths: self.table_paths().clone(),
             output_schema: self.schema(),
             table_partition_cols: self.options.table_partition_cols.clone(),
-            writer_mode: crate::datasource::file_format::FileWriterMode::Append,
+            writer_mode,
+            single_file_output: false,
         };
 
         self.options()
@@ -826,9 +965,183 @@ impl TableProvider for ListingTable {
             .create_writer_physical_plan(input, state, config)
             .await
     }
+
+    /// Returns a new [`ListingTable`] for the same files, with the schema
+    /// changed by `change`.
+    ///
+    /// This is only supported for formats whose files refer to columns by
+    /// name, so that the columns are read from the files that contain
+    /// them, and added columns are null when reading older files. Columns
+    /// cannot be renamed, as the files still refer to their old names.
+    async fn alter_schema(
+        &self,
+        state: &SessionState,
+        change: &SchemaChange,
+    ) -> Result<Arc<dyn TableProvider>> {
+        let format = self.options.format.as_any();
+        if !format.is::<ParquetFormat>() && !format.is::<JsonFormat>() {
+            return Err(DataFusionError::NotImplemented(
+                "Altering the schema of a listing table is only supported for parquet and json files".to_owned(),
+            ));
+        }
+
+        let mut fields = self.file_schema.fields().to_vec();
+        let mut options = self.options.clone();
+        let mut dropped_column_files = self.dropped_column_files.clone();
+        match change {
+            SchemaChange::AddColumn(field) => {
+                if !field.is_nullable() {
+                    return Err(DataFusionError::Plan(format!(
+                        "Cannot add non-nullable column {} to a listing table, as existing files do not contain it",
+                        field.name()
+                    )));
+                }
+                // The rows appended to a file that contains the values of the
+                // dropped column could not be told apart from the older ones
+                if options.insert_mode == ListingTableInsertMode::AppendToFile
+                    && dropped_column_files.contains_key(field.name())
+                {
+                    return Err(DataFusionError::Plan(format!(
+                        "Cannot add dropped column {} again to a listing table that appends to its files, use insert mode append_new_files instead",
+                        field.name()
+                    )));
+                }
+                fields.push(Arc::new(field.clone()));
+            }
+            SchemaChange::DropColumn(name) => {
+                let idx = match self.file_schema.index_of(name) {
+                    Ok(idx) => idx,
+                    Err(_) => {
+                        return Err(DataFusionError::Plan(format!(
+                            "Cannot drop partition column {name}"
+                        )))
+                    }
+                };
+                if fields.len() == 1 {
+                    return Err(DataFusionError::Plan(format!(
+                        "Cannot drop column {name}, which is the only column of the table"
+                    )));
+                }
+                fields.remove(idx);
+                // The values of the column in the existing files must not be
+                // read if a column with the same name is added later
+                let files = dropped_column_files.entry(name.clone()).or_default();
+                for table_path in &self.table_paths {
+                    let store = state.runtime_env().object_store(table_path)?;
+                    let mut file_list = pruned_partition_list(
+                        store.as_ref(),
+                        table_path,
+                        &[],
+                        &self.options.file_extension,
+                        &self.options.table_partition_cols,
+                    )
+                    .await?;
+                    while let Some(file) = file_list.try_next().await? {
+                        files.insert(file.object_meta.location);
+                    }
+                }
+                // The files are still sorted by the columns before the
+                // dropped one
+                for sort_order in &mut options.file_sort_order {
+                    let len = sort_order
+                        .iter()
+                        .position(|expr| {
+                            expr.to_columns().map_or(true, |columns| {
+                                columns.iter().any(|column| &column.name == name)
+                            })
+                        })
+                        .unwrap_or(sort_order.len());
+                    sort_order.truncate(len);
+                }
+                options
+                    .file_sort_order
+                    .retain(|sort_order| !sort_order.is_empty());
+            }
+            SchemaChange::RenameColumn { .. } => {
+                return Err(DataFusionError::NotImplemented(
+                    "Renaming the columns of a listing table is not supported, as the files refer to columns by name".to_owned(),
+                ));
+            }
+        }
+
+        let file_schema = Arc::new(Schema::new_with_metadata(
+            fields,
+            self.file_schema.metadata().clone(),
+        ));
+        let config = ListingTableConfig::new_with_multi_paths(self.table_paths.clone())
+            .with_listing_options(options)
+            .with_schema(file_schema);
+        let mut table =
+            ListingTable::try_new(config)?.with_definition(self.definition.clone());
+        table.dropped_column_files = dropped_column_files;
+        Ok(Arc::new(table))
+    }
 }
 
 impl ListingTable {
+    /// The indices of the file columns that `file` contained when they were
+    /// dropped, and which were added again since
+    fn hidden_file_columns(&self, file: &PartitionedFile) -> Vec<usize> {
+        self.file_schema
+            .fields()
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| {
+                self.dropped_column_files
+                    .get(field.name())
+                    .is_some_and(|files| files.contains(&file.object_meta.location))
+            })
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Creates the scan described by `config`, reading the file columns at
+    /// `hidden_columns` as nulls rather than from the files
+    async fn scan_hiding_columns(
+        &self,
+        state: &SessionState,
+        config: FileScanConfig,
+        hidden_columns: &[usize],
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let file_indices = (0..self.file_schema.fields().len())
+            .filter(|idx| !hidden_columns.contains(idx))
+            .collect::<Vec<_>>();
+        let file_schema = Arc::new(self.file_schema.project(&file_indices)?);
+
+        let table_projection = config
+            .projection
+            .clone()
+            .unwrap_or_else(|| (0..self.table_schema.fields().len()).collect());
+        let mut projection = vec![];
+        let mut exprs: Vec<(Arc<dyn PhysicalExpr>, String)> = vec![];
+        for idx in table_projection {
+            let field = self.table_schema.field(idx);
+            let expr: Arc<dyn PhysicalExpr> = if hidden_columns.contains(&idx) {
+                Arc::new(Literal::new(ScalarValue::try_from(field.data_type())?))
+            } else {
+                let column = Column::new(field.name(), projection.len());
+                projection
+                    .push(idx - hidden_columns.iter().filter(|h| **h < idx).count());
+                Arc::new(column)
+            };
+            exprs.push((expr, field.name().clone()));
+        }
+
+        let config = FileScanConfig {
+            file_schema,
+            projection: Some(projection),
+            statistics: Statistics::default(),
+            output_ordering: vec![],
+            ..config
+        };
+        let scan = self
+            .options
+            .format
+            .create_physical_plan(state, config, None)
+            .await?;
+        Ok(Arc::new(ProjectionExec::try_new(exprs, scan)?))
+    }
+
     /// Get the list of files for a scan as well as the file level statistics.
     /// The list is grouped to let the execution plan know how the files should
     /// be distributed to different threads / executors.
@@ -1215,6 +1528,49 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn alter_schema_of_csv_table() -> Result<()> {
+        let ctx = SessionContext::new();
+        register_test_store(&ctx, &[("table/file.csv", 100)]);
+
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Int32, true),
+        ]);
+        let table_path = ListingTableUrl::parse("test:///table/").unwrap();
+        let listing_options = CsvReadOptions::new()
+            .to_listing_options(&SessionConfig::new().with_target_partitions(1));
+        let config = ListingTableConfig::new(table_path)
+            .with_listing_options(listing_options)
+            .with_schema(Arc::new(schema));
+        let table = ListingTable::try_new(config)?;
+
+        let changes = [
+            SchemaChange::AddColumn(Field::new("c", DataType::Int32, true)),
+            SchemaChange::DropColumn("b".to_owned()),
+            SchemaChange::RenameColumn {
+                old_name: "a".to_owned(),
+                new_name: "c".to_owned(),
+            },
+        ];
+        for change in &changes {
+            let error = table
+                .alter_schema(&ctx.state(), change)
+                .await
+                .err()
+                .unwrap();
+            match error {
+                DataFusionError::NotImplemented(msg) => assert_eq!(
+                    msg,
+                    "Altering the schema of a listing table is only supported for parquet and json files"
+                ),
+                val => return Err(val),
+            }
+        }
+
+        Ok(())
+    }
+
     #[rstest]
     #[tokio::test]
     async fn unbounded_csv_table(
@@ -1683,4 +2039,228 @@ mod tests {
         // Return Ok if the function
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_insert_into_partitioned_table() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let tmp_dir = TempDir::new()?;
+        let table_path = ListingTableUrl::parse(tmp_dir.path().to_str().unwrap())?;
+
+        let file_schema =
+            Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let options = ListingOptions::new(Arc::new(CsvFormat::default()))
+            .with_file_extension(".csv")
+            .with_table_partition_cols(vec![
+                ("year".to_string(), DataType::Int32),
+                ("month".to_string(), DataType::Utf8),
+            ])
+            .with_insert_mode(ListingTableInsertMode::AppendNewFiles);
+        let config = ListingTableConfig::new(table_path)
+            .with_listing_options(options)
+            .with_schema(file_schema);
+        session_ctx.register_table("t", Arc::new(ListingTable::try_new(config)?))?;
+
+        let res = session_ctx
+            .sql("INSERT INTO t VALUES (1, 2022, '12'), (2, 2023, '01'), (3, 2022, '12')")
+            .await?
+            .collect()
+            .await?;
+        let expected = vec![
+            "+-------+",
+            "| count |",
+            "+-------+",
+            "| 3     |",
+            "+-------+",
+        ];
+        assert_batches_eq!(expected, &res);
+
+        // Each partition is written to a single file of its directory
+        let mut partitions = std::fs::read_dir(tmp_dir.path())?
+            .map(|year| {
+                let year = year?;
+                let months = std::fs::read_dir(year.path())?
+                    .map(|month| {
+                        let month = month?;
+                        let files = std::fs::read_dir(month.path())?.count();
+                        Ok(format!(
+                            "{}/{}: {files}",
+                            year.file_name().to_string_lossy(),
+                            month.file_name().to_string_lossy()
+                        ))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(months)
+            })
+            .collect::<Result<Vec<_>>>()?
+            .concat();
+        partitions.sort();
+        assert_eq!(
+            partitions,
+            vec!["year=2022/month=12: 1", "year=2023/month=01: 1"]
+        );
+
+        // The partition columns are not part of the written files
+        let path = tmp_dir.path().join("year=2022").join("month=12");
+        let file = std::fs::read_dir(path)?.next().unwrap()?;
+        let contents = std::fs::read_to_string(file.path())?;
+        assert_eq!(contents, "a\n1\n3\n");
+
+        let res = session_ctx
+            .sql("SELECT * FROM t ORDER BY a")
+            .await?
+            .collect()
+            .await?;
+        let expected = vec![
+            "+---+------+-------+",
+            "| a | year | month |",
+            "+---+------+-------+",
+            "| 1 | 2022 | 12    |",
+            "| 2 | 2023 | 01    |",
+            "| 3 | 2022 | 12    |",
+            "+---+------+-------+",
+        ];
+        assert_batches_eq!(expected, &res);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_insert_into_partitioned_table_escaped_values() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let tmp_dir = TempDir::new()?;
+        let table_path = ListingTableUrl::parse(tmp_dir.path().to_str().unwrap())?;
+
+        let file_schema =
+            Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let options = ListingOptions::new(Arc::new(CsvFormat::default()))
+            .with_file_extension(".csv")
+            .with_table_partition_cols(vec![("p".to_string(), DataType::Utf8)])
+            .with_insert_mode(ListingTableInsertMode::AppendNewFiles);
+        let config = ListingTableConfig::new(table_path)
+            .with_listing_options(options)
+            .with_schema(file_schema);
+        session_ctx.register_table("t", Arc::new(ListingTable::try_new(config)?))?;
+
+        session_ctx
+            .sql("INSERT INTO t VALUES (1, 'a/b'), (2, '50%'), (3, 'x=y'), (4, NULL)")
+            .await?
+            .collect()
+            .await?;
+
+        // The values are escaped the way Hive does, and nulls are written to
+        // the default partition
+        let mut partitions = std::fs::read_dir(tmp_dir.path())?
+            .map(|p| Ok(p?.file_name().to_string_lossy().into_owned()))
+            .collect::<Result<Vec<_>>>()?;
+        partitions.sort();
+        assert_eq!(
+            partitions,
+            vec![
+                "p=50%25",
+                "p=__HIVE_DEFAULT_PARTITION__",
+                "p=a%2Fb",
+                "p=x%3Dy"
+            ]
+        );
+
+        let res = session_ctx
+            .sql("SELECT * FROM t ORDER BY a")
+            .await?
+            .collect()
+            .await?;
+        let expected = vec![
+            "+---+-----+",
+            "| a | p   |",
+            "+---+-----+",
+            "| 1 | a/b |",
+            "| 2 | 50% |",
+            "| 3 | x=y |",
+            "| 4 |     |",
+            "+---+-----+",
+        ];
+        assert_batches_eq!(expected, &res);
+
+        let res = session_ctx
+            .sql("SELECT a FROM t WHERE p IS NULL OR p = '50%' ORDER BY a")
+            .await?
+            .collect()
+            .await?;
+        let expected = vec!["+---+", "| a |", "+---+", "| 2 |", "| 4 |", "+---+"];
+        assert_batches_eq!(expected, &res);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_append_to_partitioned_table_file() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let tmp_dir = TempDir::new()?;
+        let table_path = ListingTableUrl::parse(tmp_dir.path().to_str().unwrap())?;
+
+        let file_schema =
+            Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let options = ListingOptions::new(Arc::new(CsvFormat::default()))
+            .with_table_partition_cols(vec![("part".to_string(), DataType::Utf8)]);
+        let config = ListingTableConfig::new(table_path)
+            .with_listing_options(options)
+            .with_schema(file_schema);
+        session_ctx.register_table("t", Arc::new(ListingTable::try_new(config)?))?;
+
+        let err = session_ctx
+            .sql("INSERT INTO t VALUES (1, 'x')")
+            .await?
+            .collect()
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Appending to existing files of a partitioned table is not supported, use insert mode append_new_files instead"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_insert_into_parquet_table_with_default_options() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let tmp_dir = TempDir::new()?;
+        let table_path =
+            ListingTableUrl::parse(format!("{}/", tmp_dir.path().to_str().unwrap()))?;
+
+        // Parquet files can not be appended to, so new files are written
+        let options = ListingOptions::new(Arc::new(ParquetFormat::default()))
+            .with_file_extension(".parquet");
+        assert_eq!(options.insert_mode, ListingTableInsertMode::AppendNewFiles);
+
+        let file_schema =
+            Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let config = ListingTableConfig::new(table_path)
+            .with_listing_options(options)
+            .with_schema(file_schema);
+        session_ctx.register_table("t", Arc::new(ListingTable::try_new(config)?))?;
+
+        session_ctx
+            .sql("INSERT INTO t VALUES (1), (2)")
+            .await?
+            .collect()
+            .await?;
+        session_ctx
+            .sql("INSERT INTO t VALUES (3)")
+            .await?
+            .collect()
+            .await?;
+        assert_eq!(std::fs::read_dir(tmp_dir.path())?.count(), 2);
+
+        let res = session_ctx
+            .sql("SELECT * FROM t ORDER BY a")
+            .await?
+            .collect()
+            .await?;
+        let expected = vec![
+            "+---+", "| a |", "+---+", "| 1 |", "| 2 |", "| 3 |", "+---+",
+        ];
+        assert_batches_eq!(expected, &res);
+
+        Ok(())
+    }
 }
@@ -87,6 +87,44 @@ impl ListingTableUrl {
         }
     }
 
+    /// Parse a provided string as a `ListingTableUrl`, as with [`Self::parse`],
+    /// without requiring a local path to exist.
+    ///
+    /// This is used for the targets of writes, which are created when the
+    /// data is written. A path that does not exist is resolved against its
+    /// closest existing ancestor, as a directory if `is_directory` is true and
+    /// as a file otherwise.
+    pub fn parse_write_target(s: impl AsRef<str>, is_directory: bool) -> Result<Self> {
+        let s = s.as_ref();
+        let path = std::path::Path::new(s);
+        let is_path = path.is_absolute() || Url::parse(s).is_err();
+        if !is_path || split_glob_expression(s).is_some() || path.exists() {
+            return Self::parse(s);
+        }
+
+        let ancestor = path
+            .ancestors()
+            .find(|ancestor| ancestor.as_os_str().is_empty() || ancestor.exists())
+            .unwrap_or(path);
+        let base = if ancestor.as_os_str().is_empty() {
+            std::env::current_dir()?
+        } else {
+            ancestor.canonicalize()?
+        };
+        let missing = path.strip_prefix(ancestor).map_err(|e| {
+            DataFusionError::Internal(format!("Can not resolve path {s}: {e}"))
+        })?;
+        let path = base.join(missing);
+        let url = if is_directory {
+            Url::from_directory_path(path)
+        } else {
+            Url::from_file_path(path)
+        }
+        .map_err(|_| DataFusionError::Internal(format!("Can not open path: {s}")))?;
+
+        Ok(Self::new(url, None))
+    }
+
     /// Creates a new [`ListingTableUrl`] interpreting `s` as a filesystem path
     fn parse_path(s: &str) -> Result<Self> {
         let (prefix, glob) = match split_glob_expression(s) {
@@ -187,6 +225,12 @@ impl ListingTableUrl {
         self.as_ref()
     }
 
+    /// Returns `true` if this URL identifies a directory (i.e. it ends
+    /// with a path separator) rather than a single file
+    pub fn is_collection(&self) -> bool {
+        self.url.path().ends_with('/')
+    }
+
     /// Return the [`ObjectStoreUrl`] for this [`ListingTableUrl`]
     pub fn object_store(&self) -> ObjectStoreUrl {
         let url = &self.url[url::Position::BeforeScheme..url::Position::BeforePath];
@@ -242,6 +286,35 @@ fn split_glob_expression(path: &str) -> Option<(&str, &str)> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_write_target() {
+        let root = tempfile::tempdir().unwrap();
+        let root_path = root.path().canonicalize().unwrap();
+
+        let dir = root.path().join("dir").join("nested");
+        let url =
+            ListingTableUrl::parse_write_target(dir.to_string_lossy(), true).unwrap();
+        assert_eq!(
+            url.as_str(),
+            Url::from_directory_path(root_path.join("dir").join("nested"))
+                .unwrap()
+                .as_str()
+        );
+
+        let file = root.path().join("dir").join("file.csv");
+        let url =
+            ListingTableUrl::parse_write_target(file.to_string_lossy(), false).unwrap();
+        assert_eq!(
+            url.as_str(),
+            Url::from_file_path(root_path.join("dir").join("file.csv"))
+                .unwrap()
+                .as_str()
+        );
+
+        // resolving the targets does not create them
+        assert!(!root.path().join("dir").exists());
+    }
+
     #[test]
     fn test_prefix_path() {
         let root = std::env::current_dir().unwrap();
@@ -21,7 +21,7 @@ use std::path::Path;
 use std::str::FromStr;
 use std::sync::Arc;
 
-use arrow::datatypes::{DataType, SchemaRef};
+use arrow::datatypes::{DataType, Schema, SchemaRef};
 use async_trait::async_trait;
 use datafusion_common::DataFusionError;
 use datafusion_expr::CreateExternalTable;
@@ -34,7 +34,8 @@ use crate::datasource::file_format::json::JsonFormat;
 use crate::datasource::file_format::parquet::ParquetFormat;
 use crate::datasource::file_format::FileFormat;
 use crate::datasource::listing::{
-    ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
+    ListingOptions, ListingTable, ListingTableConfig, ListingTableInsertMode,
+    ListingTableUrl,
 };
 use crate::datasource::provider::TableProviderFactory;
 use crate::datasource::TableProvider;
@@ -138,11 +139,36 @@ impl TableProviderFactory for ListingTableFactory {
             .with_table_partition_cols(table_partition_cols)
             .with_infinite_source(infinite_source)
             .with_file_sort_order(cmd.order_exprs.clone());
+        // Partitioned tables are written as new files, otherwise the default
+        // insert mode of the format is used
+        let options = match cmd.options.get("insert_mode") {
+            Some(mode) => {
+                options.with_insert_mode(ListingTableInsertMode::from_str(mode)?)
+            }
+            None if !options.table_partition_cols.is_empty() => {
+                options.with_insert_mode(ListingTableInsertMode::AppendNewFiles)
+            }
+            None => options,
+        };
 
-        let table_path = ListingTableUrl::parse(&cmd.location)?;
+        // A table directory that does not exist yet is created by the sinks
+        // when data is inserted into it
+        let table_path = if cmd.location.ends_with('/') {
+            ListingTableUrl::parse_write_target(&cmd.location, true)?
+        } else {
+            ListingTableUrl::parse(&cmd.location)?
+        };
         let resolved_schema = match provided_schema {
             None => options.infer_schema(state, &table_path).await?,
-            Some(s) => s,
+            Some(s) => {
+                // Parquet files carry their schema, so the declared columns
+                // must agree with the files that already exist
+                if file_type == FileType::PARQUET {
+                    let file_schema = options.infer_schema(state, &table_path).await?;
+                    check_declared_schema(&s, &file_schema)?;
+                }
+                s
+            }
         };
         let config = ListingTableConfig::new(table_path)
             .with_listing_options(options)
@@ -153,6 +179,41 @@ impl TableProviderFactory for ListingTableFactory {
     }
 }
 
+/// Checks that the `declared` columns of a table are the columns of the
+/// existing files of the table, with the same types. The files are read by
+/// column name, so the order of the columns may differ.
+fn check_declared_schema(
+    declared: &Schema,
+    file_schema: &Schema,
+) -> datafusion_common::Result<()> {
+    if file_schema.fields().is_empty() {
+        // there are no files yet
+        return Ok(());
+    }
+    let matches = declared.fields().len() == file_schema.fields().len()
+        && declared.fields().iter().all(|field| {
+            file_schema
+                .field_with_name(field.name())
+                .is_ok_and(|file_field| file_field.data_type() == field.data_type())
+        });
+    if matches {
+        return Ok(());
+    }
+    let display = |schema: &Schema| {
+        schema
+            .fields()
+            .iter()
+            .map(|field| format!("{} {}", field.name(), field.data_type()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    Err(DataFusionError::Plan(format!(
+        "Column definitions ({}) do not match the schema of the existing files ({})",
+        display(declared),
+        display(file_schema)
+    )))
+}
+
 // Get file extension from path
 fn get_extension(path: &str) -> String {
     let res = Path::new(path).extension().and_then(|ext| ext.to_str());
@@ -168,6 +229,11 @@ mod tests {
 
     use std::collections::HashMap;
 
+    use arrow::array::{Int64Array, StringArray};
+    use arrow::datatypes::Field;
+    use arrow::record_batch::RecordBatch;
+    use parquet::arrow::ArrowWriter;
+
     use crate::execution::context::SessionContext;
     use datafusion_common::parsers::CompressionTypeVariant;
     use datafusion_common::{DFSchema, OwnedTableReference};
@@ -207,4 +273,89 @@ mod tests {
         let listing_options = listing_table.options();
         assert_eq!(".tbl", listing_options.file_extension);
     }
+
+    #[tokio::test]
+    async fn test_parquet_declared_schema_must_match_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            file_schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![1])),
+                Arc::new(StringArray::from(vec!["x"])),
+            ],
+        )
+        .unwrap();
+        let file = std::fs::File::create(dir.path().join("data.parquet")).unwrap();
+        let mut writer = ArrowWriter::try_new(file, file_schema, None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let create = |location: String, fields: Vec<Field>| async move {
+            let cmd = CreateExternalTable {
+                name: OwnedTableReference::bare("t"),
+                location,
+                file_type: "parquet".to_string(),
+                has_header: false,
+                delimiter: ',',
+                schema: Arc::new(DFSchema::try_from(Schema::new(fields)).unwrap()),
+                table_partition_cols: vec![],
+                if_not_exists: false,
+                file_compression_type: CompressionTypeVariant::UNCOMPRESSED,
+                definition: None,
+                order_exprs: vec![],
+                unbounded: false,
+                options: HashMap::new(),
+            };
+            let context = SessionContext::new();
+            ListingTableFactory::new()
+                .create(&context.state(), &cmd)
+                .await
+                .map(|_| ())
+        };
+        let location = format!("{}/", dir.path().to_str().unwrap());
+
+        // the columns may be declared in another order
+        create(
+            location.clone(),
+            vec![
+                Field::new("b", DataType::Utf8, true),
+                Field::new("a", DataType::Int64, false),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let err = create(
+            location.clone(),
+            vec![
+                Field::new("a", DataType::Int32, true),
+                Field::new("b", DataType::Utf8, true),
+            ],
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Column definitions (a Int32, b Utf8) do not match \
+             the schema of the existing files (a Int64, b Utf8)"
+        );
+
+        let err = create(location, vec![Field::new("a", DataType::Int64, true)])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Column definitions (a Int64)"));
+
+        // any columns can be declared for a table without files
+        let empty = tempfile::tempdir().unwrap();
+        create(
+            format!("{}/", empty.path().to_str().unwrap()),
+            vec![Field::new("c", DataType::Float64, true)],
+        )
+        .await
+        .unwrap();
+    }
 }
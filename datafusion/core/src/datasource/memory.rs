@@ -20,14 +20,20 @@
 use futures::StreamExt;
 use log::debug;
 use std::any::Any;
+use std::collections::HashMap;
 use std::fmt::{self, Debug};
 use std::sync::Arc;
 
-use arrow::datatypes::SchemaRef;
+use arrow::array::{new_null_array, ArrayRef, BooleanArray};
+use arrow::compute::{concat_batches, filter_record_batch, interleave};
+use arrow::datatypes::{DataType, Schema, SchemaRef};
 use arrow::record_batch::RecordBatch;
+use arrow::row::{RowConverter, SortField};
 use async_trait::async_trait;
+use datafusion_common::cast::as_string_array;
 use datafusion_common::{Constraints, SchemaExt};
 use datafusion_execution::TaskContext;
+use datafusion_expr::{MergeAction, SchemaChange, WriteOp, MERGE_ACTION_COLUMN};
 use tokio::sync::RwLock;
 use tokio::task::JoinSet;
 
@@ -217,6 +223,199 @@ impl TableProvider for MemTable {
         let sink = Arc::new(MemSink::new(self.batches.clone()));
         Ok(Arc::new(InsertExec::new(input, sink, self.schema.clone())))
     }
+
+    /// Returns an ExecutionPlan that deletes the rows of this [`MemTable`]
+    /// produced by `input`.
+    ///
+    /// The deleted rows are found by their values when the plan is
+    /// executed, and are removed from the partitions that hold them, all
+    /// at once. The plan returns the number of rows deleted.
+    async fn delete_from(
+        &self,
+        _state: &SessionState,
+        input: Arc<dyn ExecutionPlan>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if !self.schema.equivalent_names_and_types(&input.schema()) {
+            return Err(DataFusionError::Plan(
+                "Deleting query must have the same schema with the table.".to_string(),
+            ));
+        }
+        let sink = Arc::new(MemDmlSink::new(
+            WriteOp::Delete,
+            self.schema.clone(),
+            self.batches.clone(),
+        ));
+        Ok(Arc::new(InsertExec::new(input, sink, self.schema.clone())))
+    }
+
+    /// Returns an ExecutionPlan that updates the rows of this [`MemTable`]
+    /// to the new values computed by `input`.
+    ///
+    /// The updated rows are found by their previous values when the plan
+    /// is executed, and are replaced in the partitions that hold them, all
+    /// at once. The plan returns the number of rows updated.
+    async fn update(
+        &self,
+        _state: &SessionState,
+        input: Arc<dyn ExecutionPlan>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let input_schema = input.schema();
+        let num_columns = self.schema.fields().len();
+        let previous_fields = input_schema.fields().iter().skip(num_columns);
+        let valid = input_schema.fields().len() == 2 * num_columns
+            && self.schema.equivalent_names_and_types(&Arc::new(
+                input_schema.project(&(0..num_columns).collect::<Vec<_>>())?,
+            ))
+            && self
+                .schema
+                .fields()
+                .iter()
+                .zip(previous_fields.clone())
+                .all(|(field, previous)| field.data_type() == previous.data_type());
+        if !valid {
+            return Err(DataFusionError::Plan(
+                "Updating query must have the schema of the table followed by the previous values"
+                    .to_string(),
+            ));
+        }
+
+        let mut fields = self.schema.fields().to_vec();
+        fields.extend(previous_fields.cloned());
+        let sink_schema = Arc::new(Schema::new(fields));
+        let sink = Arc::new(MemDmlSink::new(
+            WriteOp::Update,
+            self.schema.clone(),
+            self.batches.clone(),
+        ));
+        Ok(Arc::new(InsertExec::new(input, sink, sink_schema)))
+    }
+
+    /// Returns an ExecutionPlan that applies the changes of a `MERGE`
+    /// statement computed by `input` to this [`MemTable`].
+    ///
+    /// The updated and deleted rows are found by their previous values
+    /// when the plan is executed, and are changed in the partitions that
+    /// hold them, all at once. The plan returns the number of rows
+    /// inserted, updated and deleted.
+    async fn merge_into(
+        &self,
+        _state: &SessionState,
+        input: Arc<dyn ExecutionPlan>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let input_schema = input.schema();
+        let num_columns = self.schema.fields().len();
+        let previous_fields = input_schema.fields().iter().skip(num_columns + 1);
+        let valid = input_schema.fields().len() == 2 * num_columns + 1
+            && self.schema.equivalent_names_and_types(&Arc::new(
+                input_schema.project(&(0..num_columns).collect::<Vec<_>>())?,
+            ))
+            && input_schema.field(num_columns).name() == MERGE_ACTION_COLUMN
+            && input_schema.field(num_columns).data_type() == &DataType::Utf8
+            && self
+                .schema
+                .fields()
+                .iter()
+                .zip(previous_fields.clone())
+                .all(|(field, previous)| field.data_type() == previous.data_type());
+        if !valid {
+            return Err(DataFusionError::Plan(
+                "Merging query must have the schema of the table followed by the merge action and the previous values"
+                    .to_string(),
+            ));
+        }
+
+        let mut fields = self.schema.fields().to_vec();
+        fields.push(input_schema.field(num_columns).clone().into());
+        fields.extend(previous_fields.cloned());
+        let sink_schema = Arc::new(Schema::new(fields));
+        let sink = Arc::new(MemDmlSink::new(
+            WriteOp::Merge,
+            self.schema.clone(),
+            self.batches.clone(),
+        ));
+        Ok(Arc::new(InsertExec::new(input, sink, sink_schema)))
+    }
+
+    /// Returns a new [`MemTable`] with the schema changed by `change`,
+    /// holding a copy of the data of this table.
+    ///
+    /// Added columns are null in the existing rows, so that a
+    /// non-nullable column can only be added to an empty table.
+    async fn alter_schema(
+        &self,
+        _state: &SessionState,
+        change: &SchemaChange,
+    ) -> Result<Arc<dyn TableProvider>> {
+        let mut partitions = Vec::with_capacity(self.batches.len());
+        for partition in &self.batches {
+            partitions.push(partition.read().await.clone());
+        }
+        let has_rows = partitions.iter().flatten().any(|b| b.num_rows() > 0);
+
+        let mut fields = self.schema.fields().to_vec();
+        let mut projection = (0..fields.len()).collect::<Vec<_>>();
+        let mut constraints = self.constraints.clone();
+        let mut added = None;
+        match change {
+            SchemaChange::AddColumn(field) => {
+                if !field.is_nullable() && has_rows {
+                    return Err(DataFusionError::Execution(format!(
+                        "Cannot add non-nullable column {} to a table with rows",
+                        field.name()
+                    )));
+                }
+                fields.push(Arc::new(field.clone()));
+                added = Some(field.data_type());
+            }
+            SchemaChange::DropColumn(name) => {
+                let idx = self.schema.index_of(name)?;
+                if fields.len() == 1 {
+                    return Err(DataFusionError::Plan(format!(
+                        "Cannot drop column {name}, which is the only column of the table"
+                    )));
+                }
+                fields.remove(idx);
+                projection.remove(idx);
+                if let Some(c) = &constraints {
+                    constraints = Some(c.project(&projection).ok_or_else(|| {
+                        DataFusionError::Plan(format!(
+                            "Cannot drop column {name}, which is part of a constraint of the table"
+                        ))
+                    })?);
+                }
+            }
+            SchemaChange::RenameColumn { old_name, new_name } => {
+                let idx = self.schema.index_of(old_name)?;
+                fields[idx] = Arc::new(fields[idx].as_ref().clone().with_name(new_name));
+            }
+        }
+        let schema = Arc::new(Schema::new_with_metadata(
+            fields,
+            self.schema.metadata().clone(),
+        ));
+
+        let partitions = partitions
+            .into_iter()
+            .map(|batches| {
+                batches
+                    .into_iter()
+                    .map(|batch| {
+                        let mut columns = projection
+                            .iter()
+                            .map(|idx| batch.column(*idx).clone())
+                            .collect::<Vec<_>>();
+                        if let Some(data_type) = added {
+                            columns.push(new_null_array(data_type, batch.num_rows()));
+                        }
+                        Ok(RecordBatch::try_new(schema.clone(), columns)?)
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let table = MemTable::try_new(schema, partitions)?
+            .with_constraints(constraints.unwrap_or_else(Constraints::empty));
+        Ok(Arc::new(table))
+    }
 }
 
 /// Implements for writing to a [`MemTable`]
@@ -280,14 +479,237 @@ impl DataSink for MemSink {
     }
 }
 
+/// Deletes, updates or merges the rows of a [`MemTable`], by finding the
+/// changed rows by their previous values in the partitions that hold them.
+/// The rows inserted by a `MERGE` are spread over the partitions.
+struct MemDmlSink {
+    /// Either [`WriteOp::Delete`], [`WriteOp::Update`] or [`WriteOp::Merge`]
+    op: WriteOp,
+    /// The schema of the table
+    schema: SchemaRef,
+    /// Target locations for modifying data
+    batches: Vec<PartitionData>,
+}
+
+impl Debug for MemDmlSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemDmlSink")
+            .field("op", &self.op)
+            .field("num_partitions", &self.batches.len())
+            .finish()
+    }
+}
+
+impl DisplayAs for MemDmlSink {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let partition_count = self.batches.len();
+                let op = self.op.to_string().to_lowercase();
+                write!(f, "MemoryTable (partitions={partition_count}, {op})")
+            }
+        }
+    }
+}
+
+impl MemDmlSink {
+    fn new(op: WriteOp, schema: SchemaRef, batches: Vec<PartitionData>) -> Self {
+        Self {
+            op,
+            schema,
+            batches,
+        }
+    }
+
+    /// Returns the [`MergeAction`] of each row of an input `batch`, with
+    /// the columns holding the previous values of the rows
+    fn actions<'a>(
+        &self,
+        batch: &'a RecordBatch,
+    ) -> Result<(Vec<MergeAction>, &'a [ArrayRef])> {
+        let num_columns = self.schema.fields().len();
+        match self.op {
+            WriteOp::Delete => {
+                Ok((vec![MergeAction::Delete; batch.num_rows()], batch.columns()))
+            }
+            WriteOp::Update => Ok((
+                vec![MergeAction::Update; batch.num_rows()],
+                &batch.columns()[num_columns..],
+            )),
+            _ => {
+                let actions = as_string_array(batch.column(num_columns))?
+                    .iter()
+                    .map(|action| match action {
+                        Some(action) => action.parse::<MergeAction>(),
+                        None => Err(DataFusionError::Internal(
+                            "Merge action must not be null".to_string(),
+                        )),
+                    })
+                    .collect::<Result<_>>()?;
+                Ok((actions, &batch.columns()[num_columns + 1..]))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl DataSink for MemDmlSink {
+    async fn write_all(
+        &self,
+        data: SendableRecordBatchStream,
+        _context: &Arc<TaskContext>,
+    ) -> Result<u64> {
+        let input = common::collect(data).await?;
+
+        // Lock all partitions, so that the changes are applied to all of
+        // them at once, and nothing is modified if an error occurs
+        let mut targets = Vec::with_capacity(self.batches.len());
+        for target in &self.batches {
+            targets.push(target.write().await);
+        }
+
+        let num_columns = self.schema.fields().len();
+        let statement = self.op.to_string().to_uppercase();
+        let mut converter = RowConverter::new(
+            self.schema
+                .fields()
+                .iter()
+                .map(|field| SortField::new(field.data_type().clone()))
+                .collect(),
+        )?;
+        // The (partition, batch, row) locations of the rows of the table,
+        // by their values
+        let mut locations: HashMap<Vec<u8>, Vec<(usize, usize, usize)>> = HashMap::new();
+        for (partition, target) in targets.iter().enumerate() {
+            for (batch_idx, batch) in target.iter().enumerate() {
+                let rows = converter.convert_columns(batch.columns())?;
+                for (row_idx, row) in rows.iter().enumerate() {
+                    locations
+                        .entry(row.as_ref().to_vec())
+                        .or_default()
+                        .push((partition, batch_idx, row_idx));
+                }
+            }
+        }
+
+        // The changed rows of each batch of the table, which are either
+        // deleted (None) or replaced by an updated row
+        let mut changes: HashMap<(usize, usize), HashMap<usize, Option<usize>>> =
+            HashMap::new();
+        let mut updates = vec![];
+        let mut inserts = vec![];
+        let mut num_updates = 0;
+        let mut row_count = 0;
+        for batch in &input {
+            let (actions, previous) = self.actions(batch)?;
+            let previous = converter.convert_columns(previous)?;
+            let mut is_update = Vec::with_capacity(batch.num_rows());
+            let mut is_insert = Vec::with_capacity(batch.num_rows());
+            for (row_idx, action) in actions.into_iter().enumerate() {
+                is_update.push(action == MergeAction::Update);
+                is_insert.push(action == MergeAction::Insert);
+                if action == MergeAction::Insert {
+                    row_count += 1;
+                    continue;
+                }
+
+                // A row that is not in the table anymore was changed by
+                // another statement
+                let Some(rows) = locations.get_mut(previous.row(row_idx).as_ref()) else {
+                    return Err(DataFusionError::Execution(format!(
+                        "The table was modified while executing the {statement} statement"
+                    )));
+                };
+                let Some((partition, batch_idx, target_row)) = rows.pop() else {
+                    return Err(DataFusionError::Execution(format!(
+                        "{statement} matched a row of the table with more than one source row"
+                    )));
+                };
+                let change = match action {
+                    MergeAction::Update => {
+                        num_updates += 1;
+                        Some(num_updates - 1)
+                    }
+                    MergeAction::Delete => None,
+                    _ => continue,
+                };
+                row_count += 1;
+                changes
+                    .entry((partition, batch_idx))
+                    .or_default()
+                    .insert(target_row, change);
+            }
+
+            let new_values = RecordBatch::try_new(
+                self.schema.clone(),
+                batch.columns()[..num_columns].to_vec(),
+            )?;
+            updates.push(filter_record_batch(
+                &new_values,
+                &BooleanArray::from(is_update),
+            )?);
+            inserts.push(filter_record_batch(
+                &new_values,
+                &BooleanArray::from(is_insert),
+            )?);
+        }
+
+        let updates = concat_batches(&self.schema, &updates)?;
+        let mut new_partitions = Vec::with_capacity(targets.len());
+        for (partition, target) in targets.iter().enumerate() {
+            let mut new_batches = Vec::with_capacity(target.len());
+            for (batch_idx, batch) in target.iter().enumerate() {
+                let Some(changes) = changes.get(&(partition, batch_idx)) else {
+                    new_batches.push(batch.clone());
+                    continue;
+                };
+                // The rows of the batch, taken from either the old values (0)
+                // or the updated rows (1)
+                let indices = (0..batch.num_rows())
+                    .filter_map(|row| match changes.get(&row) {
+                        None => Some((0, row)),
+                        Some(update) => update.map(|update| (1, update)),
+                    })
+                    .collect::<Vec<_>>();
+                if !indices.is_empty() {
+                    let columns = batch
+                        .columns()
+                        .iter()
+                        .zip(updates.columns())
+                        .map(|(old, new)| {
+                            interleave(&[old.as_ref(), new.as_ref()], &indices)
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    new_batches.push(RecordBatch::try_new(batch.schema(), columns)?);
+                }
+            }
+            new_partitions.push(new_batches);
+        }
+
+        let mut i = 0;
+        for batch in inserts {
+            if batch.num_rows() > 0 {
+                new_partitions[i].push(batch);
+                i = (i + 1) % new_partitions.len();
+            }
+        }
+
+        for (mut target, new_batches) in targets.into_iter().zip(new_partitions) {
+            *target = new_batches;
+        }
+
+        Ok(row_count as u64)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::datasource::provider_as_source;
     use crate::physical_plan::collect;
     use crate::prelude::SessionContext;
-    use arrow::array::{AsArray, Int32Array};
-    use arrow::datatypes::{DataType, Field, Schema, UInt64Type};
+    use arrow::array::{AsArray, Int32Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Int32Type, Schema, UInt64Type};
     use arrow::error::ArrowError;
     use datafusion_expr::LogicalPlanBuilder;
     use futures::StreamExt;
@@ -683,4 +1105,286 @@ mod tests {
         assert_eq!(resulting_data_in_table[0].len(), 2);
         Ok(())
     }
+
+    /// Returns the values of the single column of the batches of each
+    /// partition of `table`
+    async fn partition_values(table: &MemTable) -> Vec<Vec<i32>> {
+        let mut partitions = vec![];
+        for partition in table.batches.iter() {
+            let values = partition
+                .read()
+                .await
+                .iter()
+                .flat_map(|batch| {
+                    let col = batch.column(0).as_primitive::<Int32Type>();
+                    col.values().to_vec()
+                })
+                .collect();
+            partitions.push(values);
+        }
+        partitions
+    }
+
+    fn multi_partition_table() -> Result<MemTable> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let batch = |values: Vec<i32>| {
+            RecordBatch::try_new(schema.clone(), vec![Arc::new(Int32Array::from(values))])
+        };
+        MemTable::try_new(
+            schema.clone(),
+            vec![
+                vec![batch(vec![1, 2, 3])?, batch(vec![4, 5])?],
+                vec![batch(vec![6, 7])?],
+            ],
+        )
+    }
+
+    /// Returns a plan producing a single batch of the Int32 `columns`
+    fn int32_rows(
+        names: &[&str],
+        columns: Vec<Vec<Option<i32>>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let schema = Arc::new(Schema::new(
+            names
+                .iter()
+                .map(|name| Field::new(*name, DataType::Int32, true))
+                .collect::<Vec<_>>(),
+        ));
+        let columns = columns
+            .into_iter()
+            .map(|values| Arc::new(Int32Array::from(values)) as ArrayRef)
+            .collect();
+        let batch = RecordBatch::try_new(schema.clone(), columns)?;
+        Ok(Arc::new(MemoryExec::try_new(&[vec![batch]], schema, None)?))
+    }
+
+    // Test deleting rows from all partitions of a table
+    #[tokio::test]
+    async fn test_delete_from_multi_partition() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let table = multi_partition_table()?;
+
+        // The deleted rows are found in the partitions that hold them
+        let input = int32_rows(&["a"], vec![vec![Some(2), Some(3), Some(6)]])?;
+        let plan = table.delete_from(&session_ctx.state(), input).await?;
+        let res = collect(plan, session_ctx.task_ctx()).await?;
+        assert_eq!(extract_count(res), 3);
+        assert_eq!(partition_values(&table).await, vec![vec![1, 4, 5], vec![7]]);
+
+        // Deleting a row that is not in the table anymore fails without
+        // modifying the table
+        let input = int32_rows(&["a"], vec![vec![Some(1), Some(2)]])?;
+        let plan = table.delete_from(&session_ctx.state(), input).await?;
+        let err = collect(plan, session_ctx.task_ctx()).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: The table was modified while executing the DELETE statement"
+        );
+        assert_eq!(partition_values(&table).await, vec![vec![1, 4, 5], vec![7]]);
+
+        // Empty batches are dropped
+        let input = int32_rows(&["a"], vec![vec![Some(1), Some(4), Some(5), Some(7)]])?;
+        let plan = table.delete_from(&session_ctx.state(), input).await?;
+        let res = collect(plan, session_ctx.task_ctx()).await?;
+        assert_eq!(extract_count(res), 4);
+        for partition in table.batches.iter() {
+            assert!(partition.read().await.is_empty());
+        }
+        Ok(())
+    }
+
+    // Test updating rows in all partitions of a table
+    #[tokio::test]
+    async fn test_update_multi_partition() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let table = multi_partition_table()?;
+        let names = ["a", "__update_previous_a"];
+
+        // The updated rows are found by their previous values
+        let input = int32_rows(
+            &names,
+            vec![
+                vec![Some(30), Some(40), Some(50), Some(60)],
+                vec![Some(3), Some(4), Some(5), Some(6)],
+            ],
+        )?;
+        let plan = table.update(&session_ctx.state(), input).await?;
+        let res = collect(plan, session_ctx.task_ctx()).await?;
+        assert_eq!(extract_count(res), 4);
+        assert_eq!(
+            partition_values(&table).await,
+            vec![vec![1, 2, 30, 40, 50], vec![60, 7]]
+        );
+
+        // Updating a non-nullable column to null fails without modifying
+        // the table
+        let input = int32_rows(&names, vec![vec![None], vec![Some(1)]])?;
+        let plan = table.update(&session_ctx.state(), input).await?;
+        let err = collect(plan, session_ctx.task_ctx()).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: Invalid batch column at '0' has null but schema specifies non-nullable"
+        );
+        assert_eq!(
+            partition_values(&table).await,
+            vec![vec![1, 2, 30, 40, 50], vec![60, 7]]
+        );
+
+        // The input must have the previous values
+        let input = int32_rows(&["a"], vec![vec![Some(1)]])?;
+        let err = table.update(&session_ctx.state(), input).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Updating query must have the schema of the table followed by the previous values"
+        );
+        Ok(())
+    }
+
+    // Test applying the changes of a merge to all partitions of a table
+    #[tokio::test]
+    async fn test_merge_into_multi_partition() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let table = multi_partition_table()?;
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new(MERGE_ACTION_COLUMN, DataType::Utf8, false),
+            Field::new("__merge_previous_a", DataType::Int32, true),
+        ]));
+        let changes =
+            |rows: Vec<(i32, &str, Option<i32>)>| -> Result<Arc<dyn ExecutionPlan>> {
+                let mut values = vec![];
+                let mut actions = vec![];
+                let mut previous = vec![];
+                for (value, action, previous_value) in rows {
+                    values.push(value);
+                    actions.push(action);
+                    previous.push(previous_value);
+                }
+                let batch = RecordBatch::try_new(
+                    schema.clone(),
+                    vec![
+                        Arc::new(Int32Array::from(values)),
+                        Arc::new(StringArray::from(actions)),
+                        Arc::new(Int32Array::from(previous)),
+                    ],
+                )?;
+                Ok(Arc::new(MemoryExec::try_new(
+                    &[vec![batch]],
+                    schema.clone(),
+                    None,
+                )?))
+            };
+
+        // The rows are changed in the partitions that hold them
+        let input = changes(vec![
+            (1, "keep", Some(1)),
+            (2, "delete", Some(2)),
+            (30, "update", Some(3)),
+            (4, "keep", Some(4)),
+            (5, "keep", Some(5)),
+            (6, "delete", Some(6)),
+            (7, "keep", Some(7)),
+            (8, "insert", None),
+        ])?;
+        let plan = table.merge_into(&session_ctx.state(), input).await?;
+        let res = collect(plan, session_ctx.task_ctx()).await?;
+        assert_eq!(extract_count(res), 4);
+        assert_eq!(
+            partition_values(&table).await,
+            vec![vec![1, 30, 4, 5, 8], vec![7]]
+        );
+
+        // Rows of the table that are not in the input, such as rows
+        // inserted by another statement, are left unchanged
+        let input = changes(vec![(70, "update", Some(7))])?;
+        let plan = table.merge_into(&session_ctx.state(), input).await?;
+        let res = collect(plan, session_ctx.task_ctx()).await?;
+        assert_eq!(extract_count(res), 1);
+        assert_eq!(
+            partition_values(&table).await,
+            vec![vec![1, 30, 4, 5, 8], vec![70]]
+        );
+
+        // Changes for a row more than once fail without modifying the
+        // table
+        let input = changes(vec![(10, "update", Some(1)), (11, "update", Some(1))])?;
+        let plan = table.merge_into(&session_ctx.state(), input).await?;
+        let err = collect(plan, session_ctx.task_ctx()).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: MERGE matched a row of the table with more than one source row"
+        );
+
+        // Changes for a row that was modified by another statement fail
+        // without modifying the table
+        let input = changes(vec![(1, "delete", Some(1)), (3, "keep", Some(3))])?;
+        let plan = table.merge_into(&session_ctx.state(), input).await?;
+        let err = collect(plan, session_ctx.task_ctx()).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: The table was modified while executing the MERGE statement"
+        );
+        assert_eq!(
+            partition_values(&table).await,
+            vec![vec![1, 30, 4, 5, 8], vec![70]]
+        );
+
+        // The input must have the merge action column
+        let input = Arc::new(MemoryExec::try_new(&[vec![]], table.schema(), None)?);
+        let err = table
+            .merge_into(&session_ctx.state(), input)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Merging query must have the schema of the table followed by the merge action and the previous values"
+        );
+        Ok(())
+    }
+
+    // Test changing the schema of a table with multiple partitions
+    #[tokio::test]
+    async fn test_alter_schema_multi_partition() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let state = session_ctx.state();
+        let table = multi_partition_table()?;
+
+        let change = SchemaChange::AddColumn(Field::new("b", DataType::Utf8, true));
+        let table = table.alter_schema(&state, &change).await?;
+        let res =
+            collect(table.scan(&state, None, &[], None).await?, state.task_ctx()).await?;
+        assert_eq!(res.len(), 3);
+        for batch in &res {
+            assert_eq!(batch.schema(), table.schema());
+            assert_eq!(batch.column(1).null_count(), batch.num_rows());
+        }
+
+        // Non-nullable columns cannot be added, as existing rows would be null
+        let change = SchemaChange::AddColumn(Field::new("c", DataType::Utf8, false));
+        let err = table.alter_schema(&state, &change).await.err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: Cannot add non-nullable column c to a table with rows"
+        );
+
+        let change = SchemaChange::RenameColumn {
+            old_name: "b".to_string(),
+            new_name: "c".to_string(),
+        };
+        let table = table.alter_schema(&state, &change).await?;
+        let change = SchemaChange::DropColumn("a".to_string());
+        let table = table.alter_schema(&state, &change).await?;
+        let expected = Schema::new(vec![Field::new("c", DataType::Utf8, true)]);
+        assert_eq!(table.schema().as_ref(), &expected);
+
+        // The last column of a table cannot be dropped
+        let change = SchemaChange::DropColumn("c".to_string());
+        let err = table.alter_schema(&state, &change).await.err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Cannot drop column c, which is the only column of the table"
+        );
+        Ok(())
+    }
 }
@@ -83,6 +83,11 @@ impl NdJsonExec {
     pub fn base_config(&self) -> &FileScanConfig {
         &self.base_config
     }
+
+    /// Compression type of the files
+    pub fn file_compression_type(&self) -> FileCompressionType {
+        self.file_compression_type.clone()
+    }
 }
 
 impl DisplayAs for NdJsonExec {
@@ -51,7 +51,7 @@ use crate::{
 };
 use crate::{
     datasource::{
-        listing::{FileRange, PartitionedFile},
+        listing::{FileRange, ListingTableUrl, PartitionedFile},
         object_store::ObjectStoreUrl,
     },
     physical_plan::display::{OutputOrderingDisplay, ProjectSchemaDisplay},
@@ -73,11 +73,13 @@ use std::{
     borrow::Cow,
     cmp::min,
     collections::HashMap,
-    fmt::{Debug, Formatter, Result as FmtResult},
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    hash::Hasher,
     marker::PhantomData,
     sync::Arc,
     vec,
 };
+use twox_hash::XxHash64;
 
 use super::{ColumnStatistics, Statistics};
 
@@ -203,7 +205,7 @@ impl FileScanConfig {
                 table_fields.push(Field::new(
                     &self.table_partition_cols[partition_idx].0,
                     self.table_partition_cols[partition_idx].1.to_owned(),
-                    false,
+                    true,
                 ));
                 // TODO provide accurate stat for partition column (#1186)
                 table_cols_stats.push(ColumnStatistics::default())
@@ -320,6 +322,130 @@ impl FileScanConfig {
 
         Some(repartitioned_files)
     }
+
+    /// Keep only the files selected by `sample`, for a `SYSTEM` table sample.
+    ///
+    /// The estimated statistics are scaled down accordingly and are no
+    /// longer exact.
+    pub fn with_block_sample(mut self, sample: &BlockSample) -> Self {
+        for group in self.file_groups.iter_mut() {
+            group.retain(|file| {
+                let index = file.range.as_ref().map_or(0, |r| r.start as usize);
+                sample.contains(&file.object_meta.location, index)
+            });
+        }
+        self.statistics = sample.scale_statistics(&self.statistics);
+        self
+    }
+}
+
+/// Selects a deterministic, pseudo random subset of the blocks (files or
+/// parquet row groups) of a scan, as used by `TABLESAMPLE SYSTEM`.
+///
+/// A block is selected based on a hash of the seed, the file location and
+/// the index of the block within the file, so the same seed always selects
+/// the same blocks regardless of how files are distributed across partitions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockSample {
+    fraction: f64,
+    seed: u64,
+}
+
+impl BlockSample {
+    /// Create a new sample selecting approximately `fraction` (between 0 and 1)
+    /// of all blocks
+    pub fn new(fraction: f64, seed: u64) -> Self {
+        Self {
+            fraction: fraction.clamp(0.0, 1.0),
+            seed,
+        }
+    }
+
+    /// The fraction of blocks to select
+    pub fn fraction(&self) -> f64 {
+        self.fraction
+    }
+
+    /// The seed used to select blocks
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns true if block `index` of the file at `location` is selected
+    pub fn contains(&self, location: &Path, index: usize) -> bool {
+        // a fixed hash function, so that the same blocks are selected by
+        // every build and platform
+        let mut hasher = XxHash64::with_seed(self.seed);
+        hasher.write(location.as_ref().as_bytes());
+        hasher.write(&(index as u64).to_le_bytes());
+        // map the top 53 bits of the hash uniformly to [0, 1)
+        let value = (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64;
+        value < self.fraction
+    }
+
+    /// Estimate the statistics of the sampled data from those of the input
+    pub fn scale_statistics(&self, statistics: &Statistics) -> Statistics {
+        let scale = |n: usize| (n as f64 * self.fraction).round() as usize;
+        let column_statistics = statistics.column_statistics.as_ref().map(|cols| {
+            cols.iter()
+                .map(|col| ColumnStatistics {
+                    null_count: None,
+                    distinct_count: None,
+                    ..col.clone()
+                })
+                .collect()
+        });
+        Statistics {
+            num_rows: statistics.num_rows.map(scale),
+            total_byte_size: statistics.total_byte_size.map(scale),
+            column_statistics,
+            is_exact: false,
+        }
+    }
+}
+
+impl Display for BlockSample {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "fraction={}, seed={}", self.fraction, self.seed)
+    }
+}
+
+/// Push a `TABLESAMPLE SYSTEM` sample into `plan` if it is a file scan, so
+/// that unselected files (or parquet row groups) are never read.
+///
+/// Returns `None` if `plan` is not a file scan.
+pub(crate) fn with_block_sample(
+    plan: &Arc<dyn ExecutionPlan>,
+    sample: BlockSample,
+) -> Option<Arc<dyn ExecutionPlan>> {
+    let plan_any = plan.as_any();
+    if let Some(exec) = plan_any.downcast_ref::<ParquetExec>() {
+        Some(Arc::new(exec.clone().with_block_sample(sample)))
+    } else if let Some(exec) = plan_any.downcast_ref::<CsvExec>() {
+        Some(Arc::new(CsvExec::new(
+            exec.base_config().clone().with_block_sample(&sample),
+            exec.has_header(),
+            exec.delimiter(),
+            exec.quote(),
+            exec.escape(),
+            exec.file_compression_type.clone(),
+        )))
+    } else if let Some(exec) = plan_any.downcast_ref::<NdJsonExec>() {
+        Some(Arc::new(NdJsonExec::new(
+            exec.base_config().clone().with_block_sample(&sample),
+            exec.file_compression_type(),
+        )))
+    } else if let Some(exec) = plan_any.downcast_ref::<AvroExec>() {
+        Some(Arc::new(AvroExec::new(
+            exec.base_config().clone().with_block_sample(&sample),
+        )))
+    } else {
+        plan_any.downcast_ref::<ArrowExec>().map(|exec| {
+            Arc::new(ArrowExec::new(
+                exec.base_config().clone().with_block_sample(&sample),
+            )) as _
+        })
+    }
 }
 
 /// The base configurations to provide when creating a physical plan for
@@ -330,6 +456,8 @@ pub struct FileSinkConfig {
     pub object_store_url: ObjectStoreUrl,
     /// A vector of [`PartitionedFile`] structs, each representing a file partition
     pub file_groups: Vec<PartitionedFile>,
+    /// Vector of table paths in which new files are created
+    pub table_paths: Vec<ListingTableUrl>,
     /// The schema of the output file
     pub output_schema: SchemaRef,
     /// A vector of column names and their corresponding data types,
@@ -337,6 +465,9 @@ pub struct FileSinkConfig {
     pub table_partition_cols: Vec<(String, DataType)>,
     /// A writer mode that determines how data is written to the file
     pub writer_mode: FileWriterMode,
+    /// If true, the single table path is itself the file that is written.
+    /// Otherwise, new files are created inside the table paths
+    pub single_file_output: bool,
 }
 
 impl FileSinkConfig {
@@ -775,6 +906,10 @@ fn create_output_array(
     val: &ScalarValue,
     len: usize,
 ) -> ArrayRef {
+    // the rows of the default partition are null
+    if val.is_null() {
+        return new_null_array(&val.get_datatype(), len);
+    }
     if let ScalarValue::Dictionary(key_type, dict_val) = &val {
         match key_type.as_ref() {
             DataType::Int8 => {
@@ -849,6 +984,7 @@ fn create_output_array(
 }
 
 /// A single file or part of a file that should be read, along with its schema, statistics
+#[derive(Clone)]
 pub struct FileMeta {
     /// Path for the file (e.g. URL, filesystem path, etc)
     pub object_meta: ObjectMeta,
@@ -1509,6 +1645,71 @@ mod tests {
         );
     }
 
+    #[test]
+    fn block_sample() {
+        let paths: Vec<_> = (0..1000)
+            .map(|i| Path::parse(format!("file_{i}.parquet")).unwrap())
+            .collect();
+        let selected =
+            |sample: BlockSample| paths.iter().filter(|p| sample.contains(p, 0)).count();
+
+        assert_eq!(selected(BlockSample::new(0.0, 1)), 0);
+        assert_eq!(selected(BlockSample::new(1.0, 1)), 1000);
+        let count = selected(BlockSample::new(0.2, 1));
+        assert!((100..300).contains(&count), "unexpected {count}");
+
+        // blocks of the same file are sampled independently
+        let path = &paths[0];
+        let count = (0..1000)
+            .filter(|i| BlockSample::new(0.5, 7).contains(path, *i))
+            .count();
+        assert!((400..600).contains(&count), "unexpected {count}");
+    }
+
+    #[test]
+    fn file_scan_config_block_sample() {
+        let statistics = Statistics {
+            num_rows: Some(1000),
+            total_byte_size: Some(4000),
+            column_statistics: None,
+            is_exact: true,
+        };
+        let mut config =
+            config_for_projection(aggr_test_schema(), None, statistics, vec![]);
+        config.file_groups = vec![
+            (0..50)
+                .map(|i| partitioned_file(&format!("a{i}")))
+                .collect(),
+            (0..50)
+                .map(|i| partitioned_file(&format!("b{i}")))
+                .collect(),
+        ];
+
+        let sample = BlockSample::new(0.5, 42);
+        let sampled = config.clone().with_block_sample(&sample);
+        for (before, after) in config.file_groups.iter().zip(&sampled.file_groups) {
+            let expected: Vec<_> = before
+                .iter()
+                .filter(|f| sample.contains(&f.object_meta.location, 0))
+                .map(|f| f.object_meta.location.clone())
+                .collect();
+            let actual: Vec<_> = after
+                .iter()
+                .map(|f| f.object_meta.location.clone())
+                .collect();
+            assert_eq!(actual, expected);
+        }
+        assert_eq!(
+            sampled.statistics,
+            Statistics {
+                num_rows: Some(500),
+                total_byte_size: Some(2000),
+                column_statistics: None,
+                is_exact: false,
+            }
+        );
+    }
+
     /// create a PartitionedFile for testing
     fn partitioned_file(path: &str) -> PartitionedFile {
         let object_meta = ObjectMeta {
@@ -21,8 +21,8 @@ use crate::datasource::physical_plan::file_stream::{
     FileOpenFuture, FileOpener, FileStream,
 };
 use crate::datasource::physical_plan::{
-    parquet::page_filter::PagePruningPredicate, DisplayAs, FileMeta, FileScanConfig,
-    SchemaAdapter,
+    parquet::page_filter::PagePruningPredicate, BlockSample, DisplayAs, FileMeta,
+    FileScanConfig, SchemaAdapter,
 };
 use crate::{
     config::ConfigOptions,
@@ -82,6 +82,9 @@ pub struct ParquetExec {
     /// Override for `Self::with_enable_page_index`. If None, uses
     /// values from base_config
     enable_page_index: Option<bool>,
+    /// Override for `Self::with_bloom_filter_on_read`. If None, uses
+    /// values from base_config
+    bloom_filter_on_read: Option<bool>,
     /// Base configuration for this scan
     base_config: FileScanConfig,
     projected_statistics: Statistics,
@@ -99,6 +102,8 @@ pub struct ParquetExec {
     metadata_size_hint: Option<usize>,
     /// Optional user defined parquet file reader factory
     parquet_file_reader_factory: Option<Arc<dyn ParquetFileReaderFactory>>,
+    /// Optional sample of the row groups to read
+    block_sample: Option<BlockSample>,
 }
 
 impl ParquetExec {
@@ -151,6 +156,7 @@ impl ParquetExec {
             pushdown_filters: None,
             reorder_filters: None,
             enable_page_index: None,
+            bloom_filter_on_read: None,
             base_config,
             projected_schema,
             projected_statistics,
@@ -161,6 +167,7 @@ impl ParquetExec {
             page_pruning_predicate,
             metadata_size_hint,
             parquet_file_reader_factory: None,
+            block_sample: None,
         }
     }
 
@@ -244,6 +251,33 @@ impl ParquetExec {
             .unwrap_or(config_options.execution.parquet.enable_page_index)
     }
 
+    /// If enabled, the reader will read the bloom filters of the columns
+    /// compared to literals by the predicate, to skip the row groups that
+    /// can not contain any matching row
+    pub fn with_bloom_filter_on_read(mut self, bloom_filter_on_read: bool) -> Self {
+        self.bloom_filter_on_read = Some(bloom_filter_on_read);
+        self
+    }
+
+    /// Only read the row groups selected by `sample`, for a `SYSTEM` table
+    /// sample. Row groups are sampled after any predicate based pruning.
+    pub fn with_block_sample(mut self, sample: BlockSample) -> Self {
+        self.projected_statistics = sample.scale_statistics(&self.projected_statistics);
+        self.block_sample = Some(sample);
+        self
+    }
+
+    /// Optional sample of the row groups to read
+    pub fn block_sample(&self) -> Option<&BlockSample> {
+        self.block_sample.as_ref()
+    }
+
+    /// Return the value described in [`Self::with_bloom_filter_on_read`]
+    fn bloom_filter_on_read(&self, config_options: &ConfigOptions) -> bool {
+        self.bloom_filter_on_read
+            .unwrap_or(config_options.execution.parquet.bloom_filter_on_read)
+    }
+
     /// Redistribute files across partitions according to their size
     /// See comments on `get_file_groups_repartitioned()` for more detail.
     pub fn get_repartitioned(
@@ -285,9 +319,19 @@ impl DisplayAs for ParquetExec {
                     .map(|pre| format!(", pruning_predicate={}", pre.predicate_expr()))
                     .unwrap_or_default();
 
+                let block_sample_string = self
+                    .block_sample
+                    .as_ref()
+                    .map(|sample| format!(", block_sample=[{sample}]"))
+                    .unwrap_or_default();
+
                 write!(f, "ParquetExec: ")?;
                 self.base_config.fmt_as(t, f)?;
-                write!(f, "{}{}", predicate_string, pruning_predicate_string,)
+                write!(
+                    f,
+                    "{}{}{}",
+                    predicate_string, pruning_predicate_string, block_sample_string
+                )
             }
         }
     }
@@ -373,6 +417,8 @@ impl ExecutionPlan for ParquetExec {
             pushdown_filters: self.pushdown_filters(config_options),
             reorder_filters: self.reorder_filters(config_options),
             enable_page_index: self.enable_page_index(config_options),
+            bloom_filter_on_read: self.bloom_filter_on_read(config_options),
+            block_sample: self.block_sample,
         };
 
         let stream =
@@ -406,11 +452,15 @@ struct ParquetOpener {
     pushdown_filters: bool,
     reorder_filters: bool,
     enable_page_index: bool,
+    bloom_filter_on_read: bool,
+    block_sample: Option<BlockSample>,
 }
 
 impl FileOpener for ParquetOpener {
     fn open(&self, file_meta: FileMeta) -> Result<FileOpenFuture> {
         let file_range = file_meta.range.clone();
+        let file_size = file_meta.object_meta.size;
+        let location = file_meta.location().clone();
 
         let file_metrics = ParquetFileMetrics::new(
             self.partition_index,
@@ -418,6 +468,25 @@ impl FileOpener for ParquetOpener {
             &self.metrics,
         );
 
+        // The bloom filters are read with a reader of their own, as the
+        // other reader is owned by the stream builder
+        let bloom_filter_predicate = self
+            .predicate
+            .as_ref()
+            .filter(|_| self.bloom_filter_on_read)
+            .and_then(row_groups::BloomFilterPredicate::try_new);
+        let bloom_filter_reader = bloom_filter_predicate
+            .as_ref()
+            .map(|_| {
+                self.parquet_file_reader_factory.create_reader(
+                    self.partition_index,
+                    file_meta.clone(),
+                    self.metadata_size_hint,
+                    &self.metrics,
+                )
+            })
+            .transpose()?;
+
         let reader: Box<dyn AsyncFileReader> =
             self.parquet_file_reader_factory.create_reader(
                 self.partition_index,
@@ -441,6 +510,7 @@ impl FileOpener for ParquetOpener {
             &self.page_pruning_predicate,
         );
         let limit = self.limit;
+        let block_sample = self.block_sample;
 
         Ok(Box::pin(async move {
             let options = ArrowReaderOptions::new().with_page_index(enable_page_index);
@@ -485,13 +555,40 @@ impl FileOpener for ParquetOpener {
             // Row group pruning: attempt to skip entire row_groups
             // using metadata on the row groups
             let file_metadata = builder.metadata();
-            let row_groups = row_groups::prune_row_groups(
+            let mut row_groups = row_groups::prune_row_groups(
                 file_metadata.row_groups(),
                 file_range,
                 pruning_predicate.as_ref().map(|p| p.as_ref()),
                 &file_metrics,
             );
 
+            // Bloom filter pruning: skip the row groups whose bloom filters
+            // rule out all the values the predicate compares columns to
+            if let (Some(predicate), Some(mut reader)) =
+                (bloom_filter_predicate, bloom_filter_reader)
+            {
+                if !row_groups.is_empty() {
+                    row_groups = row_groups::prune_row_groups_by_bloom_filters(
+                        reader.as_mut(),
+                        file_size,
+                        &row_groups,
+                        file_metadata.row_groups(),
+                        &predicate,
+                        &file_metrics,
+                    )
+                    .await;
+                }
+            }
+
+            // Table sample: skip the row groups that are not sampled
+            if let Some(sample) = block_sample {
+                let before = row_groups.len();
+                row_groups.retain(|idx| sample.contains(&location, *idx));
+                file_metrics
+                    .row_groups_skipped_by_sample
+                    .add(before - row_groups.len());
+            }
+
             // page index pruning: if all data on individual pages can
             // be ruled using page metadata, rows from other columns
             // with that range can be skipped as well
@@ -1876,6 +1973,140 @@ mod tests {
         assert_eq!(predicate.unwrap().to_string(), filter_phys.to_string());
     }
 
+    /// Scans two row groups written with bloom filters, whose statistics
+    /// overlap, with `filter`, returning the batches and the metrics.
+    ///
+    /// The filter is only used for pruning, so all the rows of the row
+    /// groups that are not pruned are returned
+    async fn bloom_filter_round_trip(
+        filter: Expr,
+        bloom_filter_on_read: bool,
+    ) -> (Vec<RecordBatch>, MetricsSet) {
+        let c1: ArrayRef =
+            Arc::new(StringArray::from(vec!["a", "m", "z", "b", "n", "y"]));
+        let c2: ArrayRef = Arc::new(Int64Array::from(vec![1, 5, 9, 2, 6, 8]));
+        let batch = create_batch(vec![("c1", c1), ("c2", c2)]);
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(3)
+            .set_bloom_filter_enabled(true)
+            .build();
+        let mut writer =
+            parquet::arrow::ArrowWriter::try_new(&mut file, batch.schema(), Some(props))
+                .unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let file_schema = batch.schema();
+        let predicate = logical2physical(&filter, &file_schema);
+        let parquet_exec = Arc::new(
+            ParquetExec::new(
+                FileScanConfig {
+                    object_store_url: ObjectStoreUrl::local_filesystem(),
+                    file_groups: vec![vec![local_unpartitioned_file(file.path()).into()]],
+                    file_schema,
+                    statistics: Statistics::default(),
+                    projection: None,
+                    limit: None,
+                    table_partition_cols: vec![],
+                    output_ordering: vec![],
+                    infinite_source: false,
+                },
+                Some(predicate),
+                None,
+            )
+            .with_bloom_filter_on_read(bloom_filter_on_read),
+        );
+
+        let task_ctx = SessionContext::new().task_ctx();
+        let batches = collect(parquet_exec.clone(), task_ctx).await.unwrap();
+        (batches, parquet_exec.metrics().unwrap())
+    }
+
+    #[tokio::test]
+    async fn parquet_exec_bloom_filter_eq() {
+        let (batches, metrics) =
+            bloom_filter_round_trip(col("c1").eq(lit("m")), true).await;
+        let expected = [
+            "+----+----+",
+            "| c1 | c2 |",
+            "+----+----+",
+            "| a  | 1  |",
+            "| m  | 5  |",
+            "| z  | 9  |",
+            "+----+----+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+        assert_eq!(get_value(&metrics, "row_groups_pruned"), 0);
+        assert_eq!(get_value(&metrics, "row_groups_pruned_bloom_filter"), 1);
+        assert_eq!(get_value(&metrics, "predicate_evaluation_errors"), 0);
+
+        // the literal may be on either side
+        let (_, metrics) = bloom_filter_round_trip(lit(6_i64).eq(col("c2")), true).await;
+        assert_eq!(get_value(&metrics, "row_groups_pruned_bloom_filter"), 1);
+
+        // neither row group contains the value
+        let (batches, metrics) =
+            bloom_filter_round_trip(col("c1").eq(lit("c")), true).await;
+        assert!(batches.iter().all(|b| b.num_rows() == 0));
+        assert_eq!(get_value(&metrics, "row_groups_pruned_bloom_filter"), 2);
+    }
+
+    #[tokio::test]
+    async fn parquet_exec_bloom_filter_in_list() {
+        let filter = col("c1").in_list(vec![lit("m"), lit("q")], false);
+        let (batches, metrics) = bloom_filter_round_trip(filter, true).await;
+        let expected = [
+            "+----+----+",
+            "| c1 | c2 |",
+            "+----+----+",
+            "| a  | 1  |",
+            "| m  | 5  |",
+            "| z  | 9  |",
+            "+----+----+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+        assert_eq!(get_value(&metrics, "row_groups_pruned_bloom_filter"), 1);
+
+        // each row group contains one of the values
+        let filter = col("c1").in_list(vec![lit("m"), lit("n")], false);
+        let (batches, metrics) = bloom_filter_round_trip(filter, true).await;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 6);
+        assert_eq!(get_value(&metrics, "row_groups_pruned_bloom_filter"), 0);
+
+        // a negated list can not be checked
+        let filter = col("c1").in_list(vec![lit("m")], true);
+        let (_, metrics) = bloom_filter_round_trip(filter, true).await;
+        assert_eq!(get_value(&metrics, "row_groups_pruned_bloom_filter"), 0);
+    }
+
+    #[tokio::test]
+    async fn parquet_exec_bloom_filter_and_or() {
+        // the second term can not be checked, the first prunes a row group
+        let filter = col("c1").eq(lit("m")).and(col("c2").gt(lit(0_i64)));
+        let (_, metrics) = bloom_filter_round_trip(filter, true).await;
+        assert_eq!(get_value(&metrics, "row_groups_pruned_bloom_filter"), 1);
+
+        // each row group contains a value of either term
+        let filter = col("c1").eq(lit("m")).or(col("c2").eq(lit(6_i64)));
+        let (_, metrics) = bloom_filter_round_trip(filter, true).await;
+        assert_eq!(get_value(&metrics, "row_groups_pruned_bloom_filter"), 0);
+
+        // the second term can not be checked
+        let filter = col("c1").eq(lit("m")).or(col("c2").gt(lit(0_i64)));
+        let (_, metrics) = bloom_filter_round_trip(filter, true).await;
+        assert_eq!(get_value(&metrics, "row_groups_pruned_bloom_filter"), 0);
+    }
+
+    #[tokio::test]
+    async fn parquet_exec_bloom_filter_disabled() {
+        let (batches, metrics) =
+            bloom_filter_round_trip(col("c1").eq(lit("m")), false).await;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 6);
+        assert_eq!(get_value(&metrics, "row_groups_pruned_bloom_filter"), 0);
+    }
+
     /// returns the sum of all the metrics with the specified name
     /// the returned set.
     ///
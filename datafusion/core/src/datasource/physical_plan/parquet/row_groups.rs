@@ -15,21 +15,40 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
 use arrow::{
     array::ArrayRef,
     datatypes::{DataType, Schema},
 };
+use bytes::{Buf, Bytes};
 use datafusion_common::Column;
 use datafusion_common::ScalarValue;
+use datafusion_expr::Operator;
+use datafusion_physical_expr::expressions::{
+    BinaryExpr, Column as PhysicalColumn, InListExpr, Literal,
+};
+use datafusion_physical_expr::PhysicalExpr;
 use log::debug;
 
+use parquet::arrow::async_reader::AsyncFileReader;
+use parquet::basic::Type as PhysicalType;
+use parquet::bloom_filter::Sbbf;
+use parquet::data_type::ByteArray;
+use parquet::errors::ParquetError;
+use parquet::file::properties::ReaderProperties;
+use parquet::file::reader::{ChunkReader, Length, RowGroupReader};
+use parquet::file::serialized_reader::SerializedRowGroupReader;
 use parquet::file::{
-    metadata::RowGroupMetaData, statistics::Statistics as ParquetStatistics,
+    metadata::{ColumnChunkMetaData, RowGroupMetaData},
+    statistics::Statistics as ParquetStatistics,
 };
 
 use crate::datasource::physical_plan::parquet::{
     from_bytes_to_i128, parquet_to_arrow_decimal_type,
 };
+use crate::error::Result;
 use crate::{
     datasource::listing::FileRange,
     physical_optimizer::pruning::{PruningPredicate, PruningStatistics},
@@ -243,6 +262,377 @@ impl<'a> PruningStatistics for RowGroupPruningStatistics<'a> {
     }
 }
 
+/// The terms of a predicate that can be checked against the bloom filters
+/// of a row group: `col = literal` and `col IN (literals)`, and the
+/// conjunctions and disjunctions of those.
+#[derive(Debug)]
+pub(crate) enum BloomFilterPredicate {
+    /// The column is equal to any of the values
+    In(String, Vec<ScalarValue>),
+    /// Both terms are true
+    And(Box<BloomFilterPredicate>, Box<BloomFilterPredicate>),
+    /// Either term is true
+    Or(Box<BloomFilterPredicate>, Box<BloomFilterPredicate>),
+}
+
+impl BloomFilterPredicate {
+    /// Returns the terms of `expr` that can be checked against bloom
+    /// filters, if any
+    pub(crate) fn try_new(expr: &Arc<dyn PhysicalExpr>) -> Option<Self> {
+        let expr_any = expr.as_any();
+        if let Some(binary) = expr_any.downcast_ref::<BinaryExpr>() {
+            match binary.op() {
+                // a conjunction is false if either term is false, so a term
+                // that can not be checked is skipped
+                Operator::And => {
+                    match (Self::try_new(binary.left()), Self::try_new(binary.right())) {
+                        (Some(left), Some(right)) => {
+                            Some(Self::And(Box::new(left), Box::new(right)))
+                        }
+                        (left, right) => left.or(right),
+                    }
+                }
+                Operator::Or => {
+                    let left = Self::try_new(binary.left())?;
+                    let right = Self::try_new(binary.right())?;
+                    Some(Self::Or(Box::new(left), Box::new(right)))
+                }
+                Operator::Eq => {
+                    let (column, value) =
+                        column_and_literal(binary.left(), binary.right()).or_else(
+                            || column_and_literal(binary.right(), binary.left()),
+                        )?;
+                    Some(Self::In(column.name().to_string(), vec![value.clone()]))
+                }
+                _ => None,
+            }
+        } else if let Some(in_list) = expr_any.downcast_ref::<InListExpr>() {
+            if in_list.negated() {
+                return None;
+            }
+            let column = in_list.expr().as_any().downcast_ref::<PhysicalColumn>()?;
+            let values = in_list
+                .list()
+                .iter()
+                .map(|e| {
+                    e.as_any()
+                        .downcast_ref::<Literal>()
+                        .map(|l| l.value().clone())
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(Self::In(column.name().to_string(), values))
+        } else {
+            None
+        }
+    }
+
+    /// Adds the names of the columns referenced by the predicate to `columns`
+    fn add_columns<'a>(&'a self, columns: &mut HashSet<&'a str>) {
+        match self {
+            Self::In(column, _) => {
+                columns.insert(column);
+            }
+            Self::And(left, right) | Self::Or(left, right) => {
+                left.add_columns(columns);
+                right.add_columns(columns);
+            }
+        }
+    }
+
+    /// Returns false if the bloom filters, by column name, prove that no row
+    /// matches the predicate
+    fn may_match(&self, bloom_filters: &HashMap<String, (Sbbf, PhysicalType)>) -> bool {
+        match self {
+            Self::In(column, values) => match bloom_filters.get(column) {
+                Some((sbbf, physical_type)) => values
+                    .iter()
+                    .any(|v| bloom_filter_may_contain(sbbf, *physical_type, v)),
+                None => true,
+            },
+            Self::And(left, right) => {
+                left.may_match(bloom_filters) && right.may_match(bloom_filters)
+            }
+            Self::Or(left, right) => {
+                left.may_match(bloom_filters) || right.may_match(bloom_filters)
+            }
+        }
+    }
+}
+
+/// Returns `column` and the value of `literal` if they are a column and a
+/// literal respectively
+fn column_and_literal<'a>(
+    column: &'a Arc<dyn PhysicalExpr>,
+    literal: &'a Arc<dyn PhysicalExpr>,
+) -> Option<(&'a PhysicalColumn, &'a ScalarValue)> {
+    let column = column.as_any().downcast_ref::<PhysicalColumn>()?;
+    let literal = literal.as_any().downcast_ref::<Literal>()?;
+    Some((column, literal.value()))
+}
+
+/// Returns false if the bloom filter of a column stored as `physical_type`
+/// proves that the column does not contain `value`
+fn bloom_filter_may_contain(
+    sbbf: &Sbbf,
+    physical_type: PhysicalType,
+    value: &ScalarValue,
+) -> bool {
+    match (value, physical_type) {
+        // NULL is not equal to any value
+        _ if value.is_null() => false,
+        (ScalarValue::Dictionary(_, value), _) => {
+            bloom_filter_may_contain(sbbf, physical_type, value)
+        }
+        (
+            ScalarValue::Utf8(Some(v)) | ScalarValue::LargeUtf8(Some(v)),
+            PhysicalType::BYTE_ARRAY,
+        ) => sbbf.check(&ByteArray::from(v.as_str())),
+        (
+            ScalarValue::Binary(Some(v)) | ScalarValue::LargeBinary(Some(v)),
+            PhysicalType::BYTE_ARRAY,
+        ) => sbbf.check(&ByteArray::from(v.clone())),
+        (ScalarValue::Boolean(Some(v)), PhysicalType::BOOLEAN) => sbbf.check(v),
+        (ScalarValue::Int8(Some(v)), PhysicalType::INT32) => sbbf.check(&(*v as i32)),
+        (ScalarValue::Int16(Some(v)), PhysicalType::INT32) => sbbf.check(&(*v as i32)),
+        (ScalarValue::Int32(Some(v)), PhysicalType::INT32) => sbbf.check(v),
+        (ScalarValue::UInt8(Some(v)), PhysicalType::INT32) => sbbf.check(&(*v as i32)),
+        (ScalarValue::UInt16(Some(v)), PhysicalType::INT32) => sbbf.check(&(*v as i32)),
+        (ScalarValue::UInt32(Some(v)), PhysicalType::INT32) => sbbf.check(&(*v as i32)),
+        (ScalarValue::Date32(Some(v)), PhysicalType::INT32) => sbbf.check(v),
+        (ScalarValue::Int64(Some(v)), PhysicalType::INT64) => sbbf.check(v),
+        (ScalarValue::UInt64(Some(v)), PhysicalType::INT64) => sbbf.check(&(*v as i64)),
+        // floats are not checked, as 0.0 = -0.0 but their bits hash
+        // differently, and neither are the types that may be stored with
+        // a different representation than their `ScalarValue`
+        _ => true,
+    }
+}
+
+/// Returns a vector of the indexes in `row_groups` which should be scanned,
+/// after checking `predicate` against the bloom filters of the row groups.
+///
+/// The bloom filters of all the row groups are fetched at once. A row group
+/// whose bloom filters can not be read is scanned.
+pub(crate) async fn prune_row_groups_by_bloom_filters(
+    reader: &mut dyn AsyncFileReader,
+    file_size: usize,
+    row_groups: &[usize],
+    groups: &[RowGroupMetaData],
+    predicate: &BloomFilterPredicate,
+    metrics: &ParquetFileMetrics,
+) -> Vec<usize> {
+    let mut columns = HashSet::new();
+    predicate.add_columns(&mut columns);
+
+    let chunks = match fetch_bloom_filters(
+        reader, file_size, row_groups, groups, &columns,
+    )
+    .await
+    {
+        Ok(chunks) => Arc::new(chunks),
+        Err(e) => {
+            debug!("Error fetching bloom filters {e}");
+            metrics.predicate_evaluation_errors.add(row_groups.len());
+            return row_groups.to_vec();
+        }
+    };
+
+    let mut filtered = Vec::with_capacity(row_groups.len());
+    for idx in row_groups {
+        match read_bloom_filters(chunks.clone(), &groups[*idx], &columns) {
+            Ok(bloom_filters) => {
+                if !predicate.may_match(&bloom_filters) {
+                    metrics.row_groups_pruned_bloom_filter.add(1);
+                    continue;
+                }
+            }
+            Err(e) => {
+                debug!("Error reading row group bloom filters {e}");
+                metrics.predicate_evaluation_errors.add(1);
+            }
+        }
+        filtered.push(*idx);
+    }
+    filtered
+}
+
+/// Returns the name of a column if it is a top level column in `columns`
+fn bloom_filter_column<'a>(
+    metadata: &'a ColumnChunkMetaData,
+    columns: &HashSet<&str>,
+) -> Option<&'a str> {
+    match metadata.column_path().parts() {
+        [name] if columns.contains(name.as_str()) => Some(name),
+        _ => None,
+    }
+}
+
+/// Fetches with a single request the bloom filters of the top level
+/// `columns` in `row_groups`.
+///
+/// Parquet metadata records where a bloom filter starts but not its length,
+/// so each bloom filter is assumed to end where the next structure of the
+/// file recorded in the metadata starts: a column chunk, another bloom
+/// filter or a page index. The last one ends at the footer at the latest.
+async fn fetch_bloom_filters(
+    reader: &mut dyn AsyncFileReader,
+    file_size: usize,
+    row_groups: &[usize],
+    groups: &[RowGroupMetaData],
+    columns: &HashSet<&str>,
+) -> Result<FetchedBloomFilters> {
+    // the parquet footer is its metadata length and magic number
+    let footer_start = file_size.saturating_sub(8);
+    let mut boundaries = vec![footer_start];
+    for c in groups.iter().flat_map(|g| g.columns()) {
+        let (start, _) = c.byte_range();
+        boundaries.push(start as usize);
+        boundaries.extend(
+            [
+                c.bloom_filter_offset(),
+                c.column_index_offset(),
+                c.offset_index_offset(),
+            ]
+            .into_iter()
+            .flatten()
+            .map(|offset| offset as usize),
+        );
+    }
+    boundaries.sort_unstable();
+
+    let mut ranges = row_groups
+        .iter()
+        .flat_map(|idx| groups[*idx].columns())
+        .filter(|c| bloom_filter_column(c, columns).is_some())
+        .filter_map(|c| c.bloom_filter_offset())
+        .map(|offset| {
+            let start = offset as usize;
+            let next = boundaries.partition_point(|b| *b <= start);
+            let end = boundaries.get(next).copied().unwrap_or(footer_start);
+            start..end.min(footer_start)
+        })
+        .filter(|range| !range.is_empty())
+        .collect::<Vec<_>>();
+    ranges.sort_unstable_by_key(|r| r.start);
+    ranges.dedup();
+
+    let starts = ranges.iter().map(|r| r.start).collect::<Vec<_>>();
+    let bytes = if ranges.is_empty() {
+        vec![]
+    } else {
+        reader.get_byte_ranges(ranges).await?
+    };
+    Ok(FetchedBloomFilters {
+        file_size: file_size as u64,
+        chunks: starts.into_iter().zip(bytes).collect(),
+    })
+}
+
+/// Reads the bloom filters of the top level `columns` of a row group from
+/// the fetched bloom filters, and returns them by column name along with
+/// the physical type of the column
+fn read_bloom_filters(
+    chunks: Arc<FetchedBloomFilters>,
+    metadata: &RowGroupMetaData,
+    columns: &HashSet<&str>,
+) -> Result<HashMap<String, (Sbbf, PhysicalType)>> {
+    // the parquet reader reads the bloom filters of all the columns that
+    // have one, so they are removed from the metadata of the other columns
+    let mut names = Vec::with_capacity(metadata.num_columns());
+    let column_metadata = metadata
+        .columns()
+        .iter()
+        .map(|c| {
+            let name = bloom_filter_column(c, columns).map(str::to_string);
+            let c = match (&name, c.bloom_filter_offset()) {
+                (Some(_), Some(_)) => c.clone(),
+                _ => c
+                    .clone()
+                    .into_builder()
+                    .set_bloom_filter_offset(None)
+                    .build()?,
+            };
+            names.push(name);
+            Ok(c)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if column_metadata
+        .iter()
+        .all(|c| c.bloom_filter_offset().is_none())
+    {
+        return Ok(HashMap::new());
+    }
+    let metadata = metadata
+        .clone()
+        .into_builder()
+        .set_column_metadata(column_metadata)
+        .build()?;
+
+    let props = Arc::new(
+        ReaderProperties::builder()
+            .set_read_bloom_filter(true)
+            .build(),
+    );
+    let row_group_reader = SerializedRowGroupReader::new(chunks, &metadata, None, props)?;
+    Ok(metadata
+        .columns()
+        .iter()
+        .zip(names)
+        .enumerate()
+        .filter_map(|(i, (c, name))| {
+            let sbbf = row_group_reader.get_column_bloom_filter(i)?;
+            Some((name?, (sbbf.clone(), c.column_type())))
+        })
+        .collect())
+}
+
+/// The bloom filters of a parquet file fetched by
+/// [`fetch_bloom_filters`], which the parquet crate reads with the
+/// [`ChunkReader`] API
+struct FetchedBloomFilters {
+    /// The size of the parquet file
+    file_size: u64,
+    /// The start offsets and the bytes of the fetched bloom filters
+    chunks: Vec<(usize, Bytes)>,
+}
+
+impl FetchedBloomFilters {
+    /// Returns the fetched bytes from `start`, up to `length` bytes if given
+    fn get(&self, start: usize, length: Option<usize>) -> parquet::errors::Result<Bytes> {
+        self.chunks
+            .iter()
+            .find_map(|(offset, bytes)| {
+                let end = offset + bytes.len();
+                let slice_end = length.map_or(end, |length| start + length);
+                (*offset <= start && start < end && slice_end <= end)
+                    .then(|| bytes.slice(start - offset..slice_end - offset))
+            })
+            .ok_or_else(|| {
+                ParquetError::General(format!(
+                    "Bytes from {start} are not in a fetched bloom filter"
+                ))
+            })
+    }
+}
+
+impl Length for FetchedBloomFilters {
+    fn len(&self) -> u64 {
+        self.file_size
+    }
+}
+
+impl ChunkReader for FetchedBloomFilters {
+    type T = bytes::buf::Reader<Bytes>;
+
+    fn get_read(&self, start: u64) -> parquet::errors::Result<Self::T> {
+        Ok(self.get(start as usize, None)?.reader())
+    }
+
+    fn get_bytes(&self, start: u64, length: usize) -> parquet::errors::Result<Bytes> {
+        self.get(start as usize, Some(length))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
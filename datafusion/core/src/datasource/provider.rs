@@ -22,7 +22,7 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use datafusion_common::{Constraints, DataFusionError, Statistics};
-use datafusion_expr::{CreateExternalTable, LogicalPlan};
+use datafusion_expr::{CreateExternalTable, LogicalPlan, SchemaChange};
 pub use datafusion_expr::{TableProviderFilterPushDown, TableType};
 
 use crate::arrow::datatypes::SchemaRef;
@@ -131,6 +131,92 @@ pub trait TableProvider: Sync + Send {
         let msg = "Insertion not implemented for this table".to_owned();
         Err(DataFusionError::NotImplemented(msg))
     }
+
+    /// Return an [`ExecutionPlan`] to delete the rows of this table
+    /// produced by `input`, if supported.
+    ///
+    /// `input` has the columns of this table, and produces each deleted
+    /// row once with its current values. It may be the result of any
+    /// query on this table, e.g. with subqueries in the `WHERE` clause of
+    /// the `DELETE` statement. The rows should be deleted at once, so that
+    /// either all or none of them are deleted.
+    ///
+    /// The returned plan should return a single row in a UInt64
+    /// column called "count" with the number of deleted rows, as for
+    /// [`Self::insert_into`].
+    async fn delete_from(
+        &self,
+        _state: &SessionState,
+        _input: Arc<dyn ExecutionPlan>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let msg = "Deletion not implemented for this table".to_owned();
+        Err(DataFusionError::NotImplemented(msg))
+    }
+
+    /// Return an [`ExecutionPlan`] to update the rows of this table to
+    /// the values produced by `input`, if supported.
+    ///
+    /// `input` has the columns of this table with the new values of the
+    /// updated rows, followed by the columns of this table again with
+    /// their previous values. It produces one row for each updated row.
+    /// The rows should be updated at once, so that either all or none of
+    /// them are updated.
+    ///
+    /// The returned plan should return a single row in a UInt64
+    /// column called "count" with the number of updated rows, as for
+    /// [`Self::insert_into`].
+    async fn update(
+        &self,
+        _state: &SessionState,
+        _input: Arc<dyn ExecutionPlan>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let msg = "Update not implemented for this table".to_owned();
+        Err(DataFusionError::NotImplemented(msg))
+    }
+
+    /// Return an [`ExecutionPlan`] to apply the changes of a `MERGE`
+    /// statement to this table, if supported.
+    ///
+    /// `input` has the columns of this table followed by a Utf8 column
+    /// named [`MERGE_ACTION_COLUMN`], and by the columns of this table
+    /// again with the previous values of the rows. It produces one row for
+    /// each row of the table with the [`MergeAction`] applied to it, where
+    /// updated rows hold their new values, followed by one row for each
+    /// inserted row, whose previous values are null. The inserted, updated
+    /// and deleted rows should be applied at once, so that either all or
+    /// none of the changes are visible.
+    ///
+    /// The returned plan should return a single row in a UInt64
+    /// column called "count" with the number of inserted, updated and
+    /// deleted rows, as for [`Self::insert_into`].
+    ///
+    /// [`MERGE_ACTION_COLUMN`]: datafusion_expr::MERGE_ACTION_COLUMN
+    /// [`MergeAction`]: datafusion_expr::MergeAction
+    async fn merge_into(
+        &self,
+        _state: &SessionState,
+        _input: Arc<dyn ExecutionPlan>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let msg = "Merge not implemented for this table".to_owned();
+        Err(DataFusionError::NotImplemented(msg))
+    }
+
+    /// Return a new [`TableProvider`] for this table with its schema
+    /// changed by `change`, if supported.
+    ///
+    /// The new provider replaces this one in its [`SchemaProvider`] when
+    /// executing an `ALTER TABLE` statement. Whether the columns referred
+    /// to by `change` exist is checked before calling this method.
+    ///
+    /// [`SchemaProvider`]: crate::catalog::schema::SchemaProvider
+    async fn alter_schema(
+        &self,
+        _state: &SessionState,
+        _change: &SchemaChange,
+    ) -> Result<Arc<dyn TableProvider>> {
+        let msg = "Altering the schema not implemented for this table".to_owned();
+        Err(DataFusionError::NotImplemented(msg))
+    }
 }
 
 /// A factory which creates [`TableProvider`]s at runtime given a URL.
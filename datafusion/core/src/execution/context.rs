@@ -29,10 +29,15 @@ use crate::{
     physical_optimizer::optimizer::{PhysicalOptimizer, PhysicalOptimizerRule},
 };
 use datafusion_common::alias::AliasGenerator;
+use datafusion_common::tree_node::{Transformed, TreeNode, VisitRecursion};
 use datafusion_execution::registry::SerializerRegistry;
+use datafusion_expr::expr::{Exists, InSubquery};
+use datafusion_expr::utils::from_plan;
 use datafusion_expr::{
-    logical_plan::{DdlStatement, Statement},
-    DescribeTable, StringifiedPlan, UserDefinedLogicalNode, WindowUDF,
+    logical_plan::{DdlStatement, Statement, Subquery, TableScan},
+    type_coercion::functions::can_coerce_from,
+    DescribeTable, Execute, Expr, ScalarMacro, StringifiedPlan, UserDefinedLogicalNode,
+    WindowUDF,
 };
 pub use datafusion_physical_expr::execution_props::ExecutionProps;
 use datafusion_physical_expr::var_provider::is_system_variables;
@@ -46,6 +51,7 @@ use std::{
 };
 use std::{ops::ControlFlow, sync::Weak};
 
+use arrow::compute::{cast_with_options, CastOptions};
 use arrow::record_batch::RecordBatch;
 use arrow::{
     array::StringBuilder,
@@ -58,14 +64,16 @@ use crate::catalog::{
 };
 use crate::dataframe::DataFrame;
 use crate::datasource::{
+    cte_worktable::CteWorkTable,
     listing::{ListingTableConfig, ListingTableUrl},
-    provider_as_source, TableProvider,
+    provider_as_source, source_as_provider, TableProvider,
 };
 use crate::error::{DataFusionError, Result};
 use crate::logical_expr::{
-    CreateCatalog, CreateCatalogSchema, CreateExternalTable, CreateMemoryTable,
-    CreateView, DropCatalogSchema, DropTable, DropView, Explain, LogicalPlan,
-    LogicalPlanBuilder, SetVariable, TableSource, TableType, UNNAMED_TABLE,
+    AlterTable, AlterTableOperation, CreateCatalog, CreateCatalogSchema,
+    CreateExternalTable, CreateFunction, CreateMemoryTable, CreateView,
+    DropCatalogSchema, DropFunction, DropTable, DropView, Explain, LogicalPlan,
+    LogicalPlanBuilder, Prepare, SetVariable, TableSource, TableType, UNNAMED_TABLE,
 };
 use crate::optimizer::OptimizerRule;
 use datafusion_sql::{
@@ -86,7 +94,7 @@ use crate::physical_planner::PhysicalPlanner;
 use crate::variable::{VarProvider, VarType};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use datafusion_common::{OwnedTableReference, SchemaReference};
+use datafusion_common::{OwnedTableReference, ScalarValue, SchemaReference};
 use datafusion_sql::{
     parser::DFParser,
     planner::{ContextProvider, SqlToRel},
@@ -99,6 +107,7 @@ use crate::catalog::listing_schema::ListingSchemaProvider;
 use crate::datasource::object_store::ObjectStoreUrl;
 use datafusion_optimizer::{
     analyzer::{Analyzer, AnalyzerRule},
+    simplify_expressions::{ExprSimplifier, SimplifyContext},
     OptimizerConfig,
 };
 use datafusion_sql::planner::object_name_to_table_reference;
@@ -365,7 +374,10 @@ impl SessionContext {
                     self.create_external_table(&cmd).await
                 }
                 DdlStatement::CreateMemoryTable(cmd) => {
-                    self.create_memory_table(cmd).await
+                    // boxed, as collecting the input embeds the futures of
+                    // planning and executing a query, which would otherwise
+                    // overflow the stack of the callers in debug builds
+                    Box::pin(self.create_memory_table(cmd)).await
                 }
                 DdlStatement::CreateView(cmd) => self.create_view(cmd).await,
                 DdlStatement::CreateCatalogSchema(cmd) => {
@@ -375,11 +387,34 @@ impl SessionContext {
                 DdlStatement::DropTable(cmd) => self.drop_table(cmd).await,
                 DdlStatement::DropView(cmd) => self.drop_view(cmd).await,
                 DdlStatement::DropCatalogSchema(cmd) => self.drop_schema(cmd).await,
+                DdlStatement::AlterTable(cmd) => self.alter_table(cmd).await,
+                DdlStatement::CreateFunction(cmd) => self.create_function(cmd).await,
+                DdlStatement::DropFunction(cmd) => self.drop_function(cmd).await,
             },
             // TODO what about the other statements (like TransactionStart and TransactionEnd)
             LogicalPlan::Statement(Statement::SetVariable(stmt)) => {
                 self.set_variable(stmt).await
             }
+            LogicalPlan::Prepare(prepare) => {
+                let state = self.state();
+                let data_types = prepared_parameter_types(&prepare)?;
+                self.state.write().store_prepared(
+                    prepare.name.clone(),
+                    data_types,
+                    prepare.input.clone(),
+                )?;
+
+                // return the `Prepare` plan so that parameters can also be
+                // bound with `DataFrame::with_param_values`
+                Ok(DataFrame::new(state, LogicalPlan::Prepare(prepare)))
+            }
+            LogicalPlan::Statement(Statement::Execute(stmt)) => {
+                self.execute_prepared(stmt).await
+            }
+            LogicalPlan::Statement(Statement::Deallocate(stmt)) => {
+                self.state.write().remove_prepared(&stmt.name)?;
+                self.return_empty_dataframe()
+            }
             LogicalPlan::DescribeTable(DescribeTable { schema, .. }) => {
                 self.return_describe_table_dataframe(schema).await
             }
@@ -643,6 +678,126 @@ impl SessionContext {
         }
     }
 
+    async fn create_function(&self, cmd: CreateFunction) -> Result<DataFrame> {
+        let CreateFunction {
+            function,
+            or_replace,
+            ..
+        } = cmd;
+        let mut state = self.state.write();
+        if state.scalar_functions.contains_key(&function.name)
+            || state.aggregate_functions.contains_key(&function.name)
+            || state.window_functions.contains_key(&function.name)
+            || (!or_replace && state.scalar_macros.contains_key(&function.name))
+        {
+            return Err(DataFusionError::Execution(format!(
+                "Function '{}' already exists.",
+                function.name
+            )));
+        }
+        state
+            .scalar_macros
+            .insert(function.name.clone(), Arc::new(function));
+        drop(state);
+        self.return_empty_dataframe()
+    }
+
+    async fn drop_function(&self, cmd: DropFunction) -> Result<DataFrame> {
+        let DropFunction {
+            name, if_exists, ..
+        } = cmd;
+        match (self.deregister_scalar_macro(&name), if_exists) {
+            (Some(_), _) | (None, true) => self.return_empty_dataframe(),
+            (None, false) => Err(DataFusionError::Execution(format!(
+                "Function '{name}' doesn't exist."
+            ))),
+        }
+    }
+
+    async fn alter_table(&self, cmd: AlterTable) -> Result<DataFrame> {
+        let AlterTable {
+            name, operation, ..
+        } = cmd;
+        let (resolved, maybe_schema) = {
+            let state = self.state.read();
+            let resolved = state.resolve_table_ref(&name);
+            let schema = state
+                .catalog_list
+                .catalog(&resolved.catalog)
+                .and_then(|c| c.schema(&resolved.schema));
+            let resolved = (resolved.catalog.to_string(), resolved.schema.to_string());
+            (resolved, schema)
+        };
+        let table = match &maybe_schema {
+            Some(schema) => schema.table(name.table()).await,
+            None => None,
+        };
+        let (Some(schema), Some(table)) = (maybe_schema, table) else {
+            return Err(DataFusionError::Execution(format!(
+                "Table '{name}' doesn't exist."
+            )));
+        };
+
+        let table_schema = table.schema();
+        let column_exists = |column: &str| table_schema.field_with_name(column).is_ok();
+        let column_error = |column: &str, message: &str| {
+            Err(DataFusionError::Execution(format!(
+                "Column '{column}' of table '{name}' {message}"
+            )))
+        };
+        match &operation {
+            AlterTableOperation::AddColumn {
+                field,
+                if_not_exists,
+            } if column_exists(field.name()) => {
+                if *if_not_exists {
+                    return self.return_empty_dataframe();
+                }
+                return column_error(field.name(), "already exists.");
+            }
+            AlterTableOperation::DropColumn {
+                name: column,
+                if_exists,
+            } if !column_exists(column) => {
+                if *if_exists {
+                    return self.return_empty_dataframe();
+                }
+                return column_error(column, "doesn't exist.");
+            }
+            AlterTableOperation::RenameColumn { old_name, new_name } => {
+                if !column_exists(old_name) {
+                    return column_error(old_name, "doesn't exist.");
+                }
+                if column_exists(new_name) {
+                    return column_error(new_name, "already exists.");
+                }
+            }
+            AlterTableOperation::RenameTable { new_name } => {
+                let same_schema = {
+                    let state = self.state.read();
+                    let new_resolved = state.resolve_table_ref(new_name);
+                    new_resolved.catalog == resolved.0
+                        && new_resolved.schema == resolved.1
+                };
+                if !same_schema {
+                    return Err(DataFusionError::NotImplemented(
+                        "Renaming a table into another schema is not supported"
+                            .to_owned(),
+                    ));
+                }
+                schema.rename_table(name.table(), new_name.table().to_owned())?;
+                return self.return_empty_dataframe();
+            }
+            _ => {}
+        }
+
+        if let Some(change) = operation.schema_change() {
+            let new_table = table.alter_schema(&self.state(), &change).await?;
+            schema.replace_table(name.table().to_owned(), new_table)?;
+        }
+        self.return_empty_dataframe()
+    }
+
     async fn drop_schema(&self, cmd: DropCatalogSchema) -> Result<DataFrame> {
         let DropCatalogSchema {
             name,
@@ -695,6 +850,59 @@ impl SessionContext {
         self.return_empty_dataframe()
     }
 
+    async fn execute_prepared(&self, stmt: Execute) -> Result<DataFrame> {
+        let Execute {
+            name, parameters, ..
+        } = stmt;
+
+        let state = self.state();
+        let prepared = state.get_prepared(&name).ok_or_else(|| {
+            DataFusionError::Execution(format!(
+                "Prepared statement '{name}' does not exist"
+            ))
+        })?;
+        if prepared.data_types.len() != parameters.len() {
+            return Err(DataFusionError::Plan(format!(
+                "Expected {} parameters, got {}",
+                prepared.data_types.len(),
+                parameters.len()
+            )));
+        }
+
+        // evaluate the parameters and bind them to the declared types
+        let simplifier =
+            ExprSimplifier::new(SimplifyContext::new(state.execution_props()));
+        let param_values = parameters
+            .into_iter()
+            .zip(prepared.data_types.iter())
+            .enumerate()
+            .map(|(i, (expr, data_type))| match simplifier.simplify(expr)? {
+                Expr::Literal(value) => bind_parameter_value(i, value, data_type),
+                expr => Err(DataFusionError::Plan(format!(
+                    "Parameter at index {i} must be a constant, got {expr}"
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // the tables may have been dropped or replaced since `PREPARE`
+        let plan = self.resolve_prepared_tables(&prepared.plan).await?;
+        let plan = plan.replace_params_with_values(&param_values)?;
+        Ok(DataFrame::new(state, plan))
+    }
+
+    /// Replace the sources of the table scans of the prepared `plan`, and of
+    /// its subqueries, with the tables currently registered under their names
+    async fn resolve_prepared_tables(&self, plan: &LogicalPlan) -> Result<LogicalPlan> {
+        let mut table_names = HashSet::new();
+        collect_scanned_tables(plan, &mut table_names)?;
+        let mut sources = HashMap::with_capacity(table_names.len());
+        for table_name in table_names {
+            let provider = self.table_provider(table_name.clone()).await?;
+            sources.insert(table_name, provider_as_source(provider));
+        }
+        replace_table_sources(plan, &sources)
+    }
+
     async fn create_custom_table(
         &self,
         cmd: &CreateExternalTable,
@@ -769,6 +977,24 @@ impl SessionContext {
             .insert(f.name.clone(), Arc::new(f));
     }
 
+    /// Registers a function defined by an expression within this context,
+    /// replacing any function of the same name created by `CREATE FUNCTION`.
+    ///
+    /// Calls of the function in SQL queries are replaced by its body during
+    /// planning. Function names are looked up as for [`Self::register_udf`].
+    pub fn register_scalar_macro(&self, f: ScalarMacro) {
+        self.state
+            .write()
+            .scalar_macros
+            .insert(f.name.clone(), Arc::new(f));
+    }
+
+    /// Deregisters a function registered by [`Self::register_scalar_macro`]
+    /// or created by `CREATE FUNCTION`, returning it if it existed.
+    pub fn deregister_scalar_macro(&self, name: &str) -> Option<Arc<ScalarMacro>> {
+        self.state.write().scalar_macros.remove(name)
+    }
+
     /// Registers an aggregate UDF within this context.
     ///
     /// Note in SQL queries, aggregate names are looked up using
@@ -1346,6 +1572,9 @@ pub struct SessionState {
     aggregate_functions: HashMap<String, Arc<AggregateUDF>>,
     /// Window functions registered in the context
     window_functions: HashMap<String, Arc<WindowUDF>>,
+    /// Functions defined by expressions, which are inlined into the
+    /// expressions calling them
+    scalar_macros: HashMap<String, Arc<ScalarMacro>>,
     /// Deserializer registry for extensions.
     serializer_registry: Arc<dyn SerializerRegistry>,
     /// Session configuration
@@ -1362,6 +1591,8 @@ pub struct SessionState {
     table_factories: HashMap<String, Arc<dyn TableProviderFactory>>,
     /// Runtime environment
     runtime_env: Arc<RuntimeEnv>,
+    /// Statements prepared with `PREPARE`, by name
+    prepared_plans: HashMap<String, Arc<PreparedPlan>>,
 }
 
 impl Debug for SessionState {
@@ -1373,6 +1604,195 @@ impl Debug for SessionState {
     }
 }
 
+/// A statement prepared with `PREPARE`
+#[derive(Debug)]
+pub(crate) struct PreparedPlan {
+    /// Data types of the parameters
+    data_types: Vec<DataType>,
+    /// The logical plan of the statement, whose tables are resolved again
+    /// when it is executed
+    plan: Arc<LogicalPlan>,
+}
+
+/// Returns true if `scan` reads the work table of a recursive query, which is
+/// not registered in the catalog
+fn scans_cte_work_table(scan: &TableScan) -> bool {
+    source_as_provider(&scan.source)
+        .map(|provider| provider.as_any().is::<CteWorkTable>())
+        .unwrap_or(false)
+}
+
+/// Returns the subquery plans of the expressions of `plan`
+fn expression_subqueries(plan: &LogicalPlan) -> Result<Vec<Arc<LogicalPlan>>> {
+    let mut subqueries = vec![];
+    for expr in plan.expressions() {
+        expr.apply(&mut |expr| {
+            match expr {
+                Expr::ScalarSubquery(subquery)
+                | Expr::Exists(Exists { subquery, .. })
+                | Expr::InSubquery(InSubquery { subquery, .. }) => {
+                    subqueries.push(subquery.subquery.clone())
+                }
+                _ => {}
+            }
+            Ok(VisitRecursion::Continue)
+        })?;
+    }
+    Ok(subqueries)
+}
+
+/// Add the names of the tables scanned by `plan` and its subqueries to
+/// `table_names`
+fn collect_scanned_tables(
+    plan: &LogicalPlan,
+    table_names: &mut HashSet<OwnedTableReference>,
+) -> Result<()> {
+    plan.apply(&mut |plan| {
+        if let LogicalPlan::TableScan(scan) = plan {
+            if !scans_cte_work_table(scan) {
+                table_names.insert(scan.table_name.clone());
+            }
+        }
+        for subquery in expression_subqueries(plan)? {
+            collect_scanned_tables(&subquery, table_names)?;
+        }
+        Ok(VisitRecursion::Continue)
+    })?;
+    Ok(())
+}
+
+/// Replace the sources of the table scans of `plan` and its subqueries with
+/// `sources`, by table name. A table must have kept its schema.
+///
+/// The plan is rebuilt rather than transformed, as table scans are compared
+/// without their sources.
+fn replace_table_sources(
+    plan: &LogicalPlan,
+    sources: &HashMap<OwnedTableReference, Arc<dyn TableSource>>,
+) -> Result<LogicalPlan> {
+    if let LogicalPlan::TableScan(scan) = plan {
+        let Some(source) = sources.get(&scan.table_name) else {
+            return Ok(plan.clone());
+        };
+        if source.schema() != scan.source.schema() {
+            return Err(DataFusionError::Plan(format!(
+                "The schema of table '{}' changed since the statement was prepared",
+                scan.table_name
+            )));
+        }
+        let mut scan = scan.clone();
+        scan.source = source.clone();
+        return Ok(LogicalPlan::TableScan(scan));
+    }
+
+    let inputs = plan
+        .inputs()
+        .into_iter()
+        .map(|input| replace_table_sources(input, sources))
+        .collect::<Result<Vec<_>>>()?;
+    if expression_subqueries(plan)?.is_empty() {
+        return plan.with_new_inputs(&inputs);
+    }
+    let exprs = plan
+        .expressions()
+        .into_iter()
+        .map(|expr| {
+            expr.transform_up(&|expr| {
+                let expr = match expr {
+                    Expr::ScalarSubquery(subquery) => {
+                        Expr::ScalarSubquery(replace_subquery_sources(subquery, sources)?)
+                    }
+                    Expr::Exists(Exists { subquery, negated }) => Expr::Exists(Exists {
+                        subquery: replace_subquery_sources(subquery, sources)?,
+                        negated,
+                    }),
+                    Expr::InSubquery(InSubquery {
+                        expr,
+                        subquery,
+                        negated,
+                    }) => Expr::InSubquery(InSubquery {
+                        expr,
+                        subquery: replace_subquery_sources(subquery, sources)?,
+                        negated,
+                    }),
+                    expr => return Ok(Transformed::No(expr)),
+                };
+                Ok(Transformed::Yes(expr))
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    from_plan(plan, &exprs, &inputs)
+}
+
+/// Replace the sources of the table scans of `subquery` with `sources`
+fn replace_subquery_sources(
+    subquery: Subquery,
+    sources: &HashMap<OwnedTableReference, Arc<dyn TableSource>>,
+) -> Result<Subquery> {
+    Ok(Subquery {
+        subquery: Arc::new(replace_table_sources(&subquery.subquery, sources)?),
+        outer_ref_columns: subquery.outer_ref_columns,
+    })
+}
+
+/// The data types of the parameters of `prepare`, which must be declared
+/// for every parameter used by the statement
+fn prepared_parameter_types(prepare: &Prepare) -> Result<Vec<DataType>> {
+    let num_params = prepare
+        .input
+        .get_parameter_types()?
+        .keys()
+        .filter_map(|id| id.strip_prefix('$')?.parse::<usize>().ok())
+        .max()
+        .unwrap_or_default();
+    if num_params > prepare.data_types.len() {
+        return Err(DataFusionError::Plan(format!(
+            "Expected data types for all {num_params} parameters, got {}",
+            prepare.data_types.len()
+        )));
+    }
+    Ok(prepare.data_types.clone())
+}
+
+/// Bind `value` to the parameter at `index` of a prepared statement declared
+/// as `data_type`, casting it if it can be coerced to that type
+fn bind_parameter_value(
+    index: usize,
+    value: ScalarValue,
+    data_type: &DataType,
+) -> Result<ScalarValue> {
+    let value_type = value.get_datatype();
+    if &value_type == data_type {
+        return Ok(value);
+    }
+    // numeric literals are typed as Int64 / Float64, so allow narrowing them
+    // as long as the value is kept exactly
+    let coercible = can_coerce_from(data_type, &value_type)
+        || (data_type.is_numeric() && value_type.is_numeric());
+    if !coercible {
+        return Err(DataFusionError::Plan(format!(
+            "Expected parameter of type {data_type:?}, got {value_type:?} at index {index}"
+        )));
+    }
+    let options = CastOptions {
+        safe: false,
+        ..Default::default()
+    };
+    let array = cast_with_options(&value.to_array(), data_type, &options)?;
+    let bound = ScalarValue::try_from_array(&array, 0)?;
+    if value.is_null() {
+        return Ok(bound);
+    }
+    // reject binds that lose precision, such as 1.5 for an integer
+    let round_trip = cast_with_options(&array, &value_type, &options)?;
+    if ScalarValue::try_from_array(&round_trip, 0)? != value {
+        return Err(DataFusionError::Plan(format!(
+            "Cannot bind {value} to parameter of type {data_type:?} without losing precision at index {index}"
+        )));
+    }
+    Ok(bound)
+}
+
 /// Default session builder using the provided configuration
 #[deprecated(
     since = "23.0.0",
@@ -1441,11 +1861,13 @@ impl SessionState {
             scalar_functions: HashMap::new(),
             aggregate_functions: HashMap::new(),
             window_functions: HashMap::new(),
+            scalar_macros: HashMap::new(),
             serializer_registry: Arc::new(EmptySerializerRegistry),
             config,
             execution_props: ExecutionProps::new(),
             runtime_env: runtime,
             table_factories,
+            prepared_plans: HashMap::new(),
         }
     }
 
@@ -1511,9 +1933,10 @@ impl SessionState {
     ) -> Result<Arc<dyn SchemaProvider>> {
         let resolved_ref = self.resolve_table_ref(table_ref);
         if self.config.information_schema() && resolved_ref.schema == INFORMATION_SCHEMA {
-            return Ok(Arc::new(InformationSchemaProvider::new(
-                self.catalog_list.clone(),
-            )));
+            return Ok(Arc::new(
+                InformationSchemaProvider::new(self.catalog_list.clone())
+                    .with_scalar_macros(self.scalar_macros.values().cloned()),
+            ));
         }
 
         self.catalog_list
@@ -1922,6 +2345,45 @@ impl SessionState {
         &self.window_functions
     }
 
+    /// Return reference to the functions defined by expressions
+    pub fn scalar_macros(&self) -> &HashMap<String, Arc<ScalarMacro>> {
+        &self.scalar_macros
+    }
+
+    /// Store the logical plan of a prepared statement under `name`
+    pub(crate) fn store_prepared(
+        &mut self,
+        name: String,
+        data_types: Vec<DataType>,
+        plan: Arc<LogicalPlan>,
+    ) -> Result<()> {
+        match self.prepared_plans.entry(name) {
+            Entry::Occupied(entry) => Err(DataFusionError::Execution(format!(
+                "Prepared statement '{}' already exists",
+                entry.key()
+            ))),
+            Entry::Vacant(entry) => {
+                entry.insert(Arc::new(PreparedPlan { data_types, plan }));
+                Ok(())
+            }
+        }
+    }
+
+    /// Get the prepared statement named `name`
+    pub(crate) fn get_prepared(&self, name: &str) -> Option<Arc<PreparedPlan>> {
+        self.prepared_plans.get(name).cloned()
+    }
+
+    /// Remove the prepared statement named `name`
+    pub(crate) fn remove_prepared(&mut self, name: &str) -> Result<()> {
+        match self.prepared_plans.remove(name) {
+            Some(_) => Ok(()),
+            None => Err(DataFusionError::Execution(format!(
+                "Prepared statement '{name}' does not exist"
+            ))),
+        }
+    }
+
     /// Return [SerializerRegistry] for extensions
     pub fn serializer_registry(&self) -> Arc<dyn SerializerRegistry> {
         self.serializer_registry.clone()
@@ -1959,6 +2421,10 @@ impl<'a> ContextProvider for SessionContextProvider<'a> {
         self.state.window_functions().get(name).cloned()
     }
 
+    fn get_scalar_macro(&self, name: &str) -> Option<Arc<ScalarMacro>> {
+        self.state.scalar_macros().get(name).cloned()
+    }
+
     fn get_variable_type(&self, variable_names: &[String]) -> Option<DataType> {
         if variable_names.is_empty() {
             return None;
@@ -1980,6 +2446,15 @@ impl<'a> ContextProvider for SessionContextProvider<'a> {
     fn options(&self) -> &ConfigOptions {
         self.state.config_options()
     }
+
+    fn create_cte_work_table(
+        &self,
+        name: &str,
+        schema: SchemaRef,
+    ) -> Result<Arc<dyn TableSource>> {
+        let table = Arc::new(CteWorkTable::new(name, schema));
+        Ok(provider_as_source(table))
+    }
 }
 
 impl FunctionRegistry for SessionState {
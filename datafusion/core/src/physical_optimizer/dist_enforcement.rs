@@ -191,7 +191,8 @@ fn adjust_input_keys_ordering(
                     JoinType::Left
                     | JoinType::LeftSemi
                     | JoinType::LeftAnti
-                    | JoinType::Full => None,
+                    | JoinType::Full
+                    | JoinType::AsOf => None,
                 };
 
                 // Push down requirements to the right side
@@ -224,6 +225,7 @@ fn adjust_input_keys_ordering(
         right,
         on,
         join_type,
+        filter,
         sort_options,
         null_equals_null,
         ..
@@ -235,6 +237,7 @@ fn adjust_input_keys_ordering(
                     left.clone(),
                     right.clone(),
                     new_conditions.0,
+                    filter.clone(),
                     *join_type,
                     new_conditions.1,
                     *null_equals_null,
@@ -585,6 +588,7 @@ fn reorder_join_keys_to_inputs(
         right,
         on,
         join_type,
+        filter,
         sort_options,
         null_equals_null,
         ..
@@ -614,6 +618,7 @@ fn reorder_join_keys_to_inputs(
                     left.clone(),
                     right.clone(),
                     new_join_on,
+                    filter.clone(),
                     *join_type,
                     new_sort_options,
                     *null_equals_null,
@@ -1139,6 +1144,7 @@ mod tests {
                 left,
                 right,
                 join_on.clone(),
+                None,
                 *join_type,
                 vec![SortOptions::default(); join_on.len()],
                 false,
@@ -1284,7 +1290,7 @@ mod tests {
                     };
                     assert_optimized!(expected, top_join);
                 }
-                JoinType::RightSemi | JoinType::RightAnti => {}
+                JoinType::RightSemi | JoinType::RightAnti | JoinType::AsOf => {}
             }
 
             match join_type {
@@ -1341,7 +1347,7 @@ mod tests {
                     };
                     assert_optimized!(expected, top_join);
                 }
-                JoinType::LeftSemi | JoinType::LeftAnti => {}
+                JoinType::LeftSemi | JoinType::LeftAnti | JoinType::AsOf => {}
             }
         }
 
@@ -119,6 +119,7 @@ fn swap_join_type(join_type: JoinType) -> JoinType {
         JoinType::RightSemi => JoinType::LeftSemi,
         JoinType::LeftAnti => JoinType::RightAnti,
         JoinType::RightAnti => JoinType::LeftAnti,
+        JoinType::AsOf => unreachable!("ASOF joins do not support input swapping"),
     }
 }
 
@@ -272,7 +273,7 @@ fn try_collect_left(
     let join_type = hash_join.join_type();
 
     let left_can_collect = match join_type {
-        JoinType::Left | JoinType::Full | JoinType::LeftAnti => false,
+        JoinType::Left | JoinType::Full | JoinType::LeftAnti | JoinType::AsOf => false,
         JoinType::Inner
         | JoinType::LeftSemi
         | JoinType::Right
@@ -287,7 +288,8 @@ fn try_collect_left(
         | JoinType::RightSemi
         | JoinType::Left
         | JoinType::LeftSemi
-        | JoinType::LeftAnti => collect_threshold.map_or(true, |threshold| {
+        | JoinType::LeftAnti
+        | JoinType::AsOf => collect_threshold.map_or(true, |threshold| {
             supports_collect_by_size(&**right, threshold)
         }),
     };
@@ -357,7 +357,11 @@ fn expr_source_sides(
     left_columns_len: usize,
 ) -> Option<JoinSide> {
     match join_type {
-        JoinType::Inner | JoinType::Left | JoinType::Right | JoinType::Full => {
+        JoinType::Inner
+        | JoinType::Left
+        | JoinType::Right
+        | JoinType::Full
+        | JoinType::AsOf => {
             let all_column_sides = required_exprs
                 .iter()
                 .filter_map(|r| {
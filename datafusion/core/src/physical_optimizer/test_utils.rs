@@ -164,6 +164,7 @@ pub fn sort_merge_join_exec(
             left,
             right,
             join_on.clone(),
+            None,
             *join_type,
             vec![SortOptions::default(); join_on.len()],
             false,
@@ -233,6 +234,7 @@ pub fn bounded_window_exec(
                 &sort_exprs,
                 Arc::new(WindowFrame::new(true)),
                 schema.as_ref(),
+                false,
             )
             .unwrap()],
             input.clone(),
@@ -34,7 +34,9 @@ use datafusion_execution::TaskContext;
 use datafusion_expr::Accumulator;
 use datafusion_physical_expr::{
     equivalence::project_equivalence_properties,
-    expressions::{Avg, CastExpr, Column, Sum},
+    expressions::{
+        Avg, CastExpr, Column, OrderSensitiveArrayAgg, OrderSensitiveStringAgg, Sum,
+    },
     normalize_out_expr_with_columns_map, reverse_order_bys,
     utils::{convert_to_expr, get_indices_of_matching_exprs},
     AggregateExpr, LexOrdering, LexOrderingReq, OrderingEquivalenceProperties,
@@ -385,6 +387,14 @@ fn get_init_req(
     None
 }
 
+/// Checks whether the given aggregate expression merges partial states according
+/// to the ordering values stored in them, so that it does not need its input to
+/// be ordered in the `Final` and `FinalPartitioned` modes.
+fn merges_ordered_states(aggr_expr: &Arc<dyn AggregateExpr>) -> bool {
+    aggr_expr.as_any().is::<OrderSensitiveArrayAgg>()
+        || aggr_expr.as_any().is::<OrderSensitiveStringAgg>()
+}
+
 /// This function gets the finest ordering requirement among all the aggregation
 /// functions. If requirements are conflicting, (i.e. we can not compute the
 /// aggregations in a single [`AggregateExec`]), the function returns an error.
@@ -621,7 +631,18 @@ impl AggregateExec {
         let aggregator_requirement = requirement
             .as_ref()
             .map(|exprs| PhysicalSortRequirement::from_sort_exprs(exprs.iter()));
-        let aggregator_reqs = aggregator_requirement.unwrap_or(vec![]);
+        let mut aggregator_reqs = aggregator_requirement.unwrap_or(vec![]);
+        // The input of the final aggregation consists of partial states, which
+        // are merged in order by order-sensitive aggregators such as ARRAY_AGG.
+        // The ordering requirement refers to the columns of the original input,
+        // hence it cannot be applied to the partial states.
+        if matches!(mode, AggregateMode::Final | AggregateMode::FinalPartitioned)
+            && aggr_expr
+                .iter()
+                .all(|expr| !is_order_sensitive(expr) || merges_ordered_states(expr))
+        {
+            aggregator_reqs = vec![];
+        }
         // If all aggregate expressions are reversible, also consider reverse
         // requirement(s). The reason is that existing ordering may satisfy the
         // given requirement or its reverse. By considering both, we can generate better plans.
@@ -1229,6 +1250,7 @@ mod tests {
     use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
     use arrow::record_batch::RecordBatch;
     use datafusion_common::{DataFusionError, Result, ScalarValue};
+    use datafusion_execution::memory_pool::FairSpillPool;
     use datafusion_execution::runtime_env::{RuntimeConfig, RuntimeEnv};
     use datafusion_physical_expr::expressions::{
         lit, ApproxDistinct, Column, Count, FirstValue, Median,
@@ -1370,6 +1392,18 @@ mod tests {
         )
     }
 
+    /// Returns a task context with small batches and a memory pool of
+    /// `max_memory` bytes, so that aggregating more than a few groups spills
+    fn new_spill_ctx(batch_size: usize, max_memory: usize) -> Arc<TaskContext> {
+        let runtime_config = RuntimeConfig::default()
+            .with_memory_pool(Arc::new(FairSpillPool::new(max_memory)));
+        let session_ctx = SessionContext::with_config_rt(
+            SessionConfig::default().with_batch_size(batch_size),
+            Arc::new(RuntimeEnv::new(runtime_config).unwrap()),
+        );
+        session_ctx.task_ctx()
+    }
+
     async fn check_grouping_sets(input: Arc<dyn ExecutionPlan>) -> Result<()> {
         let input_schema = input.schema();
 
@@ -1574,6 +1608,164 @@ mod tests {
         Ok(())
     }
 
+    /// Runs the partial aggregation of `aggregates` grouped by `grouping_set`
+    /// over `input`, and its final aggregation, returning the results of both
+    /// and the metrics of the final aggregation
+    async fn run_aggregation(
+        input: Arc<dyn ExecutionPlan>,
+        grouping_set: PhysicalGroupBy,
+        aggregates: Vec<Arc<dyn AggregateExpr>>,
+        task_ctx: Arc<TaskContext>,
+    ) -> Result<(Vec<RecordBatch>, Vec<RecordBatch>, MetricsSet)> {
+        let input_schema = input.schema();
+
+        let partial_aggregate = Arc::new(AggregateExec::try_new(
+            AggregateMode::Partial,
+            grouping_set,
+            aggregates.clone(),
+            vec![None],
+            vec![None],
+            input,
+            input_schema.clone(),
+        )?);
+        let partial_result =
+            common::collect(partial_aggregate.execute(0, task_ctx.clone())?).await?;
+
+        let final_group: Vec<(Arc<dyn PhysicalExpr>, String)> = partial_aggregate
+            .group_expr()
+            .expr()
+            .iter()
+            .map(|(_expr, name)| Ok((col(name, &input_schema)?, name.clone())))
+            .collect::<Result<_>>()?;
+        let merge = Arc::new(CoalescePartitionsExec::new(partial_aggregate));
+        let merged_aggregate = Arc::new(AggregateExec::try_new(
+            AggregateMode::Final,
+            PhysicalGroupBy::new_single(final_group),
+            aggregates,
+            vec![None],
+            vec![None],
+            merge,
+            input_schema,
+        )?);
+        let final_result =
+            common::collect(merged_aggregate.execute(0, task_ctx)?).await?;
+
+        Ok((
+            partial_result,
+            final_result,
+            merged_aggregate.metrics().unwrap(),
+        ))
+    }
+
+    /// Check the grouping sets of the data from some_data() are aggregated the
+    /// same way when the aggregation spills
+    async fn check_spilling_grouping_sets(input: Arc<dyn ExecutionPlan>) -> Result<()> {
+        let input_schema = input.schema();
+
+        let grouping_set = PhysicalGroupBy {
+            expr: vec![
+                (col("a", &input_schema)?, "a".to_string()),
+                (col("b", &input_schema)?, "b".to_string()),
+            ],
+            null_expr: vec![
+                (lit(ScalarValue::UInt32(None)), "a".to_string()),
+                (lit(ScalarValue::Float64(None)), "b".to_string()),
+            ],
+            groups: vec![
+                vec![false, true],  // (a, NULL)
+                vec![true, false],  // (NULL, b)
+                vec![false, false], // (a,b)
+            ],
+        };
+
+        let aggregates: Vec<Arc<dyn AggregateExpr>> = vec![Arc::new(Count::new(
+            lit(1i8),
+            "COUNT(1)".to_string(),
+            DataType::Int64,
+        ))];
+
+        let (_, result, metrics) =
+            run_aggregation(input, grouping_set, aggregates, new_spill_ctx(2, 3000))
+                .await?;
+
+        let expected = vec![
+            "+---+-----+----------+",
+            "| a | b   | COUNT(1) |",
+            "+---+-----+----------+",
+            "|   | 1.0 | 2        |",
+            "|   | 2.0 | 2        |",
+            "|   | 3.0 | 2        |",
+            "|   | 4.0 | 2        |",
+            "| 2 |     | 2        |",
+            "| 2 | 1.0 | 2        |",
+            "| 3 |     | 3        |",
+            "| 3 | 2.0 | 2        |",
+            "| 3 | 3.0 | 1        |",
+            "| 4 |     | 3        |",
+            "| 4 | 3.0 | 1        |",
+            "| 4 | 4.0 | 2        |",
+            "+---+-----+----------+",
+        ];
+        assert_batches_sorted_eq!(&expected, &result);
+
+        assert_eq!(12, metrics.output_rows().unwrap());
+        assert!(metrics.spill_count().unwrap() > 0);
+
+        Ok(())
+    }
+
+    /// Check the aggregates of the data from some_data() are the same when the
+    /// aggregation spills
+    async fn check_spilling_aggregates(input: Arc<dyn ExecutionPlan>) -> Result<()> {
+        let input_schema = input.schema();
+
+        let grouping_set = PhysicalGroupBy {
+            expr: vec![(col("a", &input_schema)?, "a".to_string())],
+            null_expr: vec![],
+            groups: vec![vec![false]],
+        };
+
+        let aggregates: Vec<Arc<dyn AggregateExpr>> = vec![Arc::new(Avg::new(
+            col("b", &input_schema)?,
+            "AVG(b)".to_string(),
+            DataType::Float64,
+        ))];
+
+        let (partial_result, result, metrics) =
+            run_aggregation(input, grouping_set, aggregates, new_spill_ctx(2, 1500))
+                .await?;
+
+        // the partial aggregation emits its groups early under the memory limit
+        let expected = vec![
+            "+---+---------------+-------------+",
+            "| a | AVG(b)[count] | AVG(b)[sum] |",
+            "+---+---------------+-------------+",
+            "| 2 | 1             | 1.0         |",
+            "| 2 | 1             | 1.0         |",
+            "| 3 | 1             | 2.0         |",
+            "| 3 | 2             | 5.0         |",
+            "| 4 | 3             | 11.0        |",
+            "+---+---------------+-------------+",
+        ];
+        assert_batches_sorted_eq!(expected, &partial_result);
+
+        let expected = vec![
+            "+---+--------------------+",
+            "| a | AVG(b)             |",
+            "+---+--------------------+",
+            "| 2 | 1.0                |",
+            "| 3 | 2.3333333333333335 |", // 3, (2 + 3 + 2) / 3
+            "| 4 | 3.6666666666666665 |", // 4, (3 + 4 + 4) / 3
+            "+---+--------------------+",
+        ];
+        assert_batches_sorted_eq!(&expected, &result);
+
+        assert_eq!(3, metrics.output_rows().unwrap());
+        assert!(metrics.spill_count().unwrap() > 0);
+
+        Ok(())
+    }
+
     /// Define a test source that can yield back to runtime before returning its first item ///
 
     #[derive(Debug)]
@@ -1695,6 +1887,14 @@ mod tests {
         check_aggregates(input).await
     }
 
+    #[tokio::test]
+    async fn aggregate_source_not_yielding_with_spill() -> Result<()> {
+        let input: Arc<dyn ExecutionPlan> =
+            Arc::new(TestYieldingExec { yield_first: false });
+
+        check_spilling_aggregates(input).await
+    }
+
     #[tokio::test]
     async fn aggregate_grouping_sets_source_not_yielding() -> Result<()> {
         let input: Arc<dyn ExecutionPlan> =
@@ -1703,6 +1903,14 @@ mod tests {
         check_grouping_sets(input).await
     }
 
+    #[tokio::test]
+    async fn aggregate_grouping_sets_source_not_yielding_with_spill() -> Result<()> {
+        let input: Arc<dyn ExecutionPlan> =
+            Arc::new(TestYieldingExec { yield_first: false });
+
+        check_spilling_grouping_sets(input).await
+    }
+
     #[tokio::test]
     async fn aggregate_source_with_yielding() -> Result<()> {
         let input: Arc<dyn ExecutionPlan> =
@@ -1711,6 +1919,14 @@ mod tests {
         check_aggregates(input).await
     }
 
+    #[tokio::test]
+    async fn aggregate_source_with_yielding_with_spill() -> Result<()> {
+        let input: Arc<dyn ExecutionPlan> =
+            Arc::new(TestYieldingExec { yield_first: true });
+
+        check_spilling_aggregates(input).await
+    }
+
     #[tokio::test]
     async fn aggregate_grouping_sets_with_yielding() -> Result<()> {
         let input: Arc<dyn ExecutionPlan> =
@@ -1719,6 +1935,14 @@ mod tests {
         check_grouping_sets(input).await
     }
 
+    #[tokio::test]
+    async fn aggregate_grouping_sets_with_yielding_with_spill() -> Result<()> {
+        let input: Arc<dyn ExecutionPlan> =
+            Arc::new(TestYieldingExec { yield_first: true });
+
+        check_spilling_grouping_sets(input).await
+    }
+
     #[tokio::test]
     async fn test_oom() -> Result<()> {
         let input: Arc<dyn ExecutionPlan> =
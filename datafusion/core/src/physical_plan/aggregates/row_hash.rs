@@ -33,15 +33,26 @@ use crate::physical_plan::aggregates::{
     evaluate_group_by, evaluate_many, evaluate_optional, group_schema, AggregateMode,
     PhysicalGroupBy,
 };
-use crate::physical_plan::metrics::{BaselineMetrics, RecordOutput};
+use crate::physical_plan::common::IPCWriter;
+use crate::physical_plan::expressions::{Column, PhysicalSortExpr};
+use crate::physical_plan::metrics::{
+    BaselineMetrics, Count, MetricBuilder, RecordOutput,
+};
+use crate::physical_plan::sorts::sort::{read_spill_as_stream, sort_batch};
+use crate::physical_plan::sorts::streaming_merge;
+use crate::physical_plan::stream::RecordBatchStreamAdapter;
 use crate::physical_plan::{aggregates, PhysicalExpr};
 use crate::physical_plan::{RecordBatchStream, SendableRecordBatchStream};
 use arrow::array::*;
+use arrow::compute::SortOptions;
+use arrow::datatypes::Schema;
 use arrow::{datatypes::SchemaRef, record_batch::RecordBatch};
-use datafusion_common::Result;
+use datafusion_common::{DataFusionError, Result};
 use datafusion_execution::memory_pool::proxy::VecAllocExt;
 use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::runtime_env::RuntimeEnv;
 use datafusion_execution::TaskContext;
+use tempfile::NamedTempFile;
 
 #[derive(Debug, Clone)]
 /// This object tracks the aggregation phase (input/output)
@@ -53,9 +64,41 @@ pub(crate) enum ExecutionState {
     Done,
 }
 
-use super::order::GroupOrdering;
+use super::order::{GroupOrdering, GroupOrderingFull};
 use super::AggregateExec;
 
+/// Tracks the state of spilling the groups of a
+/// [`GroupedHashAggregateStream`] to disk
+struct SpillState {
+    /// Sorted runs of the spilled groups, written to disk in the
+    /// Arrow IPC format
+    spills: Vec<NamedTempFile>,
+
+    /// Sort expressions of the spilled groups, on all the group columns
+    spill_expr: Vec<PhysicalSortExpr>,
+
+    /// Schema of the spilled groups: the group columns followed by the
+    /// intermediate state of each accumulator
+    spill_schema: SchemaRef,
+
+    /// True when the input has been replaced by the merge of the spills,
+    /// in which case the batches contain intermediate states that are
+    /// merged into the accumulators
+    is_stream_merging: bool,
+
+    /// Arguments of the accumulators when merging the spilled state
+    merging_aggregate_arguments: Vec<Vec<Arc<dyn PhysicalExpr>>>,
+
+    /// GROUP BY expressions when merging the spilled state
+    merging_group_by: PhysicalGroupBy,
+
+    /// Number of spills
+    spill_count: Count,
+
+    /// Total size of the spilled groups, in bytes of memory released
+    spilled_bytes: Count,
+}
+
 /// Hash based Grouping Aggregator
 ///
 /// # Design Goals
@@ -119,6 +162,22 @@ use super::AggregateExec;
 /// The accumulator state is not managed by this operator (e.g in the
 /// hash table).
 ///
+/// # Spilling
+///
+/// If the memory reservation cannot grow while aggregating groups
+/// that are not ordered by the input, the stream avoids failing:
+///
+/// * In [`AggregateMode::Partial`], groups are emitted early as
+///   their intermediate state, which the final aggregation merges.
+///
+/// * In the other modes, the group values and the intermediate state
+///   of all groups are sorted by the group values and spilled to disk
+///   as a sorted run, releasing the memory. Once the input is done,
+///   the in-memory groups and all the spilled runs are merged with
+///   [`streaming_merge`], and the merged stream, which is then fully
+///   ordered by the group values, is aggregated by merging the states,
+///   emitting each group as soon as it is complete.
+///
 /// [`group_values`]: Self::group_values
 pub(crate) struct GroupedHashAggregateStream {
     schema: SchemaRef,
@@ -178,6 +237,12 @@ pub(crate) struct GroupedHashAggregateStream {
 
     /// Have we seen the end of the input
     input_done: bool,
+
+    /// The runtime, used to create spill files
+    runtime: Arc<RuntimeEnv>,
+
+    /// The state of spilling groups to disk
+    spill_state: SpillState,
 }
 
 impl GroupedHashAggregateStream {
@@ -225,8 +290,52 @@ impl GroupedHashAggregateStream {
 
         let group_schema = group_schema(&agg_schema, agg_group_by.expr.len());
 
+        // Spilled groups are sorted by all the group columns, and contain
+        // the intermediate state of the accumulators
+        let spill_expr = group_schema
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| PhysicalSortExpr {
+                expr: Arc::new(Column::new(field.name(), idx)) as _,
+                options: SortOptions::default(),
+            })
+            .collect();
+        let mut spill_fields = group_schema.fields().to_vec();
+        for expr in &agg.aggr_expr {
+            spill_fields.extend(expr.state_fields()?.into_iter().map(Arc::new));
+        }
+        let merging_group_by = PhysicalGroupBy::new_single(
+            group_schema
+                .fields()
+                .iter()
+                .enumerate()
+                .map(|(idx, field)| {
+                    let expr = Arc::new(Column::new(field.name(), idx)) as _;
+                    (expr, field.name().clone())
+                })
+                .collect(),
+        );
+        let merging_aggregate_arguments = aggregates::aggregate_expressions(
+            &agg.aggr_expr,
+            &AggregateMode::Final,
+            agg_group_by.expr.len(),
+        )?;
+        let spill_state = SpillState {
+            spills: vec![],
+            spill_expr,
+            spill_schema: Arc::new(Schema::new(spill_fields)),
+            is_stream_merging: false,
+            merging_aggregate_arguments,
+            merging_group_by,
+            spill_count: MetricBuilder::new(&agg.metrics).spill_count(partition),
+            spilled_bytes: MetricBuilder::new(&agg.metrics).spilled_bytes(partition),
+        };
+
         let name = format!("GroupedHashAggregateStream[{partition}]");
-        let reservation = MemoryConsumer::new(name).register(context.memory_pool());
+        let reservation = MemoryConsumer::new(name)
+            .with_can_spill(true)
+            .register(context.memory_pool());
 
         let group_ordering = agg
             .aggregation_ordering
@@ -259,6 +368,8 @@ impl GroupedHashAggregateStream {
             batch_size,
             group_ordering,
             input_done: false,
+            runtime: context.runtime_env(),
+            spill_state,
         })
     }
 }
@@ -310,6 +421,10 @@ impl Stream for GroupedHashAggregateStream {
                         // new batch to aggregate
                         Some(Ok(batch)) => {
                             let timer = elapsed_compute.timer();
+                            // Make sure there is enough memory for `batch`,
+                            // otherwise spill the groups aggregated so far
+                            extract_ok!(self.spill_previous_if_necessary(&batch));
+
                             // Do the grouping
                             extract_ok!(self.group_aggregate_batch(batch));
 
@@ -318,8 +433,10 @@ impl Stream for GroupedHashAggregateStream {
                             assert!(!self.input_done);
 
                             if let Some(to_emit) = self.group_ordering.emit_to() {
-                                let batch = extract_ok!(self.emit(to_emit));
+                                let batch = extract_ok!(self.emit(to_emit, false));
                                 self.exec_state = ExecutionState::ProducingOutput(batch);
+                            } else {
+                                extract_ok!(self.emit_early_if_necessary());
                             }
                             timer.done();
                         }
@@ -329,11 +446,8 @@ impl Stream for GroupedHashAggregateStream {
                         }
                         None => {
                             // inner is done, emit all rows and switch to producing output
-                            self.input_done = true;
-                            self.group_ordering.input_done();
                             let timer = elapsed_compute.timer();
-                            let batch = extract_ok!(self.emit(EmitTo::All));
-                            self.exec_state = ExecutionState::ProducingOutput(batch);
+                            extract_ok!(self.set_input_done_and_produce_output());
                             timer.done();
                         }
                     }
@@ -375,14 +489,29 @@ impl RecordBatchStream for GroupedHashAggregateStream {
 impl GroupedHashAggregateStream {
     /// Perform group-by aggregation for the given [`RecordBatch`].
     fn group_aggregate_batch(&mut self, batch: RecordBatch) -> Result<()> {
-        // Evaluate the grouping expressions
-        let group_by_values = evaluate_group_by(&self.group_by, &batch)?;
-
-        // Evaluate the aggregation expressions.
-        let input_values = evaluate_many(&self.aggregate_arguments, &batch)?;
-
-        // Evaluate the filter expressions, if any, against the inputs
-        let filter_values = evaluate_optional(&self.filter_expressions, &batch)?;
+        let (group_by_values, input_values, filter_values) =
+            if self.spill_state.is_stream_merging {
+                // The batch holds the intermediate state of spilled groups
+                let group_by_values =
+                    evaluate_group_by(&self.spill_state.merging_group_by, &batch)?;
+                let input_values =
+                    evaluate_many(&self.spill_state.merging_aggregate_arguments, &batch)?;
+                (
+                    group_by_values,
+                    input_values,
+                    vec![None; self.accumulators.len()],
+                )
+            } else {
+                // Evaluate the grouping expressions
+                let group_by_values = evaluate_group_by(&self.group_by, &batch)?;
+
+                // Evaluate the aggregation expressions.
+                let input_values = evaluate_many(&self.aggregate_arguments, &batch)?;
+
+                // Evaluate the filter expressions, if any, against the inputs
+                let filter_values = evaluate_optional(&self.filter_expressions, &batch)?;
+                (group_by_values, input_values, filter_values)
+            };
 
         for group_values in &group_by_values {
             // calculate the group indices for each input row
@@ -416,7 +545,9 @@ impl GroupedHashAggregateStream {
                 match self.mode {
                     AggregateMode::Partial
                     | AggregateMode::Single
-                    | AggregateMode::SinglePartitioned => {
+                    | AggregateMode::SinglePartitioned
+                        if !self.spill_state.is_stream_merging =>
+                    {
                         acc.update_batch(
                             values,
                             group_indices,
@@ -424,7 +555,7 @@ impl GroupedHashAggregateStream {
                             total_num_groups,
                         )?;
                     }
-                    AggregateMode::FinalPartitioned | AggregateMode::Final => {
+                    _ => {
                         // if aggregation is over intermediate states,
                         // use merge
                         acc.merge_batch(
@@ -438,23 +569,52 @@ impl GroupedHashAggregateStream {
             }
         }
 
-        self.update_memory_reservation()
+        match self.update_memory_reservation() {
+            // The groups are spilled or emitted early before the next
+            // batch, as long as there are enough groups to fill a batch
+            Err(DataFusionError::ResourcesExhausted(_))
+                if self.can_spill_or_emit_early()
+                    && self.group_values.len() >= self.batch_size =>
+            {
+                Ok(())
+            }
+            // When merging the spills, all complete groups are emitted
+            // after each batch, so only about one batch of groups is kept
+            Err(DataFusionError::ResourcesExhausted(_))
+                if self.spill_state.is_stream_merging =>
+            {
+                Ok(())
+            }
+            other => other,
+        }
     }
 
     fn update_memory_reservation(&mut self) -> Result<()> {
+        let size = self.memory_size();
+        self.reservation.try_resize(size)
+    }
+
+    /// Returns the memory used by the groups and the accumulators
+    fn memory_size(&self) -> usize {
         let acc = self.accumulators.iter().map(|x| x.size()).sum::<usize>();
-        self.reservation.try_resize(
-            acc + self.group_values.size()
-                + self.group_ordering.size()
-                + self.current_group_indices.allocated_size(),
-        )
+        acc + self.group_values.size()
+            + self.group_ordering.size()
+            + self.current_group_indices.allocated_size()
     }
 
     /// Create an output RecordBatch with the group keys and
     /// accumulator states/values specified in emit_to
-    fn emit(&mut self, emit_to: EmitTo) -> Result<RecordBatch> {
+    ///
+    /// When `spilling`, the intermediate states of the accumulators are
+    /// emitted regardless of the mode, so that they can be merged later
+    fn emit(&mut self, emit_to: EmitTo, spilling: bool) -> Result<RecordBatch> {
+        let schema = if spilling {
+            self.spill_state.spill_schema.clone()
+        } else {
+            self.schema()
+        };
         if self.group_values.is_empty() {
-            return Ok(RecordBatch::new_empty(self.schema()));
+            return Ok(RecordBatch::new_empty(schema));
         }
 
         let mut output = self.group_values.emit(emit_to)?;
@@ -466,6 +626,7 @@ impl GroupedHashAggregateStream {
         for acc in self.accumulators.iter_mut() {
             match self.mode {
                 AggregateMode::Partial => output.extend(acc.state(emit_to)?),
+                _ if spilling => output.extend(acc.state(emit_to)?),
                 AggregateMode::Final
                 | AggregateMode::FinalPartitioned
                 | AggregateMode::Single
@@ -473,8 +634,139 @@ impl GroupedHashAggregateStream {
             }
         }
 
-        self.update_memory_reservation()?;
-        let batch = RecordBatch::try_new(self.schema(), output)?;
+        // Emitting only releases memory. The reservation is not grown here
+        // when it was already exceeded, as the next batch checks the limit
+        // and spills or emits the remaining groups early
+        let size = self.memory_size();
+        if size < self.reservation.size() {
+            self.reservation.shrink(self.reservation.size() - size);
+        }
+        let batch = RecordBatch::try_new(schema, output)?;
         Ok(batch)
     }
+
+    /// Returns true if the groups can be spilled or emitted early when
+    /// the memory reservation cannot grow. This is currently only
+    /// supported when the groups are not ordered.
+    fn can_spill_or_emit_early(&self) -> bool {
+        matches!(self.group_ordering, GroupOrdering::None)
+            && !self.spill_state.is_stream_merging
+    }
+
+    /// Aggregating a batch may exceed the memory limit by up to about
+    /// one batch of groups. In that case, spill the groups to disk
+    /// before aggregating the next batch, unless in
+    /// [`AggregateMode::Partial`] where they are emitted early instead.
+    fn spill_previous_if_necessary(&mut self, batch: &RecordBatch) -> Result<()> {
+        if !self.group_values.is_empty()
+            && batch.num_rows() > 0
+            && self.mode != AggregateMode::Partial
+            && self.can_spill_or_emit_early()
+            && self.update_memory_reservation().is_err()
+        {
+            self.spill()?;
+            self.clear_shrink(batch);
+        }
+        Ok(())
+    }
+
+    /// Emits the intermediate state of all groups, sorts them by the group
+    /// values and writes them to a new spill file
+    fn spill(&mut self) -> Result<()> {
+        let used = self.reservation.size();
+        let emitted = self.emit(EmitTo::All, true)?;
+        let sorted = sort_batch(&emitted, &self.spill_state.spill_expr, None)?;
+
+        let spill_file = self.runtime.disk_manager.create_tmp_file("HashAggSpill")?;
+        let mut writer = IPCWriter::new(spill_file.path(), &sorted.schema())?;
+        let mut offset = 0;
+        while offset < sorted.num_rows() {
+            let length = self.batch_size.min(sorted.num_rows() - offset);
+            writer.write(&sorted.slice(offset, length))?;
+            offset += length;
+        }
+        writer.finish()?;
+        debug!(
+            "Spilled {} groups of {used} bytes to {:?}",
+            sorted.num_rows(),
+            spill_file.path()
+        );
+
+        self.spill_state.spills.push(spill_file);
+        self.spill_state.spill_count.add(1);
+        self.spill_state.spilled_bytes.add(used);
+        Ok(())
+    }
+
+    /// Clears the groups and shrinks the capacities to the size of `batch`
+    fn clear_shrink(&mut self, batch: &RecordBatch) {
+        self.group_values.clear_shrink(batch);
+        self.current_group_indices.clear();
+        self.current_group_indices.shrink_to(batch.num_rows());
+    }
+
+    /// In [`AggregateMode::Partial`], emits the groups early if the memory
+    /// reservation cannot grow, as the final aggregation merges them
+    fn emit_early_if_necessary(&mut self) -> Result<()> {
+        if self.group_values.len() >= self.batch_size
+            && self.mode == AggregateMode::Partial
+            && self.can_spill_or_emit_early()
+            && self.update_memory_reservation().is_err()
+        {
+            let n = self.group_values.len() / self.batch_size * self.batch_size;
+            let batch = self.emit(EmitTo::First(n), false)?;
+            self.exec_state = ExecutionState::ProducingOutput(batch);
+        }
+        Ok(())
+    }
+
+    /// Called once the input is done: emits all groups if nothing was
+    /// spilled, otherwise starts merging the spills
+    fn set_input_done_and_produce_output(&mut self) -> Result<()> {
+        self.input_done = true;
+        self.group_ordering.input_done();
+        self.exec_state = if self.spill_state.spills.is_empty() {
+            let batch = self.emit(EmitTo::All, false)?;
+            ExecutionState::ProducingOutput(batch)
+        } else {
+            self.update_merged_stream()?;
+            ExecutionState::ReadingInput
+        };
+        Ok(())
+    }
+
+    /// Replaces the input with the streaming merge of the groups still in
+    /// memory and all the spilled runs. As the merged stream is sorted by
+    /// all the group values, the groups are then aggregated with
+    /// [`GroupOrdering::Full`], and emitted as soon as they are complete.
+    fn update_merged_stream(&mut self) -> Result<()> {
+        let batch = self.emit(EmitTo::All, true)?;
+        let sorted = sort_batch(&batch, &self.spill_state.spill_expr, None)?;
+        // release the memory for the merge
+        self.clear_shrink(&RecordBatch::new_empty(self.schema()));
+        self.update_memory_reservation()?;
+
+        let schema = self.spill_state.spill_schema.clone();
+        let mut streams: Vec<SendableRecordBatchStream> =
+            vec![Box::pin(RecordBatchStreamAdapter::new(
+                schema.clone(),
+                futures::stream::once(futures::future::ready(Ok(sorted))),
+            ))];
+        for spill in self.spill_state.spills.drain(..) {
+            streams.push(read_spill_as_stream(spill, schema.clone())?);
+        }
+
+        self.input = streaming_merge(
+            streams,
+            schema,
+            &self.spill_state.spill_expr,
+            self.baseline_metrics.intermediate(),
+            self.batch_size,
+            None,
+        )?;
+        self.spill_state.is_stream_merging = true;
+        self.input_done = false;
+        self.group_ordering = GroupOrdering::Full(GroupOrderingFull::new());
+        Ok(())
+    }
 }
@@ -125,14 +125,22 @@ impl InsertExec {
             })
             .collect();
 
-        if risky_columns.is_empty() {
+        if risky_columns.is_empty() && self.input.schema() == self.sink_schema {
             Ok(input_stream)
         } else {
-            // Check not null constraint on the input stream
+            // Check not null constraint on the input stream, and give the
+            // batches the schema of the sink, whose nullability may differ,
+            // so that the sink can store them as is
+            let sink_schema = self.sink_schema.clone();
             Ok(Box::pin(RecordBatchStreamAdapter::new(
                 self.sink_schema.clone(),
-                input_stream
-                    .map(move |batch| check_not_null_contraits(batch?, &risky_columns)),
+                input_stream.map(move |batch| {
+                    let batch = check_not_null_contraits(batch?, &risky_columns)?;
+                    Ok(RecordBatch::try_new(
+                        sink_schema.clone(),
+                        batch.columns().to_vec(),
+                    )?)
+                }),
             )))
         }
     }
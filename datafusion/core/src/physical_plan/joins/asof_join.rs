@@ -0,0 +1,816 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the ASOF join plan, which joins every row of its sorted left input
+//! with the closest row of its sorted right input satisfying a match condition
+
+use std::any::Any;
+use std::cmp::Ordering;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use arrow::array::{new_null_array, Array, ArrayRef};
+use arrow::compute::{interleave, SortOptions};
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::{DataFusionError, JoinType, Result};
+use datafusion_execution::TaskContext;
+use datafusion_expr::Operator;
+use datafusion_physical_expr::{OrderingEquivalenceProperties, PhysicalSortRequirement};
+use futures::StreamExt;
+
+use crate::physical_plan::expressions::{Column, PhysicalSortExpr};
+use crate::physical_plan::joins::sort_merge_join::compare_join_arrays;
+use crate::physical_plan::joins::utils::{
+    build_join_schema, check_join_is_valid, combine_join_equivalence_properties,
+    estimate_join_statistics, JoinOn,
+};
+use crate::physical_plan::metrics::{
+    BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet,
+};
+use crate::physical_plan::stream::RecordBatchStreamAdapter;
+use crate::physical_plan::{
+    DisplayAs, DisplayFormatType, Distribution, EquivalenceProperties, ExecutionPlan,
+    Partitioning, PhysicalExpr, SendableRecordBatchStream, Statistics,
+};
+
+/// Joins every row of the left input with at most one row of the right
+/// input: among the right rows with the same `on` keys, the closest one
+/// satisfying the match condition `left_expr <op> right_expr`.
+///
+/// For instance, with the match condition `trades.ts >= quotes.ts` every trade
+/// is joined with the latest quote at or before its timestamp, and with
+/// `trades.ts < quotes.ts` with the earliest quote strictly after it. Left rows
+/// without a match are padded with nulls.
+///
+/// Both inputs are required to be sorted on the `on` keys, then on their side
+/// of the match condition (ascending for `>=` and `>`, descending for `<=` and
+/// `<`), so that the join is evaluated by merging the two inputs in a single
+/// pass without buffering the right input.
+#[derive(Debug)]
+pub struct AsOfJoinExec {
+    /// Left sorted input, every row of which is in the output
+    left: Arc<dyn ExecutionPlan>,
+    /// Right sorted input, the rows of which are matched with the left rows
+    right: Arc<dyn ExecutionPlan>,
+    /// Set of common columns used to join on
+    on: JoinOn,
+    /// Left side of the match condition, evaluated on the left input
+    left_expr: Arc<dyn PhysicalExpr>,
+    /// Comparison of the match condition, one of `>=`, `>`, `<=` and `<`
+    op: Operator,
+    /// Right side of the match condition, evaluated on the right input
+    right_expr: Arc<dyn PhysicalExpr>,
+    /// The schema once the join is applied
+    schema: SchemaRef,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl AsOfJoinExec {
+    /// Tries to create a new [`AsOfJoinExec`] joining `left` and `right` on the
+    /// columns `on` and the match condition `left_expr <op> right_expr`.
+    pub fn try_new(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        on: JoinOn,
+        left_expr: Arc<dyn PhysicalExpr>,
+        op: Operator,
+        right_expr: Arc<dyn PhysicalExpr>,
+    ) -> Result<Self> {
+        let left_schema = left.schema();
+        let right_schema = right.schema();
+        check_join_is_valid(&left_schema, &right_schema, &on)?;
+        if !matches!(
+            op,
+            Operator::GtEq | Operator::Gt | Operator::LtEq | Operator::Lt
+        ) {
+            return Err(DataFusionError::Plan(format!(
+                "ASOF join match condition must use one of >=, >, <=, <, got {op}"
+            )));
+        }
+
+        let (schema, _) = build_join_schema(&left_schema, &right_schema, &JoinType::AsOf);
+
+        Ok(Self {
+            left,
+            right,
+            on,
+            left_expr,
+            op,
+            right_expr,
+            schema: Arc::new(schema),
+            metrics: ExecutionPlanMetricsSet::new(),
+        })
+    }
+
+    /// Left sorted input
+    pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.left
+    }
+
+    /// Right sorted input
+    pub fn right(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.right
+    }
+
+    /// Set of common columns used to join on
+    pub fn on(&self) -> &[(Column, Column)] {
+        &self.on
+    }
+
+    /// The match condition, as the expression on the left input, the
+    /// comparison and the expression on the right input
+    pub fn match_condition(
+        &self,
+    ) -> (&Arc<dyn PhysicalExpr>, Operator, &Arc<dyn PhysicalExpr>) {
+        (&self.left_expr, self.op, &self.right_expr)
+    }
+
+    /// The sort options the `on` columns are sorted with
+    fn key_sort_options(&self) -> Vec<SortOptions> {
+        vec![SortOptions::default(); self.on.len()]
+    }
+
+    /// The sort options the sides of the match condition are sorted with, so
+    /// that the right rows satisfying it come before the others
+    fn match_sort_options(&self) -> SortOptions {
+        SortOptions {
+            descending: matches!(self.op, Operator::LtEq | Operator::Lt),
+            nulls_first: true,
+        }
+    }
+
+    /// The sort requirement of one of the inputs
+    fn sort_requirement(
+        &self,
+        keys: impl Iterator<Item = Column>,
+        expr: &Arc<dyn PhysicalExpr>,
+    ) -> Vec<PhysicalSortRequirement> {
+        let sort_exprs = keys
+            .zip(self.key_sort_options())
+            .map(|(column, options)| PhysicalSortExpr {
+                expr: Arc::new(column),
+                options,
+            })
+            .chain(std::iter::once(PhysicalSortExpr {
+                expr: expr.clone(),
+                options: self.match_sort_options(),
+            }))
+            .collect::<Vec<_>>();
+        PhysicalSortRequirement::from_sort_exprs(&sort_exprs)
+    }
+}
+
+impl DisplayAs for AsOfJoinExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut Formatter) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let on = self
+                    .on
+                    .iter()
+                    .map(|(c1, c2)| format!("({}, {})", c1, c2))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(
+                    f,
+                    "AsOfJoinExec: on=[{}], match_condition={} {} {}",
+                    on, self.left_expr, self.op, self.right_expr
+                )
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for AsOfJoinExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        if self.on.is_empty() {
+            return vec![Distribution::SinglePartition, Distribution::SinglePartition];
+        }
+        let (left_expr, right_expr) = self
+            .on
+            .iter()
+            .map(|(l, r)| {
+                (
+                    Arc::new(l.clone()) as Arc<dyn PhysicalExpr>,
+                    Arc::new(r.clone()) as Arc<dyn PhysicalExpr>,
+                )
+            })
+            .unzip();
+        vec![
+            Distribution::HashPartitioned(left_expr),
+            Distribution::HashPartitioned(right_expr),
+        ]
+    }
+
+    fn required_input_ordering(&self) -> Vec<Option<Vec<PhysicalSortRequirement>>> {
+        vec![
+            Some(self.sort_requirement(
+                self.on.iter().map(|(l, _)| l.clone()),
+                &self.left_expr,
+            )),
+            Some(self.sort_requirement(
+                self.on.iter().map(|(_, r)| r.clone()),
+                &self.right_expr,
+            )),
+        ]
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        self.left.output_partitioning()
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        self.left.output_ordering()
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true, false]
+    }
+
+    fn equivalence_properties(&self) -> EquivalenceProperties {
+        let left_columns_len = self.left.schema().fields.len();
+        combine_join_equivalence_properties(
+            JoinType::AsOf,
+            self.left.equivalence_properties(),
+            self.right.equivalence_properties(),
+            left_columns_len,
+            self.on(),
+            self.schema(),
+        )
+    }
+
+    fn ordering_equivalence_properties(&self) -> OrderingEquivalenceProperties {
+        let mut new_properties = OrderingEquivalenceProperties::new(self.schema());
+        new_properties.extend(
+            self.left
+                .ordering_equivalence_properties()
+                .classes()
+                .iter()
+                .cloned(),
+        );
+        new_properties
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.left.clone(), self.right.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        match &children[..] {
+            [left, right] => Ok(Arc::new(AsOfJoinExec::try_new(
+                left.clone(),
+                right.clone(),
+                self.on.clone(),
+                self.left_expr.clone(),
+                self.op,
+                self.right_expr.clone(),
+            )?)),
+            _ => Err(DataFusionError::Internal(
+                "AsOfJoinExec wrong number of children".to_string(),
+            )),
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let left_partitions = self.left.output_partitioning().partition_count();
+        let right_partitions = self.right.output_partitioning().partition_count();
+        if left_partitions != right_partitions {
+            return Err(DataFusionError::Internal(format!(
+                "Invalid AsOfJoinExec, partition count mismatch {left_partitions}!={right_partitions},\
+                 consider using RepartitionExec",
+            )));
+        }
+
+        let stream = AsOfJoinStream {
+            left: self.left.execute(partition, context.clone())?,
+            right: self.right.execute(partition, context)?,
+            on_left: self.on.iter().map(|(l, _)| l.clone()).collect(),
+            on_right: self.on.iter().map(|(_, r)| r.clone()).collect(),
+            left_expr: self.left_expr.clone(),
+            right_expr: self.right_expr.clone(),
+            inclusive: matches!(self.op, Operator::GtEq | Operator::LtEq),
+            key_sort_options: self.key_sort_options(),
+            match_sort_options: self.match_sort_options(),
+            right_batch: None,
+            right_idx: 0,
+            right_finished: false,
+            candidate: None,
+            schema: self.schema.clone(),
+            baseline_metrics: BaselineMetrics::new(&self.metrics, partition),
+        };
+        let stream = futures::stream::try_unfold(stream, |mut stream| async move {
+            Ok(stream.next_batch().await?.map(|batch| (batch, stream)))
+        });
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema.clone(),
+            stream,
+        )))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Statistics {
+        estimate_join_statistics(
+            self.left.clone(),
+            self.right.clone(),
+            self.on.clone(),
+            &JoinType::AsOf,
+        )
+    }
+}
+
+/// A batch of the right input, along with its join keys and its side of the
+/// match condition
+#[derive(Debug)]
+struct RightBatch {
+    batch: RecordBatch,
+    keys: Vec<ArrayRef>,
+    values: Vec<ArrayRef>,
+}
+
+/// Merges the sorted inputs of an [`AsOfJoinExec`] partition
+struct AsOfJoinStream {
+    left: SendableRecordBatchStream,
+    right: SendableRecordBatchStream,
+    on_left: Vec<Column>,
+    on_right: Vec<Column>,
+    left_expr: Arc<dyn PhysicalExpr>,
+    right_expr: Arc<dyn PhysicalExpr>,
+    /// Whether right rows equal to the left row satisfy the match condition
+    inclusive: bool,
+    key_sort_options: Vec<SortOptions>,
+    match_sort_options: SortOptions,
+    /// The right batch being scanned
+    right_batch: Option<Arc<RightBatch>>,
+    /// The next row of `right_batch` to scan
+    right_idx: usize,
+    /// Whether the right input is exhausted
+    right_finished: bool,
+    /// The last scanned right row satisfying the match condition, which is the
+    /// match of the current left row if it has the same keys
+    candidate: Option<(Arc<RightBatch>, usize)>,
+    schema: SchemaRef,
+    baseline_metrics: BaselineMetrics,
+}
+
+impl AsOfJoinStream {
+    /// Joins the next left batch, returning `None` once the left input is
+    /// exhausted
+    async fn next_batch(&mut self) -> Result<Option<RecordBatch>> {
+        let Some(left_batch) = self.left.next().await.transpose()? else {
+            return Ok(None);
+        };
+        let left_keys = join_arrays(&left_batch, &self.on_left);
+        let left_values = vec![self
+            .left_expr
+            .evaluate(&left_batch)?
+            .into_array(left_batch.num_rows())];
+
+        // the right batches referenced by the output, after a batch of nulls
+        // for the unmatched rows
+        let mut matched_batches: Vec<Arc<RightBatch>> = vec![];
+        let mut indices = Vec::with_capacity(left_batch.num_rows());
+        for left_idx in 0..left_batch.num_rows() {
+            let matched = if left_keys.iter().any(|keys| keys.is_null(left_idx))
+                || left_values[0].is_null(left_idx)
+            {
+                None
+            } else {
+                self.advance(&left_keys, &left_values, left_idx).await?
+            };
+            let index = match matched {
+                Some((batch, row)) => {
+                    if !matches!(matched_batches.last(), Some(last) if Arc::ptr_eq(last, &batch))
+                    {
+                        matched_batches.push(batch);
+                    }
+                    (matched_batches.len(), row)
+                }
+                None => (0, 0),
+            };
+            indices.push(index);
+        }
+
+        let left_columns = left_batch.num_columns();
+        let right_columns = self
+            .schema
+            .fields()
+            .iter()
+            .skip(left_columns)
+            .enumerate()
+            .map(|(i, field)| {
+                let null_array = new_null_array(field.data_type(), 1);
+                let arrays = std::iter::once(null_array.as_ref())
+                    .chain(
+                        matched_batches
+                            .iter()
+                            .map(|right| right.batch.column(i).as_ref()),
+                    )
+                    .collect::<Vec<_>>();
+                Ok(interleave(&arrays, &indices)?)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let columns = left_batch
+            .columns()
+            .iter()
+            .cloned()
+            .chain(right_columns)
+            .collect();
+        let batch = RecordBatch::try_new(self.schema.clone(), columns)?;
+        self.baseline_metrics.record_output(batch.num_rows());
+        Ok(Some(batch))
+    }
+
+    /// Scans the right input up to the last row satisfying the match condition
+    /// for the given left row, and returns the match of the left row
+    async fn advance(
+        &mut self,
+        left_keys: &[ArrayRef],
+        left_values: &[ArrayRef],
+        left_idx: usize,
+    ) -> Result<Option<(Arc<RightBatch>, usize)>> {
+        loop {
+            let right = match &self.right_batch {
+                Some(right) if self.right_idx < right.batch.num_rows() => right.clone(),
+                _ if self.right_finished => break,
+                _ => {
+                    self.poll_right().await?;
+                    continue;
+                }
+            };
+            let right_idx = self.right_idx;
+            match compare_join_arrays(
+                left_keys,
+                left_idx,
+                &right.keys,
+                right_idx,
+                &self.key_sort_options,
+                false,
+            )? {
+                // the right row can't match this or any of the following left rows
+                Ordering::Greater => self.right_idx += 1,
+                Ordering::Less => break,
+                Ordering::Equal if right.values[0].is_null(right_idx) => {
+                    self.right_idx += 1
+                }
+                Ordering::Equal => {
+                    let ordering = compare_join_arrays(
+                        &right.values,
+                        right_idx,
+                        left_values,
+                        left_idx,
+                        &[self.match_sort_options],
+                        false,
+                    )?;
+                    let satisfied = match ordering {
+                        Ordering::Less => true,
+                        Ordering::Equal => self.inclusive,
+                        Ordering::Greater => false,
+                    };
+                    if !satisfied {
+                        break;
+                    }
+                    self.candidate = Some((right, right_idx));
+                    self.right_idx += 1;
+                }
+            }
+        }
+
+        match &self.candidate {
+            Some((right, right_idx))
+                if compare_join_arrays(
+                    left_keys,
+                    left_idx,
+                    &right.keys,
+                    *right_idx,
+                    &self.key_sort_options,
+                    false,
+                )?
+                .is_eq() =>
+            {
+                Ok(Some((right.clone(), *right_idx)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Fetches the next non-empty batch of the right input
+    async fn poll_right(&mut self) -> Result<()> {
+        match self.right.next().await.transpose()? {
+            Some(batch) => {
+                let keys = join_arrays(&batch, &self.on_right);
+                let values = vec![self
+                    .right_expr
+                    .evaluate(&batch)?
+                    .into_array(batch.num_rows())];
+                self.right_batch = Some(Arc::new(RightBatch {
+                    batch,
+                    keys,
+                    values,
+                }));
+                self.right_idx = 0;
+            }
+            None => {
+                self.right_batch = None;
+                self.right_finished = true;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Get join array refs of given batch and join columns
+fn join_arrays(batch: &RecordBatch, on_column: &[Column]) -> Vec<ArrayRef> {
+    on_column
+        .iter()
+        .map(|c| batch.column(c.index()).clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow::array::Int32Array;
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    use crate::assert_batches_eq;
+    use crate::physical_plan::memory::MemoryExec;
+    use crate::physical_plan::{common, expressions::col};
+    use crate::prelude::SessionContext;
+    use crate::test::build_table_i32;
+
+    fn build_table(
+        a: (&str, &Vec<i32>),
+        b: (&str, &Vec<i32>),
+        c: (&str, &Vec<i32>),
+    ) -> Arc<dyn ExecutionPlan> {
+        let batch = build_table_i32(a, b, c);
+        let schema = batch.schema();
+        Arc::new(MemoryExec::try_new(&[vec![batch]], schema, None).unwrap())
+    }
+
+    async fn join_collect(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        on: &[(&str, &str)],
+        match_condition: (&str, Operator, &str),
+    ) -> Result<Vec<RecordBatch>> {
+        let on = on
+            .iter()
+            .map(|(l, r)| {
+                Ok((
+                    Column::new_with_schema(l, &left.schema())?,
+                    Column::new_with_schema(r, &right.schema())?,
+                ))
+            })
+            .collect::<Result<_>>()?;
+        let (l, op, r) = match_condition;
+        let left_expr = col(l, &left.schema())?;
+        let right_expr = col(r, &right.schema())?;
+        let join = AsOfJoinExec::try_new(left, right, on, left_expr, op, right_expr)?;
+        let task_ctx = SessionContext::new().task_ctx();
+        common::collect(join.execute(0, task_ctx)?).await
+    }
+
+    #[tokio::test]
+    async fn asof_join_latest_before() -> Result<()> {
+        let trades = build_table(
+            ("sym", &vec![1, 1, 1, 2, 3]),
+            ("ts", &vec![3, 5, 9, 1, 4]),
+            ("qty", &vec![10, 20, 30, 40, 50]),
+        );
+        let quotes = build_table(
+            ("sym", &vec![1, 1, 1, 2, 2, 4]),
+            ("ts", &vec![1, 5, 10, 2, 3, 1]),
+            ("bid", &vec![100, 101, 102, 200, 201, 400]),
+        );
+
+        let batches = join_collect(
+            trades,
+            quotes,
+            &[("sym", "sym")],
+            ("ts", Operator::GtEq, "ts"),
+        )
+        .await?;
+        let expected = vec![
+            "+-----+----+-----+-----+----+-----+",
+            "| sym | ts | qty | sym | ts | bid |",
+            "+-----+----+-----+-----+----+-----+",
+            "| 1   | 3  | 10  | 1   | 1  | 100 |",
+            "| 1   | 5  | 20  | 1   | 5  | 101 |",
+            "| 1   | 9  | 30  | 1   | 5  | 101 |",
+            "| 2   | 1  | 40  |     |    |     |",
+            "| 3   | 4  | 50  |     |    |     |",
+            "+-----+----+-----+-----+----+-----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn asof_join_strict() -> Result<()> {
+        let trades = build_table(
+            ("sym", &vec![1, 1, 1]),
+            ("ts", &vec![1, 5, 9]),
+            ("qty", &vec![10, 20, 30]),
+        );
+        let quotes = build_table(
+            ("sym", &vec![1, 1, 1]),
+            ("ts", &vec![1, 5, 10]),
+            ("bid", &vec![100, 101, 102]),
+        );
+
+        let batches = join_collect(
+            trades,
+            quotes,
+            &[("sym", "sym")],
+            ("ts", Operator::Gt, "ts"),
+        )
+        .await?;
+        let expected = [
+            "+-----+----+-----+-----+----+-----+",
+            "| sym | ts | qty | sym | ts | bid |",
+            "+-----+----+-----+-----+----+-----+",
+            "| 1   | 1  | 10  |     |    |     |",
+            "| 1   | 5  | 20  | 1   | 1  | 100 |",
+            "| 1   | 9  | 30  | 1   | 5  | 101 |",
+            "+-----+----+-----+-----+----+-----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn asof_join_earliest_after() -> Result<()> {
+        // sorted descending on the match condition
+        let trades = build_table(
+            ("sym", &vec![1, 1, 1]),
+            ("ts", &vec![9, 5, 3]),
+            ("qty", &vec![30, 20, 10]),
+        );
+        let quotes = build_table(
+            ("sym", &vec![1, 1, 1]),
+            ("ts", &vec![10, 5, 1]),
+            ("bid", &vec![102, 101, 100]),
+        );
+
+        let batches = join_collect(
+            trades,
+            quotes,
+            &[("sym", "sym")],
+            ("ts", Operator::Lt, "ts"),
+        )
+        .await?;
+        let expected = [
+            "+-----+----+-----+-----+----+-----+",
+            "| sym | ts | qty | sym | ts | bid |",
+            "+-----+----+-----+-----+----+-----+",
+            "| 1   | 9  | 30  | 1   | 10 | 102 |",
+            "| 1   | 5  | 20  | 1   | 10 | 102 |",
+            "| 1   | 3  | 10  | 1   | 5  | 101 |",
+            "+-----+----+-----+-----+----+-----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn asof_join_without_keys() -> Result<()> {
+        let trades = build_table(
+            ("a", &vec![1, 2, 3]),
+            ("ts", &vec![0, 4, 8]),
+            ("qty", &vec![10, 20, 30]),
+        );
+        let quotes = build_table(
+            ("b", &vec![1, 2, 3]),
+            ("ts", &vec![1, 3, 5]),
+            ("bid", &vec![100, 101, 102]),
+        );
+
+        let batches =
+            join_collect(trades, quotes, &[], ("ts", Operator::GtEq, "ts")).await?;
+        let expected = [
+            "+---+----+-----+---+----+-----+",
+            "| a | ts | qty | b | ts | bid |",
+            "+---+----+-----+---+----+-----+",
+            "| 1 | 0  | 10  |   |    |     |",
+            "| 2 | 4  | 20  | 2 | 3  | 101 |",
+            "| 3 | 8  | 30  | 3 | 5  | 102 |",
+            "+---+----+-----+---+----+-----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn asof_join_nulls_and_batches() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("sym", DataType::Int32, true),
+            Field::new("ts", DataType::Int32, true),
+        ]));
+        let batch = |sym: Vec<Option<i32>>, ts: Vec<Option<i32>>| {
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(Int32Array::from(sym)),
+                    Arc::new(Int32Array::from(ts)),
+                ],
+            )
+            .unwrap()
+        };
+        let trades = Arc::new(MemoryExec::try_new(
+            &[vec![
+                batch(vec![None, Some(1)], vec![Some(5), None]),
+                batch(vec![Some(1), Some(1)], vec![Some(2), Some(7)]),
+            ]],
+            schema.clone(),
+            None,
+        )?);
+        // the right rows are spread over several batches
+        let quotes = Arc::new(MemoryExec::try_new(
+            &[vec![
+                batch(vec![None], vec![Some(1)]),
+                batch(vec![Some(1), Some(1)], vec![None, Some(1)]),
+                batch(vec![], vec![]),
+                batch(vec![Some(1)], vec![Some(6)]),
+            ]],
+            schema.clone(),
+            None,
+        )?);
+
+        let batches = join_collect(
+            trades,
+            quotes,
+            &[("sym", "sym")],
+            ("ts", Operator::GtEq, "ts"),
+        )
+        .await?;
+        let expected = [
+            "+-----+----+-----+----+",
+            "| sym | ts | sym | ts |",
+            "+-----+----+-----+----+",
+            "|     | 5  |     |    |",
+            "| 1   |    |     |    |",
+            "| 1   | 2  | 1   | 1  |",
+            "| 1   | 7  | 1   | 6  |",
+            "+-----+----+-----+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[test]
+    fn asof_join_invalid_operator() -> Result<()> {
+        let left = build_table(("a", &vec![]), ("b", &vec![]), ("c", &vec![]));
+        let right = build_table(("a", &vec![]), ("b", &vec![]), ("c", &vec![]));
+        let left_expr = col("b", &left.schema())?;
+        let right_expr = col("b", &right.schema())?;
+        let err = AsOfJoinExec::try_new(
+            left,
+            right,
+            vec![],
+            left_expr,
+            Operator::Eq,
+            right_expr,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: ASOF join match condition must use one of >=, >, <=, <, got ="
+        );
+        Ok(())
+    }
+}
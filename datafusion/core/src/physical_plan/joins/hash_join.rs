@@ -18,6 +18,7 @@
 //! Defines the join plan for executing partitions in parallel and then joining the results
 //! into a set of partitions.
 
+use std::collections::VecDeque;
 use std::fmt;
 use std::mem::size_of;
 use std::sync::Arc;
@@ -34,19 +35,23 @@ use crate::physical_plan::DisplayAs;
 use crate::physical_plan::{
     coalesce_batches::concat_batches,
     coalesce_partitions::CoalescePartitionsExec,
+    common::IPCWriter,
     expressions::Column,
     expressions::PhysicalSortExpr,
     hash_utils::create_hashes,
     joins::hash_join_utils::JoinHashMap,
     joins::utils::{
         adjust_right_output_partitioning, build_join_schema, check_join_is_valid,
-        combine_join_equivalence_properties, estimate_join_statistics,
-        partitioned_join_output_partitioning, BuildProbeJoinMetrics, ColumnIndex,
-        JoinFilter, JoinOn,
+        check_join_type_is_supported, combine_join_equivalence_properties,
+        estimate_join_statistics, partitioned_join_output_partitioning,
+        BuildProbeJoinMetrics, ColumnIndex, JoinFilter, JoinOn,
     },
-    metrics::{ExecutionPlanMetricsSet, MetricsSet},
-    DisplayFormatType, Distribution, EquivalenceProperties, ExecutionPlan, Partitioning,
-    PhysicalExpr, RecordBatchStream, SendableRecordBatchStream, Statistics,
+    metrics::{Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet},
+    sorts::sort::{read_spill, read_spill_as_stream},
+    stream::RecordBatchReceiverStream,
+    DisplayFormatType, Distribution, EmptyRecordBatchStream, EquivalenceProperties,
+    ExecutionPlan, Partitioning, PhysicalExpr, RecordBatchStream,
+    SendableRecordBatchStream, Statistics,
 };
 
 use super::{
@@ -79,13 +84,51 @@ use arrow_schema::ArrowError;
 use datafusion_common::cast::{as_dictionary_array, as_string_array};
 use datafusion_common::{DataFusionError, JoinType, Result};
 use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::runtime_env::RuntimeEnv;
 use datafusion_execution::TaskContext;
 use datafusion_physical_expr::OrderingEquivalenceProperties;
 
 use ahash::RandomState;
-use futures::{ready, Stream, StreamExt, TryStreamExt};
+use futures::future::BoxFuture;
+use futures::{ready, FutureExt, Stream, StreamExt};
+use log::debug;
+use tempfile::NamedTempFile;
+
+/// Number of partitions the inputs are hash partitioned into when spilling
+const SPILL_PARTITIONS: usize = 16;
+
+/// Number of times the inputs may be partitioned, as a partition that does
+/// not fit in memory is partitioned again. Rows with the same key can never
+/// be separated, so this bounds the recursion on skewed keys.
+const MAX_SPILL_LEVEL: usize = 3;
+
+/// Build-side data
+enum JoinLeftData {
+    /// The build side fits in memory
+    InMemory {
+        /// Hash table of the keys
+        hash_map: JoinHashMap,
+        /// The batches merged into a single batch
+        batch: RecordBatch,
+        /// Memory reserved for the hash table and the batch
+        _reservation: MemoryReservation,
+    },
+    /// The build side does not fit in memory and was hash partitioned on its
+    /// keys into spill files
+    Spilled(SpilledPartitions),
+}
 
-type JoinLeftData = (JoinHashMap, RecordBatch, MemoryReservation);
+/// The build side of a join, hash partitioned on its keys into spill files
+#[derive(Clone)]
+struct SpilledPartitions {
+    /// Schema of the spilled batches
+    schema: SchemaRef,
+    /// Spill file of each partition, shared by all the output partitions
+    /// when the build side is collected once
+    files: Vec<Arc<NamedTempFile>>,
+    /// Level of the partitioning, see [`SpillPartitioner`]
+    level: usize,
+}
 
 /// Join execution plan executes partitions in parallel and combines them into a set of
 /// partitions.
@@ -93,6 +136,21 @@ type JoinLeftData = (JoinHashMap, RecordBatch, MemoryReservation);
 /// Filter expression expected to contain non-equality predicates that can not be pushed
 /// down to any of join inputs.
 /// In case of outer join, filter applied to only matched rows.
+///
+/// # Spilling
+///
+/// When the build side does not fit in the memory reservation, it is hash
+/// partitioned on the join keys into spill files instead (grace hash join).
+/// Each output partition then partitions its probe side the same way and
+/// joins the pairs of partitions one at a time, as the rows of a build
+/// partition only match rows of the corresponding probe partition. A build
+/// partition that still does not fit in memory is partitioned again, up to
+/// [`MAX_SPILL_LEVEL`] times.
+///
+/// As spilling reorders the probe side, it is disabled when the probe side
+/// has an output ordering, as well as when the [`DiskManager`] is disabled.
+///
+/// [`DiskManager`]: datafusion_execution::disk_manager::DiskManager
 #[derive(Debug)]
 pub struct HashJoinExec {
     /// left (build) side which gets hashed
@@ -145,6 +203,7 @@ impl HashJoinExec {
         }
 
         check_join_is_valid(&left_schema, &right_schema, &on)?;
+        check_join_type_is_supported(join_type, "HashJoinExec")?;
 
         let (schema, column_indices) =
             build_join_schema(&left_schema, &right_schema, join_type);
@@ -318,7 +377,8 @@ impl ExecutionPlan for HashJoinExec {
                 JoinType::Left
                 | JoinType::LeftSemi
                 | JoinType::LeftAnti
-                | JoinType::Full => Partitioning::UnknownPartitioning(
+                | JoinType::Full
+                | JoinType::AsOf => Partitioning::UnknownPartitioning(
                     self.right.output_partitioning().partition_count(),
                 ),
             },
@@ -441,10 +501,15 @@ impl ExecutionPlan for HashJoinExec {
         }
 
         let join_metrics = BuildProbeJoinMetrics::new(partition, &self.metrics);
+        let spill_metrics = SpillMetrics::new(partition, &self.metrics);
+        // Spilling partitions the probe side, which does not preserve its order
+        let can_spill = self.right.output_ordering().is_none()
+            && context.runtime_env().disk_manager.tmp_files_enabled();
         let left_fut = match self.mode {
             PartitionMode::CollectLeft => self.left_fut.once(|| {
-                let reservation =
-                    MemoryConsumer::new("HashJoinInput").register(context.memory_pool());
+                let reservation = MemoryConsumer::new("HashJoinInput")
+                    .with_can_spill(can_spill)
+                    .register(context.memory_pool());
                 collect_left_input(
                     None,
                     self.random_state.clone(),
@@ -452,12 +517,15 @@ impl ExecutionPlan for HashJoinExec {
                     on_left.clone(),
                     context.clone(),
                     join_metrics.clone(),
+                    spill_metrics.clone(),
                     reservation,
+                    can_spill,
                 )
             }),
             PartitionMode::Partitioned => {
                 let reservation =
                     MemoryConsumer::new(format!("HashJoinInput[{partition}]"))
+                        .with_can_spill(can_spill)
                         .register(context.memory_pool());
 
                 OnceFut::new(collect_left_input(
@@ -467,7 +535,9 @@ impl ExecutionPlan for HashJoinExec {
                     on_left.clone(),
                     context.clone(),
                     join_metrics.clone(),
+                    spill_metrics.clone(),
                     reservation,
+                    can_spill,
                 ))
             }
             PartitionMode::Auto => {
@@ -483,7 +553,7 @@ impl ExecutionPlan for HashJoinExec {
 
         // we have the batches and the hash map with their keys. We can how create a stream
         // over the right that uses this information to issue new batches.
-        let right_stream = self.right.execute(partition, context)?;
+        let right_stream = self.right.execute(partition, context.clone())?;
 
         Ok(Box::pin(HashJoinStream {
             schema: self.schema(),
@@ -500,6 +570,10 @@ impl ExecutionPlan for HashJoinExec {
             null_equals_null: self.null_equals_null,
             is_exhausted: false,
             reservation,
+            partition,
+            runtime: context.runtime_env(),
+            spill_metrics,
+            spilled: None,
         }))
     }
 
@@ -520,6 +594,7 @@ impl ExecutionPlan for HashJoinExec {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn collect_left_input(
     partition: Option<usize>,
     random_state: RandomState,
@@ -527,10 +602,10 @@ async fn collect_left_input(
     on_left: Vec<Column>,
     context: Arc<TaskContext>,
     metrics: BuildProbeJoinMetrics,
+    spill_metrics: SpillMetrics,
     reservation: MemoryReservation,
+    can_spill: bool,
 ) -> Result<JoinLeftData> {
-    let schema = left.schema();
-
     let (left_input, left_input_partition) = if let Some(partition) = partition {
         (left, partition)
     } else {
@@ -548,26 +623,76 @@ async fn collect_left_input(
     // Depending on partition argument load single partition or whole left side in memory
     let stream = left_input.execute(left_input_partition, context.clone())?;
 
+    collect_build_side(
+        stream,
+        random_state,
+        on_left,
+        context.runtime_env(),
+        metrics,
+        spill_metrics,
+        reservation,
+        0,
+        can_spill,
+    )
+    .await
+}
+
+/// Collects the build side `stream` of a join in memory, or hash partitions
+/// it into spill files if it does not fit in `reservation` and `can_spill`.
+///
+/// `level` is the number of times the build side was already partitioned:
+/// 0 for the input of the join, more for a spilled partition read back.
+#[allow(clippy::too_many_arguments)]
+async fn collect_build_side(
+    mut stream: SendableRecordBatchStream,
+    random_state: RandomState,
+    on_left: Vec<Column>,
+    runtime: Arc<RuntimeEnv>,
+    metrics: BuildProbeJoinMetrics,
+    spill_metrics: SpillMetrics,
+    mut reservation: MemoryReservation,
+    level: usize,
+    can_spill: bool,
+) -> Result<JoinLeftData> {
+    let schema = stream.schema();
+
     // This operation performs 2 steps at once:
     // 1. creates a [JoinHashMap] of all batches from the stream
     // 2. stores the batches in a vector.
-    let initial = (Vec::new(), 0, metrics, reservation);
-    let (batches, num_rows, metrics, mut reservation) = stream
-        .try_fold(initial, |mut acc, batch| async {
-            let batch_size = batch.get_array_memory_size();
-            // Reserve memory for incoming batch
-            acc.3.try_grow(batch_size)?;
-            // Update metrics
-            acc.2.build_mem_used.add(batch_size);
-            acc.2.build_input_batches.add(1);
-            acc.2.build_input_rows.add(batch.num_rows());
-            // Update rowcount
-            acc.1 += batch.num_rows();
-            // Push batch to output
-            acc.0.push(batch);
-            Ok(acc)
-        })
-        .await?;
+    let mut batches = vec![];
+    let mut num_rows = 0;
+    while let Some(batch) = stream.next().await.transpose()? {
+        // Update metrics
+        if level == 0 {
+            metrics.build_input_batches.add(1);
+            metrics.build_input_rows.add(batch.num_rows());
+        }
+        let batch_size = batch.get_array_memory_size();
+        // Reserve memory for incoming batch
+        if let Err(e) = reservation.try_grow(batch_size) {
+            if !can_spill {
+                return Err(e);
+            }
+            batches.push(batch);
+            return spill_build_side(
+                schema,
+                batches,
+                Some(stream),
+                on_left,
+                &runtime,
+                &metrics,
+                &spill_metrics,
+                reservation,
+                level,
+            )
+            .await;
+        }
+        metrics.build_mem_used.add(batch_size);
+        // Update rowcount
+        num_rows += batch.num_rows();
+        // Push batch to output
+        batches.push(batch);
+    }
 
     // Estimation of memory size, required for hashtable, prior to allocation.
     // Final result can be verified using `RawTable.allocation_info()`
@@ -586,7 +711,23 @@ async fn collect_left_input(
     let estimated_hastable_size =
         16 * estimated_buckets + estimated_buckets + size_of::<JoinHashMap>();
 
-    reservation.try_grow(estimated_hastable_size)?;
+    if let Err(e) = reservation.try_grow(estimated_hastable_size) {
+        if !can_spill {
+            return Err(e);
+        }
+        return spill_build_side(
+            schema,
+            batches,
+            None,
+            on_left,
+            &runtime,
+            &metrics,
+            &spill_metrics,
+            reservation,
+            level,
+        )
+        .await;
+    }
     metrics.build_mem_used.add(estimated_hastable_size);
 
     let mut hashmap = JoinHashMap::with_capacity(num_rows);
@@ -609,7 +750,212 @@ async fn collect_left_input(
     // can directly index into the arrays
     let single_batch = concat_batches(&schema, &batches, num_rows)?;
 
-    Ok((hashmap, single_batch, reservation))
+    Ok(JoinLeftData::InMemory {
+        hash_map: hashmap,
+        batch: single_batch,
+        _reservation: reservation,
+    })
+}
+
+/// Hash partitions the build side of a join into spill files: the `batches`
+/// collected so far, then the rest of `stream`, if any
+#[allow(clippy::too_many_arguments)]
+async fn spill_build_side(
+    schema: SchemaRef,
+    batches: Vec<RecordBatch>,
+    stream: Option<SendableRecordBatchStream>,
+    on_left: Vec<Column>,
+    runtime: &RuntimeEnv,
+    metrics: &BuildProbeJoinMetrics,
+    spill_metrics: &SpillMetrics,
+    mut reservation: MemoryReservation,
+    level: usize,
+) -> Result<JoinLeftData> {
+    let mut partitioner =
+        SpillPartitioner::try_new(on_left, level, &schema, runtime, "HashJoinBuild")?;
+    for batch in batches {
+        partitioner.write(&batch)?;
+    }
+    reservation.free();
+    if let Some(mut stream) = stream {
+        while let Some(batch) = stream.next().await.transpose()? {
+            if level == 0 {
+                metrics.build_input_batches.add(1);
+                metrics.build_input_rows.add(batch.num_rows());
+            }
+            partitioner.write(&batch)?;
+        }
+    }
+    let files = partitioner.finish(spill_metrics)?;
+    spill_metrics.spilled_partitions.add(files.len());
+
+    Ok(JoinLeftData::Spilled(SpilledPartitions {
+        schema,
+        files: files.into_iter().map(Arc::new).collect(),
+        level,
+    }))
+}
+
+/// Hash partitions the probe side of a join into spill files, the same way
+/// as the build side was partitioned at `level`
+async fn spill_probe_side(
+    mut stream: SendableRecordBatchStream,
+    on_right: Vec<Column>,
+    runtime: Arc<RuntimeEnv>,
+    spill_metrics: SpillMetrics,
+    level: usize,
+) -> Result<Vec<NamedTempFile>> {
+    let mut partitioner = SpillPartitioner::try_new(
+        on_right,
+        level,
+        &stream.schema(),
+        &runtime,
+        "HashJoinProbe",
+    )?;
+    while let Some(batch) = stream.next().await.transpose()? {
+        partitioner.write(&batch)?;
+    }
+    partitioner.finish(&spill_metrics)
+}
+
+/// Hash partitions the batches of a join input on the join keys into
+/// [`SPILL_PARTITIONS`] spill files.
+///
+/// The keys are hashed with a different [`RandomState`] at each `level`, so
+/// that a partition can be partitioned again, and from the one of the hash
+/// table, so that the rows of a partition spread over all its buckets.
+struct SpillPartitioner {
+    /// Join keys of the input
+    on: Vec<Column>,
+    /// Hashes the keys to select the partition of each row
+    random_state: RandomState,
+    /// Spill file and writer of each partition
+    partitions: Vec<(NamedTempFile, IPCWriter)>,
+    /// Buffer for the hashes of the keys of a batch
+    hashes_buffer: Vec<u64>,
+    /// Total size of the partitioned batches, in bytes of memory
+    spilled_bytes: usize,
+}
+
+impl SpillPartitioner {
+    fn try_new(
+        on: Vec<Column>,
+        level: usize,
+        schema: &Schema,
+        runtime: &RuntimeEnv,
+        request_description: &str,
+    ) -> Result<Self> {
+        let partitions = (0..SPILL_PARTITIONS)
+            .map(|_| {
+                let file = runtime.disk_manager.create_tmp_file(request_description)?;
+                let writer = IPCWriter::new(file.path(), schema)?;
+                Ok((file, writer))
+            })
+            .collect::<Result<_>>()?;
+        let seed = level as u64 + 1;
+
+        Ok(Self {
+            on,
+            random_state: RandomState::with_seeds(seed, seed, seed, seed),
+            partitions,
+            hashes_buffer: vec![],
+            spilled_bytes: 0,
+        })
+    }
+
+    /// Writes the rows of `batch` to their partitions
+    fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        let keys_values = self
+            .on
+            .iter()
+            .map(|c| Ok(c.evaluate(batch)?.into_array(batch.num_rows())))
+            .collect::<Result<Vec<_>>>()?;
+        self.hashes_buffer.clear();
+        self.hashes_buffer.resize(batch.num_rows(), 0);
+        create_hashes(&keys_values, &self.random_state, &mut self.hashes_buffer)?;
+
+        let mut indices = vec![vec![]; SPILL_PARTITIONS];
+        for (row, hash) in self.hashes_buffer.iter().enumerate() {
+            indices[(*hash % SPILL_PARTITIONS as u64) as usize].push(row as u32);
+        }
+        for ((_, writer), indices) in self.partitions.iter_mut().zip(indices) {
+            if indices.is_empty() {
+                continue;
+            }
+            let indices = UInt32Array::from(indices);
+            let columns = batch
+                .columns()
+                .iter()
+                .map(|c| take(c.as_ref(), &indices, None))
+                .collect::<Result<Vec<_>, _>>()?;
+            writer.write(&RecordBatch::try_new(batch.schema(), columns)?)?;
+        }
+        self.spilled_bytes += batch.get_array_memory_size();
+        Ok(())
+    }
+
+    /// Finishes writing and returns the spill file of each partition
+    fn finish(self, spill_metrics: &SpillMetrics) -> Result<Vec<NamedTempFile>> {
+        let mut num_rows = 0;
+        let files = self
+            .partitions
+            .into_iter()
+            .map(|(file, mut writer)| {
+                writer.finish()?;
+                num_rows += writer.num_rows;
+                Ok(file)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        debug!(
+            "Spilled {num_rows} rows of {} bytes into {} partitions",
+            self.spilled_bytes,
+            files.len()
+        );
+
+        spill_metrics.spill_count.add(files.len());
+        spill_metrics.spilled_bytes.add(self.spilled_bytes);
+        Ok(files)
+    }
+}
+
+/// Reads back a spilled build partition, which may be shared by several
+/// output partitions
+fn read_build_partition(
+    file: Arc<NamedTempFile>,
+    schema: SchemaRef,
+) -> SendableRecordBatchStream {
+    let mut builder = RecordBatchReceiverStream::builder(schema, 2);
+    let sender = builder.tx();
+
+    builder.spawn_blocking(move || {
+        if let Err(e) = read_spill(sender.clone(), file.path()) {
+            sender.blocking_send(Err(e)).ok();
+        }
+    });
+
+    builder.build()
+}
+
+/// Metrics of spilling the inputs of a [`HashJoinExec`]
+#[derive(Clone)]
+struct SpillMetrics {
+    /// Number of spill files written, for both inputs
+    spill_count: Count,
+    /// Total size of the spilled batches, in bytes of memory
+    spilled_bytes: Count,
+    /// Number of partitions the build side was spilled into
+    spilled_partitions: Count,
+}
+
+impl SpillMetrics {
+    fn new(partition: usize, metrics: &ExecutionPlanMetricsSet) -> Self {
+        Self {
+            spill_count: MetricBuilder::new(metrics).spill_count(partition),
+            spilled_bytes: MetricBuilder::new(metrics).spilled_bytes(partition),
+            spilled_partitions: MetricBuilder::new(metrics)
+                .counter("spilled_partitions", partition),
+        }
+    }
 }
 
 /// Updates `hash` with new entries from [RecordBatch] evaluated against the expressions `on`,
@@ -687,6 +1033,26 @@ struct HashJoinStream {
     null_equals_null: bool,
     /// Memory reservation
     reservation: MemoryReservation,
+    /// Output partition of the stream
+    partition: usize,
+    /// Runtime, used to spill the probe side when the build side has spilled
+    runtime: Arc<RuntimeEnv>,
+    /// Spilling metrics
+    spill_metrics: SpillMetrics,
+    /// State of the join of the spilled partitions, once the build side has spilled
+    spilled: Option<SpilledJoinState>,
+}
+
+/// State of a [`HashJoinStream`] whose build side has spilled
+struct SpilledJoinState {
+    /// Spilled partitions of the build side
+    build: SpilledPartitions,
+    /// Partitions the probe side into spill files, until done
+    probe_fut: Option<BoxFuture<'static, Result<Vec<NamedTempFile>>>>,
+    /// Build and probe spill files of the partitions remaining to join
+    partitions: VecDeque<(Arc<NamedTempFile>, NamedTempFile)>,
+    /// Joins the current partition
+    current: Option<SendableRecordBatchStream>,
 }
 
 impl RecordBatchStream for HashJoinStream {
@@ -1212,6 +1578,10 @@ impl HashJoinStream {
         &mut self,
         cx: &mut std::task::Context<'_>,
     ) -> Poll<Option<Result<RecordBatch>>> {
+        if self.spilled.is_some() {
+            return self.poll_next_spilled(cx);
+        }
+
         let build_timer = self.join_metrics.build_time.timer();
         let left_data = match ready!(self.left_fut.get(cx)) {
             Ok(left_data) => left_data,
@@ -1219,6 +1589,17 @@ impl HashJoinStream {
         };
         build_timer.done();
 
+        let left_data = match left_data {
+            JoinLeftData::InMemory {
+                hash_map, batch, ..
+            } => (hash_map, batch),
+            JoinLeftData::Spilled(build) => {
+                let build = build.clone();
+                self.start_spilled_join(build);
+                return self.poll_next_spilled(cx);
+            }
+        };
+
         // Reserving memory for visited_left_side bitmap in case it hasn't been initialied yet
         // and join_type requires to store it
         if self.visited_left_side.is_none()
@@ -1348,6 +1729,127 @@ impl HashJoinStream {
                 Some(err) => Some(err),
             })
     }
+
+    /// Starts partitioning the probe side into spill files like the `build`
+    /// side, to join the partitions one at a time
+    fn start_spilled_join(&mut self, build: SpilledPartitions) {
+        let empty = EmptyRecordBatchStream::new(self.right.schema());
+        let probe = std::mem::replace(&mut self.right, Box::pin(empty));
+        let probe_fut = spill_probe_side(
+            probe,
+            self.on_right.clone(),
+            self.runtime.clone(),
+            self.spill_metrics.clone(),
+            build.level,
+        )
+        .boxed();
+
+        self.spilled = Some(SpilledJoinState {
+            build,
+            probe_fut: Some(probe_fut),
+            partitions: VecDeque::new(),
+            current: None,
+        });
+    }
+
+    /// Joins the spilled partitions one at a time, each with a
+    /// [`HashJoinStream`] of its own
+    fn poll_next_spilled(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Result<RecordBatch>>> {
+        loop {
+            let spilled = self.spilled.as_mut().unwrap();
+            if let Some(probe_fut) = spilled.probe_fut.as_mut() {
+                let probe_files = match ready!(probe_fut.poll_unpin(cx)) {
+                    Ok(probe_files) => probe_files,
+                    Err(e) => return Poll::Ready(Some(Err(e))),
+                };
+                spilled.probe_fut = None;
+                spilled.partitions = spilled
+                    .build
+                    .files
+                    .iter()
+                    .cloned()
+                    .zip(probe_files)
+                    .collect();
+            }
+
+            if let Some(current) = spilled.current.as_mut() {
+                match ready!(current.poll_next_unpin(cx)) {
+                    Some(result) => return Poll::Ready(Some(result)),
+                    None => spilled.current = None,
+                }
+            }
+
+            let Some((build_file, probe_file)) = spilled.partitions.pop_front() else {
+                return Poll::Ready(None);
+            };
+            let build_schema = spilled.build.schema.clone();
+            let level = spilled.build.level + 1;
+            let current = match self.partition_stream(
+                build_file,
+                build_schema,
+                probe_file,
+                level,
+            ) {
+                Ok(current) => current,
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            };
+            self.spilled.as_mut().unwrap().current = Some(current);
+        }
+    }
+
+    /// Returns a stream joining a pair of spilled partitions, partitioned
+    /// `level` times
+    fn partition_stream(
+        &self,
+        build_file: Arc<NamedTempFile>,
+        build_schema: SchemaRef,
+        probe_file: NamedTempFile,
+        level: usize,
+    ) -> Result<SendableRecordBatchStream> {
+        let reservation =
+            MemoryConsumer::new(format!("HashJoinInput[{}]", self.partition))
+                .with_can_spill(true)
+                .register(&self.runtime.memory_pool);
+        let left_fut = OnceFut::new(collect_build_side(
+            read_build_partition(build_file, build_schema),
+            self.random_state.clone(),
+            self.on_left.clone(),
+            self.runtime.clone(),
+            self.join_metrics.clone(),
+            self.spill_metrics.clone(),
+            reservation,
+            level,
+            level < MAX_SPILL_LEVEL,
+        ));
+        let right = read_spill_as_stream(probe_file, self.right.schema())?;
+        let reservation =
+            MemoryConsumer::new(format!("HashJoinStream[{}]", self.partition))
+                .register(&self.runtime.memory_pool);
+
+        Ok(Box::pin(HashJoinStream {
+            schema: self.schema.clone(),
+            on_left: self.on_left.clone(),
+            on_right: self.on_right.clone(),
+            filter: self.filter.clone(),
+            join_type: self.join_type,
+            left_fut,
+            visited_left_side: None,
+            right,
+            column_indices: self.column_indices.clone(),
+            random_state: self.random_state.clone(),
+            join_metrics: self.join_metrics.clone(),
+            null_equals_null: self.null_equals_null,
+            is_exhausted: false,
+            reservation,
+            partition: self.partition,
+            runtime: self.runtime.clone(),
+            spill_metrics: self.spill_metrics.clone(),
+            spilled: None,
+        }))
+    }
 }
 
 impl Stream for HashJoinStream {
@@ -1372,6 +1874,7 @@ mod tests {
     use datafusion_expr::Operator;
     use datafusion_physical_expr::expressions::Literal;
     use hashbrown::raw::RawTable;
+    use itertools::Itertools;
 
     use crate::execution::context::SessionConfig;
     use crate::physical_expr::expressions::BinaryExpr;
@@ -3241,4 +3744,165 @@ mod tests {
 
         Ok(())
     }
+
+    /// Builds an input of the spilling tests: `num_rows` rows in batches of
+    /// 100 rows, with the join key `b` from `b_values`
+    fn build_spill_table(
+        names: (&str, &str, &str),
+        num_rows: i32,
+        b_values: impl Fn(i32) -> i32,
+    ) -> (RecordBatch, Vec<RecordBatch>) {
+        let batch = build_table_i32(
+            (names.0, &(0..num_rows).collect()),
+            (names.1, &(0..num_rows).map(b_values).collect()),
+            (names.2, &(0..num_rows).map(|i| i * 10).collect()),
+        );
+        let batches = (0..batch.num_rows())
+            .step_by(100)
+            .map(|offset| batch.slice(offset, 100.min(batch.num_rows() - offset)))
+            .collect();
+        (batch, batches)
+    }
+
+    fn sorted_lines(batches: &[RecordBatch]) -> Result<Vec<String>> {
+        let mut lines = arrow::util::pretty::pretty_format_batches(batches)?
+            .to_string()
+            .lines()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        lines.sort_unstable();
+        Ok(lines)
+    }
+
+    /// Joins two tables with all join types and partition modes, checks that
+    /// the results with `memory_limit` match the results without, and
+    /// returns the number of spilled partitions of each join
+    async fn check_join_with_spill(memory_limit: usize) -> Result<Vec<usize>> {
+        let (left_batch, left_batches) =
+            build_spill_table(("a1", "b1", "c1"), 1000, |i| i % 500);
+        let (right_batch, right_batches) =
+            build_spill_table(("a2", "b2", "c2"), 300, |i| i * 3);
+        let left = Arc::new(MemoryExec::try_new(
+            &[left_batches],
+            left_batch.schema(),
+            None,
+        )?);
+        let right = Arc::new(MemoryExec::try_new(
+            &[right_batches],
+            right_batch.schema(),
+            None,
+        )?);
+        let on = vec![(
+            Column::new_with_schema("b1", &left.schema())?,
+            Column::new_with_schema("b2", &right.schema())?,
+        )];
+
+        let join_types = vec![
+            JoinType::Inner,
+            JoinType::Left,
+            JoinType::Right,
+            JoinType::Full,
+            JoinType::LeftSemi,
+            JoinType::LeftAnti,
+            JoinType::RightSemi,
+            JoinType::RightAnti,
+        ];
+        let modes = [PartitionMode::CollectLeft, PartitionMode::Partitioned];
+
+        let mut spilled_partitions = vec![];
+        for (join_type, mode) in join_types.into_iter().cartesian_product(modes) {
+            let new_join = || {
+                HashJoinExec::try_new(
+                    left.clone(),
+                    right.clone(),
+                    on.clone(),
+                    Some(prepare_join_filter()),
+                    &join_type,
+                    mode,
+                    false,
+                )
+            };
+            let task_ctx = SessionContext::new().task_ctx();
+            let expected = common::collect(new_join()?.execute(0, task_ctx)?).await?;
+
+            let runtime_config =
+                RuntimeConfig::new().with_memory_limit(memory_limit, 1.0);
+            let runtime = Arc::new(RuntimeEnv::new(runtime_config)?);
+            let session_ctx =
+                SessionContext::with_config_rt(SessionConfig::default(), runtime);
+            let join = new_join()?;
+            let stream = join.execute(0, session_ctx.task_ctx())?;
+            let batches = common::collect(stream).await?;
+
+            assert_eq!(
+                sorted_lines(&batches)?,
+                sorted_lines(&expected)?,
+                "{join_type:?} {mode:?}"
+            );
+            let metrics = join.metrics().unwrap();
+            assert!(metrics.spill_count().unwrap() > 0);
+            assert!(metrics.spilled_bytes().unwrap() > 0);
+            spilled_partitions.push(
+                metrics
+                    .sum_by_name("spilled_partitions")
+                    .unwrap()
+                    .as_usize(),
+            );
+        }
+
+        Ok(spilled_partitions)
+    }
+
+    #[tokio::test]
+    async fn join_with_spill() -> Result<()> {
+        let spilled_partitions = check_join_with_spill(10_000).await?;
+        assert!(spilled_partitions.iter().all(|p| *p == SPILL_PARTITIONS));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_with_recursive_spill() -> Result<()> {
+        // The partitions do not fit either and are partitioned again
+        let spilled_partitions = check_join_with_spill(4_000).await?;
+        assert!(spilled_partitions.iter().all(|p| *p > SPILL_PARTITIONS));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_ordered_probe_side_does_not_spill() -> Result<()> {
+        let (left_batch, left_batches) =
+            build_spill_table(("a1", "b1", "c1"), 1000, |i| i % 500);
+        let (right_batch, right_batches) =
+            build_spill_table(("a2", "b2", "c2"), 300, |i| i * 3);
+        let left = Arc::new(MemoryExec::try_new(
+            &[left_batches],
+            left_batch.schema(),
+            None,
+        )?);
+        let sort_information = vec![PhysicalSortExpr {
+            expr: Arc::new(Column::new("a2", 0)),
+            options: Default::default(),
+        }];
+        let right = Arc::new(
+            MemoryExec::try_new(&[right_batches], right_batch.schema(), None)?
+                .with_sort_information(sort_information),
+        );
+        let on = vec![(
+            Column::new_with_schema("b1", &left.schema())?,
+            Column::new_with_schema("b2", &right.schema())?,
+        )];
+
+        let runtime_config = RuntimeConfig::new().with_memory_limit(10_000, 1.0);
+        let runtime = Arc::new(RuntimeEnv::new(runtime_config)?);
+        let session_ctx =
+            SessionContext::with_config_rt(SessionConfig::default(), runtime);
+        let join = join(left, right, on, &JoinType::Inner, false)?;
+        let stream = join.execute(0, session_ctx.task_ctx())?;
+        let err = common::collect(stream).await.unwrap_err();
+
+        assert_contains!(err.to_string(), "Resources exhausted");
+        assert_eq!(join.metrics().unwrap().spill_count(), Some(0));
+
+        Ok(())
+    }
 }
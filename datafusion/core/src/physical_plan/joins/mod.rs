@@ -17,12 +17,14 @@
 
 //! DataFusion Join implementations
 
+pub use asof_join::AsOfJoinExec;
 pub use cross_join::CrossJoinExec;
 pub use hash_join::HashJoinExec;
 pub use nested_loop_join::NestedLoopJoinExec;
 // Note: SortMergeJoin is not used in plans yet
 pub use sort_merge_join::SortMergeJoinExec;
 pub use symmetric_hash_join::SymmetricHashJoinExec;
+mod asof_join;
 mod cross_join;
 mod hash_join;
 mod hash_join_utils;
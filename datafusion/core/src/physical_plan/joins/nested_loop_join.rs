@@ -21,11 +21,11 @@
 
 use crate::physical_plan::joins::utils::{
     append_right_indices, apply_join_filter_to_indices, build_batch_from_indices,
-    build_join_schema, check_join_is_valid, combine_join_equivalence_properties,
-    estimate_join_statistics, get_anti_indices, get_anti_u64_indices,
-    get_final_indices_from_bit_map, get_semi_indices, get_semi_u64_indices,
-    partitioned_join_output_partitioning, BuildProbeJoinMetrics, ColumnIndex, JoinFilter,
-    JoinSide, OnceAsync, OnceFut,
+    build_join_schema, check_join_is_valid, check_join_type_is_supported,
+    combine_join_equivalence_properties, estimate_join_statistics, get_anti_indices,
+    get_anti_u64_indices, get_final_indices_from_bit_map, get_semi_indices,
+    get_semi_u64_indices, partitioned_join_output_partitioning, BuildProbeJoinMetrics,
+    ColumnIndex, JoinFilter, JoinSide, OnceAsync, OnceFut,
 };
 use crate::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricsSet};
 use crate::physical_plan::{
@@ -105,6 +105,7 @@ impl NestedLoopJoinExec {
         let left_schema = left.schema();
         let right_schema = right.schema();
         check_join_is_valid(&left_schema, &right_schema, &[])?;
+        check_join_type_is_supported(join_type, "NestedLoopJoinExec")?;
         let (schema, column_indices) =
             build_join_schema(&left_schema, &right_schema, join_type);
         Ok(NestedLoopJoinExec {
@@ -295,7 +296,11 @@ impl ExecutionPlan for NestedLoopJoinExec {
 // left and right node.
 fn distribution_from_join_type(join_type: &JoinType) -> Vec<Distribution> {
     match join_type {
-        JoinType::Inner | JoinType::Left | JoinType::LeftSemi | JoinType::LeftAnti => {
+        JoinType::Inner
+        | JoinType::Left
+        | JoinType::LeftSemi
+        | JoinType::LeftAnti
+        | JoinType::AsOf => {
             // need the left data, and the right should be one partition
             vec![
                 Distribution::UnspecifiedDistribution,
@@ -642,7 +647,7 @@ fn adjust_indices_by_join_type(
 ) -> (UInt64Array, UInt32Array) {
     match join_type {
         JoinType::Inner => (left_indices, right_indices),
-        JoinType::Left => {
+        JoinType::Left | JoinType::AsOf => {
             // matched
             // unmatched left row will be produced in this batch
             let left_unmatched_indices =
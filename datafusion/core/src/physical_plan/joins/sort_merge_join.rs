@@ -22,7 +22,7 @@
 
 use std::any::Any;
 use std::cmp::Ordering;
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::fmt::Formatter;
 use std::mem;
 use std::ops::Range;
@@ -34,8 +34,9 @@ use crate::physical_plan::expressions::Column;
 use crate::physical_plan::expressions::PhysicalSortExpr;
 use crate::physical_plan::joins::utils::{
     add_offset_to_lex_ordering, add_offset_to_ordering_equivalence_classes,
-    build_join_schema, check_join_is_valid, combine_join_equivalence_properties,
-    estimate_join_statistics, partitioned_join_output_partitioning, JoinOn,
+    build_join_schema, check_join_is_valid, check_join_type_is_supported,
+    combine_join_equivalence_properties, estimate_join_statistics,
+    partitioned_join_output_partitioning, JoinFilter, JoinOn, JoinSide,
 };
 use crate::physical_plan::metrics::{ExecutionPlanMetricsSet, MetricBuilder, MetricsSet};
 use crate::physical_plan::{
@@ -48,7 +49,8 @@ use arrow::array::*;
 use arrow::compute::{concat_batches, take, SortOptions};
 use arrow::datatypes::{DataType, SchemaRef, TimeUnit};
 use arrow::error::ArrowError;
-use arrow::record_batch::RecordBatch;
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use datafusion_common::cast::as_boolean_array;
 use datafusion_common::{DataFusionError, JoinType, Result};
 use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
 use datafusion_execution::TaskContext;
@@ -68,6 +70,8 @@ pub struct SortMergeJoinExec {
     pub(crate) right: Arc<dyn ExecutionPlan>,
     /// Set of common columns used to join on
     pub(crate) on: JoinOn,
+    /// Filters which are applied while finding matching rows
+    pub(crate) filter: Option<JoinFilter>,
     /// How the join is performed
     pub(crate) join_type: JoinType,
     /// The schema once the join is applied
@@ -127,6 +131,7 @@ impl SortMergeJoinExec {
         left: Arc<dyn ExecutionPlan>,
         right: Arc<dyn ExecutionPlan>,
         on: JoinOn,
+        filter: Option<JoinFilter>,
         join_type: JoinType,
         sort_options: Vec<SortOptions>,
         null_equals_null: bool,
@@ -134,13 +139,8 @@ impl SortMergeJoinExec {
         let left_schema = left.schema();
         let right_schema = right.schema();
 
-        if join_type == JoinType::RightSemi {
-            return Err(DataFusionError::NotImplemented(
-                "SortMergeJoinExec does not support JoinType::RightSemi".to_string(),
-            ));
-        }
-
         check_join_is_valid(&left_schema, &right_schema, &on)?;
+        check_join_type_is_supported(&join_type, "SortMergeJoinExec")?;
         if sort_options.len() != on.len() {
             return Err(DataFusionError::Plan(format!(
                 "Expected number of sort options: {}, actual: {}",
@@ -185,7 +185,7 @@ impl SortMergeJoinExec {
                     _ => None,
                 }
             }
-            JoinType::Left | JoinType::LeftSemi | JoinType::LeftAnti => {
+            JoinType::Left | JoinType::LeftSemi | JoinType::LeftAnti | JoinType::AsOf => {
                 left.output_ordering().map(|sort_exprs| sort_exprs.to_vec())
             }
             JoinType::RightSemi | JoinType::RightAnti => right
@@ -210,6 +210,7 @@ impl SortMergeJoinExec {
             left,
             right,
             on,
+            filter,
             join_type,
             schema,
             metrics: ExecutionPlanMetricsSet::new(),
@@ -225,12 +226,21 @@ impl SortMergeJoinExec {
     pub fn on(&self) -> &[(Column, Column)] {
         &self.on
     }
+
+    /// Filters applied before join output
+    pub fn filter(&self) -> Option<&JoinFilter> {
+        self.filter.as_ref()
+    }
 }
 
 impl DisplayAs for SortMergeJoinExec {
     fn fmt_as(&self, t: DisplayFormatType, f: &mut Formatter) -> std::fmt::Result {
         match t {
             DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let display_filter = self.filter.as_ref().map_or_else(
+                    || "".to_string(),
+                    |f| format!(", filter={}", f.expression()),
+                );
                 let on = self
                     .on
                     .iter()
@@ -239,8 +249,8 @@ impl DisplayAs for SortMergeJoinExec {
                     .join(", ");
                 write!(
                     f,
-                    "SortMergeJoin: join_type={:?}, on=[{}]",
-                    self.join_type, on
+                    "SortMergeJoin: join_type={:?}, on=[{}]{}",
+                    self.join_type, on, display_filter
                 )
             }
         }
@@ -393,6 +403,7 @@ impl ExecutionPlan for SortMergeJoinExec {
                 left.clone(),
                 right.clone(),
                 self.on.clone(),
+                self.filter.clone(),
                 self.join_type,
                 self.sort_options.clone(),
                 self.null_equals_null,
@@ -422,7 +433,8 @@ impl ExecutionPlan for SortMergeJoinExec {
             | JoinType::Left
             | JoinType::Full
             | JoinType::LeftAnti
-            | JoinType::LeftSemi => (
+            | JoinType::LeftSemi
+            | JoinType::AsOf => (
                 self.left.clone(),
                 self.right.clone(),
                 self.on.iter().map(|on| on.0.clone()).collect(),
@@ -456,6 +468,7 @@ impl ExecutionPlan for SortMergeJoinExec {
             buffered,
             on_streamed,
             on_buffered,
+            self.filter.clone(),
             self.join_type,
             batch_size,
             SortMergeJoinMetrics::new(partition, &self.metrics),
@@ -580,6 +593,10 @@ struct StreamedBatch {
     pub output_indices: Vec<StreamedJoinedChunk>,
     // Index of currently scanned batch from buffered data
     pub buffered_batch_idx: Option<usize>,
+    // (used with a join filter) The last streamed row of the frozen output
+    // indices, which may be joined to more buffered rows, and whether any of
+    // its joined rows passed the filter
+    pub filter_state: Option<(usize, bool)>,
 }
 
 impl StreamedBatch {
@@ -591,6 +608,7 @@ impl StreamedBatch {
             join_arrays,
             output_indices: vec![],
             buffered_batch_idx: None,
+            filter_state: None,
         }
     }
 
@@ -601,6 +619,7 @@ impl StreamedBatch {
             join_arrays: vec![],
             output_indices: vec![],
             buffered_batch_idx: None,
+            filter_state: None,
         }
     }
 
@@ -642,6 +661,9 @@ struct BufferedBatch {
     pub join_arrays: Vec<ArrayRef>,
     /// Buffered joined index (null joining buffered)
     pub null_joined: Vec<usize>,
+    /// (used in full join with a join filter) Indices of the rows joined to
+    /// streamed rows, and whether any of the joined rows passed the filter
+    pub filter_matched: HashMap<usize, bool>,
     /// Size estimation used for reserving / releasing memory
     pub size_estimation: usize,
 }
@@ -670,6 +692,7 @@ impl BufferedBatch {
             range,
             join_arrays,
             null_joined: vec![],
+            filter_matched: HashMap::new(),
             size_estimation,
         }
     }
@@ -712,6 +735,8 @@ struct SMJStream {
     pub on_streamed: Vec<Column>,
     /// Join key columns of buffered
     pub on_buffered: Vec<Column>,
+    /// Filter applied to the joined rows
+    pub filter: Option<JoinFilter>,
     /// Staging output array builders
     pub output_record_batches: Vec<RecordBatch>,
     /// Staging output size, including output batches and staging joined results
@@ -812,11 +837,13 @@ impl Stream for SMJStream {
                             let record_batch = self.output_record_batch_and_reset()?;
                             return Poll::Ready(Some(Ok(record_batch)));
                         }
-                        return Poll::Pending;
+                        // the join filter may have rejected all the joined rows
                     }
                 }
                 SMJState::Exhausted => {
-                    self.freeze_all()?;
+                    self.freeze_streamed()?;
+                    let batch_count = self.buffered_data.batches.len();
+                    self.freeze_buffered(batch_count, true)?;
                     if !self.output_record_batches.is_empty() {
                         let record_batch = self.output_record_batch_and_reset()?;
                         return Poll::Ready(Some(Ok(record_batch)));
@@ -838,6 +865,7 @@ impl SMJStream {
         buffered: SendableRecordBatchStream,
         on_streamed: Vec<Column>,
         on_buffered: Vec<Column>,
+        filter: Option<JoinFilter>,
         join_type: JoinType,
         batch_size: usize,
         join_metrics: SortMergeJoinMetrics,
@@ -863,6 +891,7 @@ impl SMJStream {
             current_ordering: Ordering::Equal,
             on_streamed,
             on_buffered,
+            filter,
             output_record_batches: vec![],
             output_size: 0,
             batch_size,
@@ -1055,15 +1084,30 @@ impl SMJStream {
                     self.join_type,
                     JoinType::Left
                         | JoinType::Right
-                        | JoinType::RightSemi
                         | JoinType::Full
                         | JoinType::LeftAnti
+                        | JoinType::RightAnti
                 ) {
                     join_streamed = !self.streamed_joined;
                 }
             }
             Ordering::Equal => {
-                if matches!(self.join_type, JoinType::LeftSemi) {
+                let is_semi_or_anti = matches!(
+                    self.join_type,
+                    JoinType::LeftSemi
+                        | JoinType::RightSemi
+                        | JoinType::LeftAnti
+                        | JoinType::RightAnti
+                );
+                if is_semi_or_anti && self.filter.is_some() {
+                    // whether the streamed row is output depends on the
+                    // joined buffered rows that pass the filter
+                    join_streamed = true;
+                    join_buffered = true;
+                } else if matches!(
+                    self.join_type,
+                    JoinType::LeftSemi | JoinType::RightSemi
+                ) {
                     join_streamed = !self.streamed_joined;
                 }
                 if matches!(
@@ -1130,7 +1174,7 @@ impl SMJStream {
 
     fn freeze_all(&mut self) -> Result<()> {
         self.freeze_streamed()?;
-        self.freeze_buffered(self.buffered_data.batches.len())?;
+        self.freeze_buffered(self.buffered_data.batches.len(), false)?;
         Ok(())
     }
 
@@ -1140,19 +1184,35 @@ impl SMJStream {
     //   2. freezes NULLs joined to dequeued buffered batch to "release" it
     fn freeze_dequeuing_buffered(&mut self) -> Result<()> {
         self.freeze_streamed()?;
-        self.freeze_buffered(1)?;
+        self.freeze_buffered(1, true)?;
         Ok(())
     }
 
     // Produces and stages record batch from buffered indices with corresponding
-    // NULLs on streamed side.
+    // NULLs on streamed side. If `filter_unmatched` is true, the buffered
+    // batches are no longer joined, so their rows that were joined to streamed
+    // rows but never passed the join filter are also joined to NULLs.
     //
     // Applicable only in case of Full join.
-    fn freeze_buffered(&mut self, batch_count: usize) -> Result<()> {
+    fn freeze_buffered(
+        &mut self,
+        batch_count: usize,
+        filter_unmatched: bool,
+    ) -> Result<()> {
         if !matches!(self.join_type, JoinType::Full) {
             return Ok(());
         }
         for buffered_batch in self.buffered_data.batches.range_mut(..batch_count) {
+            if filter_unmatched {
+                let mut unmatched = buffered_batch
+                    .filter_matched
+                    .drain()
+                    .filter_map(|(index, matched)| (!matched).then_some(index))
+                    .collect::<Vec<_>>();
+                unmatched.sort_unstable();
+                self.output_size += unmatched.len();
+                buffered_batch.null_joined.extend(unmatched);
+            }
             let buffered_indices = UInt64Array::from_iter_values(
                 buffered_batch.null_joined.iter().map(|&index| index as u64),
             );
@@ -1188,58 +1248,253 @@ impl SMJStream {
     // Produces and stages record batch for all output indices found
     // for current streamed batch and clears staged output indices.
     fn freeze_streamed(&mut self) -> Result<()> {
-        for chunk in self.streamed_batch.output_indices.iter_mut() {
+        if self.filter.is_some() {
+            return self.freeze_streamed_with_filter();
+        }
+        for chunk in mem::take(&mut self.streamed_batch.output_indices) {
+            let mut chunk = chunk;
             let streamed_indices = chunk.streamed_indices.finish();
 
             if streamed_indices.is_empty() {
                 continue;
             }
 
-            let mut streamed_columns = self
-                .streamed_batch
-                .batch
-                .columns()
-                .iter()
-                .map(|column| take(column, &streamed_indices, None))
-                .collect::<Result<Vec<_>, ArrowError>>()?;
-
-            let buffered_indices: UInt64Array = chunk.buffered_indices.finish();
-
-            let mut buffered_columns =
-                if matches!(self.join_type, JoinType::LeftSemi | JoinType::LeftAnti) {
-                    vec![]
-                } else if let Some(buffered_idx) = chunk.buffered_batch_idx {
-                    self.buffered_data.batches[buffered_idx]
-                        .batch
-                        .columns()
-                        .iter()
-                        .map(|column| take(column, &buffered_indices, None))
-                        .collect::<Result<Vec<_>, ArrowError>>()?
-                } else {
-                    self.buffered_schema
-                        .fields()
-                        .iter()
-                        .map(|f| new_null_array(f.data_type(), buffered_indices.len()))
-                        .collect::<Vec<_>>()
-                };
+            let buffered_indices = chunk.buffered_indices.finish();
+            let record_batch = self.output_record_batch(
+                &streamed_indices,
+                chunk.buffered_batch_idx,
+                &buffered_indices,
+            )?;
+            self.output_record_batches.push(record_batch);
+        }
 
-            let columns = if matches!(self.join_type, JoinType::Right) {
-                buffered_columns.extend(streamed_columns);
-                buffered_columns
-            } else {
-                streamed_columns.extend(buffered_columns);
-                streamed_columns
+        Ok(())
+    }
+
+    // Produces and stages record batch for all output indices found for
+    // current streamed batch that pass the join filter, and clears staged
+    // output indices.
+    //
+    // Once all the rows joined to a streamed row are known, the streamed row
+    // is joined to NULLs (outer joins) or output (anti joins) if none of them
+    // passed the filter. The rows joined to the last streamed row may not all
+    // be known yet, in which case its state is kept in `filter_state`.
+    fn freeze_streamed_with_filter(&mut self) -> Result<()> {
+        let output_nulls_if_unmatched = matches!(
+            self.join_type,
+            JoinType::Left
+                | JoinType::Right
+                | JoinType::Full
+                | JoinType::LeftAnti
+                | JoinType::RightAnti
+        );
+        let mut filter_state = self.streamed_batch.filter_state.take();
+        let mut frozen_size = 0;
+        let mut output_size = 0;
+
+        for chunk in mem::take(&mut self.streamed_batch.output_indices) {
+            let mut chunk = chunk;
+            let streamed_indices = chunk.streamed_indices.finish();
+            let buffered_indices = chunk.buffered_indices.finish();
+            frozen_size += streamed_indices.len();
+
+            let mask = match chunk.buffered_batch_idx {
+                Some(buffered_idx) if !streamed_indices.is_empty() => {
+                    Some(self.evaluate_filter(
+                        &streamed_indices,
+                        buffered_idx,
+                        &buffered_indices,
+                    )?)
+                }
+                _ => None,
             };
 
-            self.output_record_batches
-                .push(RecordBatch::try_new(self.schema.clone(), columns)?);
+            let mut output_streamed =
+                UInt64Builder::with_capacity(streamed_indices.len());
+            let mut output_buffered =
+                UInt64Builder::with_capacity(streamed_indices.len());
+            for i in 0..streamed_indices.len() {
+                let streamed_idx = streamed_indices.value(i) as usize;
+                if let Some((last_idx, matched)) = filter_state {
+                    if last_idx != streamed_idx {
+                        if !matched && output_nulls_if_unmatched {
+                            output_streamed.append_value(last_idx as u64);
+                            output_buffered.append_null();
+                        }
+                        filter_state = None;
+                    }
+                }
+
+                if buffered_indices.is_null(i) {
+                    // the streamed row has no equal join keys
+                    output_streamed.append_value(streamed_idx as u64);
+                    output_buffered.append_null();
+                    continue;
+                }
+
+                let buffered_idx = buffered_indices.value(i);
+                let passed = mask
+                    .as_ref()
+                    .is_some_and(|mask| mask.is_valid(i) && mask.value(i));
+                let matched = filter_state.is_some_and(|(_, matched)| matched);
+                match self.join_type {
+                    JoinType::Inner
+                    | JoinType::Left
+                    | JoinType::Right
+                    | JoinType::Full
+                        if passed =>
+                    {
+                        output_streamed.append_value(streamed_idx as u64);
+                        output_buffered.append_value(buffered_idx);
+                    }
+                    JoinType::LeftSemi | JoinType::RightSemi if passed && !matched => {
+                        output_streamed.append_value(streamed_idx as u64);
+                        output_buffered.append_null();
+                    }
+                    _ => {}
+                }
+                if let (JoinType::Full, Some(batch_idx)) =
+                    (self.join_type, chunk.buffered_batch_idx)
+                {
+                    *self.buffered_data.batches[batch_idx]
+                        .filter_matched
+                        .entry(buffered_idx as usize)
+                        .or_default() |= passed;
+                }
+                filter_state = Some((streamed_idx, matched || passed));
+            }
+
+            let output_streamed = output_streamed.finish();
+            if !output_streamed.is_empty() {
+                output_size += output_streamed.len();
+                let record_batch = self.output_record_batch(
+                    &output_streamed,
+                    chunk.buffered_batch_idx,
+                    &output_buffered.finish(),
+                )?;
+                self.output_record_batches.push(record_batch);
+            }
         }
 
-        self.streamed_batch.output_indices.clear();
+        // the last streamed row may be joined to more buffered rows, unless
+        // the streamed side has moved past it
+        if let Some((last_idx, matched)) = filter_state {
+            if last_idx < self.streamed_batch.idx
+                || self.streamed_state != StreamedState::Ready
+            {
+                if !matched && output_nulls_if_unmatched {
+                    output_size += 1;
+                    let record_batch = self.output_record_batch(
+                        &UInt64Array::from(vec![last_idx as u64]),
+                        None,
+                        &UInt64Array::from(vec![None]),
+                    )?;
+                    self.output_record_batches.push(record_batch);
+                }
+            } else {
+                self.streamed_batch.filter_state = filter_state;
+            }
+        }
 
+        self.output_size = self.output_size - frozen_size + output_size;
         Ok(())
     }
 
+    // Evaluates the join filter on the rows of the streamed batch joined to
+    // the rows of the buffered batch with index `buffered_batch_idx`.
+    fn evaluate_filter(
+        &self,
+        streamed_indices: &UInt64Array,
+        buffered_batch_idx: usize,
+        buffered_indices: &UInt64Array,
+    ) -> Result<BooleanArray> {
+        let filter = self.filter.as_ref().unwrap();
+        let streamed_side = if matches!(
+            self.join_type,
+            JoinType::Right | JoinType::RightSemi | JoinType::RightAnti
+        ) {
+            JoinSide::Right
+        } else {
+            JoinSide::Left
+        };
+        let buffered_batch = &self.buffered_data.batches[buffered_batch_idx].batch;
+        let columns = filter
+            .column_indices()
+            .iter()
+            .map(|column_index| {
+                let (batch, indices) = if column_index.side == streamed_side {
+                    (&self.streamed_batch.batch, streamed_indices)
+                } else {
+                    (buffered_batch, buffered_indices)
+                };
+                take(batch.column(column_index.index), indices, None)
+            })
+            .collect::<Result<Vec<_>, ArrowError>>()?;
+        let options =
+            RecordBatchOptions::new().with_row_count(Some(streamed_indices.len()));
+        let intermediate_batch = RecordBatch::try_new_with_options(
+            Arc::new(filter.schema().clone()),
+            columns,
+            &options,
+        )?;
+        let mask = filter
+            .expression()
+            .evaluate(&intermediate_batch)?
+            .into_array(intermediate_batch.num_rows());
+        Ok(as_boolean_array(&mask)?.clone())
+    }
+
+    // Produces a record batch of the rows of the streamed batch joined to
+    // the rows of the buffered batch with index `buffered_batch_idx`, or to
+    // NULLs.
+    fn output_record_batch(
+        &self,
+        streamed_indices: &UInt64Array,
+        buffered_batch_idx: Option<usize>,
+        buffered_indices: &UInt64Array,
+    ) -> Result<RecordBatch> {
+        let mut streamed_columns = self
+            .streamed_batch
+            .batch
+            .columns()
+            .iter()
+            .map(|column| take(column, streamed_indices, None))
+            .collect::<Result<Vec<_>, ArrowError>>()?;
+
+        let mut buffered_columns = if matches!(
+            self.join_type,
+            JoinType::LeftSemi
+                | JoinType::LeftAnti
+                | JoinType::RightSemi
+                | JoinType::RightAnti
+        ) {
+            vec![]
+        } else if let Some(buffered_idx) = buffered_batch_idx {
+            self.buffered_data.batches[buffered_idx]
+                .batch
+                .columns()
+                .iter()
+                .map(|column| take(column, buffered_indices, None))
+                .collect::<Result<Vec<_>, ArrowError>>()?
+        } else {
+            self.buffered_schema
+                .fields()
+                .iter()
+                .map(|f| new_null_array(f.data_type(), buffered_indices.len()))
+                .collect::<Vec<_>>()
+        };
+
+        let columns = if matches!(self.join_type, JoinType::Right) {
+            buffered_columns.extend(streamed_columns);
+            buffered_columns
+        } else {
+            streamed_columns.extend(buffered_columns);
+            streamed_columns
+        };
+
+        Ok(RecordBatch::try_new(self.schema.clone(), columns)?)
+    }
+
     fn output_record_batch_and_reset(&mut self) -> Result<RecordBatch> {
         let record_batch = concat_batches(&self.schema, &self.output_record_batches)?;
         self.join_metrics.output_batches.add(1);
@@ -1326,7 +1581,7 @@ fn join_arrays(batch: &RecordBatch, on_column: &[Column]) -> Vec<ArrayRef> {
 }
 
 /// Get comparison result of two rows of join arrays
-fn compare_join_arrays(
+pub(crate) fn compare_join_arrays(
     left_arrays: &[ArrayRef],
     left: usize,
     right_arrays: &[ArrayRef],
@@ -1489,8 +1744,8 @@ mod tests {
     use arrow::record_batch::RecordBatch;
 
     use crate::common::assert_contains;
-    use crate::physical_plan::expressions::Column;
-    use crate::physical_plan::joins::utils::JoinOn;
+    use crate::physical_plan::expressions::{BinaryExpr, Column};
+    use crate::physical_plan::joins::utils::{ColumnIndex, JoinFilter, JoinOn, JoinSide};
     use crate::physical_plan::joins::SortMergeJoinExec;
     use crate::physical_plan::memory::MemoryExec;
     use crate::physical_plan::{common, ExecutionPlan};
@@ -1500,6 +1755,8 @@ mod tests {
     use datafusion_common::JoinType;
     use datafusion_common::Result;
     use datafusion_execution::runtime_env::{RuntimeConfig, RuntimeEnv};
+    use datafusion_expr::Operator;
+    use datafusion_physical_expr::PhysicalExpr;
 
     fn build_table(
         a: (&str, &Vec<i32>),
@@ -1596,7 +1853,7 @@ mod tests {
         join_type: JoinType,
     ) -> Result<SortMergeJoinExec> {
         let sort_options = vec![SortOptions::default(); on.len()];
-        SortMergeJoinExec::try_new(left, right, on, join_type, sort_options, false)
+        SortMergeJoinExec::try_new(left, right, on, None, join_type, sort_options, false)
     }
 
     fn join_with_options(
@@ -1611,6 +1868,7 @@ mod tests {
             left,
             right,
             on,
+            None,
             join_type,
             sort_options,
             null_equals_null,
@@ -1652,6 +1910,34 @@ mod tests {
         Ok((columns, batches))
     }
 
+    async fn join_collect_with_filter(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        on: JoinOn,
+        filter: JoinFilter,
+        join_type: JoinType,
+        batch_size: usize,
+    ) -> Result<(Vec<String>, Vec<RecordBatch>)> {
+        let session_ctx =
+            SessionContext::with_config(SessionConfig::new().with_batch_size(batch_size));
+        let task_ctx = session_ctx.task_ctx();
+        let sort_options = vec![SortOptions::default(); on.len()];
+        let join = SortMergeJoinExec::try_new(
+            left,
+            right,
+            on,
+            Some(filter),
+            join_type,
+            sort_options,
+            false,
+        )?;
+        let columns = columns(&join.schema());
+
+        let stream = join.execute(0, task_ctx)?;
+        let batches = common::collect(stream).await?;
+        Ok((columns, batches))
+    }
+
     async fn join_collect_batch_size_equals_two(
         left: Arc<dyn ExecutionPlan>,
         right: Arc<dyn ExecutionPlan>,
@@ -2388,6 +2674,239 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn join_right_semi() -> Result<()> {
+        let left = build_table(
+            ("a1", &vec![10, 20, 30]),
+            ("b1", &vec![4, 5, 6]), // 5 is double on the right
+            ("c1", &vec![70, 80, 90]),
+        );
+        let right = build_table(
+            ("a2", &vec![1, 2, 2, 3]),
+            ("b2", &vec![4, 5, 5, 7]), // 7 does not exist on the left
+            ("c2", &vec![7, 8, 8, 9]),
+        );
+        let on = vec![(
+            Column::new_with_schema("b1", &left.schema())?,
+            Column::new_with_schema("b2", &right.schema())?,
+        )];
+
+        let (_, batches) = join_collect(left, right, on, JoinType::RightSemi).await?;
+        let expected = [
+            "+----+----+----+",
+            "| a2 | b2 | c2 |",
+            "+----+----+----+",
+            "| 1  | 4  | 7  |",
+            "| 2  | 5  | 8  |",
+            "| 2  | 5  | 8  |",
+            "+----+----+----+",
+        ];
+        // The output order is important as SMJ preserves sortedness
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn join_right_anti() -> Result<()> {
+        let left = build_table(
+            ("a1", &vec![10, 20, 30]),
+            ("b1", &vec![4, 5, 6]),
+            ("c1", &vec![70, 80, 90]),
+        );
+        let right = build_table(
+            ("a2", &vec![1, 2, 2, 3, 5]),
+            ("b2", &vec![4, 5, 5, 7, 7]), // 7 does not exist on the left
+            ("c2", &vec![7, 8, 8, 9, 11]),
+        );
+        let on = vec![(
+            Column::new_with_schema("b1", &left.schema())?,
+            Column::new_with_schema("b2", &right.schema())?,
+        )];
+
+        let (_, batches) = join_collect(left, right, on, JoinType::RightAnti).await?;
+        let expected = [
+            "+----+----+----+",
+            "| a2 | b2 | c2 |",
+            "+----+----+----+",
+            "| 3  | 7  | 9  |",
+            "| 5  | 7  | 11 |",
+            "+----+----+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    /// Joins the tables below on `b1 = b2` with the filter `c1 < c2`, with
+    /// several batch sizes, and checks that the results are `expected`.
+    ///
+    /// ```text
+    /// left                right
+    /// a1 | b1 | c1        a2 | b2 | c2
+    /// 1  | 4  | 7         10 | 4  | 70
+    /// 2  | 5  | 8         20 | 5  | 8
+    /// 3  | 5  | 9         30 | 5  | 9
+    /// 4  | 7  | 10        40 | 6  | 100
+    /// ```
+    ///
+    /// Left rows 3 and 4 and right rows 20 and 40 have no joined row that
+    /// passes the filter.
+    async fn check_join_with_filter(join_type: JoinType, expected: &[&str]) {
+        for batch_size in [1, 2, 3, 8192] {
+            let left = build_table(
+                ("a1", &vec![1, 2, 3, 4]),
+                ("b1", &vec![4, 5, 5, 7]),
+                ("c1", &vec![7, 8, 9, 10]),
+            );
+            let right = build_table(
+                ("a2", &vec![10, 20, 30, 40]),
+                ("b2", &vec![4, 5, 5, 6]),
+                ("c2", &vec![70, 8, 9, 100]),
+            );
+            let on = vec![(
+                Column::new_with_schema("b1", &left.schema()).unwrap(),
+                Column::new_with_schema("b2", &right.schema()).unwrap(),
+            )];
+            let filter = JoinFilter::new(
+                Arc::new(BinaryExpr::new(
+                    Arc::new(Column::new("c1", 0)),
+                    Operator::Lt,
+                    Arc::new(Column::new("c2", 1)),
+                )) as Arc<dyn PhysicalExpr>,
+                vec![
+                    ColumnIndex {
+                        index: 2,
+                        side: JoinSide::Left,
+                    },
+                    ColumnIndex {
+                        index: 2,
+                        side: JoinSide::Right,
+                    },
+                ],
+                Schema::new(vec![
+                    Field::new("c1", DataType::Int32, true),
+                    Field::new("c2", DataType::Int32, true),
+                ]),
+            );
+
+            let (_, batches) =
+                join_collect_with_filter(left, right, on, filter, join_type, batch_size)
+                    .await
+                    .unwrap();
+            // The output order is important as SMJ preserves sortedness
+            if join_type == JoinType::Full {
+                assert_batches_sorted_eq!(expected, &batches);
+            } else {
+                assert_batches_eq!(expected, &batches);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn join_inner_with_filter() {
+        let expected = [
+            "+----+----+----+----+----+----+",
+            "| a1 | b1 | c1 | a2 | b2 | c2 |",
+            "+----+----+----+----+----+----+",
+            "| 1  | 4  | 7  | 10 | 4  | 70 |",
+            "| 2  | 5  | 8  | 30 | 5  | 9  |",
+            "+----+----+----+----+----+----+",
+        ];
+        check_join_with_filter(JoinType::Inner, &expected).await;
+    }
+
+    #[tokio::test]
+    async fn join_left_with_filter() {
+        let expected = [
+            "+----+----+----+----+----+----+",
+            "| a1 | b1 | c1 | a2 | b2 | c2 |",
+            "+----+----+----+----+----+----+",
+            "| 1  | 4  | 7  | 10 | 4  | 70 |",
+            "| 2  | 5  | 8  | 30 | 5  | 9  |",
+            "| 3  | 5  | 9  |    |    |    |",
+            "| 4  | 7  | 10 |    |    |    |",
+            "+----+----+----+----+----+----+",
+        ];
+        check_join_with_filter(JoinType::Left, &expected).await;
+    }
+
+    #[tokio::test]
+    async fn join_right_with_filter() {
+        let expected = [
+            "+----+----+----+----+----+-----+",
+            "| a1 | b1 | c1 | a2 | b2 | c2  |",
+            "+----+----+----+----+----+-----+",
+            "| 1  | 4  | 7  | 10 | 4  | 70  |",
+            "|    |    |    | 20 | 5  | 8   |",
+            "| 2  | 5  | 8  | 30 | 5  | 9   |",
+            "|    |    |    | 40 | 6  | 100 |",
+            "+----+----+----+----+----+-----+",
+        ];
+        check_join_with_filter(JoinType::Right, &expected).await;
+    }
+
+    #[tokio::test]
+    async fn join_full_with_filter() {
+        let expected = [
+            "+----+----+----+----+----+-----+",
+            "| a1 | b1 | c1 | a2 | b2 | c2  |",
+            "+----+----+----+----+----+-----+",
+            "|    |    |    | 20 | 5  | 8   |",
+            "|    |    |    | 40 | 6  | 100 |",
+            "| 1  | 4  | 7  | 10 | 4  | 70  |",
+            "| 2  | 5  | 8  | 30 | 5  | 9   |",
+            "| 3  | 5  | 9  |    |    |     |",
+            "| 4  | 7  | 10 |    |    |     |",
+            "+----+----+----+----+----+-----+",
+        ];
+        check_join_with_filter(JoinType::Full, &expected).await;
+    }
+
+    #[tokio::test]
+    async fn join_semi_with_filter() {
+        let expected = [
+            "+----+----+----+",
+            "| a1 | b1 | c1 |",
+            "+----+----+----+",
+            "| 1  | 4  | 7  |",
+            "| 2  | 5  | 8  |",
+            "+----+----+----+",
+        ];
+        check_join_with_filter(JoinType::LeftSemi, &expected).await;
+
+        let expected = [
+            "+----+----+----+",
+            "| a2 | b2 | c2 |",
+            "+----+----+----+",
+            "| 10 | 4  | 70 |",
+            "| 30 | 5  | 9  |",
+            "+----+----+----+",
+        ];
+        check_join_with_filter(JoinType::RightSemi, &expected).await;
+    }
+
+    #[tokio::test]
+    async fn join_anti_with_filter() {
+        let expected = [
+            "+----+----+----+",
+            "| a1 | b1 | c1 |",
+            "+----+----+----+",
+            "| 3  | 5  | 9  |",
+            "| 4  | 7  | 10 |",
+            "+----+----+----+",
+        ];
+        check_join_with_filter(JoinType::LeftAnti, &expected).await;
+
+        let expected = [
+            "+----+----+-----+",
+            "| a2 | b2 | c2  |",
+            "+----+----+-----+",
+            "| 20 | 5  | 8   |",
+            "| 40 | 6  | 100 |",
+            "+----+----+-----+",
+        ];
+        check_join_with_filter(JoinType::RightAnti, &expected).await;
+    }
+
     #[tokio::test]
     async fn overallocation_single_batch() -> Result<()> {
         let left = build_table(
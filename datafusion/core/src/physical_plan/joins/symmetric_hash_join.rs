@@ -66,8 +66,9 @@ use crate::physical_plan::{
         hash_join_utils::SortedFilterExpr,
         utils::{
             build_batch_from_indices, build_join_schema, check_join_is_valid,
-            combine_join_equivalence_properties, partitioned_join_output_partitioning,
-            ColumnIndex, JoinFilter, JoinOn, JoinSide,
+            check_join_type_is_supported, combine_join_equivalence_properties,
+            partitioned_join_output_partitioning, ColumnIndex, JoinFilter, JoinOn,
+            JoinSide,
         },
     },
     metrics::{self, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet},
@@ -285,6 +286,7 @@ impl SymmetricHashJoinExec {
 
         // Check if the join is valid with the given on constraints:
         check_join_is_valid(&left_schema, &right_schema, &on)?;
+        check_join_type_is_supported(join_type, "SymmetricHashJoinExec")?;
 
         // Build the join schema from the left and right schemas:
         let (schema, column_indices) =
@@ -75,6 +75,21 @@ pub fn check_join_is_valid(left: &Schema, right: &Schema, on: JoinOnRef) -> Resu
     check_join_set_is_valid(&left, &right, on)
 }
 
+/// Checks that `join_type` can be evaluated by the join operator `name`.
+/// ASOF joins pick a single match for every left row and are only
+/// evaluated by the [`AsOfJoinExec`](super::AsOfJoinExec).
+pub(crate) fn check_join_type_is_supported(
+    join_type: &JoinType,
+    name: &str,
+) -> Result<()> {
+    if *join_type == JoinType::AsOf {
+        return Err(DataFusionError::NotImplemented(format!(
+            "{name} does not support ASOF joins"
+        )));
+    }
+    Ok(())
+}
+
 /// Checks whether the sets left, right and on compose a valid join.
 /// They are valid whenever their intersection equals the set `on`
 fn check_join_set_is_valid(
@@ -105,9 +120,11 @@ pub fn partitioned_join_output_partitioning(
     left_columns_len: usize,
 ) -> Partitioning {
     match join_type {
-        JoinType::Inner | JoinType::Left | JoinType::LeftSemi | JoinType::LeftAnti => {
-            left_partitioning
-        }
+        JoinType::Inner
+        | JoinType::Left
+        | JoinType::LeftSemi
+        | JoinType::LeftAnti
+        | JoinType::AsOf => left_partitioning,
         JoinType::RightSemi | JoinType::RightAnti => right_partitioning,
         JoinType::Right => {
             adjust_right_output_partitioning(right_partitioning, left_columns_len)
@@ -215,7 +232,11 @@ pub fn combine_join_equivalence_properties(
 ) -> EquivalenceProperties {
     let mut new_properties = EquivalenceProperties::new(schema);
     match join_type {
-        JoinType::Inner | JoinType::Left | JoinType::Full | JoinType::Right => {
+        JoinType::Inner
+        | JoinType::Left
+        | JoinType::Full
+        | JoinType::Right
+        | JoinType::AsOf => {
             new_properties.extend(left_properties.classes().to_vec());
             let new_right_properties = right_properties
                 .classes()
@@ -444,6 +465,7 @@ fn output_join_field(old_field: &Field, join_type: &JoinType, is_left: bool) ->
     let force_nullable = match join_type {
         JoinType::Inner => false,
         JoinType::Left => !is_left, // right input is padded with nulls
+        JoinType::AsOf => !is_left, // right input is padded with nulls
         JoinType::Right => is_left, // left input is padded with nulls
         JoinType::Full => true,     // both inputs can be padded with nulls
         JoinType::LeftSemi => false, // doesn't introduce nulls
@@ -467,7 +489,11 @@ pub fn build_join_schema(
     join_type: &JoinType,
 ) -> (Schema, Vec<ColumnIndex>) {
     let (fields, column_indices): (SchemaBuilder, Vec<ColumnIndex>) = match join_type {
-        JoinType::Inner | JoinType::Left | JoinType::Full | JoinType::Right => {
+        JoinType::Inner
+        | JoinType::Left
+        | JoinType::Full
+        | JoinType::Right
+        | JoinType::AsOf => {
             let left_fields = left
                 .fields()
                 .iter()
@@ -688,6 +714,28 @@ fn estimate_join_cardinality(
             })
         }
 
+        JoinType::AsOf => {
+            // Every left row is joined with at most one right row, so the
+            // cardinality is that of the left side
+            let num_rows = left_stats.num_rows?;
+            let right_col_stats =
+                right_stats.column_statistics?.into_iter().map(|stats| {
+                    ColumnStatistics {
+                        // unmatched rows are padded with nulls
+                        null_count: None,
+                        ..stats
+                    }
+                });
+            Some(PartialJoinStatistics {
+                num_rows,
+                column_statistics: left_stats
+                    .column_statistics?
+                    .into_iter()
+                    .chain(right_col_stats)
+                    .collect(),
+            })
+        }
+
         JoinType::LeftSemi
         | JoinType::RightSemi
         | JoinType::LeftAnti
@@ -985,7 +1033,7 @@ pub(crate) fn adjust_indices_by_join_type(
             // matched
             (left_indices, right_indices)
         }
-        JoinType::Left => {
+        JoinType::Left | JoinType::AsOf => {
             // matched
             (left_indices, right_indices)
             // unmatched left row will be produced in the end of loop, and it has been set in the left visited bitmap
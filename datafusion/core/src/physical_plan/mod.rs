@@ -709,7 +709,9 @@ pub mod limit;
 pub mod memory;
 pub mod metrics;
 pub mod projection;
+pub mod recursive_query;
 pub mod repartition;
+pub mod sample;
 pub mod sorts;
 pub mod stream;
 pub mod streaming;
@@ -719,6 +721,7 @@ pub mod union;
 pub mod unnest;
 pub mod values;
 pub mod windows;
+pub mod work_table;
 
 use crate::physical_plan::repartition::RepartitionExec;
 use crate::physical_plan::sorts::sort_preserving_merge::SortPreservingMergeExec;
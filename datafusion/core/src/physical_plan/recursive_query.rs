@@ -0,0 +1,556 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the recursive query plan, which evaluates the body of a
+//! `WITH RECURSIVE` common table expression
+
+use std::any::Any;
+use std::collections::HashSet;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use arrow::array::BooleanArray;
+use arrow::compute::filter_record_batch;
+use arrow::datatypes::{Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use arrow::row::{OwnedRow, RowConverter, SortField};
+use datafusion_common::tree_node::Transformed;
+use datafusion_common::{DataFusionError, Result};
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::TaskContext;
+use futures::{ready, Stream, StreamExt};
+
+use super::expressions::PhysicalSortExpr;
+use super::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use super::work_table::{WorkTable, WorkTableExec};
+use super::{
+    with_new_children_if_necessary, DisplayAs, DisplayFormatType, Distribution,
+    ExecutionPlan, Partitioning, RecordBatchStream, SendableRecordBatchStream,
+    Statistics,
+};
+
+/// Recursive query execution plan.
+///
+/// This plan has two components: a base part (the static term) and
+/// a dynamic part (the recursive term). The execution will start from
+/// the base, and as long as the previous iteration produced at least
+/// a single new row (taking care of the distinction) the recursive
+/// part will be continuously executed.
+///
+/// Before each execution of the dynamic part, the rows from the previous
+/// iteration will be available in a "working table" (not a real table,
+/// can be only accessed using a [`WorkTableExec`]).
+///
+/// The number of iterations is limited by the
+/// `datafusion.execution.max_recursive_iterations` configuration option.
+#[derive(Debug)]
+pub struct RecursiveQueryExec {
+    /// Name of the recursive query
+    name: String,
+    /// The working table of cte
+    work_table: Arc<WorkTable>,
+    /// The base part (static term)
+    static_term: Arc<dyn ExecutionPlan>,
+    /// The dynamic part (recursive term)
+    recursive_term: Arc<dyn ExecutionPlan>,
+    /// Distinction
+    is_distinct: bool,
+    /// The output schema
+    schema: SchemaRef,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl RecursiveQueryExec {
+    /// Create a new RecursiveQueryExec
+    pub fn try_new(
+        name: String,
+        static_term: Arc<dyn ExecutionPlan>,
+        recursive_term: Arc<dyn ExecutionPlan>,
+        is_distinct: bool,
+    ) -> Result<Self> {
+        let static_schema = static_term.schema();
+        let recursive_schema = recursive_term.schema();
+        if static_schema.fields().len() != recursive_schema.fields().len() {
+            return Err(DataFusionError::Internal(format!(
+                "Static term and recursive term of recursive query {name} have a different number of columns ({} != {})",
+                static_schema.fields().len(),
+                recursive_schema.fields().len()
+            )));
+        }
+        // The recursive term may produce nulls in columns that are not
+        // nullable in the static term
+        let fields = static_schema
+            .fields()
+            .iter()
+            .zip(recursive_schema.fields().iter())
+            .map(|(static_field, recursive_field)| {
+                static_field.as_ref().clone().with_nullable(
+                    static_field.is_nullable() || recursive_field.is_nullable(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let schema = Arc::new(Schema::new_with_metadata(
+            fields,
+            static_schema.metadata().clone(),
+        ));
+
+        // Each recursive query needs its own work table
+        let work_table = Arc::new(WorkTable::new());
+        // Use the same work table for both the WorkTableExec and the recursive term
+        let mut work_table_refs = 0;
+        let recursive_term =
+            assign_work_table(recursive_term, &work_table, &mut work_table_refs)?;
+
+        Ok(RecursiveQueryExec {
+            name,
+            static_term,
+            recursive_term,
+            is_distinct,
+            work_table,
+            schema,
+            metrics: ExecutionPlanMetricsSet::new(),
+        })
+    }
+
+    /// The name of the recursive query
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The static term of the recursive query
+    pub fn static_term(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.static_term
+    }
+
+    /// The recursive term of the recursive query
+    pub fn recursive_term(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.recursive_term
+    }
+
+    /// Whether rows already produced by the query are discarded (`UNION`)
+    /// or not (`UNION ALL`)
+    pub fn is_distinct(&self) -> bool {
+        self.is_distinct
+    }
+}
+
+impl DisplayAs for RecursiveQueryExec {
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "RecursiveQueryExec: name={}, is_distinct={}",
+                    self.name, self.is_distinct
+                )
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for RecursiveQueryExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.static_term.clone(), self.recursive_term.clone()]
+    }
+
+    // Distribution on a recursive query is really tricky to handle.
+    // For now, we are going to use a single partition but in the
+    // future we might find a better way to handle this.
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    // TODO: control these hints and see whether we can
+    // infer some from the child plans (static/recursive terms).
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![false, false]
+    }
+
+    fn benefits_from_input_partitioning(&self) -> bool {
+        false
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![Distribution::SinglePartition, Distribution::SinglePartition]
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        RecursiveQueryExec::try_new(
+            self.name.clone(),
+            children[0].clone(),
+            children[1].clone(),
+            self.is_distinct,
+        )
+        .map(|e| Arc::new(e) as _)
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        // TODO: we might be able to handle multiple partitions in the future.
+        if partition != 0 {
+            return Err(DataFusionError::Internal(format!(
+                "RecursiveQueryExec got an invalid partition {partition} (expected 0)"
+            )));
+        }
+
+        let static_stream = self.static_term.execute(partition, context.clone())?;
+        let baseline_metrics = BaselineMetrics::new(&self.metrics, partition);
+        RecursiveQueryStream::try_new(
+            context,
+            self.name.clone(),
+            self.work_table.clone(),
+            self.recursive_term.clone(),
+            static_stream,
+            self.schema.clone(),
+            self.is_distinct,
+            baseline_metrics,
+        )
+        .map(|s| Box::pin(s) as _)
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+/// The actual logic of the recursive queries happens during the streaming
+/// process. A simplified version of the algorithm is the following:
+///
+/// ```text
+/// buffer = []
+///
+/// while batch := static_stream.next():
+///    buffer.push(batch)
+///    yield batch
+///
+/// while buffer.len() > 0:
+///    work_table.update(buffer.drain())
+///    recursive_stream = recursive_term.execute()
+///    while batch := recursive_stream.next():
+///        buffer.push(batch)
+///        yield batch
+/// ```
+struct RecursiveQueryStream {
+    /// The context used to execute the recursive term
+    task_context: Arc<TaskContext>,
+    /// Name of the recursive query
+    name: String,
+    /// The working table state, representing the self referencing cte table
+    work_table: Arc<WorkTable>,
+    /// The dynamic part (recursive term) as is (without being executed)
+    recursive_term: Arc<dyn ExecutionPlan>,
+    /// The static part (static term) as a stream. If the processing of this
+    /// part is completed, then it will be None.
+    static_stream: Option<SendableRecordBatchStream>,
+    /// The dynamic part (recursive term) as a stream. If the processing of this
+    /// part has not started yet, or has been completed, then it will be None.
+    recursive_stream: Option<SendableRecordBatchStream>,
+    /// The schema of the output.
+    schema: SchemaRef,
+    /// In-memory buffer for storing a copy of the current results. Will be
+    /// cleared after each iteration.
+    buffer: Vec<RecordBatch>,
+    /// Size in bytes of the batches in `buffer`
+    buffer_size: usize,
+    /// Size in bytes of the batches handed to the work table in the
+    /// previous iteration
+    work_table_size: usize,
+    /// Tracks the memory used by the buffer and the rows seen so far
+    reservation: MemoryReservation,
+    /// The rows produced so far, if rows must not be repeated (`UNION`)
+    distinct: Option<DistinctRows>,
+    /// Number of times the recursive term has been evaluated
+    iterations: usize,
+    /// Maximum number of times the recursive term may be evaluated
+    max_iterations: usize,
+    /// Metrics.
+    baseline_metrics: BaselineMetrics,
+}
+
+impl RecursiveQueryStream {
+    /// Create a new recursive query stream
+    #[allow(clippy::too_many_arguments)]
+    fn try_new(
+        task_context: Arc<TaskContext>,
+        name: String,
+        work_table: Arc<WorkTable>,
+        recursive_term: Arc<dyn ExecutionPlan>,
+        static_stream: SendableRecordBatchStream,
+        schema: SchemaRef,
+        is_distinct: bool,
+        baseline_metrics: BaselineMetrics,
+    ) -> Result<Self> {
+        let max_iterations = task_context
+            .session_config()
+            .options()
+            .execution
+            .max_recursive_iterations;
+        let reservation = MemoryConsumer::new(format!("RecursiveQueryStream[{name}]"))
+            .register(task_context.memory_pool());
+        let distinct = if is_distinct {
+            Some(DistinctRows::try_new(&schema)?)
+        } else {
+            None
+        };
+        Ok(Self {
+            task_context,
+            name,
+            work_table,
+            recursive_term,
+            static_stream: Some(static_stream),
+            recursive_stream: None,
+            schema,
+            buffer: vec![],
+            buffer_size: 0,
+            work_table_size: 0,
+            reservation,
+            distinct,
+            iterations: 0,
+            max_iterations,
+            baseline_metrics,
+        })
+    }
+
+    /// Push a clone of the given batch to the in memory buffer, and return
+    /// the rows that should be emitted (if any).
+    fn push_batch(&mut self, batch: RecordBatch) -> Result<Option<RecordBatch>> {
+        let _timer = self.baseline_metrics.elapsed_compute().timer();
+
+        // The batches of the recursive term carry its own field names
+        let batch = RecordBatch::try_new(self.schema.clone(), batch.columns().to_vec())?;
+        let batch = match self.distinct.as_mut() {
+            Some(distinct) => {
+                let (batch, seen_size) = distinct.retain_new_rows(&batch)?;
+                self.reservation.try_grow(seen_size)?;
+                batch
+            }
+            None => batch,
+        };
+        if batch.num_rows() == 0 {
+            return Ok(None);
+        }
+
+        let batch_size = batch.get_array_memory_size();
+        self.reservation.try_grow(batch_size)?;
+        self.buffer_size += batch_size;
+        self.buffer.push(batch.clone());
+        Ok(Some(batch))
+    }
+
+    /// Start the next iteration of the recursive term, handing the rows
+    /// produced by the previous iteration to the work table.
+    fn start_iteration(&mut self) -> Result<()> {
+        self.iterations += 1;
+        if self.iterations > self.max_iterations {
+            return Err(DataFusionError::Execution(format!(
+                "Recursive query {} exceeded the maximum number of iterations ({}), \
+                 see datafusion.execution.max_recursive_iterations",
+                self.name, self.max_iterations
+            )));
+        }
+
+        // The batches of the previous iteration have been consumed by now
+        self.reservation.shrink(self.work_table_size);
+        self.work_table_size = std::mem::take(&mut self.buffer_size);
+        self.work_table.update(std::mem::take(&mut self.buffer));
+
+        // Operators such as joins cache state between executions, so the
+        // recursive term is rebuilt before being executed again
+        let recursive_plan = reset_plan_states(self.recursive_term.clone())?;
+        self.recursive_stream =
+            Some(recursive_plan.execute(0, self.task_context.clone())?);
+        Ok(())
+    }
+
+    fn poll_next_inner(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<RecordBatch>>> {
+        loop {
+            let stream = match (&mut self.static_stream, &mut self.recursive_stream) {
+                (Some(stream), _) | (None, Some(stream)) => stream,
+                (None, None) => {
+                    // The previous iteration produced no new rows: we are done
+                    if self.buffer.is_empty() {
+                        return Poll::Ready(None);
+                    }
+                    if let Err(e) = self.start_iteration() {
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                    continue;
+                }
+            };
+
+            match ready!(stream.poll_next_unpin(cx)) {
+                Some(Ok(batch)) => match self.push_batch(batch) {
+                    Ok(Some(batch)) => return Poll::Ready(Some(Ok(batch))),
+                    Ok(None) => continue,
+                    Err(e) => return Poll::Ready(Some(Err(e))),
+                },
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => {
+                    if self.static_stream.take().is_none() {
+                        self.recursive_stream = None;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Stream for RecursiveQueryStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let poll = self.poll_next_inner(cx);
+        self.baseline_metrics.record_poll(poll)
+    }
+}
+
+impl RecordBatchStream for RecursiveQueryStream {
+    /// Get the schema
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+/// The rows produced so far by a recursive query using `UNION`, which
+/// does not repeat rows
+struct DistinctRows {
+    converter: RowConverter,
+    seen: HashSet<OwnedRow>,
+}
+
+impl DistinctRows {
+    fn try_new(schema: &Schema) -> Result<Self> {
+        let sort_fields = schema
+            .fields()
+            .iter()
+            .map(|field| SortField::new(field.data_type().clone()))
+            .collect();
+        Ok(Self {
+            converter: RowConverter::new(sort_fields)?,
+            seen: HashSet::new(),
+        })
+    }
+
+    /// Filter out the rows of `batch` that have been seen before, returning
+    /// the remaining rows and the number of bytes added to the seen rows.
+    fn retain_new_rows(&mut self, batch: &RecordBatch) -> Result<(RecordBatch, usize)> {
+        let rows = self.converter.convert_columns(batch.columns())?;
+        let mut seen_size = 0;
+        let mask = rows
+            .iter()
+            .map(|row| {
+                let row_size = row.as_ref().len();
+                let is_new = self.seen.insert(row.owned());
+                if is_new {
+                    seen_size += row_size;
+                }
+                Some(is_new)
+            })
+            .collect::<BooleanArray>();
+        Ok((filter_record_batch(batch, &mask)?, seen_size))
+    }
+}
+
+/// Connect the [`WorkTableExec`] of `plan` to `work_table`, checking that the
+/// work table is referenced at most once.
+///
+/// Recursive queries nested in `plan` are left untouched, as they own their
+/// work tables.
+fn assign_work_table(
+    plan: Arc<dyn ExecutionPlan>,
+    work_table: &Arc<WorkTable>,
+    work_table_refs: &mut usize,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    if let Some(exec) = plan.as_any().downcast_ref::<WorkTableExec>() {
+        *work_table_refs += 1;
+        if *work_table_refs > 1 {
+            return Err(DataFusionError::NotImplemented(format!(
+                "Recursive query {} can only reference itself once",
+                exec.name()
+            )));
+        }
+        return Ok(Arc::new(exec.with_work_table(work_table.clone())));
+    }
+    if plan.as_any().is::<RecursiveQueryExec>() {
+        return Ok(plan);
+    }
+
+    let children = plan.children();
+    if children.is_empty() {
+        return Ok(plan);
+    }
+    let children = children
+        .into_iter()
+        .map(|child| assign_work_table(child, work_table, work_table_refs))
+        .collect::<Result<Vec<_>>>()?;
+    with_new_children_if_necessary(plan, children).map(Transformed::into)
+}
+
+/// Some plans will change their internal states after execution, making them
+/// unable to be executed again. This function uses `ExecutionPlan::with_new_children`
+/// to fork a new plan with initial states.
+///
+/// The [`WorkTableExec`] is left untouched, as it is connected to the work
+/// table of the recursive query.
+fn reset_plan_states(plan: Arc<dyn ExecutionPlan>) -> Result<Arc<dyn ExecutionPlan>> {
+    if plan.as_any().is::<WorkTableExec>() {
+        return Ok(plan);
+    }
+    let children = plan.children();
+    if children.is_empty() {
+        return Ok(plan);
+    }
+    let children = children
+        .into_iter()
+        .map(reset_plan_states)
+        .collect::<Result<Vec<_>>>()?;
+    plan.with_new_children(children)
+}
@@ -0,0 +1,519 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the TABLESAMPLE plan
+
+use std::any::Any;
+use std::hash::Hasher;
+use std::sync::Arc;
+
+use crate::physical_plan::{
+    DisplayFormatType, Distribution, EquivalenceProperties, ExecutionPlan, Partitioning,
+};
+
+use super::common::AbortOnDropSingle;
+use super::expressions::PhysicalSortExpr;
+use super::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use super::stream::RecordBatchStreamAdapter;
+use super::{DisplayAs, SendableRecordBatchStream, Statistics};
+
+use arrow::array::{BooleanArray, UInt32Array};
+use arrow::compute::{concat_batches, filter_record_batch, take};
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::{DataFusionError, Result};
+use datafusion_execution::TaskContext;
+use datafusion_expr::{SampleMethod, SampleSize};
+use datafusion_physical_expr::OrderingEquivalenceProperties;
+
+use futures::future::try_join_all;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use log::trace;
+use twox_hash::XxHash64;
+
+/// Returns a random subset of the rows of its input.
+///
+/// A [`SampleSize::Percent`] sample keeps each row with the given
+/// probability, based on a hash of the number of the row in its partition
+/// and a seed derived from `seed` and the partition. The partitions are
+/// sampled independently, so the partitioning and the order of the input
+/// are kept. As the batches of the input are not stable blocks, `SYSTEM`
+/// samples rows like `BERNOULLI` here; block samples of files are taken by
+/// the scans instead.
+///
+/// A [`SampleSize::Rows`] sample returns exactly the requested number of
+/// rows (or all rows if the input is smaller) in a single partition. Each
+/// input partition is read in its own task, which keeps the requested
+/// number of rows with the smallest random keys, and the rows with the
+/// smallest keys of all partitions are returned, which is a uniform sample.
+///
+/// The same `seed` and input, partitioned the same way, always produce
+/// the same sample.
+#[derive(Debug)]
+pub struct SampleExec {
+    /// Input execution plan
+    input: Arc<dyn ExecutionPlan>,
+    /// How rows are selected
+    method: SampleMethod,
+    /// How many rows to select
+    size: SampleSize,
+    /// Seed for the random number generator
+    seed: u64,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+}
+
+impl SampleExec {
+    /// Create a new SampleExec
+    pub fn new(
+        input: Arc<dyn ExecutionPlan>,
+        method: SampleMethod,
+        size: SampleSize,
+        seed: u64,
+    ) -> Self {
+        Self {
+            input,
+            method,
+            size,
+            seed,
+            metrics: ExecutionPlanMetricsSet::new(),
+        }
+    }
+
+    /// Input execution plan
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// How rows are selected
+    pub fn method(&self) -> SampleMethod {
+        self.method
+    }
+
+    /// How many rows to select
+    pub fn size(&self) -> SampleSize {
+        self.size
+    }
+
+    /// Seed for the random number generator
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns true if the partitions of the input are sampled
+    /// independently, so that the rows are returned in the order of the
+    /// input, rather than the rows of all partitions being compared
+    fn maintains_input_order(&self) -> bool {
+        matches!(self.size, SampleSize::Percent(_))
+    }
+}
+
+impl DisplayAs for SampleExec {
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "SampleExec: method={}, size={}, seed={}",
+                    self.method, self.size, self.seed
+                )
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for SampleExec {
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![Distribution::UnspecifiedDistribution]
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        if self.maintains_input_order() {
+            self.input.output_partitioning()
+        } else {
+            Partitioning::UnknownPartitioning(1)
+        }
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![self.maintains_input_order()]
+    }
+
+    fn benefits_from_input_partitioning(&self) -> bool {
+        false
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        if self.maintains_input_order() {
+            self.input.output_ordering()
+        } else {
+            None
+        }
+    }
+
+    fn equivalence_properties(&self) -> EquivalenceProperties {
+        self.input.equivalence_properties()
+    }
+
+    fn ordering_equivalence_properties(&self) -> OrderingEquivalenceProperties {
+        if self.maintains_input_order() {
+            self.input.ordering_equivalence_properties()
+        } else {
+            OrderingEquivalenceProperties::new(self.schema())
+        }
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(SampleExec::new(
+            children[0].clone(),
+            self.method,
+            self.size,
+            self.seed,
+        )))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        trace!("Start SampleExec::execute for partition: {}", partition);
+        let schema = self.schema();
+        let baseline_metrics = BaselineMetrics::new(&self.metrics, partition);
+
+        match self.size {
+            SampleSize::Percent(percent) => {
+                let input = self.input.execute(partition, context)?;
+                let probability = percent / 100.0;
+                let seed = partition_seed(self.seed, partition);
+                // the number of the first row of the next batch
+                let mut offset = 0;
+                let stream = input
+                    .map(move |batch| {
+                        let batch = batch?;
+                        let _timer = baseline_metrics.elapsed_compute().timer();
+                        let num_rows = batch.num_rows() as u64;
+                        let mask: BooleanArray = (offset..offset + num_rows)
+                            .map(|row| Some(row_value(seed, row) < probability))
+                            .collect();
+                        offset += num_rows;
+                        let batch = filter_record_batch(&batch, &mask)?;
+                        baseline_metrics.record_output(batch.num_rows());
+                        Ok(batch)
+                    })
+                    .try_filter(|batch| futures::future::ready(batch.num_rows() > 0));
+                Ok(Box::pin(RecordBatchStreamAdapter::new(schema, stream)))
+            }
+            SampleSize::Rows(rows) => {
+                if 0 != partition {
+                    return Err(DataFusionError::Internal(format!(
+                        "SampleExec invalid partition {partition}"
+                    )));
+                }
+
+                let input_partitions = self.input.output_partitioning().partition_count();
+                let tasks = (0..input_partitions)
+                    .map(|i| {
+                        let input = self.input.execute(i, context.clone())?;
+                        let sample = SmallestKeys::new(
+                            schema.clone(),
+                            rows as usize,
+                            partition_seed(self.seed, i),
+                        );
+                        let elapsed_compute = baseline_metrics.elapsed_compute().clone();
+                        let fut = input.try_fold(sample, move |mut sample, batch| {
+                            let _timer = elapsed_compute.timer();
+                            let result = sample.insert(&batch).map(|_| sample);
+                            futures::future::ready(result)
+                        });
+                        Ok(AbortOnDropSingle::new(tokio::spawn(fut)))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                let capacity = rows as usize;
+                let stream = stream::once(async move {
+                    let mut sample = SmallestKeys::new(schema, capacity, 0);
+                    for partition_sample in try_join_all(tasks).await.map_err(|e| {
+                        DataFusionError::Context(
+                            "Join Error".to_string(),
+                            Box::new(DataFusionError::External(Box::new(e))),
+                        )
+                    })? {
+                        let _timer = baseline_metrics.elapsed_compute().timer();
+                        sample.merge(partition_sample?)?;
+                    }
+                    let batch = sample.finish();
+                    baseline_metrics.record_output(batch.num_rows());
+                    Ok(batch)
+                })
+                .try_filter(|batch| futures::future::ready(batch.num_rows() > 0));
+                Ok(Box::pin(RecordBatchStreamAdapter::new(
+                    self.schema(),
+                    stream,
+                )))
+            }
+        }
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Statistics {
+        let input_rows = self.input.statistics().num_rows;
+        let num_rows = match self.size {
+            SampleSize::Percent(percent) => {
+                input_rows.map(|rows| (rows as f64 * percent / 100.0).round() as usize)
+            }
+            SampleSize::Rows(rows) => {
+                Some(input_rows.map_or(rows as usize, |input| input.min(rows as usize)))
+            }
+        };
+        Statistics {
+            num_rows,
+            is_exact: false,
+            ..Default::default()
+        }
+    }
+}
+
+/// Derives the seed of the sample of a partition from the seed of the
+/// sample, so that the partitions select different rows
+fn partition_seed(seed: u64, partition: usize) -> u64 {
+    let mut hasher = XxHash64::with_seed(seed);
+    hasher.write(&(partition as u64).to_le_bytes());
+    hasher.finish()
+}
+
+/// Maps a fixed hash of `seed` and the number of a row uniformly to [0, 1),
+/// so that the same rows are selected by every build and platform
+fn row_value(seed: u64, row: u64) -> f64 {
+    let mut hasher = XxHash64::with_seed(seed);
+    hasher.write(&row.to_le_bytes());
+    // map the top 53 bits of the hash uniformly to [0, 1)
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Keeps the rows with the smallest random keys of a stream of record
+/// batches ("bottom-k" sampling), which are a uniform sample of the rows.
+/// Unlike a reservoir, the samples of several streams are merged by keeping
+/// the rows with the smallest keys of all of them.
+struct SmallestKeys {
+    schema: SchemaRef,
+    capacity: usize,
+    seed: u64,
+    /// Number of input rows seen so far
+    seen: u64,
+    /// The rows currently kept
+    rows: RecordBatch,
+    /// The keys of `rows`
+    keys: Vec<f64>,
+}
+
+impl SmallestKeys {
+    fn new(schema: SchemaRef, capacity: usize, seed: u64) -> Self {
+        let rows = RecordBatch::new_empty(schema.clone());
+        Self {
+            schema,
+            capacity,
+            seed,
+            seen: 0,
+            rows,
+            keys: vec![],
+        }
+    }
+
+    fn insert(&mut self, batch: &RecordBatch) -> Result<()> {
+        let num_rows = batch.num_rows() as u64;
+        let keys = (self.seen..self.seen + num_rows)
+            .map(|row| row_value(self.seed, row))
+            .collect();
+        self.seen += num_rows;
+        self.keep_smallest(batch, keys)
+    }
+
+    fn merge(&mut self, other: SmallestKeys) -> Result<()> {
+        self.keep_smallest(&other.rows, other.keys)
+    }
+
+    /// Keeps the rows with the `capacity` smallest keys of the kept rows
+    /// and `batch`, whose rows have the given keys
+    fn keep_smallest(&mut self, batch: &RecordBatch, keys: Vec<f64>) -> Result<()> {
+        // Indices into the concatenation of the kept rows followed by `batch`
+        let kept = self.rows.num_rows();
+        let mut keys: Vec<(f64, u32)> = self
+            .keys
+            .iter()
+            .chain(keys.iter())
+            .enumerate()
+            .map(|(idx, key)| (*key, idx as u32))
+            .collect();
+        if keys.len() > self.capacity {
+            keys.select_nth_unstable_by(self.capacity, |a, b| a.0.total_cmp(&b.0));
+            keys.truncate(self.capacity);
+        }
+        // keep the rows in the order of the input
+        keys.sort_unstable_by_key(|(_, idx)| *idx);
+        if keys.len() == kept && keys.iter().all(|(_, idx)| (*idx as usize) < kept) {
+            return Ok(());
+        }
+
+        let combined = concat_batches(&self.schema, [&self.rows, batch])?;
+        let indices: UInt32Array = keys.iter().map(|(_, idx)| Some(*idx)).collect();
+        let columns = combined
+            .columns()
+            .iter()
+            .map(|c| take(c.as_ref(), &indices, None))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        self.rows = RecordBatch::try_new(self.schema.clone(), columns)?;
+        self.keys = keys.into_iter().map(|(key, _)| key).collect();
+        Ok(())
+    }
+
+    fn finish(self) -> RecordBatch {
+        self.rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physical_plan::common;
+    use crate::physical_plan::memory::MemoryExec;
+    use crate::prelude::SessionContext;
+    use crate::test::make_partition;
+
+    fn memory_exec(partitions: usize) -> Result<Arc<dyn ExecutionPlan>> {
+        let batches: Vec<_> = (0..10).map(|_| make_partition(100)).collect();
+        let schema = batches[0].schema();
+        let partitions = vec![batches; partitions];
+        Ok(Arc::new(MemoryExec::try_new(&partitions, schema, None)?))
+    }
+
+    async fn sampled_rows(
+        method: SampleMethod,
+        size: SampleSize,
+        seed: u64,
+    ) -> Result<usize> {
+        let session_ctx = SessionContext::new();
+        let sample = SampleExec::new(memory_exec(1)?, method, size, seed);
+        let batches = common::collect(sample.execute(0, session_ctx.task_ctx())?).await?;
+        Ok(batches.iter().map(|batch| batch.num_rows()).sum())
+    }
+
+    #[tokio::test]
+    async fn sample_percent() -> Result<()> {
+        for method in [SampleMethod::Bernoulli, SampleMethod::System] {
+            assert_eq!(sampled_rows(method, SampleSize::Percent(0.0), 1).await?, 0);
+            assert_eq!(
+                sampled_rows(method, SampleSize::Percent(100.0), 1).await?,
+                1000
+            );
+
+            let rows = sampled_rows(method, SampleSize::Percent(50.0), 1).await?;
+            assert!(rows > 0 && rows < 1000, "unexpected {rows}");
+            // same seed, same sample
+            assert_eq!(
+                sampled_rows(method, SampleSize::Percent(50.0), 1).await?,
+                rows
+            );
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn sample_rows() -> Result<()> {
+        for rows in [0, 1, 150, 1000] {
+            let sampled =
+                sampled_rows(SampleMethod::Bernoulli, SampleSize::Rows(rows), 7).await?;
+            assert_eq!(sampled, rows as usize);
+        }
+        let sampled =
+            sampled_rows(SampleMethod::Bernoulli, SampleSize::Rows(5000), 7).await?;
+        assert_eq!(sampled, 1000);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn sample_percent_of_partitions() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let sample = SampleExec::new(
+            memory_exec(4)?,
+            SampleMethod::Bernoulli,
+            SampleSize::Percent(30.0),
+            3,
+        );
+        assert_eq!(sample.output_partitioning().partition_count(), 4);
+
+        let mut samples = vec![];
+        for partition in 0..4 {
+            let batches =
+                common::collect(sample.execute(partition, session_ctx.task_ctx())?)
+                    .await?;
+            samples.push(concat_batches(&sample.schema(), &batches)?);
+        }
+        assert!(samples[0].num_rows() > 0);
+        // the partitions are equal, but their samples are not
+        assert_ne!(samples[0], samples[1]);
+
+        // same seed, same sample
+        let batches = common::collect(sample.execute(1, session_ctx.task_ctx())?).await?;
+        assert_eq!(concat_batches(&sample.schema(), &batches)?, samples[1]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn sample_rows_of_partitions() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let sample = SampleExec::new(
+            memory_exec(4)?,
+            SampleMethod::Bernoulli,
+            SampleSize::Rows(50),
+            3,
+        );
+        assert_eq!(sample.output_partitioning().partition_count(), 1);
+
+        let batches = common::collect(sample.execute(0, session_ctx.task_ctx())?).await?;
+        let rows = concat_batches(&sample.schema(), &batches)?;
+        assert_eq!(rows.num_rows(), 50);
+
+        // same seed, same sample
+        let batches = common::collect(sample.execute(0, session_ctx.task_ctx())?).await?;
+        assert_eq!(concat_batches(&sample.schema(), &batches)?, rows);
+        Ok(())
+    }
+}
@@ -333,7 +333,8 @@ impl Debug for ExternalSorter {
     }
 }
 
-fn sort_batch(
+/// Sorts `batch` by `expressions`, keeping only the first `fetch` rows if set
+pub(crate) fn sort_batch(
     batch: &RecordBatch,
     expressions: &[PhysicalSortExpr],
     fetch: Option<usize>,
@@ -369,7 +370,9 @@ async fn spill_sorted_batches(
     }
 }
 
-fn read_spill_as_stream(
+/// Reads the batches spilled to `path` as a stream. The file is removed
+/// once the stream has been read.
+pub(crate) fn read_spill_as_stream(
     path: NamedTempFile,
     schema: SchemaRef,
 ) -> Result<SendableRecordBatchStream> {
@@ -404,7 +407,8 @@ fn write_sorted(
     Ok(())
 }
 
-fn read_spill(sender: Sender<Result<RecordBatch>>, path: &Path) -> Result<()> {
+/// Reads the batches spilled to `path`, sending them to `sender`
+pub(crate) fn read_spill(sender: Sender<Result<RecordBatch>>, path: &Path) -> Result<()> {
     let file = BufReader::new(File::open(path)?);
     let reader = FileReader::try_new(file, None)?;
     for batch in reader {
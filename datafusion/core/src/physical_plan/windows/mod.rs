@@ -56,6 +56,10 @@ use datafusion_physical_expr::{OrderingEquivalenceProperties, PhysicalSortRequir
 pub use window_agg_exec::WindowAggExec;
 
 /// Create a physical expression for window function
+///
+/// `ignore_nulls` corresponds to `IGNORE NULLS` and is only supported by
+/// `LEAD`, `LAG`, `FIRST_VALUE`, `LAST_VALUE` and `NTH_VALUE`
+#[allow(clippy::too_many_arguments)]
 pub fn create_window_expr(
     fun: &WindowFunction,
     name: String,
@@ -64,7 +68,13 @@ pub fn create_window_expr(
     order_by: &[PhysicalSortExpr],
     window_frame: Arc<WindowFrame>,
     input_schema: &Schema,
+    ignore_nulls: bool,
 ) -> Result<Arc<dyn WindowExpr>> {
+    if ignore_nulls && !fun.supports_ignore_nulls() {
+        return Err(DataFusionError::NotImplemented(format!(
+            "IGNORE NULLS is not supported for window function {fun}"
+        )));
+    }
     Ok(match fun {
         WindowFunction::AggregateFunction(fun) => {
             let aggregate = aggregates::create_aggregate_expr(
@@ -83,7 +93,7 @@ pub fn create_window_expr(
             )
         }
         WindowFunction::BuiltInWindowFunction(fun) => Arc::new(BuiltInWindowExpr::new(
-            create_built_in_window_expr(fun, args, input_schema, name)?,
+            create_built_in_window_expr(fun, args, input_schema, name, ignore_nulls)?,
             partition_by,
             order_by,
             window_frame,
@@ -158,6 +168,7 @@ fn create_built_in_window_expr(
     args: &[Arc<dyn PhysicalExpr>],
     input_schema: &Schema,
     name: String,
+    ignore_nulls: bool,
 ) -> Result<Arc<dyn BuiltInWindowFunctionExpr>> {
     Ok(match fun {
         BuiltInWindowFunction::RowNumber => Arc::new(RowNumber::new(name)),
@@ -183,7 +194,14 @@ fn create_built_in_window_expr(
                 .map(|v| v.try_into())
                 .and_then(|v| v.ok());
             let default_value = get_scalar_value_from_args(args, 2)?;
-            Arc::new(lag(name, data_type, arg, shift_offset, default_value))
+            Arc::new(lag(
+                name,
+                data_type,
+                arg,
+                shift_offset,
+                default_value,
+                ignore_nulls,
+            ))
         }
         BuiltInWindowFunction::Lead => {
             let arg = args[0].clone();
@@ -192,7 +210,14 @@ fn create_built_in_window_expr(
                 .map(|v| v.try_into())
                 .and_then(|v| v.ok());
             let default_value = get_scalar_value_from_args(args, 2)?;
-            Arc::new(lead(name, data_type, arg, shift_offset, default_value))
+            Arc::new(lead(
+                name,
+                data_type,
+                arg,
+                shift_offset,
+                default_value,
+                ignore_nulls,
+            ))
         }
         BuiltInWindowFunction::NthValue => {
             let arg = args[0].clone();
@@ -203,17 +228,17 @@ fn create_built_in_window_expr(
                 .map_err(|e| DataFusionError::Execution(format!("{e:?}")))?;
             let n: u32 = n as u32;
             let data_type = args[0].data_type(input_schema)?;
-            Arc::new(NthValue::nth(name, arg, data_type, n)?)
+            Arc::new(NthValue::nth(name, arg, data_type, n, ignore_nulls)?)
         }
         BuiltInWindowFunction::FirstValue => {
             let arg = args[0].clone();
             let data_type = args[0].data_type(input_schema)?;
-            Arc::new(NthValue::first(name, arg, data_type))
+            Arc::new(NthValue::first(name, arg, data_type, ignore_nulls))
         }
         BuiltInWindowFunction::LastValue => {
             let arg = args[0].clone();
             let data_type = args[0].data_type(input_schema)?;
-            Arc::new(NthValue::last(name, arg, data_type))
+            Arc::new(NthValue::last(name, arg, data_type, ignore_nulls))
         }
     })
 }
@@ -559,6 +584,7 @@ mod tests {
                 &[],
                 Arc::new(WindowFrame::new(false)),
                 schema.as_ref(),
+                false,
             )?],
             input,
             schema.clone(),
@@ -593,6 +619,7 @@ mod tests {
                     &[],
                     Arc::new(WindowFrame::new(false)),
                     schema.as_ref(),
+                    false,
                 )?,
                 create_window_expr(
                     &WindowFunction::AggregateFunction(AggregateFunction::Max),
@@ -602,6 +629,7 @@ mod tests {
                     &[],
                     Arc::new(WindowFrame::new(false)),
                     schema.as_ref(),
+                    false,
                 )?,
                 create_window_expr(
                     &WindowFunction::AggregateFunction(AggregateFunction::Min),
@@ -611,6 +639,7 @@ mod tests {
                     &[],
                     Arc::new(WindowFrame::new(false)),
                     schema.as_ref(),
+                    false,
                 )?,
             ],
             input,
@@ -659,6 +688,7 @@ mod tests {
                 &[],
                 Arc::new(WindowFrame::new(false)),
                 schema.as_ref(),
+                false,
             )?],
             blocking_exec,
             schema,
@@ -0,0 +1,221 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the work table query plan, which reads the rows produced by the
+//! previous iteration of a recursive query
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::{DataFusionError, Result};
+use datafusion_execution::TaskContext;
+use parking_lot::Mutex;
+
+use super::expressions::PhysicalSortExpr;
+use super::memory::MemoryStream;
+use super::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning, SendableRecordBatchStream,
+    Statistics,
+};
+
+/// The name is from PostgreSQL's terminology.
+/// See <https://wiki.postgresql.org/wiki/CTEReadme#How_Recursion_Works>
+///
+/// This table holds the rows produced by the latest iteration of a recursive
+/// query, to be consumed by the next evaluation of its recursive term.
+#[derive(Debug)]
+pub(super) struct WorkTable {
+    batches: Mutex<Option<Vec<RecordBatch>>>,
+}
+
+impl WorkTable {
+    /// Create a new, unset work table.
+    pub(super) fn new() -> Self {
+        Self {
+            batches: Mutex::new(None),
+        }
+    }
+
+    /// Take the previously written batches from the work table.
+    /// This will be called by the [`WorkTableExec`] when it is executed.
+    fn take(&self) -> Result<Vec<RecordBatch>> {
+        self.batches.lock().take().ok_or_else(|| {
+            DataFusionError::Internal("Unexpected empty work table".to_string())
+        })
+    }
+
+    /// Write the results of a recursive query iteration to the work table.
+    pub(super) fn update(&self, batches: Vec<RecordBatch>) {
+        self.batches.lock().replace(batches);
+    }
+}
+
+/// Reads the rows produced by the previous iteration of a recursive query.
+///
+/// The batches are taken from the work table shared with the enclosing
+/// [`RecursiveQueryExec`], which writes the output of each iteration before
+/// re-evaluating its recursive term.
+///
+/// [`RecursiveQueryExec`]: super::recursive_query::RecursiveQueryExec
+#[derive(Debug)]
+pub struct WorkTableExec {
+    /// Name of the recursive query
+    name: String,
+    /// The schema of the stream
+    schema: SchemaRef,
+    /// The columns of the work table read by this plan
+    projection: Option<Vec<usize>>,
+    /// The work table
+    work_table: Arc<WorkTable>,
+}
+
+impl WorkTableExec {
+    /// Create a new execution plan for a worktable exec.
+    pub fn new(name: String, schema: SchemaRef, projection: Option<Vec<usize>>) -> Self {
+        Self {
+            name,
+            schema,
+            projection,
+            work_table: Arc::new(WorkTable::new()),
+        }
+    }
+
+    /// The name of the recursive query this plan reads from
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Create a copy of this plan that reads from `work_table`.
+    pub(super) fn with_work_table(&self, work_table: Arc<WorkTable>) -> Self {
+        Self {
+            name: self.name.clone(),
+            schema: self.schema.clone(),
+            projection: self.projection.clone(),
+            work_table,
+        }
+    }
+}
+
+impl DisplayAs for WorkTableExec {
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "WorkTableExec: name={}", self.name)
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for WorkTableExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        // Keep the link to the work table of the enclosing recursive query
+        Ok(self.clone())
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        // WorkTable streams must be the plan base.
+        if partition != 0 {
+            return Err(DataFusionError::Internal(format!(
+                "WorkTableExec got an invalid partition {partition} (expected 0)"
+            )));
+        }
+
+        // The batches carry the schema of the recursive query, so they are
+        // re-labelled with the (possibly projected) schema of this plan
+        let batches = self
+            .work_table
+            .take()?
+            .into_iter()
+            .map(|batch| {
+                let batch = match self.projection.as_ref() {
+                    Some(projection) => batch.project(projection)?,
+                    None => batch,
+                };
+                Ok(RecordBatch::try_new(
+                    self.schema.clone(),
+                    batch.columns().to_vec(),
+                )?)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Box::pin(MemoryStream::try_new(
+            batches,
+            self.schema.clone(),
+            None,
+        )?))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{ArrayRef, Int32Array};
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    #[test]
+    fn test_work_table() {
+        let work_table = WorkTable::new();
+        // take from empty work_table
+        assert!(work_table.take().is_err());
+
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let array: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3, 4, 5]));
+        let batch = RecordBatch::try_new(schema, vec![array]).unwrap();
+        work_table.update(vec![batch.clone()]);
+        // take from work_table
+        assert_eq!(work_table.take().unwrap(), vec![batch]);
+        // the work table is empty again
+        assert!(work_table.take().is_err());
+    }
+}
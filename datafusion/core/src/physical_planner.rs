@@ -17,20 +17,30 @@
 
 //! Planner for [`LogicalPlan`] to [`ExecutionPlan`]
 
+use crate::datasource::file_format::arrow::ArrowFormat;
+use crate::datasource::file_format::csv::CsvFormat;
+use crate::datasource::file_format::file_type::FileCompressionType;
+use crate::datasource::file_format::json::JsonFormat;
+use crate::datasource::file_format::parquet::{
+    apply_writer_options, writer_properties_builder, ParquetFormat,
+};
+use crate::datasource::file_format::{FileFormat, FileWriterMode};
+use crate::datasource::listing::ListingTableUrl;
+use crate::datasource::physical_plan::{with_block_sample, BlockSample, FileSinkConfig};
 use crate::datasource::source_as_provider;
 use crate::execution::context::{ExecutionProps, SessionState};
 use crate::logical_expr::utils::generate_sort_key;
 use crate::logical_expr::{
-    Aggregate, EmptyRelation, Join, Projection, Sort, SubqueryAlias, TableScan, Unnest,
-    Window,
+    Aggregate, EmptyRelation, Join, JoinType, Projection, Sort, Subquery, SubqueryAlias,
+    TableScan, Unnest, Window,
 };
 use crate::logical_expr::{
     CrossJoin, Expr, LogicalPlan, Partitioning as LogicalPartitioning, PlanType,
-    Repartition, Union, UserDefinedLogicalNode,
+    RecursiveQuery, Repartition, Union, UserDefinedLogicalNode,
 };
 use datafusion_common::display::ToStringifiedPlan;
 
-use crate::logical_expr::{Limit, Values};
+use crate::logical_expr::{Limit, Prepare, Sample, SampleMethod, SampleSize, Values};
 use crate::physical_expr::create_physical_expr;
 use crate::physical_optimizer::optimizer::PhysicalOptimizerRule;
 use crate::physical_plan::aggregates::{AggregateExec, AggregateMode, PhysicalGroupBy};
@@ -40,10 +50,12 @@ use crate::physical_plan::expressions::{Column, PhysicalSortExpr};
 use crate::physical_plan::filter::FilterExec;
 use crate::physical_plan::joins::HashJoinExec;
 use crate::physical_plan::joins::SortMergeJoinExec;
-use crate::physical_plan::joins::{CrossJoinExec, NestedLoopJoinExec};
+use crate::physical_plan::joins::{AsOfJoinExec, CrossJoinExec, NestedLoopJoinExec};
 use crate::physical_plan::limit::{GlobalLimitExec, LocalLimitExec};
 use crate::physical_plan::projection::ProjectionExec;
+use crate::physical_plan::recursive_query::RecursiveQueryExec;
 use crate::physical_plan::repartition::RepartitionExec;
+use crate::physical_plan::sample::SampleExec;
 use crate::physical_plan::sorts::sort::SortExec;
 use crate::physical_plan::unnest::UnnestExec;
 use crate::physical_plan::windows::{
@@ -69,7 +81,8 @@ use datafusion_expr::expr::{
 };
 use datafusion_expr::expr_rewriter::{unalias, unnormalize_cols};
 use datafusion_expr::logical_plan::builder::wrap_projection_for_join_if_necessary;
-use datafusion_expr::{DmlStatement, StringifiedPlan, WriteOp};
+use datafusion_expr::utils::find_asof_match_condition;
+use datafusion_expr::{CopyTo, DmlStatement, OutputFileFormat, StringifiedPlan, WriteOp};
 use datafusion_expr::{WindowFrame, WindowFrameBound};
 use datafusion_physical_expr::expressions::Literal;
 use datafusion_sql::utils::window_expr_common_partition_keys;
@@ -79,8 +92,90 @@ use itertools::{multiunzip, Itertools};
 use log::{debug, trace};
 use std::collections::HashMap;
 use std::fmt::Write;
+use std::str::FromStr;
 use std::sync::Arc;
 
+/// Creates the [`FileFormat`] used to write the output of a `COPY` statement
+/// in `file_format`, configured with the user provided `options`.
+///
+/// Parquet writer settings that are not given in `options` are taken from
+/// the session configuration.
+fn create_copy_file_format(
+    session_state: &SessionState,
+    file_format: OutputFileFormat,
+    options: &[(String, String)],
+) -> Result<Arc<dyn FileFormat>> {
+    let unsupported_option = |key: &str| {
+        Err(DataFusionError::Plan(format!(
+            "Unsupported option for {file_format} output: {key}"
+        )))
+    };
+    let parse_compression = |value: &str| {
+        FileCompressionType::from_str(value).map_err(|_| {
+            DataFusionError::Plan(format!(
+                "Unsupported compression for {file_format} output: {value}"
+            ))
+        })
+    };
+
+    let format: Arc<dyn FileFormat> = match file_format {
+        OutputFileFormat::CSV => {
+            let mut format = CsvFormat::default();
+            for (key, value) in options {
+                format = match key.as_str() {
+                    "header" => format.with_has_header(value.parse().map_err(|_| {
+                        DataFusionError::Plan(format!(
+                            "Invalid value \"{value}\" for option header, expected true or false"
+                        ))
+                    })?),
+                    "delimiter" => match value.as_bytes() {
+                        [delimiter] => format.with_delimiter(*delimiter),
+                        _ => {
+                            return Err(DataFusionError::Plan(format!(
+                                "Invalid delimiter \"{value}\", expected a single character"
+                            )))
+                        }
+                    },
+                    "compression" => {
+                        format.with_file_compression_type(parse_compression(value)?)
+                    }
+                    _ => return unsupported_option(key),
+                };
+            }
+            Arc::new(format)
+        }
+        OutputFileFormat::JSON => {
+            let mut format = JsonFormat::default();
+            for (key, value) in options {
+                format = match key.as_str() {
+                    "compression" => {
+                        format.with_file_compression_type(parse_compression(value)?)
+                    }
+                    _ => return unsupported_option(key),
+                };
+            }
+            Arc::new(format)
+        }
+        OutputFileFormat::PARQUET => {
+            // options given in the statement override the session configuration
+            let builder = apply_writer_options(
+                writer_properties_builder(session_state.config_options())?,
+                options,
+            )?;
+            Arc::new(
+                ParquetFormat::default().with_writer_properties(Some(builder.build())),
+            )
+        }
+        OutputFileFormat::ARROW => {
+            if let Some((key, _)) = options.first() {
+                return unsupported_option(key);
+            }
+            Arc::new(ArrowFormat)
+        }
+    };
+    Ok(format)
+}
+
 fn create_function_physical_name(
     fun: &str,
     distinct: bool,
@@ -547,6 +642,73 @@ impl DefaultPhysicalPlanner {
                         )));
                     }
                 }
+                LogicalPlan::Dml(DmlStatement {
+                    table_name,
+                    op: op @ (WriteOp::Delete | WriteOp::Update),
+                    input,
+                    ..
+                }) => {
+                    let name = table_name.table();
+                    let schema = session_state.schema_for_ref(table_name)?;
+                    if let Some(provider) = schema.table(name).await {
+                        let input_exec = self.create_initial_plan(input, session_state).await?;
+                        if *op == WriteOp::Delete {
+                            provider.delete_from(session_state, input_exec).await
+                        } else {
+                            provider.update(session_state, input_exec).await
+                        }
+                    } else {
+                        return Err(DataFusionError::Execution(format!(
+                            "Table '{table_name}' does not exist"
+                        )));
+                    }
+                }
+                LogicalPlan::Dml(DmlStatement {
+                    table_name,
+                    op: WriteOp::Merge,
+                    input,
+                    ..
+                }) => {
+                    let name = table_name.table();
+                    let schema = session_state.schema_for_ref(table_name)?;
+                    if let Some(provider) = schema.table(name).await {
+                        let input_exec = self.create_initial_plan(input, session_state).await?;
+                        provider.merge_into(session_state, input_exec).await
+                    } else {
+                        return Err(DataFusionError::Execution(format!(
+                            "Table '{table_name}' does not exist"
+                        )));
+                    }
+                }
+                LogicalPlan::Copy(CopyTo {
+                    input,
+                    output_url,
+                    file_format,
+                    single_file_output,
+                    options,
+                }) => {
+                    let input_exec = self.create_initial_plan(input, session_state).await?;
+
+                    // The target is only resolved here, it is created by the
+                    // sink when the data is written
+                    let table_path = ListingTableUrl::parse_write_target(
+                        output_url,
+                        !*single_file_output,
+                    )?;
+                    let config = FileSinkConfig {
+                        object_store_url: table_path.object_store(),
+                        file_groups: vec![],
+                        table_paths: vec![table_path],
+                        output_schema: Arc::new(input.schema().as_ref().into()),
+                        table_partition_cols: vec![],
+                        writer_mode: FileWriterMode::PutMultipart,
+                        single_file_output: *single_file_output,
+                    };
+
+                    let format =
+                        create_copy_file_format(session_state, *file_format, options)?;
+                    format.create_writer_physical_plan(input_exec, session_state, config).await
+                }
                 LogicalPlan::Values(Values {
                     values,
                     schema,
@@ -877,6 +1039,15 @@ impl DefaultPhysicalPlanner {
                         .with_fetch(*fetch);
                     Ok(Arc::new(new_sort))
                 }
+                LogicalPlan::Join(Join { right, .. })
+                | LogicalPlan::CrossJoin(CrossJoin { right, .. })
+                    if !right.all_out_ref_exprs().is_empty() =>
+                {
+                    Err(DataFusionError::NotImplemented(
+                        "LATERAL subquery that can not be decorrelated into a join"
+                            .to_string(),
+                    ))
+                }
                 LogicalPlan::Join(Join {
                     left,
                     right,
@@ -973,6 +1144,38 @@ impl DefaultPhysicalPlanner {
                         })
                         .collect::<Result<join_utils::JoinOn>>()?;
 
+                    if *join_type == JoinType::AsOf {
+                        // the filter of an ASOF join is its match condition
+                        let filter = filter.as_ref().ok_or_else(|| {
+                            DataFusionError::Plan("ASOF join requires a match condition".to_string())
+                        })?;
+                        let (left_expr, op, right_expr) = find_asof_match_condition(
+                            filter,
+                            left_df_schema.clone(),
+                            right_df_schema.clone(),
+                        )?;
+                        let left_expr = create_physical_expr(
+                            &left_expr,
+                            left_df_schema,
+                            &physical_left.schema(),
+                            session_state.execution_props(),
+                        )?;
+                        let right_expr = create_physical_expr(
+                            &right_expr,
+                            right_df_schema,
+                            &physical_right.schema(),
+                            session_state.execution_props(),
+                        )?;
+                        return Ok(Arc::new(AsOfJoinExec::try_new(
+                            physical_left,
+                            physical_right,
+                            join_on,
+                            left_expr,
+                            op,
+                            right_expr,
+                        )?));
+                    }
+
                     let join_filter = match filter {
                         Some(expr) => {
                             // Extract columns from filter expression and saved in a HashSet
@@ -1046,20 +1249,16 @@ impl DefaultPhysicalPlanner {
                     {
                         // Use SortMergeJoin if hash join is not preferred
                         // Sort-Merge join support currently is experimental
-                        if join_filter.is_some() {
-                            // TODO SortMergeJoinExec need to support join filter
-                            Err(DataFusionError::NotImplemented("SortMergeJoinExec does not support join_filter now.".to_string()))
-                        } else {
-                            let join_on_len = join_on.len();
-                            Ok(Arc::new(SortMergeJoinExec::try_new(
-                                physical_left,
-                                physical_right,
-                                join_on,
-                                *join_type,
-                                vec![SortOptions::default(); join_on_len],
-                                null_equals_null,
-                            )?))
-                        }
+                        let join_on_len = join_on.len();
+                        Ok(Arc::new(SortMergeJoinExec::try_new(
+                            physical_left,
+                            physical_right,
+                            join_on,
+                            join_filter,
+                            *join_type,
+                            vec![SortOptions::default(); join_on_len],
+                            null_equals_null,
+                        )?))
                     } else if session_state.config().target_partitions() > 1
                         && session_state.config().repartition_joins()
                         && prefer_hash_join {
@@ -1096,7 +1295,9 @@ impl DefaultPhysicalPlanner {
                     let [left, right]: [Arc<dyn ExecutionPlan>; 2] = left_right.try_into().map_err(|_| DataFusionError::Internal("`create_initial_plan_multi` is broken".to_string()))?;
                     Ok(Arc::new(CrossJoinExec::new(left, right)))
                 }
-                LogicalPlan::Subquery(_) => todo!(),
+                LogicalPlan::Subquery(Subquery { subquery, .. }) => {
+                    self.create_initial_plan(subquery, session_state).await
+                }
                 LogicalPlan::EmptyRelation(EmptyRelation {
                     produce_one_row,
                     schema,
@@ -1125,6 +1326,23 @@ impl DefaultPhysicalPlanner {
 
                     Ok(Arc::new(GlobalLimitExec::new(input, *skip, *fetch)))
                 }
+                LogicalPlan::Sample(Sample { input, method, size, seed }) => {
+                    let input = self.create_initial_plan(input, session_state).await?;
+                    let seed = seed.unwrap_or_else(rand::random);
+
+                    // A SYSTEM sample directly over a file scan skips whole
+                    // files (or parquet row groups) instead of reading them
+                    let scan = match (method, size) {
+                        (SampleMethod::System, SampleSize::Percent(percent)) => {
+                            with_block_sample(&input, BlockSample::new(percent / 100.0, seed))
+                        }
+                        _ => None,
+                    };
+
+                    Ok(scan.unwrap_or_else(|| {
+                        Arc::new(SampleExec::new(input, *method, *size, seed))
+                    }))
+                }
                 LogicalPlan::Unnest(Unnest { input, column, schema }) => {
                     let input = self.create_initial_plan(input, session_state).await?;
                     let column_exec = schema.index_of_column(column)
@@ -1132,6 +1350,11 @@ impl DefaultPhysicalPlanner {
                     let schema = SchemaRef::new(schema.as_ref().to_owned().into());
                     Ok(Arc::new(UnnestExec::new(input, column_exec, schema)))
                 }
+                LogicalPlan::RecursiveQuery(RecursiveQuery { name, static_term, recursive_term, is_distinct }) => {
+                    let static_term = self.create_initial_plan(static_term, session_state).await?;
+                    let recursive_term = self.create_initial_plan(recursive_term, session_state).await?;
+                    Ok(Arc::new(RecursiveQueryExec::try_new(name.clone(), static_term, recursive_term, *is_distinct)?))
+                }
                 LogicalPlan::Ddl(ddl) => {
                     // There is no default plan for DDl statements --
                     // it must be handled at a higher level (so that
@@ -1142,13 +1365,14 @@ impl DefaultPhysicalPlanner {
                         format!("Unsupported logical plan: {name}")
                     ))
                 }
-                LogicalPlan::Prepare(_) => {
-                    // There is no default plan for "PREPARE" -- it must be
-                    // handled at a higher level (so that the appropriate
-                    // statement can be prepared)
-                    Err(DataFusionError::NotImplemented(
-                        "Unsupported logical plan: Prepare".to_string(),
-                    ))
+                LogicalPlan::Prepare(Prepare { input, .. }) => {
+                    // The statement itself is prepared at a higher level (see
+                    // `SessionContext::execute_logical_plan`), executing
+                    // "PREPARE" produces no rows
+                    Ok(Arc::new(EmptyExec::new(
+                        false,
+                        SchemaRef::new(input.schema().as_ref().to_owned().into()),
+                    )))
                 }
                 LogicalPlan::Dml(_) => {
                     // DataFusion is a read-only query engine, but also a library, so consumers may implement this
@@ -1512,6 +1736,7 @@ pub fn create_window_expr_with_name(
             partition_by,
             order_by,
             window_frame,
+            ignore_nulls,
         }) => {
             let args = args
                 .iter()
@@ -1562,6 +1787,7 @@ pub fn create_window_expr_with_name(
                 &order_by,
                 window_frame,
                 physical_input_schema,
+                *ignore_nulls,
             )
         }
         other => Err(DataFusionError::Plan(format!(
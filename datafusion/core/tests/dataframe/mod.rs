@@ -178,6 +178,7 @@ async fn test_count_wildcard_on_window() -> Result<()> {
                 start_bound: WindowFrameBound::Preceding(ScalarValue::UInt32(Some(6))),
                 end_bound: WindowFrameBound::Following(ScalarValue::UInt32(Some(2))),
             },
+            false,
         ))])?
         .explain(false, false)?
         .collect()
@@ -375,6 +376,86 @@ async fn join() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn asof_join() -> Result<()> {
+    let ctx = SessionContext::new();
+    let trades = RecordBatch::try_from_iter(vec![
+        (
+            "sym",
+            Arc::new(StringArray::from(vec!["a", "a", "b", "b"])) as ArrayRef,
+        ),
+        (
+            "ts",
+            Arc::new(Int32Array::from(vec![1, 5, 2, 9])) as ArrayRef,
+        ),
+    ])?;
+    let quotes = RecordBatch::try_from_iter(vec![
+        (
+            "sym",
+            Arc::new(StringArray::from(vec!["a", "a", "b"])) as ArrayRef,
+        ),
+        ("ts", Arc::new(Int32Array::from(vec![0, 4, 3])) as ArrayRef),
+        (
+            "price",
+            Arc::new(Int32Array::from(vec![10, 11, 20])) as ArrayRef,
+        ),
+    ])?;
+    ctx.register_batch("trades", trades)?;
+    ctx.register_batch("quotes", quotes)?;
+
+    let df = ctx
+        .table("trades")
+        .await?
+        .join(
+            ctx.table("quotes").await?,
+            JoinType::AsOf,
+            &["sym"],
+            &["sym"],
+            Some(col("trades.ts").gt_eq(col("quotes.ts"))),
+        )?
+        .select(vec![
+            col("trades.sym"),
+            col("trades.ts"),
+            col("quotes.ts"),
+            col("price"),
+        ])?
+        .sort(vec![
+            col("trades.sym").sort(true, true),
+            col("trades.ts").sort(true, true),
+        ])?;
+
+    let expected = [
+        "+-----+----+----+-------+",
+        "| sym | ts | ts | price |",
+        "+-----+----+----+-------+",
+        "| a   | 1  | 0  | 10    |",
+        "| a   | 5  | 4  | 11    |",
+        "| b   | 2  |    |       |",
+        "| b   | 9  | 3  | 20    |",
+        "+-----+----+----+-------+",
+    ];
+    assert_batches_eq!(expected, &df.collect().await?);
+
+    // the join filter is the match condition and is required
+    let err = ctx
+        .table("trades")
+        .await?
+        .join(
+            ctx.table("quotes").await?,
+            JoinType::AsOf,
+            &["sym"],
+            &["sym"],
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Error during planning: ASOF join requires a match condition"
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn sort_on_unprojected_columns() -> Result<()> {
     let schema = Schema::new(vec![
@@ -795,6 +876,34 @@ async fn test_grouping_set_array_agg_with_overflow() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn sample() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+    let batch = RecordBatch::try_new(
+        schema,
+        vec![Arc::new(Int32Array::from_iter_values(0..100))],
+    )?;
+    let df = SessionContext::new().read_batch(batch)?;
+
+    assert_eq!(df.clone().sample(0.0, None)?.count().await?, 0);
+    assert_eq!(df.clone().sample(1.0, None)?.count().await?, 100);
+
+    // the same seed returns the same rows
+    let sample = df.clone().sample(0.5, Some(42))?.collect().await?;
+    let rows: usize = sample.iter().map(|batch| batch.num_rows()).sum();
+    assert!(rows > 0 && rows < 100, "unexpected sample size {rows}");
+    let formatted = pretty_format_batches(&sample)?.to_string();
+    let again = df.clone().sample(0.5, Some(42))?.collect().await?;
+    assert_eq!(pretty_format_batches(&again)?.to_string(), formatted);
+
+    let err = df.sample(1.5, None).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Error during planning: Sample fraction must be between 0 and 1, got 1.5"
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn join_with_alias_filter() -> Result<()> {
     let join_ctx = create_join_context()?;
@@ -1151,6 +1260,75 @@ async fn unnest_aggregate_columns() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn pivot() -> Result<()> {
+    let ctx = SessionContext::new();
+    let df = ctx
+        .sql(
+            "SELECT * FROM (VALUES (1, 'a', 10), (1, 'b', 20), (2, 'a', 30), (2, 'a', 40)) \
+             AS t(id, key, value)",
+        )
+        .await?;
+    let values = vec![ScalarValue::from("a"), ScalarValue::from("b")];
+    let results = df
+        .pivot(sum(col("value")), "key", values)?
+        .collect()
+        .await?;
+    let expected = [
+        "+----+----+----+",
+        "| id | a  | b  |",
+        "+----+----+----+",
+        "| 1  | 10 | 20 |",
+        "| 2  | 70 |    |",
+        "+----+----+----+",
+    ];
+    assert_batches_sorted_eq!(expected, &results);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn unpivot() -> Result<()> {
+    let ctx = SessionContext::new();
+    let df = ctx
+        .sql("SELECT * FROM (VALUES (1, 10, 100), (2, NULL, 200)) AS t(id, x, y)")
+        .await?;
+
+    let results = df
+        .clone()
+        .unpivot("value", "name", vec!["x", "y"], false)?
+        .collect()
+        .await?;
+    let expected = [
+        "+----+------+-------+",
+        "| id | name | value |",
+        "+----+------+-------+",
+        "| 1  | x    | 10    |",
+        "| 1  | y    | 100   |",
+        "| 2  | y    | 200   |",
+        "+----+------+-------+",
+    ];
+    assert_batches_sorted_eq!(expected, &results);
+
+    let results = df
+        .unpivot("value", "name", vec!["x", "y"], true)?
+        .collect()
+        .await?;
+    let expected = [
+        "+----+------+-------+",
+        "| id | name | value |",
+        "+----+------+-------+",
+        "| 1  | x    | 10    |",
+        "| 1  | y    | 100   |",
+        "| 2  | x    |       |",
+        "| 2  | y    | 200   |",
+        "+----+------+-------+",
+    ];
+    assert_batches_sorted_eq!(expected, &results);
+
+    Ok(())
+}
+
 async fn create_test_table(name: &str) -> Result<DataFrame> {
     let schema = Arc::new(Schema::new(vec![
         Field::new("a", DataType::Utf8, false),
@@ -262,6 +262,45 @@ async fn simple_udaf() -> Result<()> {
     Ok(())
 }
 
+/// tests that `CREATE FUNCTION` cannot shadow registered UDFs and UDAFs
+#[tokio::test]
+async fn create_function_with_udf_name() -> Result<()> {
+    let ctx = SessionContext::new();
+    ctx.register_udf(create_udf(
+        "my_one",
+        vec![],
+        Arc::new(DataType::Int32),
+        Volatility::Immutable,
+        Arc::new(move |_| Ok(ColumnarValue::Scalar(ScalarValue::Int32(Some(1))))),
+    ));
+    ctx.register_udaf(create_udaf(
+        "my_avg",
+        vec![DataType::Float64],
+        Arc::new(DataType::Float64),
+        Volatility::Immutable,
+        Arc::new(|_| {
+            Ok(Box::new(AvgAccumulator::try_new(
+                &DataType::Float64,
+                &DataType::Float64,
+            )?))
+        }),
+        Arc::new(vec![DataType::UInt64, DataType::Float64]),
+    ));
+
+    for name in ["my_one", "my_avg"] {
+        for create in ["CREATE", "CREATE OR REPLACE"] {
+            let sql = format!("{create} FUNCTION {name}(x INT) RETURN x");
+            let err = plan_and_collect(&ctx, &sql).await.unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!("Execution error: Function '{name}' already exists.")
+            );
+        }
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn udaf_as_window_func() -> Result<()> {
     #[derive(Debug)]
@@ -39,10 +39,18 @@ pub enum AggregateFunction {
     Avg,
     /// median
     Median,
+    /// Exact continuous percentile (ordered-set aggregate)
+    PercentileCont,
+    /// Exact discrete percentile (ordered-set aggregate)
+    PercentileDisc,
+    /// Most frequent value (ordered-set aggregate)
+    Mode,
     /// Approximate aggregate function
     ApproxDistinct,
     /// array_agg
     ArrayAgg,
+    /// string_agg
+    StringAgg,
     /// first_value
     FirstValue,
     /// last_value
@@ -91,8 +99,12 @@ impl AggregateFunction {
             Max => "MAX",
             Avg => "AVG",
             Median => "MEDIAN",
+            PercentileCont => "PERCENTILE_CONT",
+            PercentileDisc => "PERCENTILE_DISC",
+            Mode => "MODE",
             ApproxDistinct => "APPROX_DISTINCT",
             ArrayAgg => "ARRAY_AGG",
+            StringAgg => "STRING_AGG",
             FirstValue => "FIRST_VALUE",
             LastValue => "LAST_VALUE",
             Variance => "VARIANCE",
@@ -137,8 +149,12 @@ impl FromStr for AggregateFunction {
             "mean" => AggregateFunction::Avg,
             "median" => AggregateFunction::Median,
             "min" => AggregateFunction::Min,
+            "mode" => AggregateFunction::Mode,
+            "percentile_cont" => AggregateFunction::PercentileCont,
+            "percentile_disc" => AggregateFunction::PercentileDisc,
             "sum" => AggregateFunction::Sum,
             "array_agg" => AggregateFunction::ArrayAgg,
+            "string_agg" | "listagg" => AggregateFunction::StringAgg,
             "first_value" => AggregateFunction::FirstValue,
             "last_value" => AggregateFunction::LastValue,
             // statistical
@@ -234,6 +250,7 @@ impl AggregateFunction {
                 coerced_data_types[0].clone(),
                 true,
             )))),
+            AggregateFunction::StringAgg => Ok(coerced_data_types[0].clone()),
             AggregateFunction::ApproxPercentileCont => Ok(coerced_data_types[0].clone()),
             AggregateFunction::ApproxPercentileContWithWeight => {
                 Ok(coerced_data_types[0].clone())
@@ -241,6 +258,15 @@ impl AggregateFunction {
             AggregateFunction::ApproxMedian | AggregateFunction::Median => {
                 Ok(coerced_data_types[0].clone())
             }
+            AggregateFunction::PercentileCont => Ok(percentile_return_type(
+                &avg_return_type(&coerced_data_types[0])?,
+                &coerced_data_types[1],
+            )),
+            AggregateFunction::PercentileDisc => Ok(percentile_return_type(
+                &coerced_data_types[0],
+                &coerced_data_types[1],
+            )),
+            AggregateFunction::Mode => Ok(coerced_data_types[0].clone()),
             AggregateFunction::Grouping => Ok(DataType::Int32),
             AggregateFunction::FirstValue | AggregateFunction::LastValue => {
                 Ok(coerced_data_types[0].clone())
@@ -279,7 +305,12 @@ impl AggregateFunction {
             AggregateFunction::Count => Signature::variadic_any(Volatility::Immutable),
             AggregateFunction::ApproxDistinct
             | AggregateFunction::Grouping
-            | AggregateFunction::ArrayAgg => Signature::any(1, Volatility::Immutable),
+            | AggregateFunction::ArrayAgg
+            | AggregateFunction::Mode => Signature::any(1, Volatility::Immutable),
+            // the value and the fraction(s), which are checked by the type coercion
+            AggregateFunction::PercentileCont | AggregateFunction::PercentileDisc => {
+                Signature::any(2, Volatility::Immutable)
+            }
             AggregateFunction::Min | AggregateFunction::Max => {
                 let valid = STRINGS
                     .iter()
@@ -299,6 +330,10 @@ impl AggregateFunction {
             AggregateFunction::BoolAnd | AggregateFunction::BoolOr => {
                 Signature::uniform(1, vec![DataType::Boolean], Volatility::Immutable)
             }
+            // the separator is coerced to Utf8
+            AggregateFunction::StringAgg => {
+                Signature::uniform(2, STRINGS.to_vec(), Volatility::Immutable)
+            }
             AggregateFunction::Avg
             | AggregateFunction::Sum
             | AggregateFunction::Variance
@@ -23,6 +23,7 @@ use crate::{
     conditional_expressions, struct_expressions, Signature, TypeSignature, Volatility,
 };
 use arrow::datatypes::{DataType, Field, Fields, IntervalUnit, TimeUnit};
+use datafusion_common::utils::map_entries_field;
 use datafusion_common::{DataFusionError, Result};
 use std::collections::HashMap;
 use std::fmt;
@@ -160,6 +161,30 @@ pub enum BuiltinScalarFunction {
     /// trim_array
     TrimArray,
 
+    // map functions
+    /// construct a map from a list of keys and a list of values
+    MakeMap,
+    /// map_extract
+    MapExtract,
+    /// map_keys
+    MapKeys,
+    /// map_values
+    MapValues,
+
+    // json functions
+    /// json_array_length
+    JsonArrayLength,
+    /// json_as_struct
+    JsonAsStruct,
+    /// json_contains
+    JsonContains,
+    /// json_get
+    JsonGet,
+    /// json_get_int
+    JsonGetInt,
+    /// json_get_str
+    JsonGetStr,
+
     // string functions
     /// ascii
     Ascii,
@@ -362,6 +387,16 @@ impl BuiltinScalarFunction {
             BuiltinScalarFunction::Cardinality => Volatility::Immutable,
             BuiltinScalarFunction::MakeArray => Volatility::Immutable,
             BuiltinScalarFunction::TrimArray => Volatility::Immutable,
+            BuiltinScalarFunction::MakeMap => Volatility::Immutable,
+            BuiltinScalarFunction::MapExtract => Volatility::Immutable,
+            BuiltinScalarFunction::MapKeys => Volatility::Immutable,
+            BuiltinScalarFunction::MapValues => Volatility::Immutable,
+            BuiltinScalarFunction::JsonArrayLength => Volatility::Immutable,
+            BuiltinScalarFunction::JsonAsStruct => Volatility::Immutable,
+            BuiltinScalarFunction::JsonContains => Volatility::Immutable,
+            BuiltinScalarFunction::JsonGet => Volatility::Immutable,
+            BuiltinScalarFunction::JsonGetInt => Volatility::Immutable,
+            BuiltinScalarFunction::JsonGetStr => Volatility::Immutable,
             BuiltinScalarFunction::Ascii => Volatility::Immutable,
             BuiltinScalarFunction::BitLength => Volatility::Immutable,
             BuiltinScalarFunction::Btrim => Volatility::Immutable,
@@ -471,6 +506,32 @@ impl BuiltinScalarFunction {
         }
     }
 
+    /// Checks that the first argument of a json function is a string and that
+    /// its remaining arguments, which make up a path into the json value, are
+    /// object keys or array indexes.
+    fn validate_json_path_types(self, input_expr_types: &[DataType]) -> Result<()> {
+        match &input_expr_types[0] {
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Null => {}
+            other => {
+                return Err(DataFusionError::Plan(format!(
+                    "The {self} function requires a string as its first argument, got {other}"
+                )))
+            }
+        }
+        for data_type in &input_expr_types[1..] {
+            match data_type {
+                DataType::Utf8 | DataType::LargeUtf8 | DataType::Null => {}
+                data_type if data_type.is_integer() => {}
+                other => {
+                    return Err(DataFusionError::Plan(format!(
+                        "The path of the {self} function can only contain strings and integers, got {other}"
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Returns the output [`DataType`] of this function
     pub fn return_type(self, input_expr_types: &[DataType]) -> Result<DataType> {
         use DataType::*;
@@ -568,6 +629,61 @@ impl BuiltinScalarFunction {
                     "The {self} function can only accept list as the first argument"
                 ))),
             },
+            BuiltinScalarFunction::MakeMap => {
+                match (&input_expr_types[0], &input_expr_types[1]) {
+                    (List(keys), List(values)) => Ok(Map(
+                        map_entries_field(
+                            keys.data_type().clone(),
+                            values.data_type().clone(),
+                        ),
+                        false,
+                    )),
+                    _ => Err(DataFusionError::Plan(format!(
+                        "The {self} function can only accept a list of keys and a list of values"
+                    ))),
+                }
+            }
+            BuiltinScalarFunction::MapKeys
+            | BuiltinScalarFunction::MapValues
+            | BuiltinScalarFunction::MapExtract => match &input_expr_types[0] {
+                Map(field, _) => match field.data_type() {
+                    Struct(fields) if fields.len() == 2 => {
+                        let item_type = match self {
+                            BuiltinScalarFunction::MapKeys => fields[0].data_type(),
+                            _ => fields[1].data_type(),
+                        };
+                        Ok(List(Arc::new(Field::new("item", item_type.clone(), true))))
+                    }
+                    other => Err(DataFusionError::Internal(format!(
+                        "Map entries must be a struct of key and value, got {other:?}"
+                    ))),
+                },
+                _ => Err(DataFusionError::Plan(format!(
+                    "The {self} function can only accept map as the first argument"
+                ))),
+            },
+            BuiltinScalarFunction::JsonArrayLength
+            | BuiltinScalarFunction::JsonContains
+            | BuiltinScalarFunction::JsonGet
+            | BuiltinScalarFunction::JsonGetInt
+            | BuiltinScalarFunction::JsonGetStr => {
+                self.validate_json_path_types(input_expr_types)?;
+                match self {
+                    BuiltinScalarFunction::JsonArrayLength => Ok(UInt64),
+                    BuiltinScalarFunction::JsonContains => Ok(Boolean),
+                    BuiltinScalarFunction::JsonGetInt => Ok(Int64),
+                    _ => Ok(Utf8),
+                }
+            }
+            BuiltinScalarFunction::JsonAsStruct => {
+                self.validate_json_path_types(&input_expr_types[..1])?;
+                match &input_expr_types[1] {
+                    Struct(fields) => Ok(Struct(fields.clone())),
+                    other => Err(DataFusionError::Plan(format!(
+                        "The {self} function requires the schema of the struct as its second argument, got {other}"
+                    ))),
+                }
+            }
             BuiltinScalarFunction::Ascii => Ok(Int32),
             BuiltinScalarFunction::BitLength => {
                 utf8_to_int_type(&input_expr_types[0], "bit_length")
@@ -839,6 +955,19 @@ impl BuiltinScalarFunction {
                 Signature::variadic_any(self.volatility())
             }
             BuiltinScalarFunction::TrimArray => Signature::any(2, self.volatility()),
+            BuiltinScalarFunction::MakeMap => Signature::any(2, self.volatility()),
+            BuiltinScalarFunction::MapExtract => Signature::any(2, self.volatility()),
+            BuiltinScalarFunction::MapKeys | BuiltinScalarFunction::MapValues => {
+                Signature::any(1, self.volatility())
+            }
+            BuiltinScalarFunction::JsonArrayLength
+            | BuiltinScalarFunction::JsonContains
+            | BuiltinScalarFunction::JsonGet
+            | BuiltinScalarFunction::JsonGetInt
+            | BuiltinScalarFunction::JsonGetStr => {
+                Signature::variadic_any(self.volatility())
+            }
+            BuiltinScalarFunction::JsonAsStruct => Signature::any(2, self.volatility()),
             BuiltinScalarFunction::Struct => Signature::variadic(
                 struct_expressions::SUPPORTED_STRUCT_TYPES.to_vec(),
                 self.volatility(),
@@ -1324,6 +1453,20 @@ fn aliases(func: &BuiltinScalarFunction) -> &'static [&'static str] {
         BuiltinScalarFunction::Cardinality => &["cardinality"],
         BuiltinScalarFunction::MakeArray => &["make_array", "make_list"],
         BuiltinScalarFunction::TrimArray => &["trim_array"],
+
+        // map functions
+        BuiltinScalarFunction::MakeMap => &["map"],
+        BuiltinScalarFunction::MapExtract => &["map_extract"],
+        BuiltinScalarFunction::MapKeys => &["map_keys"],
+        BuiltinScalarFunction::MapValues => &["map_values"],
+
+        // json functions
+        BuiltinScalarFunction::JsonArrayLength => &["json_array_length"],
+        BuiltinScalarFunction::JsonAsStruct => &["json_as_struct"],
+        BuiltinScalarFunction::JsonContains => &["json_contains"],
+        BuiltinScalarFunction::JsonGet => &["json_get"],
+        BuiltinScalarFunction::JsonGetInt => &["json_get_int"],
+        BuiltinScalarFunction::JsonGetStr => &["json_get_str"],
     }
 }
 
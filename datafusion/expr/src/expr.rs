@@ -474,6 +474,9 @@ pub struct WindowFunction {
     pub order_by: Vec<Expr>,
     /// Window frame
     pub window_frame: window_frame::WindowFrame,
+    /// Whether NULL values of the argument are skipped (`IGNORE NULLS`)
+    /// rather than taken into account (`RESPECT NULLS`, the default)
+    pub ignore_nulls: bool,
 }
 
 impl WindowFunction {
@@ -484,6 +487,7 @@ impl WindowFunction {
         partition_by: Vec<Expr>,
         order_by: Vec<Expr>,
         window_frame: window_frame::WindowFrame,
+        ignore_nulls: bool,
     ) -> Self {
         Self {
             fun,
@@ -491,6 +495,7 @@ impl WindowFunction {
             partition_by,
             order_by,
             window_frame,
+            ignore_nulls,
         }
     }
 }
@@ -1031,8 +1036,12 @@ impl fmt::Display for Expr {
                 partition_by,
                 order_by,
                 window_frame,
+                ignore_nulls,
             }) => {
                 fmt_function(f, &fun.to_string(), false, args, true)?;
+                if *ignore_nulls {
+                    write!(f, " IGNORE NULLS")?;
+                }
                 if !partition_by.is_empty() {
                     write!(f, " PARTITION BY [{}]", expr_vec_fmt!(partition_by))?;
                 }
@@ -1346,9 +1355,13 @@ fn create_name(e: &Expr) -> Result<String> {
             window_frame,
             partition_by,
             order_by,
+            ignore_nulls,
         }) => {
             let mut parts: Vec<String> =
                 vec![create_function_name(&fun.to_string(), false, args)?];
+            if *ignore_nulls {
+                parts.push("IGNORE NULLS".to_string());
+            }
             if !partition_by.is_empty() {
                 parts.push(format!("PARTITION BY [{}]", expr_vec_fmt!(partition_by)));
             }
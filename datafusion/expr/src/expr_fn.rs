@@ -29,8 +29,8 @@ use crate::{
     BuiltinScalarFunction, Expr, LogicalPlan, Operator, ReturnTypeFunction,
     ScalarFunctionImplementation, ScalarUDF, Signature, StateTypeFunction, Volatility,
 };
-use arrow::datatypes::DataType;
-use datafusion_common::{Column, Result};
+use arrow::datatypes::{DataType, Fields};
+use datafusion_common::{Column, Result, ScalarValue};
 use std::sync::Arc;
 
 /// Create a column expression based on a qualified or unqualified column name. Will
@@ -655,6 +655,81 @@ scalar_expr!(
     "removes the last n elements from the array."
 );
 
+// map functions
+scalar_expr!(
+    MakeMap,
+    map,
+    keys values,
+    "returns a map built from a list of keys and a list of values."
+);
+scalar_expr!(
+    MapExtract,
+    map_extract,
+    map key,
+    "returns a list containing the value for the given key, or an empty list if the key is not in the map."
+);
+scalar_expr!(
+    MapKeys,
+    map_keys,
+    map,
+    "returns a list of all the keys in the map."
+);
+scalar_expr!(
+    MapValues,
+    map_values,
+    map,
+    "returns a list of all the values in the map."
+);
+
+// json functions
+macro_rules! json_path_expr {
+    ($ENUM:ident, $FUNC:ident, $DOC:expr) => {
+        #[doc = $DOC]
+        ///
+        /// `path` is a list of object keys and array indexes leading to the
+        /// value in `json`
+        pub fn $FUNC(json: Expr, path: Vec<Expr>) -> Expr {
+            let mut args = path;
+            args.insert(0, json);
+            Expr::ScalarFunction(ScalarFunction::new(BuiltinScalarFunction::$ENUM, args))
+        }
+    };
+}
+
+json_path_expr!(
+    JsonArrayLength,
+    json_array_length,
+    "returns the length of the json array at `path`."
+);
+json_path_expr!(
+    JsonContains,
+    json_contains,
+    "returns whether the json value contains `path`."
+);
+json_path_expr!(
+    JsonGet,
+    json_get,
+    "returns the json value at `path` as json text."
+);
+json_path_expr!(
+    JsonGetInt,
+    json_get_int,
+    "returns the integer at `path`, or NULL if it is not an integer."
+);
+json_path_expr!(
+    JsonGetStr,
+    json_get_str,
+    "returns the value at `path` as text, with strings unquoted."
+);
+
+/// Parses the json objects in `json` into a struct with the given fields
+pub fn json_as_struct(json: Expr, fields: Fields) -> Expr {
+    Expr::ScalarFunction(ScalarFunction::new(
+        BuiltinScalarFunction::JsonAsStruct,
+        vec![json, Expr::Literal(ScalarValue::Struct(None, fields))],
+    ))
+}
+
 // string functions
 scalar_expr!(Ascii, ascii, chr, "ASCII code value of the character");
 scalar_expr!(
@@ -881,6 +956,7 @@ pub fn call_fn(name: impl AsRef<str>, args: Vec<Expr>) -> Result<Expr> {
 mod test {
     use super::*;
     use crate::lit;
+    use arrow::datatypes::Field;
 
     #[test]
     fn filter_is_null_and_is_not_null() {
@@ -1060,6 +1136,11 @@ mod test {
         test_nary_scalar_expr!(MakeArray, array, input);
         test_scalar_expr!(TrimArray, trim_array, array, n);
 
+        test_scalar_expr!(MakeMap, map, keys, values);
+        test_scalar_expr!(MapExtract, map_extract, map, key);
+        test_unary_scalar_expr!(MapKeys, map_keys);
+        test_unary_scalar_expr!(MapValues, map_values);
+
         test_unary_scalar_expr!(ArrowTypeof, arrow_typeof);
     }
 
@@ -1074,6 +1155,31 @@ mod test {
         }
     }
 
+    #[test]
+    fn json_function_definitions() {
+        if let Expr::ScalarFunction(ScalarFunction { fun, args }) =
+            json_get(col("tableA.a"), vec![lit("b"), lit(0)])
+        {
+            assert_eq!(BuiltinScalarFunction::JsonGet, fun);
+            assert_eq!(vec![col("tableA.a"), lit("b"), lit(0)], args);
+        } else {
+            unreachable!();
+        }
+
+        let fields = Fields::from(vec![Field::new("b", DataType::Int64, true)]);
+        if let Expr::ScalarFunction(ScalarFunction { fun, args }) =
+            json_as_struct(col("tableA.a"), fields.clone())
+        {
+            assert_eq!(BuiltinScalarFunction::JsonAsStruct, fun);
+            assert_eq!(
+                vec![col("tableA.a"), lit(ScalarValue::Struct(None, fields))],
+                args
+            );
+        } else {
+            unreachable!();
+        }
+    }
+
     #[test]
     fn digest_function_definitions() {
         if let Expr::ScalarFunction(ScalarFunction { fun, args }) =
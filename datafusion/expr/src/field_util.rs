@@ -20,10 +20,11 @@
 use arrow::datatypes::{DataType, Field};
 use datafusion_common::{DataFusionError, Result, ScalarValue};
 
-/// Returns the field access indexed by `key` from a [`DataType::List`] or [`DataType::Struct`]
+/// Returns the field access indexed by `key` from a [`DataType::List`], [`DataType::Struct`]
+/// or [`DataType::Map`]
 /// # Error
 /// Errors if
-/// * the `data_type` is not a Struct or,
+/// * the `data_type` is not a List, Struct or Map or,
 /// * there is no field key is not of the required index type
 pub fn get_indexed_field(data_type: &DataType, key: &ScalarValue) -> Result<Field> {
     match (data_type, key) {
@@ -46,8 +47,16 @@ pub fn get_indexed_field(data_type: &DataType, key: &ScalarValue) -> Result<Fiel
         (DataType::List(_), _) => Err(DataFusionError::Plan(
             "Only ints are valid as an indexed field in a list".to_string(),
         )),
+        (DataType::Map(entries, _), _) => match entries.data_type() {
+            DataType::Struct(fields) if fields.len() == 2 => {
+                Ok(Field::new(key.to_string(), fields[1].data_type().clone(), true))
+            }
+            other => Err(DataFusionError::Plan(format!(
+                "Map entries must be a struct of key and value, got {other}"
+            ))),
+        },
         (other, _) => Err(DataFusionError::Plan(
-            format!("The expression to get an indexed field is only valid for `List` or `Struct` types, got {other}")
+            format!("The expression to get an indexed field is only valid for `List`, `Struct` or `Map` types, got {other}")
         )),
     }
 }
@@ -42,6 +42,7 @@ pub mod logical_plan;
 mod nullif;
 mod operator;
 mod partition_evaluator;
+mod scalar_macro;
 mod signature;
 pub mod struct_expressions;
 mod table_source;
@@ -73,6 +74,7 @@ pub use logical_plan::*;
 pub use nullif::SUPPORTED_NULLIF_TYPES;
 pub use operator::Operator;
 pub use partition_evaluator::PartitionEvaluator;
+pub use scalar_macro::ScalarMacro;
 pub use signature::{Signature, TypeSignature, Volatility};
 pub use table_source::{TableProviderFilterPushDown, TableSource, TableType};
 pub use udaf::AggregateUDF;
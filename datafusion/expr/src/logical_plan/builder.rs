@@ -24,17 +24,19 @@ use crate::expr_rewriter::{
     rewrite_sort_cols_by_aggs,
 };
 use crate::type_coercion::binary::comparison_coercion;
-use crate::utils::{columnize_expr, compare_sort_expr};
-use crate::{and, binary_expr, DmlStatement, Operator, WriteOp};
+use crate::utils::{columnize_expr, compare_sort_expr, expr_to_columns};
+use crate::{
+    and, binary_expr, CopyTo, DmlStatement, Operator, OutputFileFormat, WriteOp,
+};
 use crate::{
     logical_plan::{
         Aggregate, Analyze, CrossJoin, Distinct, EmptyRelation, Explain, Filter, Join,
         JoinConstraint, JoinType, Limit, LogicalPlan, Partitioning, PlanType, Prepare,
-        Projection, Repartition, Sort, SubqueryAlias, TableScan, Union, Unnest, Values,
-        Window,
+        Projection, RecursiveQuery, Repartition, Sample, SampleMethod, SampleSize, Sort,
+        SubqueryAlias, TableScan, Union, Unnest, Values, Window,
     },
     utils::{
-        can_hash, expand_qualified_wildcard, expand_wildcard,
+        can_hash, expand_qualified_wildcard, expand_wildcard, find_asof_match_condition,
         find_valid_equijoin_key_pair, group_window_expr_by_sort_keys,
     },
     Expr, ExprSchemable, TableSource,
@@ -233,6 +235,23 @@ impl LogicalPlanBuilder {
         Self::scan_with_filters(table_name, table_source, projection, vec![])
     }
 
+    /// Create a [CopyTo] for copying the contents of this builder to the specified file(s)
+    pub fn copy_to(
+        input: LogicalPlan,
+        output_url: String,
+        file_format: OutputFileFormat,
+        single_file_output: bool,
+        options: Vec<(String, String)>,
+    ) -> Result<Self> {
+        Ok(Self::from(LogicalPlan::Copy(CopyTo {
+            input: Arc::new(input),
+            output_url,
+            file_format,
+            single_file_output,
+            options,
+        })))
+    }
+
     /// Create a [DmlStatement] for inserting the contents of this builder into the named table
     pub fn insert_into(
         input: LogicalPlan,
@@ -393,6 +412,34 @@ impl LogicalPlanBuilder {
         })))
     }
 
+    /// Apply a sample of the rows
+    ///
+    /// `method` - How the rows are chosen
+    ///
+    /// `size` - The percentage or number of rows to choose
+    ///
+    /// `seed` - Seed of the random choice, to take a repeatable sample
+    pub fn sample(
+        self,
+        method: SampleMethod,
+        size: SampleSize,
+        seed: Option<u64>,
+    ) -> Result<Self> {
+        if let SampleSize::Percent(percent) = size {
+            if !(0.0..=100.0).contains(&percent) {
+                return Err(DataFusionError::Plan(format!(
+                    "Sample percentage must be between 0 and 100, got {percent}"
+                )));
+            }
+        }
+        Ok(Self::from(LogicalPlan::Sample(Sample {
+            method,
+            size,
+            seed,
+            input: Arc::new(self.plan),
+        })))
+    }
+
     /// Apply an alias
     pub fn alias(self, alias: impl Into<OwnedTableReference>) -> Result<Self> {
         Ok(Self::from(subquery_alias(self.plan, alias)?))
@@ -594,6 +641,35 @@ impl LogicalPlanBuilder {
         })))
     }
 
+    /// Convert a regular plan into a recursive query, using `self` as the
+    /// static term and `recursive_term` as the recursive term.
+    ///
+    /// The output of the recursive term is coerced to the schema of the
+    /// static term. If `is_distinct` is true, rows already produced by the
+    /// query are not emitted again (`UNION` rather than `UNION ALL`).
+    pub fn to_recursive_query(
+        &self,
+        name: String,
+        recursive_term: LogicalPlan,
+        is_distinct: bool,
+    ) -> Result<Self> {
+        let static_fields_len = self.plan.schema().fields().len();
+        let recursive_fields_len = recursive_term.schema().fields().len();
+        if static_fields_len != recursive_fields_len {
+            return Err(DataFusionError::Plan(format!(
+                "Non-recursive term and recursive term must have the same number of columns ({static_fields_len} != {recursive_fields_len})"
+            )));
+        }
+        let coerced_recursive_term =
+            coerce_plan_expr_for_schema(&recursive_term, self.plan.schema())?;
+        Ok(Self::from(LogicalPlan::RecursiveQuery(RecursiveQuery {
+            name,
+            static_term: Arc::new(self.plan.clone()),
+            recursive_term: Arc::new(coerced_recursive_term),
+            is_distinct,
+        })))
+    }
+
     /// Apply a join with on constraint.
     ///
     /// Filter expression expected to contain non-equality predicates that can not be pushed
@@ -648,6 +724,9 @@ impl LogicalPlanBuilder {
         } else {
             None
         };
+        if join_type == JoinType::AsOf {
+            check_asof_match_condition(&self.plan, &right, filter.as_ref())?;
+        }
 
         let (left_keys, right_keys): (Vec<Result<Column>>, Vec<Result<Column>>) =
             join_keys
@@ -755,6 +834,9 @@ impl LogicalPlanBuilder {
         join_type: JoinType,
         using_keys: Vec<impl Into<Column> + Clone>,
     ) -> Result<Self> {
+        if join_type == JoinType::AsOf {
+            check_asof_match_condition(&self.plan, &right, None)?;
+        }
         let left_keys: Vec<Column> = using_keys
             .clone()
             .into_iter()
@@ -862,6 +944,112 @@ impl LogicalPlanBuilder {
         )?)))
     }
 
+    /// Rotate the values of `pivot_column` into columns.
+    ///
+    /// For each of the `pivot_values`, a column named after the value is
+    /// created, computing the aggregate `aggr_expr` over the rows where
+    /// `pivot_column` is equal to the value. The rows are grouped by all the
+    /// columns that are neither `pivot_column` nor used by `aggr_expr`.
+    ///
+    /// This is equivalent to
+    /// `SELECT <other columns>, aggr(...) FILTER (WHERE pivot_column = value) AS value, ...
+    /// GROUP BY <other columns>`
+    pub fn pivot(
+        self,
+        aggr_expr: Expr,
+        pivot_column: impl Into<Column>,
+        pivot_values: Vec<ScalarValue>,
+    ) -> Result<Self> {
+        let pivot_column = normalize_col(Expr::Column(pivot_column.into()), &self.plan)?;
+        let aggr_expr = normalize_col(aggr_expr, &self.plan)?;
+
+        let mut used_columns = HashSet::new();
+        expr_to_columns(&pivot_column, &mut used_columns)?;
+        expr_to_columns(&aggr_expr, &mut used_columns)?;
+        let group_expr = self
+            .plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.qualified_column())
+            .filter(|column| !used_columns.contains(column))
+            .map(Expr::Column)
+            .collect::<Vec<_>>();
+
+        let aggr_exprs = pivot_values
+            .into_iter()
+            .map(|value| {
+                let name = value.to_string();
+                let predicate = pivot_column.clone().eq(Expr::Literal(value));
+                Ok(add_aggregate_filter(aggr_expr.clone(), predicate)?.alias(name))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.aggregate(group_expr, aggr_exprs)
+    }
+
+    /// Rotate the `columns` into rows.
+    ///
+    /// Each input row produces one row per column in `columns`, with the name
+    /// of the column in `name_column` and its value in `value_column`. The
+    /// other columns of the input are kept as is. Unless `include_nulls` is
+    /// true, the rows where the value is `NULL` are skipped.
+    ///
+    /// This is equivalent to the `UNION ALL` of
+    /// `SELECT <other columns>, 'column' AS name_column, column AS value_column`
+    /// for each of the `columns`
+    pub fn unpivot(
+        self,
+        value_column: impl Into<String>,
+        name_column: impl Into<String>,
+        columns: impl IntoIterator<Item = impl Into<Column>>,
+        include_nulls: bool,
+    ) -> Result<Self> {
+        let value_column = value_column.into();
+        let name_column = name_column.into();
+        let columns = columns
+            .into_iter()
+            .map(|column| self.plan.schema().field_from_column(&column.into()))
+            .map(|field| Ok(field?.qualified_column()))
+            .collect::<Result<Vec<_>>>()?;
+        if columns.is_empty() {
+            return Err(DataFusionError::Plan(
+                "UNPIVOT requires at least one column".to_string(),
+            ));
+        }
+
+        let other_columns = self
+            .plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.qualified_column())
+            .filter(|column| !columns.contains(column))
+            .map(Expr::Column)
+            .collect::<Vec<_>>();
+
+        let mut plan: Option<LogicalPlan> = None;
+        for column in columns {
+            let mut builder = LogicalPlanBuilder::from(self.plan.clone());
+            if !include_nulls {
+                builder = builder.filter(Expr::Column(column.clone()).is_not_null())?;
+            }
+            let mut expr = other_columns.clone();
+            expr.push(
+                Expr::Literal(ScalarValue::from(column.name.as_str()))
+                    .alias(&name_column),
+            );
+            expr.push(Expr::Column(column).alias(&value_column));
+            let input = builder.project(expr)?.build()?;
+            plan = Some(match plan {
+                Some(plan) => union(plan, input)?,
+                None => input,
+            });
+        }
+        // `columns` is not empty
+        Ok(Self::from(plan.unwrap()))
+    }
+
     /// Create an expression to represent the explanation of the plan
     ///
     /// if `analyze` is true, runs the actual plan and produces
@@ -1018,6 +1206,9 @@ impl LogicalPlanBuilder {
                         )))
             })
             .collect::<Result<Vec<_>>>()?;
+        if join_type == JoinType::AsOf {
+            check_asof_match_condition(&self.plan, &right, filter.as_ref())?;
+        }
 
         let join_schema =
             build_join_schema(self.plan.schema(), right.schema(), &join_type)?;
@@ -1040,6 +1231,41 @@ impl LogicalPlanBuilder {
     }
 }
 
+/// Checks that the filter of an ASOF join between `left` and `right` is a
+/// valid match condition
+fn check_asof_match_condition(
+    left: &LogicalPlan,
+    right: &LogicalPlan,
+    filter: Option<&Expr>,
+) -> Result<()> {
+    let filter = filter.ok_or_else(|| {
+        DataFusionError::Plan("ASOF join requires a match condition".to_string())
+    })?;
+    find_asof_match_condition(filter, left.schema().clone(), right.schema().clone())?;
+    Ok(())
+}
+
+/// Add `predicate` to the `FILTER` clause of the aggregate function `expr`
+fn add_aggregate_filter(expr: Expr, predicate: Expr) -> Result<Expr> {
+    let combine = |filter: Option<Box<Expr>>| match filter {
+        Some(filter) => Some(Box::new(and(*filter, predicate))),
+        None => Some(Box::new(predicate)),
+    };
+    match expr {
+        Expr::AggregateFunction(mut aggr) => {
+            aggr.filter = combine(aggr.filter);
+            Ok(Expr::AggregateFunction(aggr))
+        }
+        Expr::AggregateUDF(mut aggr) => {
+            aggr.filter = combine(aggr.filter);
+            Ok(Expr::AggregateUDF(aggr))
+        }
+        _ => Err(DataFusionError::Plan(format!(
+            "PIVOT requires an aggregate function, got {expr}"
+        ))),
+    }
+}
+
 /// Creates a schema for a join operation.
 /// The fields from the left side are first
 pub fn build_join_schema(
@@ -1066,7 +1292,7 @@ pub fn build_join_schema(
                 .cloned()
                 .collect()
         }
-        JoinType::Left => {
+        JoinType::Left | JoinType::AsOf => {
             // left then right, right set to nullable in case of not matched scenario
             left_fields
                 .iter()
@@ -1580,6 +1806,51 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn plan_builder_pivot() -> Result<()> {
+        let plan = table_scan(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 3, 4]),
+        )?
+        .pivot(
+            sum(col("salary")),
+            "state",
+            vec![ScalarValue::from("CO"), ScalarValue::from("NY")],
+        )?
+        .build()?;
+
+        let expected = "Aggregate: groupBy=[[employee_csv.id]], aggr=[[SUM(employee_csv.salary) FILTER (WHERE employee_csv.state = Utf8(\"CO\")) AS CO, SUM(employee_csv.salary) FILTER (WHERE employee_csv.state = Utf8(\"NY\")) AS NY]]\
+        \n  TableScan: employee_csv projection=[id, state, salary]";
+
+        assert_eq!(expected, format!("{plan:?}"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn plan_builder_unpivot() -> Result<()> {
+        let plan = table_scan(
+            Some("employee_csv"),
+            &employee_schema(),
+            Some(vec![0, 1, 2]),
+        )?
+        .unpivot("name", "kind", ["first_name", "last_name"], false)?
+        .build()?;
+
+        let expected = "Union\
+        \n  Projection: employee_csv.id, Utf8(\"first_name\") AS kind, employee_csv.first_name AS name\
+        \n    Filter: employee_csv.first_name IS NOT NULL\
+        \n      TableScan: employee_csv projection=[id, first_name, last_name]\
+        \n  Projection: employee_csv.id, Utf8(\"last_name\") AS kind, employee_csv.last_name AS name\
+        \n    Filter: employee_csv.last_name IS NOT NULL\
+        \n      TableScan: employee_csv projection=[id, first_name, last_name]";
+
+        assert_eq!(expected, format!("{plan:?}"));
+
+        Ok(())
+    }
+
     #[test]
     fn plan_builder_union_distinct_combined_single_union() -> Result<()> {
         let plan =
@@ -22,14 +22,15 @@ use std::{
     hash::{Hash, Hasher},
 };
 
-use crate::{Expr, LogicalPlan};
+use crate::{Expr, LogicalPlan, ScalarMacro};
 
+use arrow::datatypes::Field;
 use datafusion_common::parsers::CompressionTypeVariant;
 use datafusion_common::{
     Constraints, DFSchemaRef, OwnedSchemaReference, OwnedTableReference,
 };
 
-/// Various types of DDL  (CREATE / DROP) catalog manipulation
+/// Various types of DDL  (CREATE / DROP / ALTER) catalog manipulation
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub enum DdlStatement {
     /// Creates an external table.
@@ -48,6 +49,12 @@ pub enum DdlStatement {
     DropView(DropView),
     /// Drops a catalog schema
     DropCatalogSchema(DropCatalogSchema),
+    /// Alters a table.
+    AlterTable(AlterTable),
+    /// Creates a scalar function defined by an expression.
+    CreateFunction(CreateFunction),
+    /// Drops a function.
+    DropFunction(DropFunction),
 }
 
 impl DdlStatement {
@@ -66,6 +73,9 @@ impl DdlStatement {
             DdlStatement::DropTable(DropTable { schema, .. }) => schema,
             DdlStatement::DropView(DropView { schema, .. }) => schema,
             DdlStatement::DropCatalogSchema(DropCatalogSchema { schema, .. }) => schema,
+            DdlStatement::AlterTable(AlterTable { schema, .. }) => schema,
+            DdlStatement::CreateFunction(CreateFunction { schema, .. }) => schema,
+            DdlStatement::DropFunction(DropFunction { schema, .. }) => schema,
         }
     }
 
@@ -81,6 +91,9 @@ impl DdlStatement {
             DdlStatement::DropTable(_) => "DropTable",
             DdlStatement::DropView(_) => "DropView",
             DdlStatement::DropCatalogSchema(_) => "DropCatalogSchema",
+            DdlStatement::AlterTable(_) => "AlterTable",
+            DdlStatement::CreateFunction(_) => "CreateFunction",
+            DdlStatement::DropFunction(_) => "DropFunction",
         }
     }
 
@@ -97,6 +110,9 @@ impl DdlStatement {
             DdlStatement::DropTable(_) => vec![],
             DdlStatement::DropView(_) => vec![],
             DdlStatement::DropCatalogSchema(_) => vec![],
+            DdlStatement::AlterTable(_) => vec![],
+            DdlStatement::CreateFunction(_) => vec![],
+            DdlStatement::DropFunction(_) => vec![],
         }
     }
 
@@ -155,6 +171,33 @@ impl DdlStatement {
                     }) => {
                         write!(f, "DropCatalogSchema: {name:?} if not exist:={if_exists} cascade:={cascade}")
                     }
+                    DdlStatement::AlterTable(AlterTable {
+                        name, operation, ..
+                    }) => {
+                        write!(f, "AlterTable: {name:?} {operation}")
+                    }
+                    DdlStatement::CreateFunction(CreateFunction {
+                        function,
+                        or_replace,
+                        ..
+                    }) => {
+                        let args = function
+                            .args
+                            .iter()
+                            .map(|(name, data_type)| format!("{name} {data_type}"))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        write!(
+                            f,
+                            "CreateFunction: {}({args}) -> {} or replace:={or_replace}",
+                            function.name, function.return_type
+                        )
+                    }
+                    DdlStatement::DropFunction(DropFunction {
+                        name, if_exists, ..
+                    }) => {
+                        write!(f, "DropFunction: {name} if exist:={if_exists}")
+                    }
                 }
             }
         }
@@ -296,3 +339,132 @@ pub struct DropCatalogSchema {
     /// Dummy schema
     pub schema: DFSchemaRef,
 }
+
+/// Alters a table.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct AlterTable {
+    /// The table name
+    pub name: OwnedTableReference,
+    /// How to alter the table
+    pub operation: AlterTableOperation,
+    /// Dummy schema
+    pub schema: DFSchemaRef,
+}
+
+/// Creates a scalar function defined by an expression.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct CreateFunction {
+    /// The function
+    pub function: ScalarMacro,
+    /// Option to replace an existing function with the same name
+    pub or_replace: bool,
+    /// Dummy schema
+    pub schema: DFSchemaRef,
+}
+
+/// Drops a function.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct DropFunction {
+    /// The function name
+    pub name: String,
+    /// If the function exists
+    pub if_exists: bool,
+    /// Dummy schema
+    pub schema: DFSchemaRef,
+}
+
+/// How an [`AlterTable`] alters a table
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AlterTableOperation {
+    /// Adds a column, which is null in the existing rows
+    AddColumn {
+        /// The new column
+        field: Field,
+        /// Do nothing if a column with the same name already exists
+        if_not_exists: bool,
+    },
+    /// Drops a column
+    DropColumn {
+        /// The column name
+        name: String,
+        /// Do nothing if the column does not exist
+        if_exists: bool,
+    },
+    /// Renames a column
+    RenameColumn {
+        /// The current column name
+        old_name: String,
+        /// The new column name
+        new_name: String,
+    },
+    /// Renames the table
+    RenameTable {
+        /// The new table name
+        new_name: OwnedTableReference,
+    },
+}
+
+impl AlterTableOperation {
+    /// Returns the change of the schema of the table made by this
+    /// operation, if any
+    pub fn schema_change(&self) -> Option<SchemaChange> {
+        match self {
+            AlterTableOperation::AddColumn { field, .. } => {
+                Some(SchemaChange::AddColumn(field.clone()))
+            }
+            AlterTableOperation::DropColumn { name, .. } => {
+                Some(SchemaChange::DropColumn(name.clone()))
+            }
+            AlterTableOperation::RenameColumn { old_name, new_name } => {
+                Some(SchemaChange::RenameColumn {
+                    old_name: old_name.clone(),
+                    new_name: new_name.clone(),
+                })
+            }
+            AlterTableOperation::RenameTable { .. } => None,
+        }
+    }
+}
+
+impl Display for AlterTableOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlterTableOperation::AddColumn {
+                field,
+                if_not_exists,
+            } => write!(
+                f,
+                "add column {} {}{} if not exist:={if_not_exists}",
+                field.name(),
+                field.data_type(),
+                if field.is_nullable() { "" } else { " not null" }
+            ),
+            AlterTableOperation::DropColumn { name, if_exists } => {
+                write!(f, "drop column {name} if exist:={if_exists}")
+            }
+            AlterTableOperation::RenameColumn { old_name, new_name } => {
+                write!(f, "rename column {old_name} to {new_name}")
+            }
+            AlterTableOperation::RenameTable { new_name } => {
+                write!(f, "rename to {new_name:?}")
+            }
+        }
+    }
+}
+
+/// A change of the schema of a table, applied by
+/// `TableProvider::alter_schema`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SchemaChange {
+    /// Appends a column, which is null in the existing rows
+    AddColumn(Field),
+    /// Removes the column with the given name
+    DropColumn(String),
+    /// Renames a column
+    RenameColumn {
+        /// The current column name
+        old_name: String,
+        /// The new column name
+        new_name: String,
+    },
+}
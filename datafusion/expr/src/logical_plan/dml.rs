@@ -17,13 +17,71 @@
 
 use std::{
     fmt::{self, Display},
+    str::FromStr,
     sync::Arc,
 };
 
-use datafusion_common::{DFSchemaRef, OwnedTableReference};
+use datafusion_common::{DFSchemaRef, DataFusionError, OwnedTableReference, Result};
 
 use crate::LogicalPlan;
 
+/// Operator that copies the contents of a database to file(s)
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct CopyTo {
+    /// The relation that determines the tuples to write to the output file(s)
+    pub input: Arc<LogicalPlan>,
+    /// The location to write the file(s)
+    pub output_url: String,
+    /// The file format to output (explicitly defined or inferred from file extension)
+    pub file_format: OutputFileFormat,
+    /// If true, `output_url` is a single file to which all data is written.
+    /// Otherwise, `output_url` is a directory in which new file(s) are created
+    pub single_file_output: bool,
+    /// Format specific options (e.g. compression), as (key, value) pairs
+    pub options: Vec<(String, String)>,
+}
+
+/// The file formats that `COPY TO` can write
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutputFileFormat {
+    /// Comma separated values
+    CSV,
+    /// Newline delimited JSON
+    JSON,
+    /// Apache Parquet
+    PARQUET,
+    /// Apache Arrow IPC file
+    ARROW,
+}
+
+impl FromStr for OutputFileFormat {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(OutputFileFormat::CSV),
+            "json" | "ndjson" => Ok(OutputFileFormat::JSON),
+            "parquet" => Ok(OutputFileFormat::PARQUET),
+            "arrow" => Ok(OutputFileFormat::ARROW),
+            _ => Err(DataFusionError::NotImplemented(format!(
+                "Unsupported output file format: {s}"
+            ))),
+        }
+    }
+}
+
+impl Display for OutputFileFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let out = match self {
+            OutputFileFormat::CSV => "csv",
+            OutputFileFormat::JSON => "json",
+            OutputFileFormat::PARQUET => "parquet",
+            OutputFileFormat::ARROW => "arrow",
+        };
+        write!(f, "{out}")
+    }
+}
+
 /// The operator that modifies the content of a database (adapted from
 /// substrait WriteRel)
 #[derive(Clone, PartialEq, Eq, Hash)]
@@ -38,12 +96,15 @@ pub struct DmlStatement {
     pub input: Arc<LogicalPlan>,
 }
 
-#[derive(Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum WriteOp {
     Insert,
     Delete,
     Update,
     Ctas,
+    /// Insert, update and delete rows as computed by a `MERGE` statement,
+    /// see [`MergeAction`]
+    Merge,
 }
 
 impl Display for WriteOp {
@@ -53,6 +114,65 @@ impl Display for WriteOp {
             WriteOp::Delete => write!(f, "Delete"),
             WriteOp::Update => write!(f, "Update"),
             WriteOp::Ctas => write!(f, "Ctas"),
+            WriteOp::Merge => write!(f, "Merge"),
+        }
+    }
+}
+
+/// The name of the Utf8 column that holds the [`MergeAction`] of each
+/// row of the input of a [`WriteOp::Merge`]
+pub const MERGE_ACTION_COLUMN: &str = "__merge_action";
+
+/// What a `MERGE` statement does with a row of its input
+///
+/// The input of a [`WriteOp::Merge`] has the columns of the table,
+/// followed by the [`MERGE_ACTION_COLUMN`] and the previous values of the
+/// columns of the table. It contains one row for each row of the table,
+/// with either the [`MergeAction::Keep`], [`MergeAction::Update`] or
+/// [`MergeAction::Delete`] action, followed by one row for each inserted
+/// row, whose previous values are null.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MergeAction {
+    /// Insert the row into the table
+    Insert,
+    /// Replace the values of the row with the new values
+    Update,
+    /// Delete the row from the table
+    Delete,
+    /// Keep the row unchanged
+    Keep,
+}
+
+impl MergeAction {
+    /// The value of the [`MERGE_ACTION_COLUMN`] for this action
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MergeAction::Insert => "insert",
+            MergeAction::Update => "update",
+            MergeAction::Delete => "delete",
+            MergeAction::Keep => "keep",
         }
     }
 }
+
+impl FromStr for MergeAction {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "insert" => Ok(MergeAction::Insert),
+            "update" => Ok(MergeAction::Update),
+            "delete" => Ok(MergeAction::Delete),
+            "keep" => Ok(MergeAction::Keep),
+            _ => Err(DataFusionError::Internal(format!(
+                "Invalid merge action: {s}"
+            ))),
+        }
+    }
+}
+
+impl Display for MergeAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -28,19 +28,23 @@ pub use builder::{
     LogicalPlanBuilder, UNNAMED_TABLE,
 };
 pub use ddl::{
-    CreateCatalog, CreateCatalogSchema, CreateExternalTable, CreateMemoryTable,
-    CreateView, DdlStatement, DropCatalogSchema, DropTable, DropView,
+    AlterTable, AlterTableOperation, CreateCatalog, CreateCatalogSchema,
+    CreateExternalTable, CreateFunction, CreateMemoryTable, CreateView, DdlStatement,
+    DropCatalogSchema, DropFunction, DropTable, DropView, SchemaChange,
+};
+pub use dml::{
+    CopyTo, DmlStatement, MergeAction, OutputFileFormat, WriteOp, MERGE_ACTION_COLUMN,
 };
-pub use dml::{DmlStatement, WriteOp};
 pub use plan::{
     Aggregate, Analyze, CrossJoin, DescribeTable, Distinct, EmptyRelation, Explain,
     Extension, Filter, Join, JoinConstraint, JoinType, Limit, LogicalPlan, Partitioning,
-    PlanType, Prepare, Projection, Repartition, Sort, StringifiedPlan, Subquery,
-    SubqueryAlias, TableScan, ToStringifiedPlan, Union, Unnest, Values, Window,
+    PlanType, Prepare, Projection, RecursiveQuery, Repartition, Sample, SampleMethod,
+    SampleSize, Sort, StringifiedPlan, Subquery, SubqueryAlias, TableScan,
+    ToStringifiedPlan, Union, Unnest, Values, Window,
 };
 pub use statement::{
-    SetVariable, Statement, TransactionAccessMode, TransactionConclusion, TransactionEnd,
-    TransactionIsolationLevel, TransactionStart,
+    Deallocate, Execute, SetVariable, Statement, TransactionAccessMode,
+    TransactionConclusion, TransactionEnd, TransactionIsolationLevel, TransactionStart,
 };
 
 pub use display::display_schema;
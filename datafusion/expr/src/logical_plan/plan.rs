@@ -22,7 +22,7 @@ use crate::expr_rewriter::create_col_from_scalar_expr;
 use crate::expr_vec_fmt;
 use crate::logical_plan::display::{GraphvizVisitor, IndentVisitor};
 use crate::logical_plan::extension::UserDefinedLogicalNode;
-use crate::logical_plan::{DmlStatement, Statement};
+use crate::logical_plan::{CopyTo, DmlStatement, Statement};
 use crate::utils::{
     enumerate_grouping_sets, exprlist_to_fields, find_out_reference_exprs, from_plan,
     grouping_set_expr_count, grouping_set_to_exprlist, inspect_expr_pre,
@@ -97,6 +97,8 @@ pub enum LogicalPlan {
     SubqueryAlias(SubqueryAlias),
     /// Skip some number of rows, and then fetch some number of rows.
     Limit(Limit),
+    /// Produces a random sample of the rows of its input
+    Sample(Sample),
     /// [`Statement`]
     Statement(Statement),
     /// Values expression. See
@@ -117,12 +119,16 @@ pub enum LogicalPlan {
     Prepare(Prepare),
     /// Insert / Update / Delete
     Dml(DmlStatement),
+    /// Copy the contents of a relation to file(s)
+    Copy(CopyTo),
     /// CREATE / DROP TABLES / VIEWS / SCHEMAs
     Ddl(DdlStatement),
     /// Describe the schema of table
     DescribeTable(DescribeTable),
     /// Unnest a column that contains a nested list type.
     Unnest(Unnest),
+    /// A variadic query (e.g. "Recursive CTEs")
+    RecursiveQuery(RecursiveQuery),
 }
 
 impl LogicalPlan {
@@ -144,6 +150,7 @@ impl LogicalPlan {
             LogicalPlan::CrossJoin(CrossJoin { schema, .. }) => schema,
             LogicalPlan::Repartition(Repartition { input, .. }) => input.schema(),
             LogicalPlan::Limit(Limit { input, .. }) => input.schema(),
+            LogicalPlan::Sample(Sample { input, .. }) => input.schema(),
             LogicalPlan::Statement(statement) => statement.schema(),
             LogicalPlan::Subquery(Subquery { subquery, .. }) => subquery.schema(),
             LogicalPlan::SubqueryAlias(SubqueryAlias { schema, .. }) => schema,
@@ -156,8 +163,13 @@ impl LogicalPlan {
                 dummy_schema
             }
             LogicalPlan::Dml(DmlStatement { table_schema, .. }) => table_schema,
+            LogicalPlan::Copy(CopyTo { input, .. }) => input.schema(),
             LogicalPlan::Ddl(ddl) => ddl.schema(),
             LogicalPlan::Unnest(Unnest { schema, .. }) => schema,
+            LogicalPlan::RecursiveQuery(RecursiveQuery { static_term, .. }) => {
+                // we take the schema of the static term as the schema of the entire recursive query
+                static_term.schema()
+            }
         }
     }
 
@@ -209,13 +221,22 @@ impl LogicalPlan {
             | LogicalPlan::TableScan(_) => {
                 vec![self.schema()]
             }
+            LogicalPlan::RecursiveQuery(RecursiveQuery {
+                static_term,
+                recursive_term,
+                ..
+            }) => {
+                vec![static_term.schema(), recursive_term.schema()]
+            }
             // return children schemas
             LogicalPlan::Limit(_)
+            | LogicalPlan::Sample(_)
             | LogicalPlan::Subquery(_)
             | LogicalPlan::Repartition(_)
             | LogicalPlan::Sort(_)
             | LogicalPlan::Filter(_)
             | LogicalPlan::Distinct(_)
+            | LogicalPlan::Copy(_)
             | LogicalPlan::Prepare(_) => {
                 self.inputs().iter().map(|p| p.schema()).collect()
             }
@@ -334,6 +355,7 @@ impl LogicalPlan {
             | LogicalPlan::Subquery(_)
             | LogicalPlan::SubqueryAlias(_)
             | LogicalPlan::Limit(_)
+            | LogicalPlan::Sample(_)
             | LogicalPlan::Statement(_)
             | LogicalPlan::CrossJoin(_)
             | LogicalPlan::Analyze(_)
@@ -342,7 +364,9 @@ impl LogicalPlan {
             | LogicalPlan::Distinct(_)
             | LogicalPlan::Dml(_)
             | LogicalPlan::Ddl(_)
+            | LogicalPlan::Copy(_)
             | LogicalPlan::DescribeTable(_)
+            | LogicalPlan::RecursiveQuery(_)
             | LogicalPlan::Prepare(_) => Ok(()),
         }
     }
@@ -360,6 +384,7 @@ impl LogicalPlan {
             LogicalPlan::Join(Join { left, right, .. }) => vec![left, right],
             LogicalPlan::CrossJoin(CrossJoin { left, right, .. }) => vec![left, right],
             LogicalPlan::Limit(Limit { input, .. }) => vec![input],
+            LogicalPlan::Sample(Sample { input, .. }) => vec![input],
             LogicalPlan::Subquery(Subquery { subquery, .. }) => vec![subquery],
             LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => vec![input],
             LogicalPlan::Extension(extension) => extension.node.inputs(),
@@ -370,9 +395,15 @@ impl LogicalPlan {
             LogicalPlan::Explain(explain) => vec![&explain.plan],
             LogicalPlan::Analyze(analyze) => vec![&analyze.input],
             LogicalPlan::Dml(write) => vec![&write.input],
+            LogicalPlan::Copy(copy) => vec![&copy.input],
             LogicalPlan::Ddl(ddl) => ddl.inputs(),
             LogicalPlan::Unnest(Unnest { input, .. }) => vec![input],
             LogicalPlan::Prepare(Prepare { input, .. }) => vec![input],
+            LogicalPlan::RecursiveQuery(RecursiveQuery {
+                static_term,
+                recursive_term,
+                ..
+            }) => vec![static_term, recursive_term],
             // plans without inputs
             LogicalPlan::TableScan { .. }
             | LogicalPlan::Statement { .. }
@@ -425,6 +456,7 @@ impl LogicalPlan {
             | LogicalPlan::Distinct(Distinct { input, .. })
             | LogicalPlan::Sort(Sort { input, .. })
             | LogicalPlan::Limit(Limit { input, .. })
+            | LogicalPlan::Sample(Sample { input, .. })
             | LogicalPlan::Repartition(Repartition { input, .. })
             | LogicalPlan::Window(Window { input, .. }) => input.head_output_expr(),
             LogicalPlan::Join(Join {
@@ -433,7 +465,11 @@ impl LogicalPlan {
                 join_type,
                 ..
             }) => match join_type {
-                JoinType::Inner | JoinType::Left | JoinType::Right | JoinType::Full => {
+                JoinType::Inner
+                | JoinType::Left
+                | JoinType::Right
+                | JoinType::Full
+                | JoinType::AsOf => {
                     if left.schema().fields().is_empty() {
                         right.head_output_expr()
                     } else {
@@ -468,6 +504,9 @@ impl LogicalPlan {
                     .map_or(Ok(None), |v| v.map(Some))
             }
             LogicalPlan::Subquery(_) => Ok(None),
+            LogicalPlan::RecursiveQuery(RecursiveQuery { static_term, .. }) => {
+                static_term.head_output_expr()
+            }
             LogicalPlan::EmptyRelation(_)
             | LogicalPlan::Prepare(_)
             | LogicalPlan::Statement(_)
@@ -476,6 +515,7 @@ impl LogicalPlan {
             | LogicalPlan::Analyze(_)
             | LogicalPlan::Extension(_)
             | LogicalPlan::Dml(_)
+            | LogicalPlan::Copy(_)
             | LogicalPlan::Ddl(_)
             | LogicalPlan::DescribeTable(_)
             | LogicalPlan::Unnest(_) => Ok(None),
@@ -606,7 +646,9 @@ impl LogicalPlan {
                         _ => None,
                     }
                 }
-                JoinType::LeftSemi | JoinType::LeftAnti => left.max_rows(),
+                JoinType::LeftSemi | JoinType::LeftAnti | JoinType::AsOf => {
+                    left.max_rows()
+                }
                 JoinType::RightSemi | JoinType::RightAnti => right.max_rows(),
             },
             LogicalPlan::CrossJoin(CrossJoin { left, right, .. }) => {
@@ -632,13 +674,23 @@ impl LogicalPlan {
             LogicalPlan::Subquery(_) => None,
             LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => input.max_rows(),
             LogicalPlan::Limit(Limit { fetch, .. }) => *fetch,
+            LogicalPlan::Sample(Sample { input, size, .. }) => match size {
+                SampleSize::Rows(rows) => Some(
+                    input
+                        .max_rows()
+                        .map_or(*rows as usize, |max| max.min(*rows as usize)),
+                ),
+                SampleSize::Percent(_) => input.max_rows(),
+            },
             LogicalPlan::Distinct(Distinct { input }) => input.max_rows(),
             LogicalPlan::Values(v) => Some(v.values.len()),
             LogicalPlan::Unnest(_) => None,
+            LogicalPlan::RecursiveQuery(_) => None,
             LogicalPlan::Ddl(_)
             | LogicalPlan::Explain(_)
             | LogicalPlan::Analyze(_)
             | LogicalPlan::Dml(_)
+            | LogicalPlan::Copy(_)
             | LogicalPlan::DescribeTable(_)
             | LogicalPlan::Prepare(_)
             | LogicalPlan::Statement(_)
@@ -1086,6 +1138,20 @@ impl LogicalPlan {
                     LogicalPlan::Dml(DmlStatement { table_name, op, .. }) => {
                         write!(f, "Dml: op=[{op}] table=[{table_name}]")
                     }
+                    LogicalPlan::Copy(CopyTo {
+                        input: _,
+                        output_url,
+                        file_format,
+                        single_file_output,
+                        options,
+                    }) => {
+                        let op_str = options
+                            .iter()
+                            .map(|(k, v)| format!("{k} {v}"))
+                            .collect::<Vec<String>>()
+                            .join(", ");
+                        write!(f, "CopyTo: format={file_format} output_url={output_url} single_file_output={single_file_output} options: ({op_str})")
+                    }
                     LogicalPlan::Ddl(ddl) => {
                         write!(f, "{}", ddl.display())
                     }
@@ -1202,6 +1268,15 @@ impl LogicalPlan {
                             fetch.map_or_else(|| "None".to_string(), |x| x.to_string())
                         )
                     }
+                    LogicalPlan::Sample(Sample {
+                        method, size, seed, ..
+                    }) => {
+                        write!(f, "Sample: method={method}, size={size}")?;
+                        if let Some(seed) = seed {
+                            write!(f, ", seed={seed}")?;
+                        }
+                        Ok(())
+                    }
                     LogicalPlan::Subquery(Subquery { .. }) => {
                         write!(f, "Subquery:")
                     }
@@ -1217,6 +1292,16 @@ impl LogicalPlan {
                     LogicalPlan::Explain { .. } => write!(f, "Explain"),
                     LogicalPlan::Analyze { .. } => write!(f, "Analyze"),
                     LogicalPlan::Union(_) => write!(f, "Union"),
+                    LogicalPlan::RecursiveQuery(RecursiveQuery {
+                        name,
+                        is_distinct,
+                        ..
+                    }) => {
+                        write!(
+                            f,
+                            "RecursiveQuery: name={name}, is_distinct={is_distinct}"
+                        )
+                    }
                     LogicalPlan::Extension(e) => e.node.fmt_for_explain(f),
                     LogicalPlan::Prepare(Prepare {
                         name, data_types, ..
@@ -1515,6 +1600,26 @@ pub struct Union {
     pub schema: DFSchemaRef,
 }
 
+/// A recursive query, e.g. the body of a `WITH RECURSIVE` common table
+/// expression.
+///
+/// The static term is evaluated once; the recursive term is then evaluated
+/// repeatedly against a working table holding the rows produced by the
+/// previous iteration, until an iteration produces no rows.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct RecursiveQuery {
+    /// Name of the query
+    pub name: String,
+    /// The static term (initial contents of the working table)
+    pub static_term: Arc<LogicalPlan>,
+    /// The recursive term (evaluated on the contents of the working table until
+    /// it returns an empty set)
+    pub recursive_term: Arc<LogicalPlan>,
+    /// Should the output of the recursive term be deduplicated (`UNION`) or
+    /// not (`UNION ALL`).
+    pub is_distinct: bool,
+}
+
 /// Prepare a statement but do not execute it. Prepare statements can have 0 or more
 /// `Expr::Placeholder` expressions that are filled in during execution
 #[derive(Clone, PartialEq, Eq, Hash)]
@@ -1596,6 +1701,83 @@ pub struct Limit {
     pub input: Arc<LogicalPlan>,
 }
 
+/// Produces a random sample of the rows of its input, as requested by
+/// `TABLESAMPLE`.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Sample {
+    /// How the rows are chosen
+    pub method: SampleMethod,
+    /// The size of the sample
+    pub size: SampleSize,
+    /// Seed of the random choice, which makes the sample repeatable.
+    /// None means a different sample is taken on every execution
+    pub seed: Option<u64>,
+    /// The logical plan
+    pub input: Arc<LogicalPlan>,
+}
+
+/// How the rows of a [`Sample`] are chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SampleMethod {
+    /// Every row is chosen independently
+    Bernoulli,
+    /// Blocks of rows, such as files, row groups or batches, are chosen
+    /// as a whole, which allows skipping the blocks that are not chosen
+    System,
+}
+
+impl Display for SampleMethod {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SampleMethod::Bernoulli => write!(f, "bernoulli"),
+            SampleMethod::System => write!(f, "system"),
+        }
+    }
+}
+
+/// The size of a [`Sample`]
+#[derive(Debug, Clone, Copy)]
+pub enum SampleSize {
+    /// Each row (or block) is chosen with the given percentage as
+    /// probability, between 0 and 100
+    Percent(f64),
+    /// A fixed number of rows is chosen, or all rows if the input has fewer
+    Rows(u64),
+}
+
+impl PartialEq for SampleSize {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (SampleSize::Percent(a), SampleSize::Percent(b)) => {
+                a.to_bits() == b.to_bits()
+            }
+            (SampleSize::Rows(a), SampleSize::Rows(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for SampleSize {}
+
+impl Hash for SampleSize {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            SampleSize::Percent(percent) => percent.to_bits().hash(state),
+            SampleSize::Rows(rows) => rows.hash(state),
+        }
+    }
+}
+
+impl Display for SampleSize {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SampleSize::Percent(percent) => write!(f, "{percent}%"),
+            SampleSize::Rows(rows) => write!(f, "{rows} rows"),
+        }
+    }
+}
+
 /// Removes duplicate rows from the input
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Distinct {
@@ -19,6 +19,8 @@ use std::fmt::{self, Display};
 
 use datafusion_common::DFSchemaRef;
 
+use crate::Expr;
+
 /// Various types of Statements.
 ///
 /// # Transactions:
@@ -34,6 +36,10 @@ pub enum Statement {
     TransactionEnd(TransactionEnd),
     /// Set a Variable
     SetVariable(SetVariable),
+    /// Execute a prepared statement
+    Execute(Execute),
+    /// Deallocate a prepared statement
+    Deallocate(Deallocate),
 }
 
 impl Statement {
@@ -43,6 +49,8 @@ impl Statement {
             Statement::TransactionStart(TransactionStart { schema, .. }) => schema,
             Statement::TransactionEnd(TransactionEnd { schema, .. }) => schema,
             Statement::SetVariable(SetVariable { schema, .. }) => schema,
+            Statement::Execute(Execute { schema, .. }) => schema,
+            Statement::Deallocate(Deallocate { schema, .. }) => schema,
         }
     }
 
@@ -53,6 +61,8 @@ impl Statement {
             Statement::TransactionStart(_) => "TransactionStart",
             Statement::TransactionEnd(_) => "TransactionEnd",
             Statement::SetVariable(_) => "SetVariable",
+            Statement::Execute(_) => "Execute",
+            Statement::Deallocate(_) => "Deallocate",
         }
     }
 
@@ -85,6 +95,19 @@ impl Statement {
                     }) => {
                         write!(f, "SetVariable: set {variable:?} to {value:?}")
                     }
+                    Statement::Execute(Execute {
+                        name, parameters, ..
+                    }) => {
+                        let parameters = parameters
+                            .iter()
+                            .map(|e| e.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        write!(f, "Execute: {name:?} [{parameters}]")
+                    }
+                    Statement::Deallocate(Deallocate { name, .. }) => {
+                        write!(f, "Deallocate: {name:?}")
+                    }
                 }
             }
         }
@@ -148,3 +171,24 @@ pub struct SetVariable {
     /// Dummy schema
     pub schema: DFSchemaRef,
 }
+
+/// Execute a statement previously prepared with `PREPARE`, binding the
+/// parameters (`$1`, `$2`, ...) to the given values
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Execute {
+    /// The name of the prepared statement
+    pub name: String,
+    /// The values of the parameters
+    pub parameters: Vec<Expr>,
+    /// Dummy schema
+    pub schema: DFSchemaRef,
+}
+
+/// Remove a statement previously prepared with `PREPARE`
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Deallocate {
+    /// The name of the prepared statement
+    pub name: String,
+    /// Dummy schema
+    pub schema: DFSchemaRef,
+}
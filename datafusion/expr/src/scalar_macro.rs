@@ -0,0 +1,109 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+//! Scalar macros are functions defined by a SQL expression, which are
+//! inlined into the expressions that call them.
+
+use crate::expr::Cast;
+use crate::{Expr, ExprSchemable};
+use arrow::compute::can_cast_types;
+use arrow::datatypes::DataType;
+use datafusion_common::tree_node::{Transformed, TreeNode};
+use datafusion_common::{DFSchema, DataFusionError, Result};
+
+/// Logical representation of a scalar function defined by an expression,
+/// as created by `CREATE FUNCTION`.
+///
+/// Unlike a [`ScalarUDF`](crate::ScalarUDF), a macro has no implementation
+/// of its own: calls of it are replaced by its body during planning, so that
+/// the optimizer sees through them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ScalarMacro {
+    /// name
+    pub name: String,
+    /// names and types of the arguments
+    pub args: Vec<(String, DataType)>,
+    /// Return type
+    pub return_type: DataType,
+    /// the body, which refers to the arguments as unqualified columns
+    /// and has the return type
+    pub body: Expr,
+    /// the SQL text of the body
+    pub definition: String,
+}
+
+impl ScalarMacro {
+    /// Create a new ScalarMacro
+    pub fn new(
+        name: impl Into<String>,
+        args: Vec<(String, DataType)>,
+        return_type: DataType,
+        body: Expr,
+        definition: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            args,
+            return_type,
+            body,
+            definition: definition.into(),
+        }
+    }
+
+    /// Returns the body of the macro with its arguments replaced by `args`,
+    /// which are cast to the argument types of the macro where needed.
+    ///
+    /// `schema` is the schema `args` are evaluated against.
+    pub fn inline(&self, args: Vec<Expr>, schema: &DFSchema) -> Result<Expr> {
+        if args.len() != self.args.len() {
+            return Err(DataFusionError::Plan(format!(
+                "Function '{}' expects {} arguments but received {}",
+                self.name,
+                self.args.len(),
+                args.len()
+            )));
+        }
+        let args = args
+            .into_iter()
+            .zip(&self.args)
+            .map(|(arg, (name, data_type))| {
+                let arg_type = arg.get_type(schema)?;
+                if &arg_type == data_type {
+                    Ok(arg)
+                } else if can_cast_types(&arg_type, data_type) {
+                    Ok(Expr::Cast(Cast::new(Box::new(arg), data_type.clone())))
+                } else {
+                    Err(DataFusionError::Plan(format!(
+                        "Cannot pass a value of type {arg_type} as argument '{name}' of type {data_type} of function '{}'",
+                        self.name
+                    )))
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.body.clone().transform_up(&|expr| {
+            Ok(match &expr {
+                Expr::Column(column) if column.relation.is_none() => {
+                    match self.args.iter().position(|(name, _)| name == &column.name) {
+                        Some(idx) => Transformed::Yes(args[idx].clone()),
+                        None => Transformed::No(expr),
+                    }
+                }
+                _ => Transformed::No(expr),
+            })
+        })
+    }
+}
@@ -275,12 +275,14 @@ impl TreeNode for Expr {
                 partition_by,
                 order_by,
                 window_frame,
+                ignore_nulls,
             }) => Expr::WindowFunction(WindowFunction::new(
                 fun,
                 transform_vec(args, &mut transform)?,
                 transform_vec(partition_by, &mut transform)?,
                 transform_vec(order_by, &mut transform)?,
                 window_frame,
+                ignore_nulls,
             )),
             Expr::AggregateFunction(AggregateFunction {
                 args,
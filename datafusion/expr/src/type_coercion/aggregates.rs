@@ -16,11 +16,12 @@
 // under the License.
 
 use arrow::datatypes::{
-    DataType, TimeUnit, DECIMAL128_MAX_PRECISION, DECIMAL128_MAX_SCALE,
+    DataType, Field, TimeUnit, DECIMAL128_MAX_PRECISION, DECIMAL128_MAX_SCALE,
     DECIMAL256_MAX_PRECISION, DECIMAL256_MAX_SCALE,
 };
 use datafusion_common::{DataFusionError, Result};
 use std::ops::Deref;
+use std::sync::Arc;
 
 use crate::{AggregateFunction, Signature, TypeSignature};
 
@@ -96,7 +97,54 @@ pub fn coerce_types(
         AggregateFunction::Count | AggregateFunction::ApproxDistinct => {
             Ok(input_types.to_vec())
         }
-        AggregateFunction::ArrayAgg => Ok(input_types.to_vec()),
+        AggregateFunction::ArrayAgg | AggregateFunction::Mode => Ok(input_types.to_vec()),
+        AggregateFunction::PercentileCont => {
+            // decimals are interpolated as decimals, other values as Float64
+            let value_type = match &input_types[0] {
+                DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => {
+                    input_types[0].clone()
+                }
+                t if is_percentile_cont_supported_arg_type(t) => DataType::Float64,
+                t => {
+                    return Err(DataFusionError::Plan(format!(
+                        "The function {agg_fun:?} does not support inputs of type {t:?}."
+                    )))
+                }
+            };
+            let fraction_type = coerce_percentile_fraction(agg_fun, &input_types[1])?;
+            Ok(vec![value_type, fraction_type])
+        }
+        AggregateFunction::PercentileDisc => {
+            if !is_percentile_disc_supported_arg_type(&input_types[0]) {
+                return Err(DataFusionError::Plan(format!(
+                    "The function {:?} does not support inputs of type {:?}.",
+                    agg_fun, input_types[0]
+                )));
+            }
+            let fraction_type = coerce_percentile_fraction(agg_fun, &input_types[1])?;
+            Ok(vec![input_types[0].clone(), fraction_type])
+        }
+        AggregateFunction::StringAgg => {
+            let value_type = match &input_types[0] {
+                DataType::Utf8 | DataType::LargeUtf8 => input_types[0].clone(),
+                DataType::Null => DataType::Utf8,
+                other => {
+                    return Err(DataFusionError::Plan(format!(
+                        "The function {agg_fun:?} does not support inputs of type {other:?}."
+                    )))
+                }
+            };
+            if !matches!(
+                input_types[1],
+                DataType::Utf8 | DataType::LargeUtf8 | DataType::Null
+            ) {
+                return Err(DataFusionError::Plan(format!(
+                    "The separator of {:?} must be a string, not {:?}.",
+                    agg_fun, input_types[1]
+                )));
+            }
+            Ok(vec![value_type, DataType::Utf8])
+        }
         AggregateFunction::Min | AggregateFunction::Max => {
             // min and max support the dictionary data type
             // unpack the dictionary to get the value
@@ -470,6 +518,20 @@ pub fn avg_sum_type(arg_type: &DataType) -> Result<DataType> {
     }
 }
 
+/// function return type of percentile_cont and percentile_disc: the type of a
+/// single percentile, or a list of it for a list of fractions
+pub fn percentile_return_type(
+    value_type: &DataType,
+    fraction_type: &DataType,
+) -> DataType {
+    match fraction_type {
+        DataType::List(_) => {
+            DataType::List(Arc::new(Field::new("item", value_type.clone(), true)))
+        }
+        _ => value_type.clone(),
+    }
+}
+
 pub fn is_bit_and_or_xor_support_arg_type(arg_type: &DataType) -> bool {
     NUMERICS.contains(arg_type)
 }
@@ -546,6 +608,49 @@ pub fn is_integer_arg_type(arg_type: &DataType) -> bool {
     )
 }
 
+/// Return `true` if `arg_type` is of a [`DataType`] that the
+/// [`AggregateFunction::PercentileCont`] aggregation can operate on.
+pub fn is_percentile_cont_supported_arg_type(arg_type: &DataType) -> bool {
+    NUMERICS.contains(arg_type)
+        || matches!(
+            arg_type,
+            DataType::Decimal128(_, _) | DataType::Decimal256(_, _) | DataType::Null
+        )
+}
+
+/// Return `true` if `arg_type` is of a [`DataType`] that the
+/// [`AggregateFunction::PercentileDisc`] aggregation can operate on.
+pub fn is_percentile_disc_supported_arg_type(arg_type: &DataType) -> bool {
+    is_percentile_cont_supported_arg_type(arg_type)
+        || STRINGS.contains(arg_type)
+        || TIMESTAMPS.contains(arg_type)
+        || DATES.contains(arg_type)
+        || TIMES.contains(arg_type)
+}
+
+/// Coerces the fraction argument of [`AggregateFunction::PercentileCont`] and
+/// [`AggregateFunction::PercentileDisc`], which is either a single fraction or
+/// a list of fractions, to Float64
+fn coerce_percentile_fraction(
+    agg_fun: &AggregateFunction,
+    fraction_type: &DataType,
+) -> Result<DataType> {
+    let (is_list, item_type) = match fraction_type {
+        DataType::List(field) => (true, field.data_type()),
+        t => (false, t),
+    };
+    if !can_coerce_from(&DataType::Float64, item_type) {
+        return Err(DataFusionError::Plan(format!(
+            "Could not coerce the percentile argument for {agg_fun:?} to Float64 or a list of Float64. Was {fraction_type:?}."
+        )));
+    }
+    Ok(if is_list {
+        DataType::List(Arc::new(Field::new("item", DataType::Float64, true)))
+    } else {
+        DataType::Float64
+    })
+}
+
 /// Return `true` if `arg_type` is of a [`DataType`] that the
 /// [`AggregateFunction::ApproxPercentileCont`] aggregation can operate on.
 pub fn is_approx_percentile_cont_supported_arg_type(arg_type: &DataType) -> bool {
@@ -646,6 +751,32 @@ mod tests {
             );
             assert_eq!(*input_type, result.unwrap());
         }
+
+        // StringAgg coerces the separator to Utf8
+        let fun = AggregateFunction::StringAgg;
+        let signature = fun.signature();
+        for (input_types, expected) in [
+            (
+                vec![DataType::Utf8, DataType::LargeUtf8],
+                vec![DataType::Utf8, DataType::Utf8],
+            ),
+            (
+                vec![DataType::LargeUtf8, DataType::Null],
+                vec![DataType::LargeUtf8, DataType::Utf8],
+            ),
+            (
+                vec![DataType::Null, DataType::Utf8],
+                vec![DataType::Utf8, DataType::Utf8],
+            ),
+        ] {
+            let result = coerce_types(&fun, &input_types, &signature);
+            assert_eq!(expected, result.unwrap());
+        }
+        let result = coerce_types(&fun, &[DataType::Int32, DataType::Utf8], &signature);
+        assert_eq!(
+            "Error during planning: The function StringAgg does not support inputs of type Int32.",
+            result.unwrap_err().to_string()
+        );
     }
 
     #[test]
@@ -113,6 +113,7 @@ impl WindowUDF {
             partition_by,
             order_by,
             window_frame,
+            ignore_nulls: false,
         })
     }
 }
@@ -21,13 +21,13 @@ use crate::expr::{Alias, Sort, WindowFunction};
 use crate::logical_plan::builder::build_join_schema;
 use crate::logical_plan::{
     Aggregate, Analyze, Distinct, Extension, Filter, Join, Limit, Partitioning, Prepare,
-    Projection, Repartition, Sort as SortPlan, Subquery, SubqueryAlias, Union, Unnest,
-    Values, Window,
+    Projection, RecursiveQuery, Repartition, Sample, Sort as SortPlan, Subquery,
+    SubqueryAlias, Union, Unnest, Values, Window,
 };
 use crate::{
-    BinaryExpr, Cast, CreateMemoryTable, CreateView, DdlStatement, DmlStatement, Expr,
-    ExprSchemable, GroupingSet, LogicalPlan, LogicalPlanBuilder, Operator, TableScan,
-    TryCast,
+    BinaryExpr, Cast, CopyTo, CreateMemoryTable, CreateView, DdlStatement, DmlStatement,
+    Expr, ExprSchemable, GroupingSet, LogicalPlan, LogicalPlanBuilder, Operator,
+    TableScan, TryCast,
 };
 use arrow::datatypes::{DataType, TimeUnit};
 use datafusion_common::tree_node::{
@@ -752,6 +752,19 @@ pub fn from_plan(
             op: op.clone(),
             input: Arc::new(inputs[0].clone()),
         })),
+        LogicalPlan::Copy(CopyTo {
+            input: _,
+            output_url,
+            file_format,
+            single_file_output,
+            options,
+        }) => Ok(LogicalPlan::Copy(CopyTo {
+            input: Arc::new(inputs[0].clone()),
+            output_url: output_url.clone(),
+            file_format: *file_format,
+            single_file_output: *single_file_output,
+            options: options.clone(),
+        })),
         LogicalPlan::Values(Values { schema, .. }) => Ok(LogicalPlan::Values(Values {
             schema: schema.clone(),
             values: expr
@@ -915,6 +928,14 @@ pub fn from_plan(
             fetch: *fetch,
             input: Arc::new(inputs[0].clone()),
         })),
+        LogicalPlan::Sample(Sample {
+            method, size, seed, ..
+        }) => Ok(LogicalPlan::Sample(Sample {
+            method: *method,
+            size: *size,
+            seed: *seed,
+            input: Arc::new(inputs[0].clone()),
+        })),
         LogicalPlan::Ddl(DdlStatement::CreateMemoryTable(CreateMemoryTable {
             name,
             if_not_exists,
@@ -1000,6 +1021,14 @@ pub fn from_plan(
             Ok(plan.clone())
         }
         LogicalPlan::DescribeTable(_) => Ok(plan.clone()),
+        LogicalPlan::RecursiveQuery(RecursiveQuery {
+            name, is_distinct, ..
+        }) => Ok(LogicalPlan::RecursiveQuery(RecursiveQuery {
+            name: name.clone(),
+            static_term: Arc::new(inputs[0].clone()),
+            recursive_term: Arc::new(inputs[1].clone()),
+            is_distinct: *is_distinct,
+        })),
         LogicalPlan::Unnest(Unnest { column, schema, .. }) => {
             // Update schema with unnested column type.
             let input = Arc::new(inputs[0].clone());
@@ -1298,6 +1327,46 @@ pub fn find_valid_equijoin_key_pair(
     Ok(join_key_pair)
 }
 
+/// Splits the match condition of an ASOF join into the expression on the left
+/// input, the comparison operator and the expression on the right input.
+///
+/// A valid match condition is a single `>=`, `>`, `<=` or `<` comparison between
+/// an expression on the left input and an expression on the right input, for
+/// instance `trades.ts >= quotes.ts`. The returned operator is oriented so
+/// that the left expression is its left operand.
+pub fn find_asof_match_condition(
+    filter: &Expr,
+    left_schema: DFSchemaRef,
+    right_schema: DFSchemaRef,
+) -> Result<(Expr, Operator, Expr)> {
+    let invalid = || {
+        DataFusionError::Plan(format!(
+            "ASOF join requires a match condition comparing an expression on each \
+             input with one of >=, >, <=, <, got {filter}"
+        ))
+    };
+    let Expr::BinaryExpr(BinaryExpr { left, op, right }) = filter else {
+        return Err(invalid());
+    };
+    if !matches!(
+        op,
+        Operator::GtEq | Operator::Gt | Operator::LtEq | Operator::Lt
+    ) {
+        return Err(invalid());
+    }
+
+    let (left_expr, right_expr) =
+        find_valid_equijoin_key_pair(left, right, left_schema, right_schema)?
+            .ok_or_else(invalid)?;
+    if &left_expr == left.as_ref() {
+        Ok((left_expr, *op, right_expr))
+    } else {
+        // the operands were swapped, e.g. `quotes.ts <= trades.ts`
+        let op = op.swap().ok_or_else(invalid)?;
+        Ok((left_expr, op, right_expr))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1323,6 +1392,7 @@ mod tests {
             vec![],
             vec![],
             WindowFrame::new(false),
+            false,
         ));
         let max2 = Expr::WindowFunction(expr::WindowFunction::new(
             WindowFunction::AggregateFunction(AggregateFunction::Max),
@@ -1330,6 +1400,7 @@ mod tests {
             vec![],
             vec![],
             WindowFrame::new(false),
+            false,
         ));
         let min3 = Expr::WindowFunction(expr::WindowFunction::new(
             WindowFunction::AggregateFunction(AggregateFunction::Min),
@@ -1337,6 +1408,7 @@ mod tests {
             vec![],
             vec![],
             WindowFrame::new(false),
+            false,
         ));
         let sum4 = Expr::WindowFunction(expr::WindowFunction::new(
             WindowFunction::AggregateFunction(AggregateFunction::Sum),
@@ -1344,6 +1416,7 @@ mod tests {
             vec![],
             vec![],
             WindowFrame::new(false),
+            false,
         ));
         let exprs = &[max1.clone(), max2.clone(), min3.clone(), sum4.clone()];
         let result = group_window_expr_by_sort_keys(exprs)?;
@@ -1366,6 +1439,7 @@ mod tests {
             vec![],
             vec![age_asc.clone(), name_desc.clone()],
             WindowFrame::new(true),
+            false,
         ));
         let max2 = Expr::WindowFunction(expr::WindowFunction::new(
             WindowFunction::AggregateFunction(AggregateFunction::Max),
@@ -1373,6 +1447,7 @@ mod tests {
             vec![],
             vec![],
             WindowFrame::new(false),
+            false,
         ));
         let min3 = Expr::WindowFunction(expr::WindowFunction::new(
             WindowFunction::AggregateFunction(AggregateFunction::Min),
@@ -1380,6 +1455,7 @@ mod tests {
             vec![],
             vec![age_asc.clone(), name_desc.clone()],
             WindowFrame::new(true),
+            false,
         ));
         let sum4 = Expr::WindowFunction(expr::WindowFunction::new(
             WindowFunction::AggregateFunction(AggregateFunction::Sum),
@@ -1387,6 +1463,7 @@ mod tests {
             vec![],
             vec![name_desc.clone(), age_asc.clone(), created_at_desc.clone()],
             WindowFrame::new(true),
+            false,
         ));
         // FIXME use as_ref
         let exprs = &[max1.clone(), max2.clone(), min3.clone(), sum4.clone()];
@@ -1421,6 +1498,7 @@ mod tests {
                     Expr::Sort(expr::Sort::new(Box::new(col("name")), false, true)),
                 ],
                 WindowFrame::new(true),
+                false,
             )),
             Expr::WindowFunction(expr::WindowFunction::new(
                 WindowFunction::AggregateFunction(AggregateFunction::Sum),
@@ -1432,6 +1510,7 @@ mod tests {
                     Expr::Sort(expr::Sort::new(Box::new(col("created_at")), false, true)),
                 ],
                 WindowFrame::new(true),
+                false,
             )),
         ];
         let expected = vec![
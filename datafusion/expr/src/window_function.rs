@@ -229,6 +229,20 @@ impl WindowFunction {
             WindowFunction::WindowUDF(fun) => fun.signature.clone(),
         }
     }
+
+    /// Whether the function can skip NULL values of its argument (`IGNORE NULLS`)
+    pub fn supports_ignore_nulls(&self) -> bool {
+        matches!(
+            self,
+            WindowFunction::BuiltInWindowFunction(
+                BuiltInWindowFunction::Lag
+                    | BuiltInWindowFunction::Lead
+                    | BuiltInWindowFunction::FirstValue
+                    | BuiltInWindowFunction::LastValue
+                    | BuiltInWindowFunction::NthValue
+            )
+        )
+    }
 }
 
 /// the signatures supported by the built-in window function `fun`.
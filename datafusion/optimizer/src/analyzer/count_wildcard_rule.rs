@@ -128,6 +128,7 @@ impl TreeNodeRewriter for CountWildcardRewriter {
                 partition_by,
                 order_by,
                 window_frame,
+                ignore_nulls,
             }) if args.len() == 1 => match args[0] {
                 Expr::Wildcard => Expr::WindowFunction(expr::WindowFunction {
                     fun: window_function::WindowFunction::AggregateFunction(
@@ -137,6 +138,7 @@ impl TreeNodeRewriter for CountWildcardRewriter {
                     partition_by,
                     order_by,
                     window_frame,
+                    ignore_nulls,
                 }),
 
                 _ => old_expr,
@@ -346,6 +348,7 @@ mod tests {
                     ))),
                     end_bound: WindowFrameBound::Following(ScalarValue::UInt32(Some(2))),
                 },
+                false,
             ))])?
             .project(vec![count(Expr::Wildcard)])?
             .build()?;
@@ -215,7 +215,7 @@ fn check_inner_plan(
                 })?;
                 Ok(())
             }
-            JoinType::Left | JoinType::LeftSemi | JoinType::LeftAnti => {
+            JoinType::Left | JoinType::LeftSemi | JoinType::LeftAnti | JoinType::AsOf => {
                 check_inner_plan(left, is_scalar, is_aggregate, can_contain_outer_ref)?;
                 check_inner_plan(right, is_scalar, is_aggregate, false)
             }
@@ -0,0 +1,936 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`DecorrelateLateralJoin`] rewrites correlated `LATERAL` subqueries into joins
+
+use crate::decorrelate::PullUpCorrelatedExpr;
+use crate::optimizer::ApplyOrder;
+use crate::utils::{conjunction, replace_qualified_name, split_conjunction};
+use crate::{OptimizerConfig, OptimizerRule};
+use datafusion_common::tree_node::{Transformed, TreeNode, VisitRecursion};
+use datafusion_common::{Column, DataFusionError, Result};
+use datafusion_expr::expr;
+use datafusion_expr::logical_plan::builder::build_join_schema;
+use datafusion_expr::logical_plan::{
+    Aggregate, CrossJoin, Distinct, Filter, Join, JoinType, Projection, Sort, Subquery,
+    SubqueryAlias, Window,
+};
+use datafusion_expr::{
+    col, lit, when, AggregateFunction, BinaryExpr, BuiltInWindowFunction, Expr,
+    LogicalPlan, LogicalPlanBuilder, Operator, WindowFrame, WindowFunction,
+};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+/// Optimizer rule for rewriting correlated `LATERAL` subqueries to joins.
+///
+/// Takes a query like:
+///
+/// ```text
+/// select c.id, o.total from customers c,
+///     lateral (select total from orders where orders.c_id = c.id) o
+/// ```
+///
+/// and optimizes it into:
+///
+/// ```text
+/// select c.id, o.total from customers c
+/// join (select total, c_id from orders) o on o.c_id = c.id
+/// ```
+///
+/// If the correlated predicates of the subquery can't be evaluated as a join
+/// filter, for example because it applies a `LIMIT` to the correlated rows,
+/// the subquery is evaluated for all the distinct values of its outer
+/// references at once, and joined on those values:
+///
+/// ```text
+/// select c.id, o.total from customers c,
+///     lateral (select total from orders where orders.c_id = c.id
+///              order by total desc limit 2) o
+/// ```
+///
+/// is optimized into:
+///
+/// ```text
+/// select c.id, o.total from customers c
+/// join (select total, __lateral_outer.c_id from orders
+///       join (select distinct c.id as c_id from customers c) __lateral_outer
+///       on orders.c_id = __lateral_outer.c_id
+///       qualify row_number() over (partition by __lateral_outer.c_id
+///                                  order by total desc) <= 2) o
+/// on c.id is not distinct from o.c_id
+/// ```
+#[derive(Default)]
+pub struct DecorrelateLateralJoin {}
+
+impl DecorrelateLateralJoin {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl OptimizerRule for DecorrelateLateralJoin {
+    fn try_optimize(
+        &self,
+        plan: &LogicalPlan,
+        _config: &dyn OptimizerConfig,
+    ) -> Result<Option<LogicalPlan>> {
+        match plan {
+            LogicalPlan::Join(Join {
+                left,
+                right,
+                on,
+                filter,
+                join_type: join_type @ (JoinType::Inner | JoinType::Left),
+                ..
+            }) if on.is_empty() => match right.as_ref() {
+                LogicalPlan::Subquery(subquery) => {
+                    decorrelate(plan, left, subquery, *join_type, filter.clone())
+                }
+                _ => Ok(None),
+            },
+            LogicalPlan::CrossJoin(CrossJoin { left, right, .. }) => match right.as_ref()
+            {
+                LogicalPlan::Subquery(subquery) => {
+                    decorrelate(plan, left, subquery, JoinType::Inner, None)
+                }
+                _ => Ok(None),
+            },
+            _ => Ok(None),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "decorrelate_lateral_join"
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::TopDown)
+    }
+}
+
+/// Joins `left` with `subquery`, using the pulled up correlated predicates of
+/// the subquery as the join filter if possible, or else the values of its outer
+/// references. Returns `None` if the subquery can't be decorrelated.
+fn decorrelate(
+    plan: &LogicalPlan,
+    left: &LogicalPlan,
+    subquery: &Subquery,
+    join_type: JoinType,
+    filter: Option<Expr>,
+) -> Result<Option<LogicalPlan>> {
+    match build_join(plan, left, subquery, join_type, filter.clone())? {
+        Some(new_plan) => Ok(Some(new_plan)),
+        None => build_outer_values_join(plan, left, subquery, join_type, filter),
+    }
+}
+
+/// Joins `left` with the decorrelated `subquery`, using the pulled up correlated
+/// predicates as the join filter. Returns `None` if the subquery can't be decorrelated.
+fn build_join(
+    plan: &LogicalPlan,
+    left: &LogicalPlan,
+    subquery: &Subquery,
+    join_type: JoinType,
+    filter: Option<Expr>,
+) -> Result<Option<LogicalPlan>> {
+    let subquery_plan = subquery.subquery.as_ref();
+    if !can_pull_up_correlated_exprs(subquery_plan) {
+        return Ok(None);
+    }
+    // all the outer references must be resolved by the left side of the join
+    let references_left = subquery.outer_ref_columns.iter().all(|expr| match expr {
+        Expr::OuterReferenceColumn(_, col) => left.schema().has_column(col),
+        _ => false,
+    });
+    if !references_left {
+        return Ok(None);
+    }
+
+    let mut pull_up = PullUpCorrelatedExpr {
+        join_filters: vec![],
+        correlated_subquery_cols_map: Default::default(),
+        in_predicate_opt: None,
+        exists_sub_query: false,
+        can_pull_up: true,
+        need_handle_count_bug: false,
+        collected_count_expr_map: Default::default(),
+        pull_up_having_expr: None,
+    };
+    let new_plan = subquery_plan.clone().rewrite(&mut pull_up)?;
+    if !pull_up.can_pull_up || !new_plan.all_out_ref_exprs().is_empty() {
+        return Ok(None);
+    }
+
+    // the pulled up predicates reference the columns of the subquery's relations,
+    // which have to be qualified by the alias of the subquery if there is one
+    let join_filter = match conjunction(pull_up.join_filters) {
+        Some(join_filter) => match &new_plan {
+            LogicalPlan::SubqueryAlias(alias) => {
+                let mut all_correlated_cols = BTreeSet::new();
+                pull_up
+                    .correlated_subquery_cols_map
+                    .values()
+                    .for_each(|cols| all_correlated_cols.extend(cols.clone()));
+                Some(replace_qualified_name(
+                    join_filter,
+                    &all_correlated_cols,
+                    &alias.alias.to_string(),
+                )?)
+            }
+            _ => Some(join_filter),
+        },
+        None => None,
+    };
+    let join_filter = conjunction(join_filter.into_iter().chain(filter));
+
+    // the decorrelated subquery may expose additional columns for the join filter,
+    // project them away to preserve the schema of the original join
+    let output_exprs = plan
+        .schema()
+        .fields()
+        .iter()
+        .map(|field| Expr::Column(field.qualified_column()))
+        .collect::<Vec<_>>();
+    let new_plan = LogicalPlanBuilder::from(left.clone())
+        .join(
+            new_plan,
+            join_type,
+            (Vec::<Column>::new(), Vec::<Column>::new()),
+            join_filter,
+        )?
+        .project(output_exprs)?
+        .build()?;
+    Ok(Some(new_plan))
+}
+
+/// The alias of the distinct values of the outer references of a subquery
+const OUTER_VALUES_ALIAS: &str = "__lateral_outer";
+/// The alias of an aggregate without grouping evaluated for every set of
+/// outer values
+const AGGREGATE_ALIAS: &str = "__lateral_aggregate";
+/// The name of the column numbering the rows for each set of outer values
+const ROW_NUMBER: &str = "__lateral_row_number";
+/// The name of the column telling apart the outer values without rows in an
+/// aggregate without grouping
+const ALWAYS_TRUE: &str = "__always_true";
+
+/// Joins `left` with `subquery` evaluated for every distinct set of values
+/// of its outer references, on those values. Returns `None` if the subquery
+/// holds an operator that can't be evaluated this way.
+fn build_outer_values_join(
+    plan: &LogicalPlan,
+    left: &LogicalPlan,
+    subquery: &Subquery,
+    join_type: JoinType,
+    filter: Option<Expr>,
+) -> Result<Option<LogicalPlan>> {
+    let mut outer_refs = vec![];
+    for expr in &subquery.outer_ref_columns {
+        match expr {
+            Expr::OuterReferenceColumn(_, col) if left.schema().has_column(col) => {
+                if !outer_refs.contains(col) {
+                    outer_refs.push(col.clone());
+                }
+            }
+            _ => return Ok(None),
+        }
+    }
+    let outer_values = LogicalPlanBuilder::from(left.clone())
+        .project(
+            outer_refs
+                .iter()
+                .map(|col| Expr::Column(col.clone()).alias(outer_value_name(col))),
+        )?
+        .distinct()?
+        .alias(OUTER_VALUES_ALIAS)?
+        .build()?;
+    let rewriter = OuterValuesRewriter {
+        outer_refs: &outer_refs,
+        outer_values,
+    };
+    let Some((new_plan, value_columns)) = rewriter.rewrite(&subquery.subquery)? else {
+        return Ok(None);
+    };
+
+    let output_exprs = plan
+        .schema()
+        .fields()
+        .iter()
+        .map(|field| Expr::Column(field.qualified_column()))
+        .collect::<Vec<_>>();
+    let new_plan = LogicalPlanBuilder::from(left.clone())
+        .join_detailed(
+            new_plan,
+            join_type,
+            (outer_refs, value_columns),
+            filter,
+            true,
+        )?
+        .project(output_exprs)?
+        .build()?;
+    Ok(Some(new_plan))
+}
+
+/// The name of the column holding the values of the outer reference `col`
+fn outer_value_name(col: &Column) -> String {
+    col.flat_name().replace('.', "_")
+}
+
+/// A rewritten plan, along with its columns holding the values of the outer
+/// references
+type OuterValuesPlan = Option<(LogicalPlan, Vec<Column>)>;
+
+/// Rewrites the plan of a subquery to evaluate it for every set of values
+/// of its outer references, by joining those values into the operators that
+/// reference them, and carrying them up to the root of the plan
+struct OuterValuesRewriter<'a> {
+    /// The outer references of the subquery
+    outer_refs: &'a [Column],
+    /// The distinct values of the outer references
+    outer_values: LogicalPlan,
+}
+
+impl OuterValuesRewriter<'_> {
+    /// The columns of `outer_values`
+    fn outer_value_columns(&self) -> Vec<Column> {
+        self.outer_refs
+            .iter()
+            .map(|col| Column::new(Some(OUTER_VALUES_ALIAS), outer_value_name(col)))
+            .collect()
+    }
+
+    /// Rewrites `plan` if it holds outer references, or else joins it with
+    /// all the outer values
+    fn rewrite_input(&self, plan: &LogicalPlan) -> Result<OuterValuesPlan> {
+        if !plan.all_out_ref_exprs().is_empty() {
+            return self.rewrite(plan);
+        }
+        let plan = LogicalPlanBuilder::from(plan.clone())
+            .cross_join(self.outer_values.clone())?
+            .build()?;
+        Ok(Some((plan, self.outer_value_columns())))
+    }
+
+    /// Rewrites `plan`, which holds outer references
+    fn rewrite(&self, plan: &LogicalPlan) -> Result<OuterValuesPlan> {
+        if plan.expressions().iter().any(contains_subquery) {
+            return Ok(None);
+        }
+        match plan {
+            LogicalPlan::Filter(filter) => {
+                let Some((input, values)) = self.rewrite_input(&filter.input)? else {
+                    return Ok(None);
+                };
+                let predicate = self.replace(filter.predicate.clone(), &values, false)?;
+                let filter = Filter::try_new(predicate, Arc::new(input))?;
+                Ok(Some((LogicalPlan::Filter(filter), values)))
+            }
+            LogicalPlan::Projection(projection) => {
+                let Some((input, values)) = self.rewrite_input(&projection.input)? else {
+                    return Ok(None);
+                };
+                let mut exprs = self.replace_all(&projection.expr, &values, true)?;
+                exprs.extend(values.iter().cloned().map(Expr::Column));
+                let projection = Projection::try_new(exprs, Arc::new(input))?;
+                Ok(Some((LogicalPlan::Projection(projection), values)))
+            }
+            LogicalPlan::SubqueryAlias(alias) => {
+                let Some((input, values)) = self.rewrite_input(&alias.input)? else {
+                    return Ok(None);
+                };
+                let values = values
+                    .into_iter()
+                    .map(|col| Column::new(Some(alias.alias.clone()), col.name))
+                    .collect();
+                let alias = SubqueryAlias::try_new(input, alias.alias.clone())?;
+                Ok(Some((LogicalPlan::SubqueryAlias(alias), values)))
+            }
+            LogicalPlan::Distinct(distinct) => {
+                let Some((input, values)) = self.rewrite_input(&distinct.input)? else {
+                    return Ok(None);
+                };
+                let distinct = Distinct {
+                    input: Arc::new(input),
+                };
+                Ok(Some((LogicalPlan::Distinct(distinct), values)))
+            }
+            LogicalPlan::Aggregate(aggregate) => self.rewrite_aggregate(aggregate),
+            LogicalPlan::Window(window) => {
+                let Some((input, values)) = self.rewrite_input(&window.input)? else {
+                    return Ok(None);
+                };
+                // the rows are partitioned by outer values
+                let mut window_exprs = Vec::with_capacity(window.window_expr.len());
+                for expr in &window.window_expr {
+                    let name = expr.display_name()?;
+                    let Expr::WindowFunction(mut window_function) =
+                        expr.clone().unalias()
+                    else {
+                        return Ok(None);
+                    };
+                    window_function
+                        .partition_by
+                        .extend(values.iter().cloned().map(Expr::Column));
+                    let expr = Expr::WindowFunction(window_function);
+                    window_exprs.push(self.replace(expr, &values, false)?.alias(name));
+                }
+                let window = Window::try_new(window_exprs, Arc::new(input))?;
+                Ok(Some((LogicalPlan::Window(window), values)))
+            }
+            LogicalPlan::Sort(sort) => match sort.fetch {
+                Some(fetch) => {
+                    self.rewrite_limit(&sort.expr, &sort.input, 0, Some(fetch))
+                }
+                None => {
+                    let Some((input, values)) = self.rewrite_input(&sort.input)? else {
+                        return Ok(None);
+                    };
+                    let sort = Sort {
+                        expr: self.replace_all(&sort.expr, &values, false)?,
+                        input: Arc::new(input),
+                        fetch: None,
+                    };
+                    Ok(Some((LogicalPlan::Sort(sort), values)))
+                }
+            },
+            LogicalPlan::Limit(limit) => match limit.input.as_ref() {
+                LogicalPlan::Sort(sort) => {
+                    let fetch = match (limit.fetch, sort.fetch) {
+                        (Some(fetch), Some(sort_fetch)) => {
+                            Some(fetch.min(sort_fetch.saturating_sub(limit.skip)))
+                        }
+                        (fetch, None) => fetch,
+                        (None, Some(sort_fetch)) => {
+                            Some(sort_fetch.saturating_sub(limit.skip))
+                        }
+                    };
+                    self.rewrite_limit(&sort.expr, &sort.input, limit.skip, fetch)
+                }
+                input => self.rewrite_limit(&[], input, limit.skip, limit.fetch),
+            },
+            LogicalPlan::CrossJoin(join) => {
+                let left_holds_outer = !join.left.all_out_ref_exprs().is_empty();
+                let right_holds_outer = !join.right.all_out_ref_exprs().is_empty();
+                let (new_plan, values) = match (left_holds_outer, right_holds_outer) {
+                    (true, false) => {
+                        let Some((left, values)) = self.rewrite(&join.left)? else {
+                            return Ok(None);
+                        };
+                        let new_plan = LogicalPlanBuilder::from(left)
+                            .cross_join(join.right.as_ref().clone())?
+                            .build()?;
+                        (new_plan, values)
+                    }
+                    (false, true) => {
+                        let Some((right, values)) = self.rewrite(&join.right)? else {
+                            return Ok(None);
+                        };
+                        let new_plan =
+                            LogicalPlanBuilder::from(join.left.as_ref().clone())
+                                .cross_join(right)?
+                                .build()?;
+                        (new_plan, values)
+                    }
+                    _ => return Ok(None),
+                };
+                Ok(Some((new_plan, values)))
+            }
+            LogicalPlan::Join(join) => self.rewrite_join(join),
+            _ => Ok(None),
+        }
+    }
+
+    /// Rewrites an aggregate, which computes a group for every set of outer
+    /// values
+    fn rewrite_aggregate(&self, aggregate: &Aggregate) -> Result<OuterValuesPlan> {
+        if aggregate
+            .group_expr
+            .iter()
+            .any(|expr| matches!(expr, Expr::GroupingSet(_)))
+        {
+            return Ok(None);
+        }
+        let Some((input, values)) = self.rewrite_input(&aggregate.input)? else {
+            return Ok(None);
+        };
+        let mut group_expr = self.replace_all(&aggregate.group_expr, &values, true)?;
+        group_expr.extend(values.iter().cloned().map(Expr::Column));
+        let aggr_expr = self.replace_all(&aggregate.aggr_expr, &values, true)?;
+        if !aggregate.group_expr.is_empty() {
+            let aggregate = Aggregate::try_new(Arc::new(input), group_expr, aggr_expr)?;
+            return Ok(Some((LogicalPlan::Aggregate(aggregate), values)));
+        }
+
+        // without grouping, the aggregate produces a row even for the outer
+        // values without rows, so its groups are left joined to the outer
+        // values, and the counts of the outer values without rows are 0
+        group_expr.push(lit(true).alias(ALWAYS_TRUE));
+        let aggregated_values = values
+            .iter()
+            .map(|col| Column::new(Some(AGGREGATE_ALIAS), &col.name))
+            .collect::<Vec<_>>();
+        let aggregated = LogicalPlanBuilder::from(input)
+            .aggregate(group_expr, aggr_expr)?
+            .alias(AGGREGATE_ALIAS)?
+            .build()?;
+        let mut exprs = aggregate
+            .aggr_expr
+            .iter()
+            .zip(aggregate.schema.fields())
+            .map(|(expr, field)| {
+                let column =
+                    Expr::Column(Column::new(Some(AGGREGATE_ALIAS), field.name()));
+                let expr = match expr.clone().unalias() {
+                    Expr::AggregateFunction(expr::AggregateFunction {
+                        fun: AggregateFunction::Count,
+                        ..
+                    }) => when(
+                        Expr::Column(Column::new(Some(AGGREGATE_ALIAS), ALWAYS_TRUE))
+                            .is_null(),
+                        lit(0_i64),
+                    )
+                    .otherwise(column)?,
+                    _ => column,
+                };
+                Ok(expr.alias(field.name()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let values = self.outer_value_columns();
+        exprs.extend(values.iter().cloned().map(Expr::Column));
+        let new_plan = LogicalPlanBuilder::from(self.outer_values.clone())
+            .join_detailed(
+                aggregated,
+                JoinType::Left,
+                (values.clone(), aggregated_values),
+                None,
+                true,
+            )?
+            .project(exprs)?
+            .build()?;
+        Ok(Some((new_plan, values)))
+    }
+
+    /// Rewrites a limit of the rows of `input`, sorted by `order_by`, into a
+    /// limit of the rows for every set of outer values
+    fn rewrite_limit(
+        &self,
+        order_by: &[Expr],
+        input: &LogicalPlan,
+        skip: usize,
+        fetch: Option<usize>,
+    ) -> Result<OuterValuesPlan> {
+        let Some((input, values)) = self.rewrite_input(input)? else {
+            return Ok(None);
+        };
+        let order_by = self.replace_all(order_by, &values, false)?;
+        let row_number = Expr::WindowFunction(expr::WindowFunction::new(
+            WindowFunction::BuiltInWindowFunction(BuiltInWindowFunction::RowNumber),
+            vec![],
+            values.iter().cloned().map(Expr::Column).collect(),
+            order_by.clone(),
+            WindowFrame::new(!order_by.is_empty()),
+            false,
+        ))
+        .alias(ROW_NUMBER);
+        let mut predicate = col(ROW_NUMBER).gt(lit(skip as u64));
+        if let Some(fetch) = fetch {
+            predicate = predicate.and(col(ROW_NUMBER).lt_eq(lit((skip + fetch) as u64)));
+        }
+        let exprs = input
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| Expr::Column(field.qualified_column()))
+            .collect::<Vec<_>>();
+        let new_plan = LogicalPlanBuilder::from(input)
+            .window(vec![row_number])?
+            .filter(predicate)?
+            .project(exprs)?
+            .build()?;
+        Ok(Some((new_plan, values)))
+    }
+
+    /// Rewrites a join, joining the outer values into the input whose rows are
+    /// preserved
+    fn rewrite_join(&self, join: &Join) -> Result<OuterValuesPlan> {
+        let left_holds_outer = !join.left.all_out_ref_exprs().is_empty();
+        let right_holds_outer = !join.right.all_out_ref_exprs().is_empty();
+        let rewrite_left = match join.join_type {
+            JoinType::Inner => !right_holds_outer,
+            JoinType::Left | JoinType::LeftSemi | JoinType::LeftAnti => true,
+            JoinType::Right | JoinType::RightSemi | JoinType::RightAnti => false,
+            JoinType::Full | JoinType::AsOf => return Ok(None),
+        };
+        let (left, right, values) = if rewrite_left {
+            if right_holds_outer {
+                return Ok(None);
+            }
+            let Some((left, values)) = self.rewrite_input(&join.left)? else {
+                return Ok(None);
+            };
+            (left, join.right.as_ref().clone(), values)
+        } else {
+            if left_holds_outer {
+                return Ok(None);
+            }
+            let Some((right, values)) = self.rewrite_input(&join.right)? else {
+                return Ok(None);
+            };
+            (join.left.as_ref().clone(), right, values)
+        };
+        let on = join
+            .on
+            .iter()
+            .map(|(l, r)| {
+                Ok((
+                    self.replace(l.clone(), &values, false)?,
+                    self.replace(r.clone(), &values, false)?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let filter = join
+            .filter
+            .clone()
+            .map(|filter| self.replace(filter, &values, false))
+            .transpose()?;
+        let schema = build_join_schema(left.schema(), right.schema(), &join.join_type)?;
+        let join = Join {
+            left: Arc::new(left),
+            right: Arc::new(right),
+            on,
+            filter,
+            join_type: join.join_type,
+            join_constraint: join.join_constraint,
+            schema: Arc::new(schema),
+            null_equals_null: join.null_equals_null,
+        };
+        Ok(Some((LogicalPlan::Join(join), values)))
+    }
+
+    /// Replaces the outer references of `expr` with the `values` columns,
+    /// keeping the name of `expr` if `keep_name` is true
+    fn replace(&self, expr: Expr, values: &[Column], keep_name: bool) -> Result<Expr> {
+        let name = keep_name.then(|| expr.display_name()).transpose()?;
+        let new_expr = expr.transform_up(&|expr| match expr {
+            Expr::OuterReferenceColumn(_, col) => {
+                match self.outer_refs.iter().position(|c| *c == col) {
+                    Some(index) => {
+                        Ok(Transformed::Yes(Expr::Column(values[index].clone())))
+                    }
+                    None => Err(DataFusionError::Internal(format!(
+                        "No value for the outer reference {col}"
+                    ))),
+                }
+            }
+            _ => Ok(Transformed::No(expr)),
+        })?;
+        match name {
+            Some(name) if new_expr.display_name()? != name => Ok(new_expr.alias(name)),
+            _ => Ok(new_expr),
+        }
+    }
+
+    /// Replaces the outer references of `exprs` with the `values` columns
+    fn replace_all(
+        &self,
+        exprs: &[Expr],
+        values: &[Column],
+        keep_names: bool,
+    ) -> Result<Vec<Expr>> {
+        exprs
+            .iter()
+            .map(|expr| self.replace(expr.clone(), values, keep_names))
+            .collect()
+    }
+}
+
+/// Returns true if `expr` holds a subquery
+fn contains_subquery(expr: &Expr) -> bool {
+    let mut found = false;
+    expr.apply(&mut |expr| {
+        found = matches!(
+            expr,
+            Expr::ScalarSubquery(_) | Expr::Exists(_) | Expr::InSubquery(_)
+        );
+        Ok(if found {
+            VisitRecursion::Stop
+        } else {
+            VisitRecursion::Continue
+        })
+    })
+    .ok();
+    found
+}
+
+/// Returns true if every operator of `plan` that holds outer references,
+/// directly or in its inputs, preserves the correlated predicates when they
+/// are pulled up into a join filter.
+fn can_pull_up_correlated_exprs(plan: &LogicalPlan) -> bool {
+    if plan.all_out_ref_exprs().is_empty() {
+        return true;
+    }
+    let holds_outer = plan.expressions().iter().any(|expr| expr.contains_outer());
+    match plan {
+        LogicalPlan::Filter(filter) => can_pull_up_correlated_exprs(&filter.input),
+        LogicalPlan::SubqueryAlias(alias) => can_pull_up_correlated_exprs(&alias.input),
+        LogicalPlan::Projection(projection) => {
+            !holds_outer && can_pull_up_correlated_exprs(&projection.input)
+        }
+        // an aggregate without grouping produces a row for every outer row,
+        // even if there are no correlated rows, and the correlated columns
+        // only split the groups by outer row if they are compared for equality
+        LogicalPlan::Aggregate(aggregate) => {
+            !holds_outer
+                && !aggregate.group_expr.is_empty()
+                && has_only_equal_correlations(&aggregate.input)
+                && can_pull_up_correlated_exprs(&aggregate.input)
+        }
+        LogicalPlan::Join(Join {
+            join_type: JoinType::Inner,
+            ..
+        })
+        | LogicalPlan::CrossJoin(_) => {
+            !holds_outer && plan.inputs().into_iter().all(can_pull_up_correlated_exprs)
+        }
+        _ => false,
+    }
+}
+
+/// Returns true if all the correlated predicates of `plan` and its inputs
+/// are equalities
+fn has_only_equal_correlations(plan: &LogicalPlan) -> bool {
+    let equal_correlations = match plan {
+        LogicalPlan::Filter(filter) => split_conjunction(&filter.predicate)
+            .into_iter()
+            .filter(|expr| expr.contains_outer())
+            .all(|expr| {
+                matches!(
+                    expr,
+                    Expr::BinaryExpr(BinaryExpr {
+                        op: Operator::Eq,
+                        ..
+                    })
+                )
+            }),
+        _ => true,
+    };
+    equal_correlations && plan.inputs().into_iter().all(has_only_equal_correlations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::*;
+    use arrow::datatypes::DataType;
+    use datafusion_expr::{col, count, lit, out_ref_col};
+    use std::sync::Arc;
+
+    fn lateral_subquery(plan: LogicalPlan) -> LogicalPlan {
+        LogicalPlan::Subquery(Subquery {
+            outer_ref_columns: plan.all_out_ref_exprs(),
+            subquery: Arc::new(plan),
+        })
+    }
+
+    fn assert_optimized_plan_equal(plan: &LogicalPlan, expected: &str) {
+        assert_optimized_plan_eq_display_indent(
+            Arc::new(DecorrelateLateralJoin::new()),
+            plan,
+            expected,
+        );
+    }
+
+    #[test]
+    fn lateral_cross_join() -> Result<()> {
+        let subquery = LogicalPlanBuilder::from(scan_tpch_table("orders"))
+            .filter(
+                col("orders.o_custkey")
+                    .eq(out_ref_col(DataType::Int64, "customer.c_custkey")),
+            )?
+            .project(vec![col("orders.o_orderkey")])?
+            .alias("o")?
+            .build()?;
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .cross_join(lateral_subquery(subquery))?
+            .project(vec![col("customer.c_custkey"), col("o.o_orderkey")])?
+            .build()?;
+
+        let expected = "Projection: customer.c_custkey, o.o_orderkey [c_custkey:Int64, o_orderkey:Int64]\
+        \n  Projection: customer.c_custkey, customer.c_name, o.o_orderkey [c_custkey:Int64, c_name:Utf8, o_orderkey:Int64]\
+        \n    Inner Join:  Filter: o.o_custkey = customer.c_custkey [c_custkey:Int64, c_name:Utf8, o_orderkey:Int64, o_custkey:Int64]\
+        \n      TableScan: customer [c_custkey:Int64, c_name:Utf8]\
+        \n      SubqueryAlias: o [o_orderkey:Int64, o_custkey:Int64]\
+        \n        Projection: orders.o_orderkey, orders.o_custkey [o_orderkey:Int64, o_custkey:Int64]\
+        \n          TableScan: orders [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]";
+        assert_optimized_plan_equal(&plan, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn lateral_left_join_with_filter() -> Result<()> {
+        let subquery = LogicalPlanBuilder::from(scan_tpch_table("orders"))
+            .filter(
+                col("orders.o_custkey")
+                    .eq(out_ref_col(DataType::Int64, "customer.c_custkey"))
+                    .and(col("orders.o_totalprice").gt(lit(100.0))),
+            )?
+            .aggregate(vec![col("orders.o_orderstatus")], vec![count(lit(1))])?
+            .build()?;
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .join(
+                lateral_subquery(subquery),
+                JoinType::Left,
+                (Vec::<Column>::new(), Vec::<Column>::new()),
+                Some(col("orders.o_orderstatus").eq(col("customer.c_name"))),
+            )?
+            .build()?;
+
+        let expected = "Projection: customer.c_custkey, customer.c_name, orders.o_orderstatus, COUNT(Int32(1)) [c_custkey:Int64, c_name:Utf8, o_orderstatus:Utf8;N, COUNT(Int32(1)):Int64;N]\
+        \n  Left Join:  Filter: orders.o_custkey = customer.c_custkey AND orders.o_orderstatus = customer.c_name [c_custkey:Int64, c_name:Utf8, o_orderstatus:Utf8;N, o_custkey:Int64;N, COUNT(Int32(1)):Int64;N]\
+        \n    TableScan: customer [c_custkey:Int64, c_name:Utf8]\
+        \n    Aggregate: groupBy=[[orders.o_orderstatus, orders.o_custkey]], aggr=[[COUNT(Int32(1))]] [o_orderstatus:Utf8, o_custkey:Int64, COUNT(Int32(1)):Int64;N]\
+        \n      Filter: orders.o_totalprice > Float64(100) [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]\
+        \n        TableScan: orders [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]";
+        assert_optimized_plan_equal(&plan, expected);
+        Ok(())
+    }
+
+    /// The correlated rows are limited for every outer value
+    #[test]
+    fn lateral_with_limit() -> Result<()> {
+        let subquery = LogicalPlanBuilder::from(scan_tpch_table("orders"))
+            .filter(
+                col("orders.o_custkey")
+                    .eq(out_ref_col(DataType::Int64, "customer.c_custkey")),
+            )?
+            .sort(vec![col("orders.o_totalprice").sort(false, true)])?
+            .limit(0, Some(1))?
+            .build()?;
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .cross_join(lateral_subquery(subquery))?
+            .build()?;
+
+        let expected = "Projection: customer.c_custkey, customer.c_name, orders.o_orderkey, orders.o_custkey, orders.o_orderstatus, orders.o_totalprice [c_custkey:Int64, c_name:Utf8, o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]\
+        \n  Inner Join: customer.c_custkey = __lateral_outer.customer_c_custkey [c_custkey:Int64, c_name:Utf8, o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N, customer_c_custkey:Int64]\
+        \n    TableScan: customer [c_custkey:Int64, c_name:Utf8]\
+        \n    Projection: orders.o_orderkey, orders.o_custkey, orders.o_orderstatus, orders.o_totalprice, __lateral_outer.customer_c_custkey [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N, customer_c_custkey:Int64]\
+        \n      Filter: __lateral_row_number > UInt64(0) AND __lateral_row_number <= UInt64(1) [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N, customer_c_custkey:Int64, __lateral_row_number:UInt64;N]\
+        \n        WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [__lateral_outer.customer_c_custkey] ORDER BY [orders.o_totalprice DESC NULLS FIRST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS __lateral_row_number]] [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N, customer_c_custkey:Int64, __lateral_row_number:UInt64;N]\
+        \n          Filter: orders.o_custkey = __lateral_outer.customer_c_custkey [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N, customer_c_custkey:Int64]\
+        \n            CrossJoin: [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N, customer_c_custkey:Int64]\
+        \n              TableScan: orders [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]\
+        \n              SubqueryAlias: __lateral_outer [customer_c_custkey:Int64]\
+        \n                Distinct: [customer_c_custkey:Int64]\
+        \n                  Projection: customer.c_custkey AS customer_c_custkey [customer_c_custkey:Int64]\
+        \n                    TableScan: customer [c_custkey:Int64, c_name:Utf8]";
+        assert_optimized_plan_equal(&plan, expected);
+        Ok(())
+    }
+
+    /// An aggregate without grouping returns a row even for the outer values
+    /// without correlated rows
+    #[test]
+    fn lateral_with_scalar_aggregate() -> Result<()> {
+        let subquery = LogicalPlanBuilder::from(scan_tpch_table("orders"))
+            .filter(
+                col("orders.o_custkey")
+                    .eq(out_ref_col(DataType::Int64, "customer.c_custkey")),
+            )?
+            .aggregate(Vec::<Expr>::new(), vec![count(lit(1))])?
+            .build()?;
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .cross_join(lateral_subquery(subquery))?
+            .build()?;
+
+        let expected = "Projection: customer.c_custkey, customer.c_name, COUNT(Int32(1)) [c_custkey:Int64, c_name:Utf8, COUNT(Int32(1)):Int64;N]\
+        \n  Inner Join: customer.c_custkey = __lateral_outer.customer_c_custkey [c_custkey:Int64, c_name:Utf8, COUNT(Int32(1)):Int64;N, customer_c_custkey:Int64]\
+        \n    TableScan: customer [c_custkey:Int64, c_name:Utf8]\
+        \n    Projection: CASE WHEN __lateral_aggregate.__always_true IS NULL THEN Int64(0) ELSE __lateral_aggregate.COUNT(Int32(1)) END AS COUNT(Int32(1)), __lateral_outer.customer_c_custkey [COUNT(Int32(1)):Int64;N, customer_c_custkey:Int64]\
+        \n      Left Join: __lateral_outer.customer_c_custkey = __lateral_aggregate.customer_c_custkey [customer_c_custkey:Int64, customer_c_custkey:Int64;N, __always_true:Boolean;N, COUNT(Int32(1)):Int64;N]\
+        \n        SubqueryAlias: __lateral_outer [customer_c_custkey:Int64]\
+        \n          Distinct: [customer_c_custkey:Int64]\
+        \n            Projection: customer.c_custkey AS customer_c_custkey [customer_c_custkey:Int64]\
+        \n              TableScan: customer [c_custkey:Int64, c_name:Utf8]\
+        \n        SubqueryAlias: __lateral_aggregate [customer_c_custkey:Int64, __always_true:Boolean, COUNT(Int32(1)):Int64;N]\
+        \n          Aggregate: groupBy=[[__lateral_outer.customer_c_custkey, Boolean(true) AS __always_true]], aggr=[[COUNT(Int32(1))]] [customer_c_custkey:Int64, __always_true:Boolean, COUNT(Int32(1)):Int64;N]\
+        \n            Filter: orders.o_custkey = __lateral_outer.customer_c_custkey [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N, customer_c_custkey:Int64]\
+        \n              CrossJoin: [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N, customer_c_custkey:Int64]\
+        \n                TableScan: orders [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]\
+        \n                SubqueryAlias: __lateral_outer [customer_c_custkey:Int64]\
+        \n                  Distinct: [customer_c_custkey:Int64]\
+        \n                    Projection: customer.c_custkey AS customer_c_custkey [customer_c_custkey:Int64]\
+        \n                      TableScan: customer [c_custkey:Int64, c_name:Utf8]";
+        assert_optimized_plan_equal(&plan, expected);
+        Ok(())
+    }
+
+    /// The groups of an aggregate are split by outer value
+    #[test]
+    fn lateral_with_non_equal_grouped_aggregate() -> Result<()> {
+        let subquery = LogicalPlanBuilder::from(scan_tpch_table("orders"))
+            .filter(
+                col("orders.o_custkey")
+                    .lt(out_ref_col(DataType::Int64, "customer.c_custkey")),
+            )?
+            .aggregate(vec![col("orders.o_orderstatus")], vec![count(lit(1))])?
+            .build()?;
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .cross_join(lateral_subquery(subquery))?
+            .build()?;
+
+        let expected = "Projection: customer.c_custkey, customer.c_name, orders.o_orderstatus, COUNT(Int32(1)) [c_custkey:Int64, c_name:Utf8, o_orderstatus:Utf8, COUNT(Int32(1)):Int64;N]\
+        \n  Inner Join: customer.c_custkey = __lateral_outer.customer_c_custkey [c_custkey:Int64, c_name:Utf8, o_orderstatus:Utf8, customer_c_custkey:Int64, COUNT(Int32(1)):Int64;N]\
+        \n    TableScan: customer [c_custkey:Int64, c_name:Utf8]\
+        \n    Aggregate: groupBy=[[orders.o_orderstatus, __lateral_outer.customer_c_custkey]], aggr=[[COUNT(Int32(1))]] [o_orderstatus:Utf8, customer_c_custkey:Int64, COUNT(Int32(1)):Int64;N]\
+        \n      Filter: orders.o_custkey < __lateral_outer.customer_c_custkey [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N, customer_c_custkey:Int64]\
+        \n        CrossJoin: [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N, customer_c_custkey:Int64]\
+        \n          TableScan: orders [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]\
+        \n          SubqueryAlias: __lateral_outer [customer_c_custkey:Int64]\
+        \n            Distinct: [customer_c_custkey:Int64]\
+        \n              Projection: customer.c_custkey AS customer_c_custkey [customer_c_custkey:Int64]\
+        \n                TableScan: customer [c_custkey:Int64, c_name:Utf8]";
+        assert_optimized_plan_equal(&plan, expected);
+        Ok(())
+    }
+
+    /// The outer values can't be joined into the preserved side of a full join
+    #[test]
+    fn lateral_with_full_join_is_not_decorrelated() -> Result<()> {
+        let subquery = LogicalPlanBuilder::from(scan_tpch_table("orders"))
+            .join(
+                scan_tpch_table("lineitem"),
+                JoinType::Full,
+                (Vec::<Column>::new(), Vec::<Column>::new()),
+                Some(
+                    col("orders.o_orderkey")
+                        .eq(col("lineitem.l_orderkey"))
+                        .and(
+                            col("orders.o_custkey")
+                                .eq(out_ref_col(DataType::Int64, "customer.c_custkey")),
+                        ),
+                ),
+            )?
+            .build()?;
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .cross_join(lateral_subquery(subquery))?
+            .build()?;
+
+        assert_optimization_skipped(Arc::new(DecorrelateLateralJoin::new()), &plan)
+    }
+}
@@ -83,7 +83,7 @@ fn lr_is_preserved(plan: &LogicalPlan) -> Result<(bool, bool)> {
     match plan {
         LogicalPlan::Join(Join { join_type, .. }) => match join_type {
             JoinType::Inner => Ok((true, true)),
-            JoinType::Left => Ok((true, false)),
+            JoinType::Left | JoinType::AsOf => Ok((true, false)),
             JoinType::Right => Ok((false, true)),
             JoinType::Full => Ok((false, false)),
             // No columns from the right side of the join can be referenced in output
@@ -113,6 +113,9 @@ fn on_lr_is_preserved(plan: &LogicalPlan) -> Result<(bool, bool)> {
             JoinType::LeftSemi | JoinType::RightSemi => Ok((true, true)),
             JoinType::LeftAnti => Ok((false, true)),
             JoinType::RightAnti => Ok((true, false)),
+            // The match condition selects a single right row for every left row,
+            // so none of it can be evaluated before the join
+            JoinType::AsOf => Ok((false, false)),
         },
         LogicalPlan::CrossJoin(_) => Err(DataFusionError::Internal(
             "on_lr_is_preserved cannot be applied to CROSSJOIN nodes".to_string(),
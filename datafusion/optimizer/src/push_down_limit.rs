@@ -226,13 +226,14 @@ fn push_down_join(join: &Join, limit: usize) -> Option<Join> {
     let (left_limit, right_limit) = if is_no_join_condition(join) {
         match join.join_type {
             Left | Right | Full => (Some(limit), Some(limit)),
-            LeftAnti | LeftSemi => (Some(limit), None),
+            LeftAnti | LeftSemi | AsOf => (Some(limit), None),
             RightAnti | RightSemi => (None, Some(limit)),
             Inner => (None, None),
         }
     } else {
         match join.join_type {
-            Left => (Some(limit), None),
+            // every left row of an ASOF join produces exactly one row
+            Left | AsOf => (Some(limit), None),
             Right => (None, Some(limit)),
             _ => (None, None),
         }
@@ -108,6 +108,8 @@ impl OptimizerRule for PushDownProjection {
                 if let Some(expr) = &join.filter {
                     expr_to_columns(expr, &mut push_columns)?;
                 }
+                // columns of the left side referenced by a LATERAL subquery
+                push_columns.extend(outer_referenced_columns(&join.right));
 
                 let new_left = generate_projection(
                     &push_columns,
@@ -129,6 +131,7 @@ impl OptimizerRule for PushDownProjection {
                 for e in projection.expr.iter() {
                     expr_to_columns(e, &mut push_columns)?;
                 }
+                push_columns.extend(outer_referenced_columns(&join.right));
                 let new_left = generate_projection(
                     &push_columns,
                     join.left.schema(),
@@ -365,6 +368,11 @@ impl OptimizerRule for PushDownProjection {
                 let new_proj = plan.with_new_inputs(&[limit.input.as_ref().clone()])?;
                 child_plan.with_new_inputs(&[new_proj])?
             }
+            LogicalPlan::Sample(sample) => {
+                // can commute: sampling only drops rows
+                let new_proj = plan.with_new_inputs(&[sample.input.as_ref().clone()])?;
+                child_plan.with_new_inputs(&[new_proj])?
+            }
             _ => return Ok(None),
         };
 
@@ -451,6 +459,18 @@ fn get_expr(columns: &HashSet<Column>, schema: &DFSchemaRef) -> Result<Vec<Expr>
     }
 }
 
+/// Columns of the outer relations referenced by `plan`, i.e. the columns a
+/// `LATERAL` subquery on the right side of a join needs from its left side.
+fn outer_referenced_columns(plan: &LogicalPlan) -> Vec<Column> {
+    plan.all_out_ref_exprs()
+        .into_iter()
+        .filter_map(|e| match e {
+            Expr::OuterReferenceColumn(_, col) => Some(col),
+            _ => None,
+        })
+        .collect()
+}
+
 fn generate_projection(
     used_columns: &HashSet<Column>,
     schema: &DFSchemaRef,
@@ -1075,6 +1095,7 @@ mod tests {
             vec![col("test.b")],
             vec![],
             WindowFrame::new(false),
+            false,
         ));
 
         let max2 = Expr::WindowFunction(expr::WindowFunction::new(
@@ -1083,6 +1104,7 @@ mod tests {
             vec![],
             vec![],
             WindowFrame::new(false),
+            false,
         ));
         let col1 = col(max1.display_name()?);
         let col2 = col(max2.display_name()?);
@@ -203,10 +203,12 @@ impl Accumulator for OrderSensitiveArrayAggAccumulator {
         // values received from its ordering requirement expression. (This information is necessary for during merging).
         let agg_orderings = &states[1];
         if agg_orderings.as_any().is::<ListArray>() {
-            // Stores ARRAY_AGG results coming from each partition
-            let mut partition_values = vec![];
+            // Stores ARRAY_AGG results coming from each partition, starting
+            // with the values merged so far
+            let mut partition_values = vec![std::mem::take(&mut self.values)];
             // Stores ordering requirement expression results coming from each partition
-            let mut partition_ordering_values = vec![];
+            let mut partition_ordering_values =
+                vec![std::mem::take(&mut self.ordering_values)];
             for index in 0..agg_orderings.len() {
                 let ordering = ScalarValue::try_from_array(agg_orderings, index)?;
                 // Ordering requirement expression values for each entry in the ARRAY_AGG list
@@ -228,7 +230,7 @@ impl Accumulator for OrderSensitiveArrayAggAccumulator {
                 .iter()
                 .map(|sort_expr| sort_expr.options)
                 .collect::<Vec<_>>();
-            self.values = merge_ordered_arrays(
+            (self.values, self.ordering_values) = merge_ordered_arrays(
                 &partition_values,
                 &partition_ordering_values,
                 &sort_options,
@@ -417,6 +419,7 @@ impl<'a> PartialOrd for CustomElement<'a> {
 /// For each ScalarValue in the `values` we have a corresponding `Vec<ScalarValue>` (like timestamp of it)
 /// for the example above `sort_options` will have size two, that defines ordering requirement of the merge.
 /// Inner `Vec<ScalarValue>`s of the `ordering_values` will be compared according `sort_options` (Their sizes should match)
+/// Returns the merged values along with their ordering values.
 fn merge_ordered_arrays(
     // We will merge values into single `Vec<ScalarValue>`.
     values: &[Vec<ScalarValue>],
@@ -426,7 +429,7 @@ fn merge_ordered_arrays(
     ordering_values: &[Vec<Vec<ScalarValue>>],
     // Defines according to which ordering comparisons should be done.
     sort_options: &[SortOptions],
-) -> Result<Vec<ScalarValue>> {
+) -> Result<(Vec<ScalarValue>, Vec<Vec<ScalarValue>>)> {
     // Keep track the most recent data of each branch, in binary heap data structure.
     let mut heap: BinaryHeap<CustomElement> = BinaryHeap::new();
 
@@ -449,6 +452,7 @@ fn merge_ordered_arrays(
         .map(|idx| values[idx].len())
         .collect::<Vec<_>>();
     let mut merged_values = vec![];
+    let mut merged_orderings = vec![];
     // Continue iterating the loop until consuming data of all branches.
     loop {
         let min_elem = if let Some(min_elem) = heap.pop() {
@@ -490,6 +494,7 @@ fn merge_ordered_arrays(
         indices[branch_idx] += 1;
         let row_idx = indices[branch_idx];
         merged_values.push(min_elem.value.clone());
+        merged_orderings.push(min_elem.ordering.clone());
         if row_idx < end_indices[branch_idx] {
             // Push next entry in the most recently consumed branch to the heap
             // If there is an available entry
@@ -500,16 +505,22 @@ fn merge_ordered_arrays(
         }
     }
 
-    Ok(merged_values)
+    Ok((merged_values, merged_orderings))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::aggregate::array_agg_ordered::merge_ordered_arrays;
+    use crate::aggregate::array_agg_ordered::{
+        merge_ordered_arrays, OrderSensitiveArrayAggAccumulator,
+    };
+    use crate::expressions::col;
+    use crate::PhysicalSortExpr;
+    use arrow::datatypes::{DataType, Field, Schema};
     use arrow_array::{Array, ArrayRef, Int64Array};
     use arrow_schema::SortOptions;
     use datafusion_common::utils::get_row_at_idx;
     use datafusion_common::{Result, ScalarValue};
+    use datafusion_expr::Accumulator;
     use std::sync::Arc;
 
     #[test]
@@ -554,7 +565,7 @@ mod tests {
         let expected =
             Arc::new(Int64Array::from(vec![0, 0, 1, 1, 2, 2, 3, 3, 4, 4])) as ArrayRef;
 
-        let merged_vals = merge_ordered_arrays(
+        let (merged_vals, _) = merge_ordered_arrays(
             &[lhs_vals, rhs_vals],
             &[lhs_orderings, rhs_orderings],
             &sort_options,
@@ -608,7 +619,7 @@ mod tests {
         let expected =
             Arc::new(Int64Array::from(vec![0, 0, 1, 1, 2, 2, 1, 1, 2, 2])) as ArrayRef;
 
-        let merged_vals = merge_ordered_arrays(
+        let (merged_vals, _) = merge_ordered_arrays(
             &[lhs_vals, rhs_vals],
             &[lhs_orderings, rhs_orderings],
             &sort_options,
@@ -618,4 +629,40 @@ mod tests {
         assert_eq!(&merged_vals, &expected);
         Ok(())
     }
+
+    #[test]
+    fn test_merge_batch_incrementally() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int64, false)]);
+        let ordering_req = vec![PhysicalSortExpr {
+            expr: col("a", &schema)?,
+            options: SortOptions::default(),
+        }];
+        let new_accumulator = || {
+            OrderSensitiveArrayAggAccumulator::try_new(
+                &DataType::Int64,
+                &[DataType::Int64],
+                ordering_req.clone(),
+            )
+        };
+        let partition_state = |values: Vec<i64>| -> Result<Vec<ArrayRef>> {
+            let values: ArrayRef = Arc::new(Int64Array::from(values));
+            let mut acc = new_accumulator()?;
+            acc.update_batch(&[values.clone(), values])?;
+            Ok(acc.state()?.iter().map(|state| state.to_array()).collect())
+        };
+
+        // partial states arrive in separate batches
+        let mut acc = new_accumulator()?;
+        acc.merge_batch(&partition_state(vec![1, 4])?)?;
+        acc.merge_batch(&partition_state(vec![0, 2, 5])?)?;
+        acc.merge_batch(&partition_state(vec![])?)?;
+        acc.merge_batch(&partition_state(vec![3])?)?;
+
+        let expected = (0..6).map(|v| ScalarValue::Int64(Some(v))).collect();
+        assert_eq!(
+            acc.evaluate()?,
+            ScalarValue::new_list(Some(expected), DataType::Int64)
+        );
+        Ok(())
+    }
 }
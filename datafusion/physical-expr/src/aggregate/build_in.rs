@@ -26,6 +26,7 @@
 //! * Signature: see `Signature`
 //! * Return type: a function `(arg_types) -> return_type`. E.g. for min, ([f32]) -> f32, ([f64]) -> f64.
 
+use crate::expressions::PercentileKind;
 use crate::{expressions, AggregateExpr, PhysicalExpr, PhysicalSortExpr};
 use arrow::datatypes::Schema;
 use datafusion_common::{DataFusionError, Result};
@@ -147,6 +148,33 @@ pub fn create_aggregate_expr(
                 input_phy_types[0].clone(),
             ))
         }
+        (AggregateFunction::StringAgg, false) => {
+            let expr = input_phy_exprs[0].clone();
+            let separator = input_phy_exprs[1].clone();
+            if ordering_req.is_empty() {
+                Arc::new(expressions::StringAgg::try_new(
+                    expr, separator, name, rt_type,
+                )?)
+            } else {
+                Arc::new(expressions::OrderSensitiveStringAgg::try_new(
+                    expr,
+                    separator,
+                    name,
+                    rt_type,
+                    ordering_types,
+                    ordering_req.to_vec(),
+                )?)
+            }
+        }
+        (AggregateFunction::StringAgg, true) => {
+            Arc::new(expressions::DistinctStringAgg::try_new(
+                input_phy_exprs[0].clone(),
+                input_phy_exprs[1].clone(),
+                name,
+                rt_type,
+                ordering_req.to_vec(),
+            )?)
+        }
         (AggregateFunction::Min, _) => Arc::new(expressions::Min::new(
             input_phy_exprs[0].clone(),
             name,
@@ -307,6 +335,47 @@ pub fn create_aggregate_expr(
                 "MEDIAN(DISTINCT) aggregations are not available".to_string(),
             ));
         }
+        (AggregateFunction::PercentileCont, false) => {
+            Arc::new(expressions::Percentile::try_new(
+                input_phy_exprs[0].clone(),
+                input_phy_exprs[1].clone(),
+                PercentileKind::Continuous,
+                name,
+                rt_type,
+                ordering_req.to_vec(),
+            )?)
+        }
+        (AggregateFunction::PercentileCont, true) => {
+            return Err(DataFusionError::NotImplemented(
+                "PERCENTILE_CONT(DISTINCT) aggregations are not available".to_string(),
+            ));
+        }
+        (AggregateFunction::PercentileDisc, false) => {
+            Arc::new(expressions::Percentile::try_new(
+                input_phy_exprs[0].clone(),
+                input_phy_exprs[1].clone(),
+                PercentileKind::Discrete,
+                name,
+                rt_type,
+                ordering_req.to_vec(),
+            )?)
+        }
+        (AggregateFunction::PercentileDisc, true) => {
+            return Err(DataFusionError::NotImplemented(
+                "PERCENTILE_DISC(DISTINCT) aggregations are not available".to_string(),
+            ));
+        }
+        (AggregateFunction::Mode, false) => Arc::new(expressions::Mode::new(
+            input_phy_exprs[0].clone(),
+            name,
+            rt_type,
+            ordering_req.to_vec(),
+        )),
+        (AggregateFunction::Mode, true) => {
+            return Err(DataFusionError::NotImplemented(
+                "MODE(DISTINCT) aggregations are not available".to_string(),
+            ));
+        }
         (AggregateFunction::FirstValue, _) => Arc::new(expressions::FirstValue::new(
             input_phy_exprs[0].clone(),
             name,
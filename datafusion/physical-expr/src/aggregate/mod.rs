@@ -15,7 +15,9 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use crate::expressions::{FirstValue, LastValue, OrderSensitiveArrayAgg};
+use crate::expressions::{
+    FirstValue, LastValue, OrderSensitiveArrayAgg, OrderSensitiveStringAgg,
+};
 use crate::{PhysicalExpr, PhysicalSortExpr};
 use arrow::datatypes::Field;
 use datafusion_common::{DataFusionError, Result};
@@ -48,9 +50,12 @@ pub(crate) mod min_max;
 pub mod build_in;
 pub(crate) mod groups_accumulator;
 mod hyperloglog;
+pub(crate) mod mode;
 pub mod moving_min_max;
+pub(crate) mod percentile;
 pub(crate) mod stats;
 pub(crate) mod stddev;
+pub(crate) mod string_agg;
 pub(crate) mod sum;
 pub(crate) mod sum_distinct;
 mod tdigest;
@@ -142,4 +147,5 @@ pub fn is_order_sensitive(aggr_expr: &Arc<dyn AggregateExpr>) -> bool {
     aggr_expr.as_any().is::<FirstValue>()
         || aggr_expr.as_any().is::<LastValue>()
         || aggr_expr.as_any().is::<OrderSensitiveArrayAgg>()
+        || aggr_expr.as_any().is::<OrderSensitiveStringAgg>()
 }
@@ -0,0 +1,360 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! # Mode
+
+use crate::aggregate::percentile::is_descending;
+use crate::aggregate::utils::down_cast_any_ref;
+use crate::expressions::format_state_name;
+use crate::{AggregateExpr, LexOrdering, PhysicalExpr, PhysicalSortExpr};
+use arrow::array::{Array, ArrayRef};
+use arrow::datatypes::{DataType, Field};
+use datafusion_common::{DataFusionError, Result, ScalarValue};
+use datafusion_expr::Accumulator;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// MODE aggregate expression, returning the most frequent non-null value.
+/// When several values are equally frequent the first of them in the
+/// ordering of `WITHIN GROUP` is returned, i.e. the smallest one unless the
+/// values are ordered descending.
+#[derive(Debug, Clone)]
+pub struct Mode {
+    name: String,
+    expr: Arc<dyn PhysicalExpr>,
+    data_type: DataType,
+    ordering_req: LexOrdering,
+}
+
+impl Mode {
+    /// Create a new MODE aggregate function, with the ordering of
+    /// `WITHIN GROUP` as its ordering requirement
+    pub fn new(
+        expr: Arc<dyn PhysicalExpr>,
+        name: impl Into<String>,
+        data_type: DataType,
+        ordering_req: LexOrdering,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            expr,
+            data_type,
+            ordering_req,
+        }
+    }
+
+    fn create_mode_accumulator(&self) -> ModeAccumulator {
+        ModeAccumulator::new(self.data_type.clone(), is_descending(&self.ordering_req))
+    }
+}
+
+impl AggregateExpr for Mode {
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn field(&self) -> Result<Field> {
+        Ok(Field::new(&self.name, self.data_type.clone(), true))
+    }
+
+    fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(self.create_mode_accumulator()))
+    }
+
+    fn state_fields(&self) -> Result<Vec<Field>> {
+        // Intermediate state is the distinct values and their counts
+        let values = Field::new("item", self.data_type.clone(), true);
+        let counts = Field::new("item", DataType::Int64, true);
+        Ok(vec![
+            Field::new(
+                format_state_name(&self.name, "values"),
+                DataType::List(Arc::new(values)),
+                true,
+            ),
+            Field::new(
+                format_state_name(&self.name, "counts"),
+                DataType::List(Arc::new(counts)),
+                true,
+            ),
+        ])
+    }
+
+    fn expressions(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        vec![self.expr.clone()]
+    }
+
+    fn order_bys(&self) -> Option<&[PhysicalSortExpr]> {
+        if self.ordering_req.is_empty() {
+            None
+        } else {
+            Some(&self.ordering_req)
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn reverse_expr(&self) -> Option<Arc<dyn AggregateExpr>> {
+        Some(Arc::new(self.clone()))
+    }
+
+    fn create_sliding_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(self.create_mode_accumulator()))
+    }
+}
+
+impl PartialEq<dyn Any> for Mode {
+    fn eq(&self, other: &dyn Any) -> bool {
+        down_cast_any_ref(other)
+            .downcast_ref::<Self>()
+            .map(|x| {
+                self.name == x.name
+                    && self.data_type == x.data_type
+                    && self.ordering_req == x.ordering_req
+                    && self.expr.eq(&x.expr)
+            })
+            .unwrap_or(false)
+    }
+}
+
+#[derive(Debug)]
+struct ModeAccumulator {
+    data_type: DataType,
+    descending: bool,
+    counts: HashMap<ScalarValue, i64>,
+}
+
+impl ModeAccumulator {
+    fn new(data_type: DataType, descending: bool) -> Self {
+        Self {
+            data_type,
+            descending,
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl Accumulator for ModeAccumulator {
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        let (values, counts) = self
+            .counts
+            .iter()
+            .map(|(value, count)| (value.clone(), ScalarValue::Int64(Some(*count))))
+            .unzip();
+        Ok(vec![
+            ScalarValue::new_list(Some(values), self.data_type.clone()),
+            ScalarValue::new_list(Some(counts), DataType::Int64),
+        ])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let array = &values[0];
+        for index in 0..array.len() {
+            if array.is_valid(index) {
+                let value = ScalarValue::try_from_array(array, index)?;
+                *self.counts.entry(value).or_default() += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn retract_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let array = &values[0];
+        for index in 0..array.len() {
+            if array.is_valid(index) {
+                let value = ScalarValue::try_from_array(array, index)?;
+                if let Some(count) = self.counts.get_mut(&value) {
+                    *count -= 1;
+                    if *count == 0 {
+                        self.counts.remove(&value);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let (values, counts) = (&states[0], &states[1]);
+        for index in 0..values.len() {
+            match (
+                ScalarValue::try_from_array(values, index)?,
+                ScalarValue::try_from_array(counts, index)?,
+            ) {
+                (
+                    ScalarValue::List(Some(values), _),
+                    ScalarValue::List(Some(counts), _),
+                ) => {
+                    for (value, count) in values.into_iter().zip(counts) {
+                        let ScalarValue::Int64(Some(count)) = count else {
+                            return Err(DataFusionError::Internal(format!(
+                                "unexpected count in MODE state, got {count:?}"
+                            )));
+                        };
+                        *self.counts.entry(value).or_default() += count;
+                    }
+                }
+                (ScalarValue::List(None, _), _) => {} // skip empty state
+                (v, _) => {
+                    return Err(DataFusionError::Internal(format!(
+                        "unexpected state in MODE. Expected DataType::List, got {v:?}"
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        let mut mode: Option<(&ScalarValue, i64)> = None;
+        for (value, count) in &self.counts {
+            let is_better = match mode {
+                None => true,
+                Some((mode_value, mode_count)) => {
+                    let is_first = if self.descending {
+                        value > mode_value
+                    } else {
+                        value < mode_value
+                    };
+                    *count > mode_count || (*count == mode_count && is_first)
+                }
+            };
+            if is_better {
+                mode = Some((value, *count));
+            }
+        }
+        match mode {
+            Some((value, _)) => Ok(value.clone()),
+            None => ScalarValue::try_from(&self.data_type),
+        }
+    }
+
+    fn supports_retract_batch(&self) -> bool {
+        true
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + (std::mem::size_of::<ScalarValue>() + std::mem::size_of::<i64>())
+                * self.counts.capacity()
+            + self
+                .counts
+                .keys()
+                .map(|v| v.size() - std::mem::size_of_val(v))
+                .sum::<usize>()
+            + self.data_type.size()
+            - std::mem::size_of_val(&self.data_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::col;
+    use crate::expressions::tests::aggregate;
+    use arrow::array::{Int32Array, StringArray};
+    use arrow::compute::SortOptions;
+    use arrow::datatypes::Schema;
+    use arrow::record_batch::RecordBatch;
+
+    fn mode(array: ArrayRef, descending: bool) -> Result<ScalarValue> {
+        let schema = Schema::new(vec![Field::new("a", array.data_type().clone(), true)]);
+        let expr = col("a", &schema)?;
+        let ordering_req = if descending {
+            vec![PhysicalSortExpr {
+                expr: expr.clone(),
+                options: SortOptions {
+                    descending: true,
+                    nulls_first: true,
+                },
+            }]
+        } else {
+            vec![]
+        };
+        let data_type = array.data_type().clone();
+        let batch = RecordBatch::try_new(Arc::new(schema), vec![array])?;
+        let agg = Arc::new(Mode::new(expr, "bla", data_type, ordering_req));
+        aggregate(&batch, agg)
+    }
+
+    #[test]
+    fn mode_i32() -> Result<()> {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(3),
+            None,
+            Some(1),
+            Some(3),
+            None,
+            None,
+            Some(2),
+        ]));
+        assert_eq!(mode(a, false)?, ScalarValue::from(3));
+        Ok(())
+    }
+
+    #[test]
+    fn mode_tie_returns_smallest() -> Result<()> {
+        let a: ArrayRef = Arc::new(StringArray::from(vec!["b", "a", "c", "b", "a"]));
+        assert_eq!(mode(a, false)?, ScalarValue::from("a"));
+        Ok(())
+    }
+
+    #[test]
+    fn mode_descending_tie_returns_largest() -> Result<()> {
+        let a: ArrayRef = Arc::new(StringArray::from(vec!["b", "a", "c", "b", "a"]));
+        assert_eq!(mode(a, true)?, ScalarValue::from("b"));
+        Ok(())
+    }
+
+    #[test]
+    fn mode_all_nulls() -> Result<()> {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![None, None]));
+        assert_eq!(mode(a, false)?, ScalarValue::Int32(None));
+        Ok(())
+    }
+
+    #[test]
+    fn mode_retract() -> Result<()> {
+        let mut accumulator = ModeAccumulator::new(DataType::Int32, false);
+        let values: ArrayRef = Arc::new(Int32Array::from(vec![1, 1, 2, 2, 2]));
+        accumulator.update_batch(&[values.clone()])?;
+        assert_eq!(accumulator.evaluate()?, ScalarValue::from(2));
+        accumulator.retract_batch(&[values.slice(2, 2)])?;
+        assert_eq!(accumulator.evaluate()?, ScalarValue::from(1));
+        Ok(())
+    }
+
+    #[test]
+    fn mode_merge() -> Result<()> {
+        let mut first = ModeAccumulator::new(DataType::Int32, false);
+        first.update_batch(&[Arc::new(Int32Array::from(vec![1, 1, 2]))])?;
+        let mut second = ModeAccumulator::new(DataType::Int32, false);
+        second.update_batch(&[Arc::new(Int32Array::from(vec![2, 2, 3]))])?;
+
+        let state = second
+            .state()?
+            .iter()
+            .map(|v| v.to_array())
+            .collect::<Vec<_>>();
+        first.merge_batch(&state)?;
+        assert_eq!(first.evaluate()?, ScalarValue::from(2));
+        Ok(())
+    }
+}
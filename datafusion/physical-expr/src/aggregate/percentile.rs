@@ -0,0 +1,766 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Exact `PERCENTILE_CONT` and `PERCENTILE_DISC` aggregates
+
+use crate::aggregate::utils::down_cast_any_ref;
+use crate::expressions::{format_state_name, Literal};
+use crate::{AggregateExpr, LexOrdering, PhysicalExpr, PhysicalSortExpr};
+use arrow::array::{Array, ArrayRef, UInt32Array};
+use arrow::compute::{cast, sort_to_indices, SortOptions};
+use arrow::datatypes::{DataType, Field};
+use arrow_buffer::i256;
+use datafusion_common::cast::{
+    as_decimal128_array, as_decimal256_array, as_float64_array,
+};
+use datafusion_common::{DataFusionError, Result, ScalarValue};
+use datafusion_expr::Accumulator;
+use std::any::Any;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+
+/// Whether a percentile interpolates between the input values or picks one
+/// of them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PercentileKind {
+    /// `PERCENTILE_CONT`, interpolates linearly between adjacent values
+    Continuous,
+    /// `PERCENTILE_DISC`, the first value whose position in the ordering
+    /// equals or exceeds the fraction
+    Discrete,
+}
+
+impl fmt::Display for PercentileKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PercentileKind::Continuous => write!(f, "PERCENTILE_CONT"),
+            PercentileKind::Discrete => write!(f, "PERCENTILE_DISC"),
+        }
+    }
+}
+
+/// PERCENTILE_CONT and PERCENTILE_DISC aggregate expression. Like MEDIAN, all
+/// values are kept in memory until the result is computed. If an approximation
+/// is sufficient then APPROX_PERCENTILE_CONT is much more efficient.
+///
+/// The fraction is either a single literal, or a list literal in which case
+/// the result is the list of the percentiles for each of its fractions.
+///
+/// The values are ordered ascending, unless the ordering requirement of
+/// `WITHIN GROUP (ORDER BY <expr> DESC)` orders them descending. This mirrors
+/// the positions of the values, so `PERCENTILE_CONT` returns the percentile
+/// `1 - p` of the ascending values.
+#[derive(Debug, Clone)]
+pub struct Percentile {
+    name: String,
+    expr: Arc<dyn PhysicalExpr>,
+    fraction: Arc<dyn PhysicalExpr>,
+    kind: PercentileKind,
+    ordering_req: LexOrdering,
+    percentiles: Vec<f64>,
+    is_list: bool,
+    /// type of the values, which for `PERCENTILE_CONT` is Float64 or a decimal
+    /// with the scale of the result
+    value_type: DataType,
+    data_type: DataType,
+}
+
+impl Percentile {
+    /// Create a new PERCENTILE_CONT or PERCENTILE_DISC aggregate function
+    /// returning `data_type`, with the ordering of `WITHIN GROUP` as its
+    /// ordering requirement
+    pub fn try_new(
+        expr: Arc<dyn PhysicalExpr>,
+        fraction: Arc<dyn PhysicalExpr>,
+        kind: PercentileKind,
+        name: impl Into<String>,
+        data_type: DataType,
+        ordering_req: LexOrdering,
+    ) -> Result<Self> {
+        let (percentiles, is_list) = validate_percentile_fraction(&fraction, kind)?;
+        let value_type = match &data_type {
+            DataType::List(field) if is_list => field.data_type().clone(),
+            data_type => data_type.clone(),
+        };
+        if kind == PercentileKind::Continuous
+            && matches!(
+                value_type,
+                DataType::Decimal128(..) | DataType::Decimal256(..)
+            )
+        {
+            if let Some(p) = percentiles.iter().find(|p| decimal_fraction(**p).is_none())
+            {
+                return Err(DataFusionError::Plan(format!(
+                    "The percentile of PERCENTILE_CONT over decimal values can have at most {DECIMAL_FRACTION_DIGITS} decimal digits, {p} is invalid. Cast the values to DOUBLE to use it"
+                )));
+            }
+        }
+        Ok(Self {
+            name: name.into(),
+            expr,
+            fraction,
+            kind,
+            ordering_req,
+            percentiles,
+            is_list,
+            value_type,
+            data_type,
+        })
+    }
+
+    /// Whether this is a `PERCENTILE_CONT` or a `PERCENTILE_DISC`
+    pub fn kind(&self) -> PercentileKind {
+        self.kind
+    }
+
+    fn create_percentile_accumulator(&self) -> PercentileAccumulator {
+        PercentileAccumulator {
+            kind: self.kind,
+            descending: is_descending(&self.ordering_req),
+            percentiles: self.percentiles.clone(),
+            is_list: self.is_list,
+            value_type: self.value_type.clone(),
+            data_type: self.data_type.clone(),
+            values: VecDeque::new(),
+        }
+    }
+}
+
+/// Returns whether the ordering requirement of an ordered-set aggregate
+/// orders its values descending
+pub(crate) fn is_descending(ordering_req: &[PhysicalSortExpr]) -> bool {
+    ordering_req
+        .first()
+        .is_some_and(|sort_expr| sort_expr.options.descending)
+}
+
+/// Number of decimal digits of the percentiles of decimal `PERCENTILE_CONT`.
+/// The values are cast to a decimal with as many more digits of scale, so the
+/// interpolation between them is exact.
+const DECIMAL_FRACTION_DIGITS: u32 = 4;
+
+/// Returns the percentile `p` as a number of `1 / 10^DECIMAL_FRACTION_DIGITS`,
+/// or `None` if it has more decimal digits
+fn decimal_fraction(p: f64) -> Option<u64> {
+    let scale = 10_u64.pow(DECIMAL_FRACTION_DIGITS) as f64;
+    let units = (p * scale).round();
+    (units / scale == p).then_some(units as u64)
+}
+
+/// Returns the fractions of a fraction literal, and whether it is a list
+fn validate_percentile_fraction(
+    expr: &Arc<dyn PhysicalExpr>,
+    kind: PercentileKind,
+) -> Result<(Vec<f64>, bool)> {
+    let value = expr
+        .as_any()
+        .downcast_ref::<Literal>()
+        .ok_or_else(|| {
+            DataFusionError::Plan(format!(
+                "The percentile argument of {kind} must be a literal"
+            ))
+        })?
+        .value();
+    let (fractions, is_list) = match value {
+        ScalarValue::List(Some(fractions), _) => (fractions.as_slice(), true),
+        fraction => (std::slice::from_ref(fraction), false),
+    };
+    let percentiles = fractions
+        .iter()
+        .map(|fraction| match fraction {
+            ScalarValue::Float64(Some(p)) if (0.0..=1.0).contains(p) => Ok(*p),
+            ScalarValue::Float64(Some(p)) => Err(DataFusionError::Plan(format!(
+                "Percentile value must be between 0.0 and 1.0 inclusive, {p} is invalid"
+            ))),
+            other => Err(DataFusionError::Plan(format!(
+                "Percentile value for {kind} must be a non-null Float64, got {other:?}"
+            ))),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok((percentiles, is_list))
+}
+
+impl AggregateExpr for Percentile {
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn field(&self) -> Result<Field> {
+        Ok(Field::new(&self.name, self.data_type.clone(), true))
+    }
+
+    fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(self.create_percentile_accumulator()))
+    }
+
+    fn state_fields(&self) -> Result<Vec<Field>> {
+        // Intermediate state is a list of the values collected so far
+        let field = Field::new("item", self.value_type.clone(), true);
+        Ok(vec![Field::new(
+            format_state_name(&self.name, "values"),
+            DataType::List(Arc::new(field)),
+            true,
+        )])
+    }
+
+    fn expressions(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        vec![self.expr.clone(), self.fraction.clone()]
+    }
+
+    fn order_bys(&self) -> Option<&[PhysicalSortExpr]> {
+        if self.ordering_req.is_empty() {
+            None
+        } else {
+            Some(&self.ordering_req)
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn reverse_expr(&self) -> Option<Arc<dyn AggregateExpr>> {
+        Some(Arc::new(self.clone()))
+    }
+
+    fn create_sliding_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(self.create_percentile_accumulator()))
+    }
+}
+
+impl PartialEq<dyn Any> for Percentile {
+    fn eq(&self, other: &dyn Any) -> bool {
+        down_cast_any_ref(other)
+            .downcast_ref::<Self>()
+            .map(|x| {
+                self.name == x.name
+                    && self.kind == x.kind
+                    && self.ordering_req == x.ordering_req
+                    && self.percentiles == x.percentiles
+                    && self.is_list == x.is_list
+                    && self.data_type == x.data_type
+                    && self.expr.eq(&x.expr)
+                    && self.fraction.eq(&x.fraction)
+            })
+            .unwrap_or(false)
+    }
+}
+
+/// Accumulates the non-null input values as `ScalarValue`s, in their order of
+/// arrival so that the values leaving a sliding window frame are found first
+#[derive(Debug)]
+struct PercentileAccumulator {
+    kind: PercentileKind,
+    descending: bool,
+    percentiles: Vec<f64>,
+    is_list: bool,
+    value_type: DataType,
+    data_type: DataType,
+    values: VecDeque<ScalarValue>,
+}
+
+impl PercentileAccumulator {
+    /// Casts the input values to the type of the accumulated values, as the
+    /// arguments of window functions are not coerced
+    fn cast_values(&self, values: &ArrayRef) -> Result<ArrayRef> {
+        if values.data_type() == &self.value_type {
+            Ok(values.clone())
+        } else {
+            Ok(cast(values, &self.value_type)?)
+        }
+    }
+
+    /// Computes the percentile `p` of the values of `array` sorted by `indices`
+    fn percentile(
+        &self,
+        array: &ArrayRef,
+        indices: &UInt32Array,
+        p: f64,
+    ) -> Result<ScalarValue> {
+        let n = array.len();
+        match self.kind {
+            PercentileKind::Continuous => match &self.value_type {
+                DataType::Decimal128(precision, scale) => {
+                    let values = as_decimal128_array(array)?;
+                    let (lower, upper, units) = decimal_position(p, n)?;
+                    let result = interpolate_decimal(
+                        i256::from_i128(values.value(indices.value(lower) as usize)),
+                        i256::from_i128(values.value(indices.value(upper) as usize)),
+                        units,
+                        *scale,
+                    )?;
+                    // the result lies between the two values
+                    Ok(ScalarValue::Decimal128(
+                        Some(result.as_i128()),
+                        *precision,
+                        *scale,
+                    ))
+                }
+                DataType::Decimal256(precision, scale) => {
+                    let values = as_decimal256_array(array)?;
+                    let (lower, upper, units) = decimal_position(p, n)?;
+                    let result = interpolate_decimal(
+                        values.value(indices.value(lower) as usize),
+                        values.value(indices.value(upper) as usize),
+                        units,
+                        *scale,
+                    )?;
+                    Ok(ScalarValue::Decimal256(Some(result), *precision, *scale))
+                }
+                _ => {
+                    let values = as_float64_array(array)?;
+                    let position = p * (n - 1) as f64;
+                    let lower =
+                        values.value(indices.value(position.floor() as usize) as usize);
+                    let upper =
+                        values.value(indices.value(position.ceil() as usize) as usize);
+                    let fraction = position - position.floor();
+                    Ok(ScalarValue::Float64(Some(
+                        lower + fraction * (upper - lower),
+                    )))
+                }
+            },
+            PercentileKind::Discrete => {
+                let position = ((p * n as f64).ceil() as usize).clamp(1, n);
+                ScalarValue::try_from_array(array, indices.value(position - 1) as usize)
+            }
+        }
+    }
+}
+
+/// Returns the positions of the two sorted values the percentile `p` of `n`
+/// values lies between, and its distance from the lower one as a number of
+/// `1 / 10^DECIMAL_FRACTION_DIGITS`. Unlike `p * (n - 1)` as a float, this is
+/// exact for the percentiles checked by [`decimal_fraction`].
+fn decimal_position(p: f64, n: usize) -> Result<(usize, usize, i256)> {
+    let units = decimal_fraction(p).ok_or_else(|| {
+        DataFusionError::Internal(format!(
+            "Percentile {p} of decimal PERCENTILE_CONT has too many decimal digits"
+        ))
+    })?;
+    let scale = 10_u64.pow(DECIMAL_FRACTION_DIGITS) as u128;
+    let position = units as u128 * (n - 1) as u128;
+    let lower = (position / scale) as usize;
+    let units = position % scale;
+    let upper = if units == 0 { lower } else { lower + 1 };
+    Ok((lower, upper, i256::from_i128(units as i128)))
+}
+
+/// Interpolates exactly between two decimal values of the given scale, with
+/// the distance from `lower` given as a number of
+/// `1 / 10^DECIMAL_FRACTION_DIGITS`. The values are cast to a decimal with
+/// [`DECIMAL_FRACTION_DIGITS`] more digits of scale than the input, so the
+/// result is representable unless that scale was capped at the maximum scale.
+fn interpolate_decimal(lower: i256, upper: i256, units: i256, scale: i8) -> Result<i256> {
+    let divisor = i256::from_i128(10_u64.pow(DECIMAL_FRACTION_DIGITS) as i128);
+    let overflow = || {
+        DataFusionError::Execution(
+            "Overflow while interpolating PERCENTILE_CONT".to_string(),
+        )
+    };
+    let difference = upper
+        .checked_sub(lower)
+        .and_then(|difference| difference.checked_mul(units))
+        .ok_or_else(overflow)?;
+    if difference.wrapping_rem(divisor) != i256::ZERO {
+        return Err(DataFusionError::Execution(format!(
+            "PERCENTILE_CONT cannot be represented exactly as a decimal with scale {scale}"
+        )));
+    }
+    lower
+        .checked_add(difference.wrapping_div(divisor))
+        .ok_or_else(overflow)
+}
+
+impl Accumulator for PercentileAccumulator {
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        let values = self.values.iter().cloned().collect();
+        Ok(vec![ScalarValue::new_list(
+            Some(values),
+            self.value_type.clone(),
+        )])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let array = &self.cast_values(&values[0])?;
+        for index in 0..array.len() {
+            if array.is_valid(index) {
+                self.values
+                    .push_back(ScalarValue::try_from_array(array, index)?);
+            }
+        }
+        Ok(())
+    }
+
+    fn retract_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let array = &self.cast_values(&values[0])?;
+        for index in 0..array.len() {
+            if array.is_valid(index) {
+                let value = ScalarValue::try_from_array(array, index)?;
+                if let Some(position) = self.values.iter().position(|v| v == &value) {
+                    self.values.remove(position);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let array = &states[0];
+        for index in 0..array.len() {
+            match ScalarValue::try_from_array(array, index)? {
+                ScalarValue::List(Some(values), _) => self.values.extend(values),
+                ScalarValue::List(None, _) => {} // skip empty state
+                v => {
+                    return Err(DataFusionError::Internal(format!(
+                        "unexpected state in {}. Expected DataType::List, got {v:?}",
+                        self.kind
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        if self.values.is_empty() {
+            return ScalarValue::try_from(&self.data_type);
+        }
+        let array = ScalarValue::iter_to_array(self.values.iter().cloned())?;
+        let options = SortOptions {
+            descending: self.descending,
+            nulls_first: false,
+        };
+        let indices = sort_to_indices(&array, Some(options), None)?;
+        let mut results = self
+            .percentiles
+            .iter()
+            .map(|p| self.percentile(&array, &indices, *p))
+            .collect::<Result<Vec<_>>>()?;
+        if self.is_list {
+            Ok(ScalarValue::new_list(
+                Some(results),
+                self.value_type.clone(),
+            ))
+        } else {
+            Ok(results.remove(0))
+        }
+    }
+
+    fn supports_retract_batch(&self) -> bool {
+        true
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + std::mem::size_of::<f64>() * self.percentiles.capacity()
+            + std::mem::size_of::<ScalarValue>() * self.values.capacity()
+            + self
+                .values
+                .iter()
+                .map(|v| v.size() - std::mem::size_of_val(v))
+                .sum::<usize>()
+            + self.value_type.size()
+            - std::mem::size_of_val(&self.value_type)
+            + self.data_type.size()
+            - std::mem::size_of_val(&self.data_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::tests::aggregate;
+    use crate::expressions::{col, lit};
+    use arrow::array::{Decimal128Array, Float64Array, Int32Array, Int64Array};
+    use arrow::datatypes::Schema;
+    use arrow::record_batch::RecordBatch;
+
+    fn percentile(
+        array: ArrayRef,
+        fraction: ScalarValue,
+        kind: PercentileKind,
+    ) -> Result<ScalarValue> {
+        let schema = Schema::new(vec![Field::new("a", array.data_type().clone(), true)]);
+        let data_type = match &fraction {
+            ScalarValue::List(..) => DataType::List(Arc::new(Field::new(
+                "item",
+                array.data_type().clone(),
+                true,
+            ))),
+            _ => array.data_type().clone(),
+        };
+        let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![array])?;
+        let agg = Arc::new(Percentile::try_new(
+            col("a", &schema)?,
+            lit(fraction),
+            kind,
+            "bla",
+            data_type,
+            vec![],
+        )?);
+        aggregate(&batch, agg)
+    }
+
+    #[test]
+    fn percentile_cont() -> Result<()> {
+        let a: ArrayRef = Arc::new(Float64Array::from(vec![
+            Some(4.0),
+            None,
+            Some(1.0),
+            Some(3.0),
+            Some(2.0),
+        ]));
+        for (p, expected) in [(0.0, 1.0), (0.25, 1.75), (0.5, 2.5), (1.0, 4.0)] {
+            assert_eq!(
+                percentile(a.clone(), ScalarValue::from(p), PercentileKind::Continuous)?,
+                ScalarValue::from(expected)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn percentile_cont_decimal() -> Result<()> {
+        let schema =
+            Schema::new(vec![Field::new("a", DataType::Decimal128(20, 2), true)]);
+        let agg = Percentile::try_new(
+            col("a", &schema)?,
+            lit(ScalarValue::new_list(
+                Some(vec![
+                    ScalarValue::from(0.25),
+                    ScalarValue::from(0.5),
+                    ScalarValue::from(0.3333),
+                ]),
+                DataType::Float64,
+            )),
+            PercentileKind::Continuous,
+            "bla",
+            DataType::List(Arc::new(Field::new(
+                "item",
+                DataType::Decimal128(24, 6),
+                true,
+            ))),
+            vec![],
+        )?;
+        let mut accumulator = agg.create_accumulator()?;
+        let values: ArrayRef = Arc::new(
+            Decimal128Array::from(vec![335, 110, 220]).with_precision_and_scale(20, 2)?,
+        );
+        accumulator.update_batch(&[values])?;
+        assert_eq!(
+            accumulator.evaluate()?,
+            ScalarValue::new_list(
+                Some(vec![
+                    ScalarValue::Decimal128(Some(1_650_000), 24, 6),
+                    ScalarValue::Decimal128(Some(2_200_000), 24, 6),
+                    ScalarValue::Decimal128(Some(1_833_260), 24, 6),
+                ]),
+                DataType::Decimal128(24, 6),
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn percentile_cont_decimal_invalid_fraction() -> Result<()> {
+        let schema =
+            Schema::new(vec![Field::new("a", DataType::Decimal128(20, 2), true)]);
+        let err = Percentile::try_new(
+            col("a", &schema)?,
+            lit(1.0 / 3.0),
+            PercentileKind::Continuous,
+            "bla",
+            DataType::Decimal128(24, 6),
+            vec![],
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: The percentile of PERCENTILE_CONT over decimal values can have at most 4 decimal digits, 0.3333333333333333 is invalid. Cast the values to DOUBLE to use it"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn percentile_cont_decimal_max_scale() -> Result<()> {
+        // the scale of the values can not be increased beyond the maximum
+        // scale, in which case an inexact result is an error
+        let schema =
+            Schema::new(vec![Field::new("a", DataType::Decimal128(38, 38), true)]);
+        let agg = Percentile::try_new(
+            col("a", &schema)?,
+            lit(0.25),
+            PercentileKind::Continuous,
+            "bla",
+            DataType::Decimal128(38, 38),
+            vec![],
+        )?;
+        let mut accumulator = agg.create_accumulator()?;
+        let values: ArrayRef = Arc::new(
+            Decimal128Array::from(vec![0, 1, 5]).with_precision_and_scale(38, 38)?,
+        );
+        accumulator.update_batch(&[values])?;
+        assert_eq!(
+            accumulator.evaluate().unwrap_err().to_string(),
+            "Execution error: PERCENTILE_CONT cannot be represented exactly as a decimal with scale 38"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn percentile_cont_uncoerced() -> Result<()> {
+        // window function arguments are not coerced to Float64
+        let schema = Schema::new(vec![Field::new("a", DataType::Int64, true)]);
+        let agg = Percentile::try_new(
+            col("a", &schema)?,
+            lit(0.5),
+            PercentileKind::Continuous,
+            "bla",
+            DataType::Float64,
+            vec![],
+        )?;
+        let mut accumulator = agg.create_sliding_accumulator()?;
+        let values: ArrayRef = Arc::new(Int64Array::from(vec![1, 2, 4]));
+        accumulator.update_batch(&[values.clone()])?;
+        assert_eq!(accumulator.evaluate()?, ScalarValue::from(2.0));
+        accumulator.retract_batch(&[values.slice(0, 1)])?;
+        assert_eq!(accumulator.evaluate()?, ScalarValue::from(3.0));
+        Ok(())
+    }
+
+    #[test]
+    fn percentile_disc() -> Result<()> {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![
+            Some(4),
+            None,
+            Some(1),
+            Some(3),
+            Some(2),
+        ]));
+        for (p, expected) in [(0.0, 1), (0.25, 1), (0.5, 2), (0.51, 3), (1.0, 4)] {
+            assert_eq!(
+                percentile(a.clone(), ScalarValue::from(p), PercentileKind::Discrete)?,
+                ScalarValue::from(expected)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn percentile_descending() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Float64, true)]);
+        let a = col("a", &schema)?;
+        let ordering_req = vec![PhysicalSortExpr {
+            expr: a.clone(),
+            options: SortOptions {
+                descending: true,
+                nulls_first: true,
+            },
+        }];
+        let values: ArrayRef = Arc::new(Float64Array::from(vec![
+            Some(4.0),
+            None,
+            Some(1.0),
+            Some(3.0),
+            Some(2.0),
+        ]));
+        // descending PERCENTILE_CONT(p) is PERCENTILE_CONT(1 - p), while
+        // PERCENTILE_DISC picks the mirrored position
+        for (kind, p, expected) in [
+            (PercentileKind::Continuous, 0.25, 3.25),
+            (PercentileKind::Continuous, 1.0, 1.0),
+            (PercentileKind::Discrete, 0.25, 4.0),
+            (PercentileKind::Discrete, 0.5, 3.0),
+            (PercentileKind::Discrete, 0.51, 2.0),
+        ] {
+            let agg = Percentile::try_new(
+                a.clone(),
+                lit(p),
+                kind,
+                "bla",
+                DataType::Float64,
+                ordering_req.clone(),
+            )?;
+            assert_eq!(agg.order_bys(), Some(ordering_req.as_slice()));
+            let mut accumulator = agg.create_accumulator()?;
+            accumulator.update_batch(&[values.clone()])?;
+            assert_eq!(accumulator.evaluate()?, ScalarValue::from(expected));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn percentile_list() -> Result<()> {
+        let a: ArrayRef = Arc::new(Int32Array::from(vec![5, 1, 4, 2, 3]));
+        let fractions = ScalarValue::new_list(
+            Some(vec![ScalarValue::from(0.5), ScalarValue::from(0.1)]),
+            DataType::Float64,
+        );
+        assert_eq!(
+            percentile(a, fractions, PercentileKind::Discrete)?,
+            ScalarValue::new_list(
+                Some(vec![ScalarValue::from(3), ScalarValue::from(1)]),
+                DataType::Int32
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn percentile_all_nulls() -> Result<()> {
+        let a: ArrayRef = Arc::new(Float64Array::from(vec![None, None]));
+        assert_eq!(
+            percentile(a, ScalarValue::from(0.5), PercentileKind::Continuous)?,
+            ScalarValue::Float64(None)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn percentile_invalid_fraction() {
+        let a: ArrayRef = Arc::new(Float64Array::from(vec![1.0]));
+        let err = percentile(a, ScalarValue::from(1.5), PercentileKind::Continuous)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: Percentile value must be between 0.0 and 1.0 inclusive, 1.5 is invalid"
+        );
+    }
+
+    #[test]
+    fn percentile_retract() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Float64, true)]);
+        let agg = Percentile::try_new(
+            col("a", &schema)?,
+            lit(0.5),
+            PercentileKind::Continuous,
+            "bla",
+            DataType::Float64,
+            vec![],
+        )?;
+        let mut accumulator = agg.create_sliding_accumulator()?;
+        let values: ArrayRef = Arc::new(Float64Array::from(vec![1.0, 2.0, 3.0, 10.0]));
+        accumulator.update_batch(&[values.clone()])?;
+        assert_eq!(accumulator.evaluate()?, ScalarValue::from(2.5));
+        accumulator.retract_batch(&[values.slice(0, 2)])?;
+        assert_eq!(accumulator.evaluate()?, ScalarValue::from(6.5));
+        Ok(())
+    }
+}
@@ -0,0 +1,793 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines physical expressions for the STRING_AGG aggregate function
+
+use crate::aggregate::array_agg_ordered::OrderSensitiveArrayAggAccumulator;
+use crate::aggregate::groups_accumulator::EmitTo;
+use crate::aggregate::utils::{down_cast_any_ref, ordering_fields};
+use crate::expressions::{format_state_name, Literal};
+use crate::PhysicalSortExpr;
+use crate::{AggregateExpr, GroupsAccumulator, LexOrdering, PhysicalExpr};
+use arrow::array::{Array, ArrayRef, AsArray, GenericStringArray, OffsetSizeTrait};
+use arrow::datatypes::{DataType, Field, Fields};
+use arrow_array::BooleanArray;
+use datafusion_common::{DataFusionError, Result, ScalarValue};
+use datafusion_expr::Accumulator;
+use std::any::Any;
+use std::collections::HashSet;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// STRING_AGG aggregate expression
+#[derive(Debug)]
+pub struct StringAgg {
+    name: String,
+    data_type: DataType,
+    expr: Arc<dyn PhysicalExpr>,
+    separator_expr: Arc<dyn PhysicalExpr>,
+    separator: String,
+}
+
+impl StringAgg {
+    /// Create a new StringAgg aggregate function, concatenating the values of
+    /// `expr` separated by the string literal `separator_expr`
+    pub fn try_new(
+        expr: Arc<dyn PhysicalExpr>,
+        separator_expr: Arc<dyn PhysicalExpr>,
+        name: impl Into<String>,
+        data_type: DataType,
+    ) -> Result<Self> {
+        Ok(Self {
+            name: name.into(),
+            data_type,
+            expr,
+            separator: validate_separator_expr(&separator_expr)?,
+            separator_expr,
+        })
+    }
+}
+
+impl AggregateExpr for StringAgg {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn field(&self) -> Result<Field> {
+        Ok(Field::new(&self.name, self.data_type.clone(), true))
+    }
+
+    fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(StringAggAccumulator::new(
+            &self.separator,
+            &self.data_type,
+        )))
+    }
+
+    fn state_fields(&self) -> Result<Vec<Field>> {
+        Ok(vec![Field::new(
+            format_state_name(&self.name, "string_agg"),
+            self.data_type.clone(),
+            true,
+        )])
+    }
+
+    fn expressions(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        vec![self.expr.clone(), self.separator_expr.clone()]
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn groups_accumulator_supported(&self) -> bool {
+        true
+    }
+
+    fn create_groups_accumulator(&self) -> Result<Box<dyn GroupsAccumulator>> {
+        match self.data_type {
+            DataType::Utf8 => Ok(Box::new(StringAggGroupsAccumulator::<i32>::new(
+                &self.separator,
+            ))),
+            DataType::LargeUtf8 => Ok(Box::new(StringAggGroupsAccumulator::<i64>::new(
+                &self.separator,
+            ))),
+            _ => Err(DataFusionError::NotImplemented(format!(
+                "GroupsAccumulator not supported for {} with {}",
+                self.name(),
+                self.data_type
+            ))),
+        }
+    }
+}
+
+impl PartialEq<dyn Any> for StringAgg {
+    fn eq(&self, other: &dyn Any) -> bool {
+        down_cast_any_ref(other)
+            .downcast_ref::<Self>()
+            .map(|x| {
+                self.name == x.name
+                    && self.data_type == x.data_type
+                    && self.separator == x.separator
+                    && self.expr.eq(&x.expr)
+            })
+            .unwrap_or(false)
+    }
+}
+
+/// Expression for a STRING_AGG(ORDER BY) aggregation, which collects its
+/// values with the machinery of [`OrderSensitiveArrayAgg`] and concatenates
+/// them in order at the end.
+///
+/// [`OrderSensitiveArrayAgg`]: crate::expressions::OrderSensitiveArrayAgg
+#[derive(Debug)]
+pub struct OrderSensitiveStringAgg {
+    name: String,
+    data_type: DataType,
+    order_by_data_types: Vec<DataType>,
+    expr: Arc<dyn PhysicalExpr>,
+    separator_expr: Arc<dyn PhysicalExpr>,
+    separator: String,
+    ordering_req: LexOrdering,
+}
+
+impl OrderSensitiveStringAgg {
+    /// Create a new `OrderSensitiveStringAgg` aggregate function
+    pub fn try_new(
+        expr: Arc<dyn PhysicalExpr>,
+        separator_expr: Arc<dyn PhysicalExpr>,
+        name: impl Into<String>,
+        data_type: DataType,
+        order_by_data_types: Vec<DataType>,
+        ordering_req: LexOrdering,
+    ) -> Result<Self> {
+        Ok(Self {
+            name: name.into(),
+            data_type,
+            order_by_data_types,
+            expr,
+            separator: validate_separator_expr(&separator_expr)?,
+            separator_expr,
+            ordering_req,
+        })
+    }
+}
+
+impl AggregateExpr for OrderSensitiveStringAgg {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn field(&self) -> Result<Field> {
+        Ok(Field::new(&self.name, self.data_type.clone(), true))
+    }
+
+    fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(OrderSensitiveStringAggAccumulator {
+            values: OrderSensitiveArrayAggAccumulator::try_new(
+                &self.data_type,
+                &self.order_by_data_types,
+                self.ordering_req.clone(),
+            )?,
+            separator: self.separator.clone(),
+            data_type: self.data_type.clone(),
+        }))
+    }
+
+    fn state_fields(&self) -> Result<Vec<Field>> {
+        // Same as the state of `OrderSensitiveArrayAgg`
+        let mut fields = vec![Field::new_list(
+            format_state_name(&self.name, "string_agg"),
+            Field::new("item", self.data_type.clone(), true),
+            false,
+        )];
+        let orderings = ordering_fields(&self.ordering_req, &self.order_by_data_types);
+        fields.push(Field::new_list(
+            format_state_name(&self.name, "string_agg_orderings"),
+            Field::new(
+                "item",
+                DataType::Struct(Fields::from(orderings.clone())),
+                true,
+            ),
+            false,
+        ));
+        fields.extend(orderings);
+        Ok(fields)
+    }
+
+    fn expressions(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        vec![self.expr.clone(), self.separator_expr.clone()]
+    }
+
+    fn order_bys(&self) -> Option<&[PhysicalSortExpr]> {
+        if self.ordering_req.is_empty() {
+            None
+        } else {
+            Some(&self.ordering_req)
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl PartialEq<dyn Any> for OrderSensitiveStringAgg {
+    fn eq(&self, other: &dyn Any) -> bool {
+        down_cast_any_ref(other)
+            .downcast_ref::<Self>()
+            .map(|x| {
+                self.name == x.name
+                    && self.data_type == x.data_type
+                    && self.order_by_data_types == x.order_by_data_types
+                    && self.separator == x.separator
+                    && self.expr.eq(&x.expr)
+            })
+            .unwrap_or(false)
+    }
+}
+
+/// Expression for a STRING_AGG(DISTINCT) aggregation, which concatenates
+/// the distinct values in ascending order, or in descending order when
+/// ordered by the aggregated expression in descending order.
+#[derive(Debug)]
+pub struct DistinctStringAgg {
+    name: String,
+    data_type: DataType,
+    expr: Arc<dyn PhysicalExpr>,
+    separator_expr: Arc<dyn PhysicalExpr>,
+    separator: String,
+    ordering_req: LexOrdering,
+}
+
+impl DistinctStringAgg {
+    /// Create a new `DistinctStringAgg` aggregate function. The ordering
+    /// requirement must be empty or order by `expr`.
+    pub fn try_new(
+        expr: Arc<dyn PhysicalExpr>,
+        separator_expr: Arc<dyn PhysicalExpr>,
+        name: impl Into<String>,
+        data_type: DataType,
+        ordering_req: LexOrdering,
+    ) -> Result<Self> {
+        if !(ordering_req.is_empty()
+            || ordering_req.len() == 1 && ordering_req[0].expr.eq(&expr))
+        {
+            return Err(DataFusionError::NotImplemented(
+                "STRING_AGG(DISTINCT) can only be ordered by the aggregated expression"
+                    .to_string(),
+            ));
+        }
+        Ok(Self {
+            name: name.into(),
+            data_type,
+            expr,
+            separator: validate_separator_expr(&separator_expr)?,
+            separator_expr,
+            ordering_req,
+        })
+    }
+
+    fn descending(&self) -> bool {
+        matches!(self.ordering_req.first(), Some(sort_expr) if sort_expr.options.descending)
+    }
+}
+
+impl AggregateExpr for DistinctStringAgg {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn field(&self) -> Result<Field> {
+        Ok(Field::new(&self.name, self.data_type.clone(), true))
+    }
+
+    fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(DistinctStringAggAccumulator {
+            values: HashSet::new(),
+            separator: self.separator.clone(),
+            descending: self.descending(),
+            data_type: self.data_type.clone(),
+        }))
+    }
+
+    fn state_fields(&self) -> Result<Vec<Field>> {
+        Ok(vec![Field::new_list(
+            format_state_name(&self.name, "string_agg_distinct"),
+            Field::new("item", self.data_type.clone(), true),
+            false,
+        )])
+    }
+
+    fn expressions(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        vec![self.expr.clone(), self.separator_expr.clone()]
+    }
+
+    // The ordering is applied by the accumulator, the input does not have to
+    // be sorted
+    fn order_bys(&self) -> Option<&[PhysicalSortExpr]> {
+        if self.ordering_req.is_empty() {
+            None
+        } else {
+            Some(&self.ordering_req)
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl PartialEq<dyn Any> for DistinctStringAgg {
+    fn eq(&self, other: &dyn Any) -> bool {
+        down_cast_any_ref(other)
+            .downcast_ref::<Self>()
+            .map(|x| {
+                self.name == x.name
+                    && self.data_type == x.data_type
+                    && self.separator == x.separator
+                    && self.descending() == x.descending()
+                    && self.expr.eq(&x.expr)
+            })
+            .unwrap_or(false)
+    }
+}
+
+/// Returns the separator of a STRING_AGG, which must be a string literal. A
+/// NULL separator concatenates the values without a separator.
+fn validate_separator_expr(expr: &Arc<dyn PhysicalExpr>) -> Result<String> {
+    let value = expr
+        .as_any()
+        .downcast_ref::<Literal>()
+        .map(|lit| lit.value());
+    match value {
+        Some(ScalarValue::Utf8(separator) | ScalarValue::LargeUtf8(separator)) => {
+            Ok(separator.clone().unwrap_or_default())
+        }
+        _ => Err(DataFusionError::NotImplemented(format!(
+            "The separator of STRING_AGG must be a string literal, got {expr}"
+        ))),
+    }
+}
+
+/// Appends `value` to the concatenation `acc`
+fn append(acc: &mut Option<String>, separator: &str, value: &str) {
+    match acc {
+        Some(acc) => {
+            acc.push_str(separator);
+            acc.push_str(value);
+        }
+        None => *acc = Some(value.to_string()),
+    }
+}
+
+/// Calls `f` with every non-null value of a string array
+fn for_each_string(array: &ArrayRef, f: impl FnMut(&str)) -> Result<()> {
+    match array.data_type() {
+        DataType::Utf8 => array.as_string::<i32>().iter().flatten().for_each(f),
+        DataType::LargeUtf8 => array.as_string::<i64>().iter().flatten().for_each(f),
+        DataType::Null => {}
+        other => {
+            return Err(DataFusionError::Internal(format!(
+                "STRING_AGG does not support inputs of type {other}"
+            )))
+        }
+    }
+    Ok(())
+}
+
+fn string_scalar(value: Option<String>, data_type: &DataType) -> ScalarValue {
+    match data_type {
+        DataType::LargeUtf8 => ScalarValue::LargeUtf8(value),
+        _ => ScalarValue::Utf8(value),
+    }
+}
+
+#[derive(Debug)]
+struct StringAggAccumulator {
+    value: Option<String>,
+    separator: String,
+    data_type: DataType,
+}
+
+impl StringAggAccumulator {
+    fn new(separator: &str, data_type: &DataType) -> Self {
+        Self {
+            value: None,
+            separator: separator.to_string(),
+            data_type: data_type.clone(),
+        }
+    }
+}
+
+impl Accumulator for StringAggAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        for_each_string(&values[0], |value| {
+            append(&mut self.value, &self.separator, value)
+        })
+    }
+
+    // The state is the concatenation of the values seen so far, so merging
+    // is the same as updating
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        self.update_batch(states)
+    }
+
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        Ok(vec![self.evaluate()?])
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        Ok(string_scalar(self.value.clone(), &self.data_type))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + self.value.as_ref().map_or(0, |v| v.capacity())
+            + self.separator.capacity()
+            + self.data_type.size()
+            - std::mem::size_of_val(&self.data_type)
+    }
+}
+
+/// A [`GroupsAccumulator`] for STRING_AGG, which keeps the concatenation of
+/// the values of every group
+#[derive(Debug)]
+struct StringAggGroupsAccumulator<O: OffsetSizeTrait> {
+    values: Vec<Option<String>>,
+    separator: String,
+    /// Total length of the concatenated values
+    values_size: usize,
+    phantom: PhantomData<O>,
+}
+
+impl<O: OffsetSizeTrait> StringAggGroupsAccumulator<O> {
+    fn new(separator: &str) -> Self {
+        Self {
+            values: vec![],
+            separator: separator.to_string(),
+            values_size: 0,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<O: OffsetSizeTrait> GroupsAccumulator for StringAggGroupsAccumulator<O> {
+    fn update_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        self.values.resize(total_num_groups, None);
+        let array = values[0].as_string::<O>();
+        for (row, &group_index) in group_indices.iter().enumerate() {
+            let filtered_out =
+                matches!(opt_filter, Some(f) if f.is_null(row) || !f.value(row));
+            if array.is_null(row) || filtered_out {
+                continue;
+            }
+            let acc = &mut self.values[group_index];
+            let len_before = acc.as_ref().map_or(0, |v| v.len());
+            append(acc, &self.separator, array.value(row));
+            self.values_size += acc.as_ref().map_or(0, |v| v.len()) - len_before;
+        }
+        Ok(())
+    }
+
+    fn evaluate(&mut self, emit_to: EmitTo) -> Result<ArrayRef> {
+        let values = emit_to.take_needed(&mut self.values);
+        self.values_size -= values.iter().flatten().map(|v| v.len()).sum::<usize>();
+        Ok(Arc::new(GenericStringArray::<O>::from(values)))
+    }
+
+    fn state(&mut self, emit_to: EmitTo) -> Result<Vec<ArrayRef>> {
+        Ok(vec![self.evaluate(emit_to)?])
+    }
+
+    fn merge_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        self.update_batch(values, group_indices, opt_filter, total_num_groups)
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + self.values.capacity() * std::mem::size_of::<Option<String>>()
+            + self.values_size
+            + self.separator.capacity()
+    }
+}
+
+#[derive(Debug)]
+struct OrderSensitiveStringAggAccumulator {
+    values: OrderSensitiveArrayAggAccumulator,
+    separator: String,
+    data_type: DataType,
+}
+
+impl Accumulator for OrderSensitiveStringAggAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        // Skip the separator, which follows the aggregated values
+        let values = values
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != 1)
+            .map(|(_, array)| array.clone())
+            .collect::<Vec<_>>();
+        self.values.update_batch(&values)
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        self.values.merge_batch(states)
+    }
+
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        self.values.state()
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        let mut value = None;
+        if let ScalarValue::List(Some(values), _) = self.values.evaluate()? {
+            for v in values {
+                if let ScalarValue::Utf8(Some(v)) | ScalarValue::LargeUtf8(Some(v)) = v {
+                    append(&mut value, &self.separator, &v);
+                }
+            }
+        }
+        Ok(string_scalar(value, &self.data_type))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self) - std::mem::size_of_val(&self.values)
+            + self.values.size()
+            + self.separator.capacity()
+            + self.data_type.size()
+            - std::mem::size_of_val(&self.data_type)
+    }
+}
+
+#[derive(Debug)]
+struct DistinctStringAggAccumulator {
+    values: HashSet<String>,
+    separator: String,
+    descending: bool,
+    data_type: DataType,
+}
+
+impl Accumulator for DistinctStringAggAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        for_each_string(&values[0], |value| {
+            if !self.values.contains(value) {
+                self.values.insert(value.to_string());
+            }
+        })
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let lists = states[0].as_list::<i32>();
+        for list in lists.iter().flatten() {
+            self.update_batch(&[list])?;
+        }
+        Ok(())
+    }
+
+    fn state(&self) -> Result<Vec<ScalarValue>> {
+        let values = self
+            .values
+            .iter()
+            .map(|v| string_scalar(Some(v.clone()), &self.data_type))
+            .collect();
+        Ok(vec![ScalarValue::new_list(
+            Some(values),
+            self.data_type.clone(),
+        )])
+    }
+
+    fn evaluate(&self) -> Result<ScalarValue> {
+        let mut values = self.values.iter().collect::<Vec<_>>();
+        values.sort_unstable();
+        if self.descending {
+            values.reverse();
+        }
+        let mut value = None;
+        for v in values {
+            append(&mut value, &self.separator, v);
+        }
+        Ok(string_scalar(value, &self.data_type))
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + self.values.capacity() * std::mem::size_of::<String>()
+            + self.values.iter().map(|v| v.capacity()).sum::<usize>()
+            + self.separator.capacity()
+            + self.data_type.size()
+            - std::mem::size_of_val(&self.data_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::col;
+    use crate::expressions::tests::aggregate;
+    use arrow::array::{LargeStringArray, StringArray};
+    use arrow::datatypes::Schema;
+    use arrow::record_batch::RecordBatch;
+    use arrow_schema::SortOptions;
+
+    fn separator(separator: &str) -> Arc<dyn PhysicalExpr> {
+        Arc::new(Literal::new(ScalarValue::from(separator)))
+    }
+
+    fn batch(values: ArrayRef) -> Result<RecordBatch> {
+        let schema = Schema::new(vec![Field::new("a", values.data_type().clone(), true)]);
+        Ok(RecordBatch::try_new(Arc::new(schema), vec![values])?)
+    }
+
+    #[test]
+    fn string_agg() -> Result<()> {
+        let batch = batch(Arc::new(StringArray::from(vec![
+            Some("a"),
+            None,
+            Some("b"),
+            Some("c"),
+        ])))?;
+        let agg = Arc::new(StringAgg::try_new(
+            col("a", &batch.schema())?,
+            separator(", "),
+            "bla",
+            DataType::Utf8,
+        )?);
+        assert_eq!(aggregate(&batch, agg)?, ScalarValue::from("a, b, c"));
+        Ok(())
+    }
+
+    #[test]
+    fn string_agg_merge() -> Result<()> {
+        let a: ArrayRef = Arc::new(LargeStringArray::from(vec!["a", "b"]));
+        let b: ArrayRef = Arc::new(LargeStringArray::from(vec!["c"]));
+
+        let mut acc1 = StringAggAccumulator::new("-", &DataType::LargeUtf8);
+        acc1.update_batch(&[a])?;
+        let mut acc2 = StringAggAccumulator::new("-", &DataType::LargeUtf8);
+        acc2.update_batch(&[b])?;
+        let state = acc2.state()?[0].to_array();
+        acc1.merge_batch(&[state])?;
+
+        assert_eq!(
+            acc1.evaluate()?,
+            ScalarValue::LargeUtf8(Some("a-b-c".to_string()))
+        );
+
+        // no values
+        let acc = StringAggAccumulator::new("-", &DataType::Utf8);
+        assert_eq!(acc.evaluate()?, ScalarValue::Utf8(None));
+        Ok(())
+    }
+
+    #[test]
+    fn string_agg_groups() -> Result<()> {
+        let values: ArrayRef = Arc::new(StringArray::from(vec![
+            Some("a"),
+            Some("b"),
+            None,
+            Some("c"),
+            Some("d"),
+        ]));
+        let filter = BooleanArray::from(vec![true, true, true, true, false]);
+
+        let mut acc = StringAggGroupsAccumulator::<i32>::new(",");
+        acc.update_batch(&[values], &[0, 2, 2, 0, 1], Some(&filter), 3)?;
+        let state = acc.state(EmitTo::First(1))?;
+        assert_eq!(state[0].as_string::<i32>().value(0), "a,c");
+
+        // the remaining groups are shifted down
+        let values: ArrayRef = Arc::new(StringArray::from(vec!["e"]));
+        acc.update_batch(&[values], &[1], None, 2)?;
+        let result = acc.evaluate(EmitTo::All)?;
+        let expected: ArrayRef = Arc::new(StringArray::from(vec![None, Some("b,e")]));
+        assert_eq!(&result, &expected);
+
+        // merging the state of the first group
+        acc.merge_batch(&state, &[0], None, 1)?;
+        let values: ArrayRef = Arc::new(StringArray::from(vec!["f"]));
+        acc.merge_batch(&[values], &[0], None, 1)?;
+        let result = acc.evaluate(EmitTo::All)?;
+        let expected: ArrayRef = Arc::new(StringArray::from(vec!["a,c,f"]));
+        assert_eq!(&result, &expected);
+        assert_eq!(acc.values_size, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn string_agg_distinct() -> Result<()> {
+        let batch = batch(Arc::new(StringArray::from(vec![
+            Some("b"),
+            Some("a"),
+            None,
+            Some("b"),
+            Some("c"),
+        ])))?;
+        let expr = col("a", &batch.schema())?;
+        let agg = Arc::new(DistinctStringAgg::try_new(
+            expr.clone(),
+            separator("|"),
+            "bla",
+            DataType::Utf8,
+            vec![],
+        )?);
+        assert_eq!(aggregate(&batch, agg)?, ScalarValue::from("a|b|c"));
+
+        let ordering_req = vec![PhysicalSortExpr {
+            expr: expr.clone(),
+            options: SortOptions {
+                descending: true,
+                nulls_first: true,
+            },
+        }];
+        let agg = Arc::new(DistinctStringAgg::try_new(
+            expr.clone(),
+            separator("|"),
+            "bla",
+            DataType::Utf8,
+            ordering_req,
+        )?);
+        assert_eq!(aggregate(&batch, agg)?, ScalarValue::from("c|b|a"));
+
+        let ordering_req = vec![PhysicalSortExpr {
+            expr: Arc::new(Literal::new(ScalarValue::from(1))),
+            options: SortOptions::default(),
+        }];
+        let err = DistinctStringAgg::try_new(
+            expr,
+            separator("|"),
+            "bla",
+            DataType::Utf8,
+            ordering_req,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "This feature is not implemented: STRING_AGG(DISTINCT) can only be ordered by the aggregated expression"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn string_agg_invalid_separator() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Utf8, true)]);
+        let err = StringAgg::try_new(
+            col("a", &schema)?,
+            col("a", &schema)?,
+            "bla",
+            DataType::Utf8,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "This feature is not implemented: The separator of STRING_AGG must be a string literal, got a@0"
+        );
+        Ok(())
+    }
+}
@@ -15,18 +15,19 @@
 // specific language governing permissions and limitations
 // under the License.
 
-//! get field of a `ListArray`
+//! get field of a `ListArray`, `StructArray` or `MapArray`
 
 use crate::PhysicalExpr;
 use arrow::array::Array;
 use arrow::compute::concat;
 
+use crate::map_expressions::map_value;
 use crate::physical_expr::down_cast_any_ref;
 use arrow::{
     datatypes::{DataType, Schema},
     record_batch::RecordBatch,
 };
-use datafusion_common::cast::{as_list_array, as_struct_array};
+use datafusion_common::cast::{as_list_array, as_map_array, as_struct_array};
 use datafusion_common::DataFusionError;
 use datafusion_common::Result;
 use datafusion_common::ScalarValue;
@@ -90,6 +91,16 @@ impl PhysicalExpr for GetIndexedFieldExpr {
                 let scalar_null: ScalarValue = array.data_type().try_into()?;
                 Ok(ColumnarValue::Scalar(scalar_null))
             }
+            (DataType::Map(_, _), _) => {
+                let as_map_array = as_map_array(&array)?;
+                if self.key.is_null() {
+                    let field = get_data_type_field(array.data_type(), &self.key)?;
+                    let scalar_null: ScalarValue = field.data_type().try_into()?;
+                    return Ok(ColumnarValue::Scalar(scalar_null))
+                }
+                let keys = self.key.to_array_of_size(as_map_array.len());
+                Ok(ColumnarValue::Array(map_value(as_map_array, &keys)?))
+            }
             (DataType::List(lst), ScalarValue::Int64(Some(i))) => {
                 let as_list_array = as_list_array(&array)?;
 
@@ -136,8 +147,8 @@ impl PhysicalExpr for GetIndexedFieldExpr {
                 format!("get indexed field is only possible on struct with utf8 indexes. \
                          Tried with {key:?} index"))),
             (dt, key) => Err(DataFusionError::Execution(
-                format!("get indexed field is only possible on lists with int64 indexes or struct \
-                         with utf8 indexes. Tried {dt:?} with {key:?} index"))),
+                format!("get indexed field is only possible on lists with int64 indexes, struct \
+                         with utf8 indexes or maps. Tried {dt:?} with {key:?} index"))),
         }
     }
 
@@ -281,8 +292,8 @@ mod tests {
         let expr = lit("a");
         get_indexed_field_test_failure(
             schema, expr,  ScalarValue::Int64(Some(0)),
-            "Execution error: get indexed field is only possible on lists with int64 indexes or \
-             struct with utf8 indexes. Tried Utf8 with Int64(0) index")
+            "Execution error: get indexed field is only possible on lists with int64 indexes, \
+             struct with utf8 indexes or maps. Tried Utf8 with Int64(0) index")
     }
 
     #[test]
@@ -34,8 +34,8 @@ use crate::execution_props::ExecutionProps;
 use crate::{
     array_expressions, conditional_expressions, datetime_expressions,
     expressions::{cast_column, nullif_func},
-    math_expressions, string_expressions, struct_expressions, PhysicalExpr,
-    ScalarFunctionExpr,
+    map_expressions, math_expressions, string_expressions, struct_expressions,
+    PhysicalExpr, ScalarFunctionExpr,
 };
 use arrow::{
     array::ArrayRef,
@@ -201,6 +201,26 @@ macro_rules! invoke_if_encoding_expressions_feature_flag {
     };
 }
 
+#[cfg(feature = "json_expressions")]
+macro_rules! invoke_if_json_expressions_feature_flag {
+    ($FUNC:ident, $NAME:expr) => {{
+        use crate::json_expressions;
+        json_expressions::$FUNC
+    }};
+}
+
+#[cfg(not(feature = "json_expressions"))]
+macro_rules! invoke_if_json_expressions_feature_flag {
+    ($FUNC:ident, $NAME:expr) => {
+        |_: &[ColumnarValue]| -> Result<ColumnarValue> {
+            Err(DataFusionError::Internal(format!(
+                "function {} requires compilation with feature flag: json_expressions.",
+                $NAME
+            )))
+        }
+    };
+}
+
 #[cfg(feature = "crypto_expressions")]
 macro_rules! invoke_if_crypto_expressions_feature_flag {
     ($FUNC:ident, $NAME:expr) => {{
@@ -473,6 +493,43 @@ pub fn create_physical_fun(
             Arc::new(|args| make_scalar_function(array_expressions::trim_array)(args))
         }
 
+        // map functions
+        BuiltinScalarFunction::MakeMap => {
+            Arc::new(|args| make_scalar_function(map_expressions::make_map)(args))
+        }
+        BuiltinScalarFunction::MapExtract => {
+            Arc::new(|args| make_scalar_function(map_expressions::map_extract)(args))
+        }
+        BuiltinScalarFunction::MapKeys => {
+            Arc::new(|args| make_scalar_function(map_expressions::map_keys)(args))
+        }
+        BuiltinScalarFunction::MapValues => {
+            Arc::new(|args| make_scalar_function(map_expressions::map_values)(args))
+        }
+
+        // json functions
+        BuiltinScalarFunction::JsonArrayLength => {
+            Arc::new(invoke_if_json_expressions_feature_flag!(
+                json_array_length,
+                "json_array_length"
+            ))
+        }
+        BuiltinScalarFunction::JsonAsStruct => Arc::new(
+            invoke_if_json_expressions_feature_flag!(json_as_struct, "json_as_struct"),
+        ),
+        BuiltinScalarFunction::JsonContains => Arc::new(
+            invoke_if_json_expressions_feature_flag!(json_contains, "json_contains"),
+        ),
+        BuiltinScalarFunction::JsonGet => Arc::new(
+            invoke_if_json_expressions_feature_flag!(json_get, "json_get"),
+        ),
+        BuiltinScalarFunction::JsonGetInt => Arc::new(
+            invoke_if_json_expressions_feature_flag!(json_get_int, "json_get_int"),
+        ),
+        BuiltinScalarFunction::JsonGetStr => Arc::new(
+            invoke_if_json_expressions_feature_flag!(json_get_str, "json_get_str"),
+        ),
+
         // string functions
         BuiltinScalarFunction::Struct => Arc::new(struct_expressions::struct_expr),
         BuiltinScalarFunction::Ascii => Arc::new(|args| match args[0].data_type() {
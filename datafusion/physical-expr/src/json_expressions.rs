@@ -0,0 +1,407 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! JSON expressions
+//!
+//! These functions operate on JSON text stored in `Utf8` / `LargeUtf8`
+//! columns, parsing each value of the batch. Values that are not valid JSON
+//! evaluate to NULL.
+
+use arrow::array::*;
+use arrow::buffer::NullBuffer;
+use arrow::compute;
+use arrow::datatypes::{DataType, Fields, Schema};
+use arrow::json::ReaderBuilder;
+use datafusion_common::cast::{as_int64_array, as_string_array};
+use datafusion_common::{DataFusionError, Result, ScalarValue};
+use datafusion_expr::ColumnarValue;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// A `Utf8` argument, which is either a scalar or has one value per row
+struct StringArg {
+    values: StringArray,
+    is_scalar: bool,
+}
+
+impl StringArg {
+    fn value(&self, row: usize) -> Option<&str> {
+        let row = if self.is_scalar { 0 } else { row };
+        self.values.is_valid(row).then(|| self.values.value(row))
+    }
+}
+
+/// A single step of a json path
+enum PathStep<'a> {
+    /// Key of an object
+    Key(&'a str),
+    /// Position in an array, negative values count from the end
+    Index(i64),
+}
+
+/// A path argument of the json functions
+enum PathArg {
+    Keys(StringArg),
+    Indexes(Int64Array, bool),
+    Null,
+}
+
+impl PathArg {
+    fn try_new(arg: &ColumnarValue) -> Result<Self> {
+        let (array, is_scalar) = match arg {
+            ColumnarValue::Array(array) => (array.clone(), false),
+            ColumnarValue::Scalar(scalar) => (scalar.to_array(), true),
+        };
+        match array.data_type() {
+            DataType::Null => Ok(PathArg::Null),
+            DataType::Utf8 | DataType::LargeUtf8 => Ok(PathArg::Keys(StringArg {
+                values: as_string_array(&compute::cast(&array, &DataType::Utf8)?)?
+                    .clone(),
+                is_scalar,
+            })),
+            data_type if data_type.is_integer() => {
+                let indexes = compute::cast(&array, &DataType::Int64)?;
+                Ok(PathArg::Indexes(
+                    as_int64_array(&indexes)?.clone(),
+                    is_scalar,
+                ))
+            }
+            other => Err(DataFusionError::Internal(format!(
+                "json path elements must be strings or integers, got {other:?}"
+            ))),
+        }
+    }
+
+    /// Returns the path step for `row`, or `None` if it is null
+    fn step(&self, row: usize) -> Option<PathStep<'_>> {
+        match self {
+            PathArg::Keys(keys) => keys.value(row).map(PathStep::Key),
+            PathArg::Indexes(indexes, is_scalar) => {
+                let row = if *is_scalar { 0 } else { row };
+                indexes
+                    .is_valid(row)
+                    .then(|| PathStep::Index(indexes.value(row)))
+            }
+            PathArg::Null => None,
+        }
+    }
+}
+
+/// Converts the json argument of the json functions
+fn json_arg(arg: &ColumnarValue) -> Result<StringArg> {
+    let (array, is_scalar) = match arg {
+        ColumnarValue::Array(array) => (array.clone(), false),
+        ColumnarValue::Scalar(scalar) => (scalar.to_array(), true),
+    };
+    match array.data_type() {
+        DataType::Null | DataType::Utf8 | DataType::LargeUtf8 => Ok(StringArg {
+            values: as_string_array(&compute::cast(&array, &DataType::Utf8)?)?.clone(),
+            is_scalar,
+        }),
+        other => Err(DataFusionError::Internal(format!(
+            "json functions expect a string argument, got {other:?}"
+        ))),
+    }
+}
+
+/// Returns the number of rows to evaluate, or `None` if all arguments are
+/// scalars
+fn num_rows(args: &[ColumnarValue]) -> Option<usize> {
+    args.iter().find_map(|arg| match arg {
+        ColumnarValue::Array(array) => Some(array.len()),
+        ColumnarValue::Scalar(_) => None,
+    })
+}
+
+fn lookup<'v>(value: &'v Value, step: &PathStep) -> Option<&'v Value> {
+    match (value, step) {
+        (Value::Object(object), PathStep::Key(key)) => object.get(*key),
+        (Value::Array(array), PathStep::Index(index)) => {
+            let index = if *index < 0 {
+                array.len() as i64 + index
+            } else {
+                *index
+            };
+            usize::try_from(index)
+                .ok()
+                .and_then(|index| array.get(index))
+        }
+        _ => None,
+    }
+}
+
+/// Follows the path given by `args[1..]` in the json documents of `args[0]`
+/// and calls `f` with the value found for each row, or with `None` if the
+/// path does not exist in the document.
+///
+/// A row evaluates to NULL without calling `f` if its document is NULL or
+/// not valid JSON, or if any of its path elements is NULL.
+fn json_path_map<T>(
+    args: &[ColumnarValue],
+    f: impl Fn(Option<&Value>) -> Option<T>,
+) -> Result<Vec<Option<T>>> {
+    let json = json_arg(&args[0])?;
+    let path = args[1..]
+        .iter()
+        .map(PathArg::try_new)
+        .collect::<Result<Vec<_>>>()?;
+
+    let num_rows = num_rows(args).unwrap_or(1);
+    let mut steps = Vec::with_capacity(path.len());
+    Ok((0..num_rows)
+        .map(|row| {
+            let value = serde_json::from_str::<Value>(json.value(row)?).ok()?;
+            steps.clear();
+            for arg in &path {
+                steps.push(arg.step(row)?);
+            }
+            let found = steps
+                .iter()
+                .try_fold(&value, |value, step| lookup(value, step));
+            f(found)
+        })
+        .collect())
+}
+
+/// Returns `array` as a scalar if all the arguments were scalars
+fn to_columnar_value(args: &[ColumnarValue], array: ArrayRef) -> Result<ColumnarValue> {
+    match num_rows(args) {
+        Some(_) => Ok(ColumnarValue::Array(array)),
+        None => Ok(ColumnarValue::Scalar(ScalarValue::try_from_array(
+            &array, 0,
+        )?)),
+    }
+}
+
+/// Json_get SQL function, also used for the `->` operator
+///
+/// Returns the JSON text of the value at the given path
+pub fn json_get(args: &[ColumnarValue]) -> Result<ColumnarValue> {
+    let values = json_path_map(args, |value| value.map(Value::to_string))?;
+    to_columnar_value(args, Arc::new(StringArray::from(values)))
+}
+
+/// Json_get_str SQL function, also used for the `->>` operator
+///
+/// Returns the value at the given path as text: strings are returned
+/// without quotes, JSON null as NULL and other values as JSON text
+pub fn json_get_str(args: &[ColumnarValue]) -> Result<ColumnarValue> {
+    let values = json_path_map(args, |value| match value? {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    })?;
+    to_columnar_value(args, Arc::new(StringArray::from(values)))
+}
+
+/// Json_get_int SQL function
+///
+/// Returns the value at the given path if it is an integer that fits into
+/// an `Int64`, and NULL otherwise
+pub fn json_get_int(args: &[ColumnarValue]) -> Result<ColumnarValue> {
+    let values = json_path_map(args, |value| value?.as_i64())?;
+    to_columnar_value(args, Arc::new(Int64Array::from(values)))
+}
+
+/// Json_contains SQL function
+///
+/// Returns whether the given path exists in the JSON document
+pub fn json_contains(args: &[ColumnarValue]) -> Result<ColumnarValue> {
+    let values = json_path_map(args, |value| Some(value.is_some()))?;
+    to_columnar_value(args, Arc::new(BooleanArray::from(values)))
+}
+
+/// Json_array_length SQL function
+///
+/// Returns the number of elements of the array at the given path, or NULL
+/// if it is not an array
+pub fn json_array_length(args: &[ColumnarValue]) -> Result<ColumnarValue> {
+    let values = json_path_map(args, |value| match value? {
+        Value::Array(array) => Some(array.len() as u64),
+        _ => None,
+    })?;
+    to_columnar_value(args, Arc::new(UInt64Array::from(values)))
+}
+
+/// Json_as_struct SQL function
+///
+/// Parses JSON objects into a struct with the fields of the struct literal
+/// passed as the second argument. Keys missing from an object are NULL and
+/// keys without a matching field are ignored. Values that are not JSON
+/// objects evaluate to NULL.
+pub fn json_as_struct(args: &[ColumnarValue]) -> Result<ColumnarValue> {
+    let fields = match &args[1] {
+        ColumnarValue::Scalar(ScalarValue::Struct(_, fields)) => fields.clone(),
+        other => {
+            return Err(DataFusionError::Internal(format!(
+            "json_as_struct expects a struct literal as its second argument, got {:?}",
+            other.data_type()
+        )))
+        }
+    };
+    let json = json_arg(&args[0])?;
+    let num_rows = num_rows(args).unwrap_or(1);
+
+    let mut buffer = String::new();
+    let mut valid = BooleanBufferBuilder::new(num_rows);
+    for row in 0..num_rows {
+        let object = json.value(row).filter(|text| {
+            matches!(serde_json::from_str::<Value>(text), Ok(Value::Object(_)))
+        });
+        valid.append(object.is_some());
+        buffer.push_str(object.unwrap_or("{}"));
+        buffer.push('\n');
+    }
+
+    let columns = decode_json_objects(buffer.as_bytes(), &fields, num_rows)?;
+    let array =
+        StructArray::try_new(fields, columns, Some(NullBuffer::new(valid.finish())))?;
+    to_columnar_value(&args[..1], Arc::new(array))
+}
+
+/// Decodes `num_rows` newline delimited JSON objects into arrays of `fields`
+fn decode_json_objects(
+    mut buffer: &[u8],
+    fields: &Fields,
+    num_rows: usize,
+) -> Result<Vec<ArrayRef>> {
+    if num_rows == 0 {
+        return Ok(fields
+            .iter()
+            .map(|field| new_empty_array(field.data_type()))
+            .collect());
+    }
+
+    let schema = Arc::new(Schema::new(fields.clone()));
+    let mut decoder = ReaderBuilder::new(schema)
+        .with_batch_size(num_rows)
+        .build_decoder()?;
+    while !buffer.is_empty() {
+        let read = decoder.decode(buffer)?;
+        if read == 0 {
+            break;
+        }
+        buffer = &buffer[read..];
+    }
+    let batch = decoder.flush()?.ok_or_else(|| {
+        DataFusionError::Internal("json_as_struct decoded no rows".to_string())
+    })?;
+    Ok(batch.columns().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::datatypes::Field;
+    use datafusion_common::cast::{as_boolean_array, as_struct_array};
+
+    fn documents() -> ColumnarValue {
+        ColumnarValue::Array(Arc::new(StringArray::from(vec![
+            Some(r#"{"a": {"b": [1, "two", null]}, "c": "x"}"#),
+            Some(r#"{"a": {"b": 5}}"#),
+            Some("[1, 2, 3]"),
+            Some("not json"),
+            None,
+        ])))
+    }
+
+    fn lit(value: impl Into<ScalarValue>) -> ColumnarValue {
+        ColumnarValue::Scalar(value.into())
+    }
+
+    fn to_array(value: ColumnarValue) -> ArrayRef {
+        value.into_array(1)
+    }
+
+    #[test]
+    fn test_json_get() {
+        let result = to_array(json_get(&[documents(), lit("a"), lit("b")]).unwrap());
+        let result = as_string_array(&result).unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some(r#"[1,"two",null]"#), Some("5"), None, None, None]
+        );
+
+        let result = to_array(
+            json_get_str(&[documents(), lit("a"), lit("b"), lit(-2i64)]).unwrap(),
+        );
+        let result = as_string_array(&result).unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some("two"), None, None, None, None]
+        );
+
+        let path = ColumnarValue::Array(Arc::new(Int32Array::from(vec![
+            Some(0),
+            Some(0),
+            None,
+            Some(0),
+            Some(0),
+        ])));
+        let result = to_array(json_get_int(&[documents(), path]).unwrap());
+        let result = as_int64_array(&result).unwrap();
+        assert_eq!(result.iter().collect::<Vec<_>>(), vec![None; 5]);
+    }
+
+    #[test]
+    fn test_json_contains_and_array_length() {
+        let result = to_array(json_contains(&[documents(), lit("c")]).unwrap());
+        let result = as_boolean_array(&result).unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some(true), Some(false), Some(false), None, None]
+        );
+
+        let result = to_array(json_array_length(&[documents()]).unwrap());
+        assert_eq!(
+            result.as_any().downcast_ref::<UInt64Array>().unwrap(),
+            &UInt64Array::from(vec![None, None, Some(3), None, None])
+        );
+    }
+
+    #[test]
+    fn test_json_scalar() {
+        let result =
+            json_get_int(&[lit(r#"{"a": [10, 20]}"#), lit("a"), lit(1i64)]).unwrap();
+        match result {
+            ColumnarValue::Scalar(value) => {
+                assert_eq!(value, ScalarValue::Int64(Some(20)))
+            }
+            ColumnarValue::Array(_) => panic!("expected a scalar"),
+        }
+    }
+
+    #[test]
+    fn test_json_as_struct() {
+        let fields = Fields::from(vec![
+            Field::new("c", DataType::Utf8, true),
+            Field::new("d", DataType::Int64, true),
+        ]);
+        let schema = lit(ScalarValue::Struct(None, fields));
+        let result = to_array(json_as_struct(&[documents(), schema]).unwrap());
+        let result = as_struct_array(&result).unwrap();
+        assert_eq!(result.len(), 5);
+        assert_eq!(
+            result.nulls().unwrap().iter().collect::<Vec<_>>(),
+            vec![true, true, false, false, false]
+        );
+        let c = as_string_array(result.column(0)).unwrap();
+        assert_eq!(c.value(0), "x");
+        assert!(c.is_null(1));
+        assert_eq!(result.column(1).null_count(), 5);
+    }
+}
@@ -30,6 +30,9 @@ pub mod expressions;
 pub mod functions;
 pub mod hash_utils;
 pub mod intervals;
+#[cfg(feature = "json_expressions")]
+pub mod json_expressions;
+pub mod map_expressions;
 pub mod math_expressions;
 mod physical_expr;
 pub mod planner;
@@ -0,0 +1,352 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Map expressions
+
+use arrow::array::*;
+use arrow::buffer::{NullBuffer, OffsetBuffer};
+use arrow::compute;
+use arrow::compute::kernels::comparison::eq_dyn;
+use arrow::datatypes::{DataType, Field};
+use datafusion_common::cast::{as_list_array, as_map_array};
+use datafusion_common::utils::map_entries_field;
+use datafusion_common::{DataFusionError, Result, ScalarValue};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Concatenates the given slices, or returns an empty array of `data_type`
+/// if there are none
+fn concat_or_empty(slices: &[ArrayRef], data_type: &DataType) -> Result<ArrayRef> {
+    if slices.is_empty() {
+        return Ok(new_empty_array(data_type));
+    }
+    let slices = slices.iter().map(|a| a.as_ref()).collect::<Vec<_>>();
+    Ok(compute::concat(&slices)?)
+}
+
+/// Map SQL function
+///
+/// Builds a map from a list of keys and a list of values, e.g.
+/// `map(['a', 'b'], [1, 2])` is `{a: 1, b: 2}`
+pub fn make_map(args: &[ArrayRef]) -> Result<ArrayRef> {
+    if args.len() != 2 {
+        return Err(DataFusionError::Internal(format!(
+            "map expects two arguments, got {}",
+            args.len()
+        )));
+    }
+    let keys = as_list_array(&args[0])?;
+    let values = as_list_array(&args[1])?;
+    let key_type = keys.value_type();
+    let value_type = values.value_type();
+
+    let mut offsets = Vec::with_capacity(keys.len() + 1);
+    offsets.push(0i32);
+    let mut key_slices = vec![];
+    let mut value_slices = vec![];
+    let mut valid = BooleanBufferBuilder::new(keys.len());
+    for row in 0..keys.len() {
+        let last_offset = offsets[offsets.len() - 1];
+        if keys.is_null(row) || values.is_null(row) {
+            valid.append(false);
+            offsets.push(last_offset);
+            continue;
+        }
+
+        let row_keys = keys.value(row);
+        let row_values = values.value(row);
+        if row_keys.len() != row_values.len() {
+            return Err(DataFusionError::Execution(format!(
+                "map requires the same number of keys and values, got {} keys and {} values",
+                row_keys.len(),
+                row_values.len()
+            )));
+        }
+        if row_keys.null_count() > 0 {
+            return Err(DataFusionError::Execution(
+                "map keys can not be null".to_string(),
+            ));
+        }
+        let mut seen = HashSet::with_capacity(row_keys.len());
+        for i in 0..row_keys.len() {
+            let key = ScalarValue::try_from_array(&row_keys, i)?;
+            if !seen.insert(key.clone()) {
+                return Err(DataFusionError::Execution(format!(
+                    "map keys must be unique, found duplicate key {key}"
+                )));
+            }
+        }
+
+        valid.append(true);
+        offsets.push(last_offset + row_keys.len() as i32);
+        key_slices.push(row_keys);
+        value_slices.push(row_values);
+    }
+
+    let entries_field = map_entries_field(key_type.clone(), value_type.clone());
+    let entry_fields = match entries_field.data_type() {
+        DataType::Struct(fields) => fields.clone(),
+        _ => unreachable!("map entries are always a struct"),
+    };
+    let entries = StructArray::try_new(
+        entry_fields,
+        vec![
+            concat_or_empty(&key_slices, &key_type)?,
+            concat_or_empty(&value_slices, &value_type)?,
+        ],
+        None,
+    )?;
+
+    Ok(Arc::new(MapArray::try_new(
+        entries_field,
+        OffsetBuffer::new(offsets.into()),
+        entries,
+        Some(NullBuffer::new(valid.finish())),
+        false,
+    )?))
+}
+
+/// Returns the keys or the values of each map as a `ListArray`
+fn map_entries_to_list(map: &MapArray, items: &ArrayRef) -> Result<ArrayRef> {
+    let field = Arc::new(Field::new("item", items.data_type().clone(), true));
+    Ok(Arc::new(ListArray::try_new(
+        field,
+        map.offsets().clone(),
+        items.clone(),
+        map.nulls().cloned(),
+    )?))
+}
+
+/// Map_keys SQL function
+pub fn map_keys(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let map = as_map_array(&args[0])?;
+    map_entries_to_list(map, map.keys())
+}
+
+/// Map_values SQL function
+pub fn map_values(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let map = as_map_array(&args[0])?;
+    map_entries_to_list(map, map.values())
+}
+
+/// Returns, for each map in `map`, the position in `map.values()` of the
+/// value whose key equals the corresponding element of `keys`, or null if
+/// the map is null or does not contain that key.
+fn find_map_values(map: &MapArray, keys: &ArrayRef) -> Result<UInt32Array> {
+    let keys = if keys.data_type() == map.key_type() {
+        keys.clone()
+    } else {
+        compute::cast(keys, map.key_type())?
+    };
+
+    // repeat the searched key once for every entry of its map, so that all
+    // entries can be compared in a single kernel call
+    let offsets = map.value_offsets();
+    let first_offset = offsets[0] as usize;
+    let num_entries = offsets[map.len()] as usize - first_offset;
+    let mut take_indices = UInt32Builder::with_capacity(num_entries);
+    for (row, window) in offsets.windows(2).enumerate() {
+        for _ in window[0]..window[1] {
+            take_indices.append_value(row as u32);
+        }
+    }
+    let searched_keys = compute::take(&keys, &take_indices.finish(), None)?;
+    let map_keys = map.keys().slice(first_offset, num_entries);
+    let matches = eq_dyn(&map_keys, &searched_keys)?;
+
+    let positions = offsets
+        .windows(2)
+        .enumerate()
+        .map(|(row, window)| {
+            if map.is_null(row) {
+                return None;
+            }
+            (window[0]..window[1]).find_map(|offset| {
+                let i = offset as usize - first_offset;
+                (matches.is_valid(i) && matches.value(i)).then_some(offset as u32)
+            })
+        })
+        .collect();
+    Ok(positions)
+}
+
+/// Returns the value of each map for the corresponding key of `keys`, or
+/// null if the map does not contain that key
+pub(crate) fn map_value(map: &MapArray, keys: &ArrayRef) -> Result<ArrayRef> {
+    let positions = find_map_values(map, keys)?;
+    Ok(compute::take(map.values(), &positions, None)?)
+}
+
+/// Map_extract SQL function
+///
+/// Returns a list with the value for the given key, or an empty list if the
+/// map does not contain the key
+pub fn map_extract(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let map = as_map_array(&args[0])?;
+    let positions = find_map_values(map, &args[1])?;
+
+    let mut offsets = Vec::with_capacity(positions.len() + 1);
+    offsets.push(0i32);
+    for position in positions.iter() {
+        offsets.push(offsets[offsets.len() - 1] + position.is_some() as i32);
+    }
+    let values = compute::take(
+        map.values(),
+        &positions.iter().flatten().collect::<UInt32Array>(),
+        None,
+    )?;
+
+    let field = Arc::new(Field::new("item", values.data_type().clone(), true));
+    Ok(Arc::new(ListArray::try_new(
+        field,
+        OffsetBuffer::new(offsets.into()),
+        values,
+        map.nulls().cloned(),
+    )?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion_common::cast::{as_int64_array, as_string_array};
+
+    fn build_map() -> ArrayRef {
+        let keys: ArrayRef = Arc::new(ListArray::from_iter_primitive::<
+            arrow::datatypes::Int64Type,
+            _,
+            _,
+        >(vec![
+            Some(vec![Some(1), Some(2)]),
+            None,
+            Some(vec![]),
+            Some(vec![Some(3)]),
+        ]));
+        let values = ScalarValue::iter_to_array(vec![
+            ScalarValue::new_list(
+                Some(vec![
+                    ScalarValue::Utf8(Some("a".to_string())),
+                    ScalarValue::Utf8(None),
+                ]),
+                DataType::Utf8,
+            ),
+            ScalarValue::new_list(None, DataType::Utf8),
+            ScalarValue::new_list(Some(vec![]), DataType::Utf8),
+            ScalarValue::new_list(
+                Some(vec![ScalarValue::Utf8(Some("c".to_string()))]),
+                DataType::Utf8,
+            ),
+        ])
+        .unwrap();
+        make_map(&[keys, values]).unwrap()
+    }
+
+    #[test]
+    fn test_make_map() {
+        let map = build_map();
+        let map = as_map_array(&map).unwrap();
+        assert_eq!(map.len(), 4);
+        assert_eq!(map.null_count(), 1);
+        assert_eq!(map.value_offsets(), &[0, 2, 2, 2, 3]);
+        assert_eq!(
+            ScalarValue::try_from_array(map, 0).unwrap().to_string(),
+            "{1:a,2:NULL}"
+        );
+        assert!(ScalarValue::try_from_array(map, 1).unwrap().is_null());
+        assert_eq!(
+            ScalarValue::try_from_array(map, 2).unwrap().to_string(),
+            "{}"
+        );
+    }
+
+    #[test]
+    fn test_make_map_invalid() {
+        let keys = ScalarValue::new_list(
+            Some(vec![
+                ScalarValue::Int64(Some(1)),
+                ScalarValue::Int64(Some(1)),
+            ]),
+            DataType::Int64,
+        )
+        .to_array();
+        let values = ScalarValue::new_list(
+            Some(vec![
+                ScalarValue::Int64(Some(2)),
+                ScalarValue::Int64(Some(3)),
+            ]),
+            DataType::Int64,
+        )
+        .to_array();
+        let err = make_map(&[keys.clone(), values]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: map keys must be unique, found duplicate key 1"
+        );
+
+        let values = ScalarValue::new_list(
+            Some(vec![ScalarValue::Int64(Some(2))]),
+            DataType::Int64,
+        )
+        .to_array();
+        let err = make_map(&[keys, values]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Execution error: map requires the same number of keys and values, got 2 keys and 1 values"
+        );
+    }
+
+    #[test]
+    fn test_map_keys_and_values() {
+        let map = build_map();
+
+        let keys = map_keys(std::slice::from_ref(&map)).unwrap();
+        let keys = as_list_array(&keys).unwrap();
+        assert_eq!(keys.null_count(), 1);
+        let first = keys.value(0);
+        assert_eq!(as_int64_array(&first).unwrap().values(), &[1, 2]);
+        assert_eq!(keys.value(3).len(), 1);
+
+        let values = map_values(&[map]).unwrap();
+        let values = as_list_array(&values).unwrap();
+        assert_eq!(values.null_count(), 1);
+        let first = values.value(0);
+        let first = as_string_array(&first).unwrap();
+        assert_eq!(first.iter().collect::<Vec<_>>(), vec![Some("a"), None]);
+        assert_eq!(values.value(2).len(), 0);
+    }
+
+    #[test]
+    fn test_map_extract_and_value() {
+        let map = build_map();
+        let keys: ArrayRef = Arc::new(Int32Array::from(vec![2, 1, 1, 3]));
+
+        let extracted = map_extract(&[map.clone(), keys.clone()]).unwrap();
+        let extracted = as_list_array(&extracted).unwrap();
+        assert_eq!(extracted.value_offsets(), &[0, 1, 1, 1, 2]);
+        assert!(extracted.is_null(1));
+        let values = extracted.values();
+        let values = as_string_array(values).unwrap();
+        assert_eq!(values.iter().collect::<Vec<_>>(), vec![None, Some("c")]);
+
+        let map = as_map_array(&map).unwrap();
+        let value = map_value(map, &keys).unwrap();
+        let value = as_string_array(&value).unwrap();
+        assert_eq!(
+            value.iter().collect::<Vec<_>>(),
+            vec![None, None, None, Some("c")]
+        );
+    }
+}
@@ -20,14 +20,16 @@
 
 use crate::window::BuiltInWindowFunctionExpr;
 use crate::PhysicalExpr;
-use arrow::array::ArrayRef;
-use arrow::compute::cast;
+use arrow::array::{Array, ArrayRef, UInt32Builder};
+use arrow::compute::kernels::zip::zip;
+use arrow::compute::{cast, is_not_null, take};
 use arrow::datatypes::{DataType, Field};
 use datafusion_common::ScalarValue;
 use datafusion_common::{DataFusionError, Result};
 use datafusion_expr::PartitionEvaluator;
 use std::any::Any;
-use std::cmp::min;
+use std::cmp::{min, Ordering};
+use std::collections::VecDeque;
 use std::ops::{Neg, Range};
 use std::sync::Arc;
 
@@ -39,6 +41,7 @@ pub struct WindowShift {
     shift_offset: i64,
     expr: Arc<dyn PhysicalExpr>,
     default_value: Option<ScalarValue>,
+    ignore_nulls: bool,
 }
 
 impl WindowShift {
@@ -46,6 +49,11 @@ impl WindowShift {
     pub fn get_shift_offset(&self) -> i64 {
         self.shift_offset
     }
+
+    /// Whether NULL values are skipped when counting the offset
+    pub fn ignore_nulls(&self) -> bool {
+        self.ignore_nulls
+    }
 }
 
 /// lead() window function
@@ -55,6 +63,7 @@ pub fn lead(
     expr: Arc<dyn PhysicalExpr>,
     shift_offset: Option<i64>,
     default_value: Option<ScalarValue>,
+    ignore_nulls: bool,
 ) -> WindowShift {
     WindowShift {
         name,
@@ -62,6 +71,7 @@ pub fn lead(
         shift_offset: shift_offset.map(|v| v.neg()).unwrap_or(-1),
         expr,
         default_value,
+        ignore_nulls,
     }
 }
 
@@ -72,6 +82,7 @@ pub fn lag(
     expr: Arc<dyn PhysicalExpr>,
     shift_offset: Option<i64>,
     default_value: Option<ScalarValue>,
+    ignore_nulls: bool,
 ) -> WindowShift {
     WindowShift {
         name,
@@ -79,6 +90,7 @@ pub fn lag(
         shift_offset: shift_offset.unwrap_or(1),
         expr,
         default_value,
+        ignore_nulls,
     }
 }
 
@@ -105,6 +117,8 @@ impl BuiltInWindowFunctionExpr for WindowShift {
         Ok(Box::new(WindowShiftEvaluator {
             shift_offset: self.shift_offset,
             default_value: self.default_value.clone(),
+            ignore_nulls: self.ignore_nulls,
+            non_null_values: VecDeque::new(),
         }))
     }
 
@@ -115,6 +129,7 @@ impl BuiltInWindowFunctionExpr for WindowShift {
             shift_offset: -self.shift_offset,
             expr: self.expr.clone(),
             default_value: self.default_value.clone(),
+            ignore_nulls: self.ignore_nulls,
         }))
     }
 }
@@ -123,6 +138,10 @@ impl BuiltInWindowFunctionExpr for WindowShift {
 pub(crate) struct WindowShiftEvaluator {
     shift_offset: i64,
     default_value: Option<ScalarValue>,
+    ignore_nulls: bool,
+    /// The last `shift_offset` non-null values before the current row, used
+    /// by LAG with IGNORE NULLS during bounded execution
+    non_null_values: VecDeque<ScalarValue>,
 }
 
 fn create_empty_array(
@@ -174,8 +193,92 @@ fn shift_with_default_value(
     }
 }
 
+/// Like [`shift_with_default_value`], but only counts the non-null values of
+/// `array` when shifting by `offset`
+fn shift_ignoring_nulls(
+    array: &ArrayRef,
+    offset: i64,
+    value: Option<&ScalarValue>,
+) -> Result<ArrayRef> {
+    let non_null_indices = (0..array.len())
+        .filter(|i| array.is_valid(*i))
+        .collect::<Vec<_>>();
+    let shift = offset.unsigned_abs() as usize;
+
+    // number of non-null values before the current row
+    let mut n_before = 0;
+    let mut indices = UInt32Builder::with_capacity(array.len());
+    for idx in 0..array.len() {
+        let n_until = n_before + array.is_valid(idx) as usize;
+        let source = match offset.cmp(&0) {
+            // LAG mode
+            Ordering::Greater => n_before.checked_sub(shift).map(|n| non_null_indices[n]),
+            // LEAD mode
+            Ordering::Less => non_null_indices.get(n_until + shift - 1).copied(),
+            Ordering::Equal => Some(idx),
+        };
+        indices.append_option(source.map(|i| i as u32));
+        n_before = n_until;
+    }
+    let indices = indices.finish();
+
+    let shifted = take(array, &indices, None)?;
+    if value.is_none() || indices.null_count() == 0 {
+        return Ok(shifted);
+    }
+    let default_values = create_empty_array(value, array.data_type(), array.len())?;
+    Ok(zip(&is_not_null(&indices)?, &shifted, &default_values)?)
+}
+
+impl WindowShiftEvaluator {
+    /// Evaluates the row at `range.start` for LAG skipping NULL values, by
+    /// counting the non-null values it has seen in `non_null_values`
+    fn evaluate_ignoring_nulls(
+        &mut self,
+        array: &ArrayRef,
+        range: &Range<usize>,
+    ) -> Result<ScalarValue> {
+        let idx = range.start;
+        let shift = self.shift_offset.unsigned_abs() as usize;
+        let value = match self.shift_offset.cmp(&0) {
+            // LAG mode
+            Ordering::Greater => {
+                let value = (self.non_null_values.len() == shift)
+                    .then(|| self.non_null_values[0].clone());
+                if array.is_valid(idx) {
+                    if self.non_null_values.len() == shift {
+                        self.non_null_values.pop_front();
+                    }
+                    self.non_null_values
+                        .push_back(ScalarValue::try_from_array(array, idx)?);
+                }
+                value
+            }
+            // LEAD mode
+            Ordering::Less => {
+                return Err(DataFusionError::Internal(
+                    "LEAD with IGNORE NULLS does not support bounded execution"
+                        .to_string(),
+                ))
+            }
+            Ordering::Equal => Some(ScalarValue::try_from_array(array, idx)?),
+        };
+        match value {
+            Some(value) => Ok(value),
+            None => get_default_value(self.default_value.as_ref(), array.data_type()),
+        }
+    }
+}
+
 impl PartitionEvaluator for WindowShiftEvaluator {
     fn get_range(&self, idx: usize, n_rows: usize) -> Result<Range<usize>> {
+        if self.ignore_nulls {
+            // LAG keeps the values it needs in its state
+            return Ok(Range {
+                start: idx,
+                end: idx + 1,
+            });
+        }
         if self.shift_offset > 0 {
             let offset = self.shift_offset as usize;
             let start = idx.saturating_sub(offset);
@@ -194,6 +297,9 @@ impl PartitionEvaluator for WindowShiftEvaluator {
         range: &Range<usize>,
     ) -> Result<ScalarValue> {
         let array = &values[0];
+        if self.ignore_nulls {
+            return self.evaluate_ignoring_nulls(array, range);
+        }
         let dtype = array.data_type();
         // LAG mode
         let idx = if self.shift_offset > 0 {
@@ -217,11 +323,20 @@ impl PartitionEvaluator for WindowShiftEvaluator {
     ) -> Result<ArrayRef> {
         // LEAD, LAG window functions take single column, values will have size 1
         let value = &values[0];
+        if self.ignore_nulls {
+            return shift_ignoring_nulls(
+                value,
+                self.shift_offset,
+                self.default_value.as_ref(),
+            );
+        }
         shift_with_default_value(value, self.shift_offset, self.default_value.as_ref())
     }
 
     fn supports_bounded_execution(&self) -> bool {
-        true
+        // LEAD with IGNORE NULLS may have to look at all the remaining rows of
+        // the partition, so it is evaluated once the partition is complete
+        !(self.ignore_nulls && self.shift_offset < 0)
     }
 }
 
@@ -274,6 +389,7 @@ mod tests {
                 Arc::new(Column::new("c3", 0)),
                 None,
                 None,
+                false,
             ),
             vec![
                 Some(-2),
@@ -296,6 +412,7 @@ mod tests {
                 Arc::new(Column::new("c3", 0)),
                 None,
                 None,
+                false,
             ),
             vec![
                 None,
@@ -318,6 +435,7 @@ mod tests {
                 Arc::new(Column::new("c3", 0)),
                 None,
                 Some(ScalarValue::Int32(Some(100))),
+                false,
             ),
             vec![
                 Some(100),
@@ -334,4 +452,127 @@ mod tests {
         )?;
         Ok(())
     }
+
+    /// Checks that `evaluate_all` and the row by row evaluation used by
+    /// bounded execution, if supported, agree on `expected`
+    fn test_ignore_nulls(expr: WindowShift, expected: Vec<Option<i32>>) -> Result<()> {
+        let values: Vec<ArrayRef> = vec![Arc::new(Int32Array::from(vec![
+            None,
+            Some(1),
+            None,
+            None,
+            Some(2),
+            Some(3),
+            None,
+            Some(4),
+        ]))];
+        let expected = Int32Array::from(expected);
+        let n_rows = values[0].len();
+
+        let result = expr.create_evaluator()?.evaluate_all(&values, n_rows)?;
+        assert_eq!(expected, *as_int32_array(&result)?);
+
+        let mut evaluator = expr.create_evaluator()?;
+        if !evaluator.supports_bounded_execution() {
+            return Ok(());
+        }
+        let result = (0..n_rows)
+            .map(|idx| {
+                let range = evaluator.get_range(idx, n_rows)?;
+                evaluator.evaluate(&values, &range)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let result = ScalarValue::iter_to_array(result)?;
+        assert_eq!(expected, *as_int32_array(&result)?);
+        Ok(())
+    }
+
+    #[test]
+    fn lead_lag_ignore_nulls() -> Result<()> {
+        let expr = || Arc::new(Column::new("c3", 0));
+        // the distance to the next non-null value is unbounded
+        assert!(
+            !lead("lead".to_owned(), DataType::Int32, expr(), None, None, true)
+                .create_evaluator()?
+                .supports_bounded_execution()
+        );
+        test_ignore_nulls(
+            lag("lag".to_owned(), DataType::Int32, expr(), None, None, true),
+            vec![
+                None,
+                None,
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(2),
+                Some(3),
+                Some(3),
+            ],
+        )?;
+        test_ignore_nulls(
+            lag(
+                "lag".to_owned(),
+                DataType::Int32,
+                expr(),
+                Some(2),
+                None,
+                true,
+            ),
+            vec![None, None, None, None, None, Some(1), Some(2), Some(2)],
+        )?;
+        test_ignore_nulls(
+            lead("lead".to_owned(), DataType::Int32, expr(), None, None, true),
+            vec![
+                Some(1),
+                Some(2),
+                Some(2),
+                Some(2),
+                Some(3),
+                Some(4),
+                Some(4),
+                None,
+            ],
+        )?;
+        test_ignore_nulls(
+            lead(
+                "lead".to_owned(),
+                DataType::Int32,
+                expr(),
+                Some(2),
+                None,
+                true,
+            ),
+            vec![
+                Some(2),
+                Some(3),
+                Some(3),
+                Some(3),
+                Some(4),
+                None,
+                None,
+                None,
+            ],
+        )?;
+        test_ignore_nulls(
+            lead(
+                "lead".to_owned(),
+                DataType::Int32,
+                expr(),
+                None,
+                Some(ScalarValue::Int64(Some(100))),
+                true,
+            ),
+            vec![
+                Some(1),
+                Some(2),
+                Some(2),
+                Some(2),
+                Some(3),
+                Some(4),
+                Some(4),
+                Some(100),
+            ],
+        )?;
+        Ok(())
+    }
 }
@@ -38,6 +38,7 @@ pub struct NthValue {
     expr: Arc<dyn PhysicalExpr>,
     data_type: DataType,
     kind: NthValueKind,
+    ignore_nulls: bool,
 }
 
 impl NthValue {
@@ -46,12 +47,14 @@ impl NthValue {
         name: impl Into<String>,
         expr: Arc<dyn PhysicalExpr>,
         data_type: DataType,
+        ignore_nulls: bool,
     ) -> Self {
         Self {
             name: name.into(),
             expr,
             data_type,
             kind: NthValueKind::First,
+            ignore_nulls,
         }
     }
 
@@ -60,12 +63,14 @@ impl NthValue {
         name: impl Into<String>,
         expr: Arc<dyn PhysicalExpr>,
         data_type: DataType,
+        ignore_nulls: bool,
     ) -> Self {
         Self {
             name: name.into(),
             expr,
             data_type,
             kind: NthValueKind::Last,
+            ignore_nulls,
         }
     }
 
@@ -75,6 +80,7 @@ impl NthValue {
         expr: Arc<dyn PhysicalExpr>,
         data_type: DataType,
         n: u32,
+        ignore_nulls: bool,
     ) -> Result<Self> {
         match n {
             0 => Err(DataFusionError::Execution(
@@ -85,6 +91,7 @@ impl NthValue {
                 expr,
                 data_type,
                 kind: NthValueKind::Nth(n),
+                ignore_nulls,
             }),
         }
     }
@@ -93,6 +100,11 @@ impl NthValue {
     pub fn get_kind(&self) -> NthValueKind {
         self.kind
     }
+
+    /// Whether NULL values are skipped when picking the value
+    pub fn ignore_nulls(&self) -> bool {
+        self.ignore_nulls
+    }
 }
 
 impl BuiltInWindowFunctionExpr for NthValue {
@@ -120,7 +132,11 @@ impl BuiltInWindowFunctionExpr for NthValue {
             finalized_result: None,
             kind: self.kind,
         };
-        Ok(Box::new(NthValueEvaluator { state }))
+        Ok(Box::new(NthValueEvaluator {
+            state,
+            ignore_nulls: self.ignore_nulls,
+            last_frame_start: None,
+        }))
     }
 
     fn reverse_expr(&self) -> Option<Arc<dyn BuiltInWindowFunctionExpr>> {
@@ -134,6 +150,7 @@ impl BuiltInWindowFunctionExpr for NthValue {
             expr: self.expr.clone(),
             data_type: self.data_type.clone(),
             kind: reversed_kind,
+            ignore_nulls: self.ignore_nulls,
         }))
     }
 }
@@ -142,6 +159,12 @@ impl BuiltInWindowFunctionExpr for NthValue {
 #[derive(Debug)]
 pub(crate) struct NthValueEvaluator {
     state: NthValueState,
+    ignore_nulls: bool,
+    /// For LAST_VALUE ignoring nulls, the start of the smallest frame that
+    /// gives the same result as the frame of the last evaluated row: its last
+    /// non-null value, or its end if it has none. Taken by `memoize`, as the
+    /// index is invalidated by pruning.
+    last_frame_start: Option<usize>,
 }
 
 impl PartitionEvaluator for NthValueEvaluator {
@@ -154,6 +177,26 @@ impl PartitionEvaluator for NthValueEvaluator {
     fn memoize(&mut self, state: &mut WindowAggState) -> Result<()> {
         let out = &state.out_col;
         let size = out.len();
+        if self.ignore_nulls {
+            // The frame of LAST_VALUE can start at its last non-null value, as
+            // the later frames end with it or with a later non-null value
+            if matches!(self.state.kind, NthValueKind::Last) {
+                if let Some(start) = self.last_frame_start.take() {
+                    state.window_frame_range.start = start;
+                }
+                return Ok(());
+            }
+            // The result only stays the same once a non-null value has been found
+            if size > 0 && out.is_valid(size - 1) {
+                if self.state.finalized_result.is_none() {
+                    let result = ScalarValue::try_from_array(out, size - 1)?;
+                    self.state.finalized_result = Some(result);
+                }
+                state.window_frame_range.start =
+                    state.window_frame_range.end.saturating_sub(1);
+            }
+            return Ok(());
+        }
         let (is_prunable, is_last) = match self.state.kind {
             NthValueKind::First => {
                 let n_range =
@@ -193,6 +236,23 @@ impl PartitionEvaluator for NthValueEvaluator {
                 // We produce None if the window is empty.
                 return ScalarValue::try_from(arr.data_type());
             }
+            if self.ignore_nulls {
+                let mut valid_indices = range.clone().filter(|i| arr.is_valid(*i));
+                let index = match self.state.kind {
+                    NthValueKind::First => valid_indices.next(),
+                    NthValueKind::Last => {
+                        let index = valid_indices.next_back();
+                        self.last_frame_start = Some(index.unwrap_or(range.end));
+                        index
+                    }
+                    // We are certain that n > 0.
+                    NthValueKind::Nth(n) => valid_indices.nth((n as usize) - 1),
+                };
+                return match index {
+                    Some(index) => ScalarValue::try_from_array(arr, index),
+                    None => ScalarValue::try_from(arr.data_type()),
+                };
+            }
             match self.state.kind {
                 NthValueKind::First => ScalarValue::try_from_array(arr, range.start),
                 NthValueKind::Last => ScalarValue::try_from_array(arr, range.end - 1),
@@ -222,15 +282,29 @@ impl PartitionEvaluator for NthValueEvaluator {
 mod tests {
     use super::*;
     use crate::expressions::Column;
+    use crate::window::{
+        BuiltInWindowExpr, PartitionBatches, PartitionWindowAggStates, WindowExpr,
+    };
+    use arrow::compute::concat_batches;
     use arrow::record_batch::RecordBatch;
     use arrow::{array::*, datatypes::*};
     use datafusion_common::cast::as_int32_array;
     use datafusion_common::Result;
+    use datafusion_expr::window_state::PartitionBatchState;
+    use datafusion_expr::{WindowFrame, WindowFrameBound, WindowFrameUnits};
 
     fn test_i32_result(expr: NthValue, expected: Int32Array) -> Result<()> {
         let arr: ArrayRef = Arc::new(Int32Array::from(vec![1, -2, 3, -4, 5, -6, 7, 8]));
+        test_i32_result_with_values(expr, arr, expected)
+    }
+
+    fn test_i32_result_with_values(
+        expr: NthValue,
+        arr: ArrayRef,
+        expected: Int32Array,
+    ) -> Result<()> {
         let values = vec![arr];
-        let schema = Schema::new(vec![Field::new("arr", DataType::Int32, false)]);
+        let schema = Schema::new(vec![Field::new("arr", DataType::Int32, true)]);
         let batch = RecordBatch::try_new(Arc::new(schema), values.clone())?;
         let mut ranges: Vec<Range<usize>> = vec![];
         for i in 0..8 {
@@ -257,6 +331,7 @@ mod tests {
             "first_value".to_owned(),
             Arc::new(Column::new("arr", 0)),
             DataType::Int32,
+            false,
         );
         test_i32_result(first_value, Int32Array::from(vec![1; 8]))?;
         Ok(())
@@ -268,6 +343,7 @@ mod tests {
             "last_value".to_owned(),
             Arc::new(Column::new("arr", 0)),
             DataType::Int32,
+            false,
         );
         test_i32_result(
             last_value,
@@ -292,6 +368,7 @@ mod tests {
             Arc::new(Column::new("arr", 0)),
             DataType::Int32,
             1,
+            false,
         )?;
         test_i32_result(nth_value, Int32Array::from(vec![1; 8]))?;
         Ok(())
@@ -304,6 +381,7 @@ mod tests {
             Arc::new(Column::new("arr", 0)),
             DataType::Int32,
             2,
+            false,
         )?;
         test_i32_result(
             nth_value,
@@ -320,4 +398,165 @@ mod tests {
         )?;
         Ok(())
     }
+
+    fn nullable_values() -> ArrayRef {
+        Arc::new(Int32Array::from(vec![
+            None,
+            Some(-2),
+            None,
+            Some(-4),
+            None,
+            None,
+            Some(7),
+            None,
+        ]))
+    }
+
+    #[test]
+    fn first_value_ignore_nulls() -> Result<()> {
+        let first_value = NthValue::first(
+            "first_value".to_owned(),
+            Arc::new(Column::new("arr", 0)),
+            DataType::Int32,
+            true,
+        );
+        let mut expected = vec![Some(-2); 8];
+        expected[0] = None;
+        test_i32_result_with_values(
+            first_value,
+            nullable_values(),
+            Int32Array::from(expected),
+        )
+    }
+
+    #[test]
+    fn last_value_ignore_nulls() -> Result<()> {
+        let last_value = NthValue::last(
+            "last_value".to_owned(),
+            Arc::new(Column::new("arr", 0)),
+            DataType::Int32,
+            true,
+        );
+        test_i32_result_with_values(
+            last_value,
+            nullable_values(),
+            Int32Array::from(vec![
+                None,
+                Some(-2),
+                Some(-2),
+                Some(-4),
+                Some(-4),
+                Some(-4),
+                Some(7),
+                Some(7),
+            ]),
+        )
+    }
+
+    #[test]
+    fn nth_value_2_ignore_nulls() -> Result<()> {
+        let nth_value = NthValue::nth(
+            "nth_value".to_owned(),
+            Arc::new(Column::new("arr", 0)),
+            DataType::Int32,
+            2,
+            true,
+        )?;
+        test_i32_result_with_values(
+            nth_value,
+            nullable_values(),
+            Int32Array::from(vec![
+                None,
+                None,
+                None,
+                Some(-4),
+                Some(-4),
+                Some(-4),
+                Some(-4),
+                Some(-4),
+            ]),
+        )
+    }
+
+    #[test]
+    fn last_value_ignore_nulls_bounded() -> Result<()> {
+        // LAST_VALUE(arr IGNORE NULLS) OVER (ROWS UNBOUNDED PRECEDING) only
+        // keeps the rows since the last non-null value buffered
+        let schema =
+            Arc::new(Schema::new(vec![Field::new("arr", DataType::Int32, true)]));
+        let last_value = NthValue::last(
+            "last_value".to_owned(),
+            Arc::new(Column::new("arr", 0)),
+            DataType::Int32,
+            true,
+        );
+        let window_frame = WindowFrame {
+            units: WindowFrameUnits::Rows,
+            start_bound: WindowFrameBound::Preceding(ScalarValue::UInt64(None)),
+            end_bound: WindowFrameBound::CurrentRow,
+        };
+        let expr = BuiltInWindowExpr::new(
+            Arc::new(last_value),
+            &[],
+            &[],
+            Arc::new(window_frame),
+        );
+
+        let mut partition_batches = PartitionBatches::new();
+        let mut window_agg_states = PartitionWindowAggStates::new();
+        let mut buffered = vec![];
+        let chunks = [
+            (vec![None, Some(-2), None], false),
+            (vec![Some(-4), None, None], false),
+            (vec![Some(7), None], true),
+        ];
+        for (values, is_end) in chunks {
+            let batch = RecordBatch::try_new(
+                schema.clone(),
+                vec![Arc::new(Int32Array::from(values))],
+            )?;
+            let partition =
+                partition_batches
+                    .entry(vec![])
+                    .or_insert_with(|| PartitionBatchState {
+                        record_batch: RecordBatch::new_empty(schema.clone()),
+                        is_end: false,
+                        n_out_row: 0,
+                    });
+            partition.record_batch =
+                concat_batches(&schema, [&partition.record_batch, &batch])?;
+            partition.is_end = is_end;
+            expr.evaluate_stateful(&partition_batches, &mut window_agg_states)?;
+
+            // prune the rows before the window frame, like BoundedWindowAggExec
+            let state = &mut window_agg_states[&vec![]].state;
+            let n_prune = state
+                .window_frame_range
+                .start
+                .min(state.last_calculated_index);
+            state.prune_state(n_prune);
+            let partition = &mut partition_batches[&vec![]];
+            partition.record_batch = partition
+                .record_batch
+                .slice(n_prune, partition.record_batch.num_rows() - n_prune);
+            buffered.push(partition.record_batch.num_rows());
+        }
+
+        assert_eq!(buffered, vec![2, 3, 2]);
+        let result = &window_agg_states[&vec![]].state.out_col;
+        assert_eq!(
+            *as_int32_array(result)?,
+            Int32Array::from(vec![
+                None,
+                Some(-2),
+                Some(-2),
+                Some(-4),
+                Some(-4),
+                Some(-4),
+                Some(7),
+                Some(7),
+            ])
+        );
+        Ok(())
+    }
 }
@@ -465,6 +465,10 @@ impl serde::Serialize for AggregateFunction {
             Self::BoolOr => "BOOL_OR",
             Self::FirstValueAgg => "FIRST_VALUE_AGG",
             Self::LastValueAgg => "LAST_VALUE_AGG",
+            Self::StringAgg => "STRING_AGG",
+            Self::PercentileCont => "PERCENTILE_CONT",
+            Self::PercentileDisc => "PERCENTILE_DISC",
+            Self::Mode => "MODE",
         };
         serializer.serialize_str(variant)
     }
@@ -502,6 +506,10 @@ impl<'de> serde::Deserialize<'de> for AggregateFunction {
             "BOOL_OR",
             "FIRST_VALUE_AGG",
             "LAST_VALUE_AGG",
+            "STRING_AGG",
+            "PERCENTILE_CONT",
+            "PERCENTILE_DISC",
+            "MODE",
         ];
 
         struct GeneratedVisitor;
@@ -570,6 +578,10 @@ impl<'de> serde::Deserialize<'de> for AggregateFunction {
                     "BOOL_OR" => Ok(AggregateFunction::BoolOr),
                     "FIRST_VALUE_AGG" => Ok(AggregateFunction::FirstValueAgg),
                     "LAST_VALUE_AGG" => Ok(AggregateFunction::LastValueAgg),
+                    "STRING_AGG" => Ok(AggregateFunction::StringAgg),
+                    "PERCENTILE_CONT" => Ok(AggregateFunction::PercentileCont),
+                    "PERCENTILE_DISC" => Ok(AggregateFunction::PercentileDisc),
+                    "MODE" => Ok(AggregateFunction::Mode),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
@@ -9716,6 +9728,7 @@ impl serde::Serialize for JoinType {
             Self::Leftanti => "LEFTANTI",
             Self::Rightsemi => "RIGHTSEMI",
             Self::Rightanti => "RIGHTANTI",
+            Self::Asof => "ASOF",
         };
         serializer.serialize_str(variant)
     }
@@ -9735,6 +9748,7 @@ impl<'de> serde::Deserialize<'de> for JoinType {
             "LEFTANTI",
             "RIGHTSEMI",
             "RIGHTANTI",
+            "ASOF",
         ];
 
         struct GeneratedVisitor;
@@ -9785,6 +9799,7 @@ impl<'de> serde::Deserialize<'de> for JoinType {
                     "LEFTANTI" => Ok(JoinType::Leftanti),
                     "RIGHTSEMI" => Ok(JoinType::Rightsemi),
                     "RIGHTANTI" => Ok(JoinType::Rightanti),
+                    "ASOF" => Ok(JoinType::Asof),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
@@ -18281,6 +18296,16 @@ impl serde::Serialize for ScalarFunction {
             Self::ArrayReplaceN => "ArrayReplaceN",
             Self::ArrayRemoveAll => "ArrayRemoveAll",
             Self::ArrayReplaceAll => "ArrayReplaceAll",
+            Self::MakeMap => "MakeMap",
+            Self::MapExtract => "MapExtract",
+            Self::MapKeys => "MapKeys",
+            Self::MapValues => "MapValues",
+            Self::JsonArrayLength => "JsonArrayLength",
+            Self::JsonAsStruct => "JsonAsStruct",
+            Self::JsonContains => "JsonContains",
+            Self::JsonGet => "JsonGet",
+            Self::JsonGetInt => "JsonGetInt",
+            Self::JsonGetStr => "JsonGetStr",
         };
         serializer.serialize_str(variant)
     }
@@ -18402,6 +18427,16 @@ impl<'de> serde::Deserialize<'de> for ScalarFunction {
             "ArrayReplaceN",
             "ArrayRemoveAll",
             "ArrayReplaceAll",
+            "MakeMap",
+            "MapExtract",
+            "MapKeys",
+            "MapValues",
+            "JsonArrayLength",
+            "JsonAsStruct",
+            "JsonContains",
+            "JsonGet",
+            "JsonGetInt",
+            "JsonGetStr",
         ];
 
         struct GeneratedVisitor;
@@ -18554,6 +18589,16 @@ impl<'de> serde::Deserialize<'de> for ScalarFunction {
                     "ArrayReplaceN" => Ok(ScalarFunction::ArrayReplaceN),
                     "ArrayRemoveAll" => Ok(ScalarFunction::ArrayRemoveAll),
                     "ArrayReplaceAll" => Ok(ScalarFunction::ArrayReplaceAll),
+                    "MakeMap" => Ok(ScalarFunction::MakeMap),
+                    "MapExtract" => Ok(ScalarFunction::MapExtract),
+                    "MapKeys" => Ok(ScalarFunction::MapKeys),
+                    "MapValues" => Ok(ScalarFunction::MapValues),
+                    "JsonArrayLength" => Ok(ScalarFunction::JsonArrayLength),
+                    "JsonAsStruct" => Ok(ScalarFunction::JsonAsStruct),
+                    "JsonContains" => Ok(ScalarFunction::JsonContains),
+                    "JsonGet" => Ok(ScalarFunction::JsonGet),
+                    "JsonGetInt" => Ok(ScalarFunction::JsonGetInt),
+                    "JsonGetStr" => Ok(ScalarFunction::JsonGetStr),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
@@ -18797,6 +18842,167 @@ impl<'de> serde::Deserialize<'de> for ScalarListValue {
         deserializer.deserialize_struct("datafusion.ScalarListValue", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for ScalarMapValue {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.is_null {
+            len += 1;
+        }
+        if self.field.is_some() {
+            len += 1;
+        }
+        if self.keys_sorted {
+            len += 1;
+        }
+        if !self.keys.is_empty() {
+            len += 1;
+        }
+        if !self.values.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("datafusion.ScalarMapValue", len)?;
+        if self.is_null {
+            struct_ser.serialize_field("isNull", &self.is_null)?;
+        }
+        if let Some(v) = self.field.as_ref() {
+            struct_ser.serialize_field("field", v)?;
+        }
+        if self.keys_sorted {
+            struct_ser.serialize_field("keysSorted", &self.keys_sorted)?;
+        }
+        if !self.keys.is_empty() {
+            struct_ser.serialize_field("keys", &self.keys)?;
+        }
+        if !self.values.is_empty() {
+            struct_ser.serialize_field("values", &self.values)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ScalarMapValue {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "is_null",
+            "isNull",
+            "field",
+            "keys_sorted",
+            "keysSorted",
+            "keys",
+            "values",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            IsNull,
+            Field,
+            KeysSorted,
+            Keys,
+            Values,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "isNull" | "is_null" => Ok(GeneratedField::IsNull),
+                            "field" => Ok(GeneratedField::Field),
+                            "keysSorted" | "keys_sorted" => Ok(GeneratedField::KeysSorted),
+                            "keys" => Ok(GeneratedField::Keys),
+                            "values" => Ok(GeneratedField::Values),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ScalarMapValue;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct datafusion.ScalarMapValue")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<ScalarMapValue, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut is_null__ = None;
+                let mut field__ = None;
+                let mut keys_sorted__ = None;
+                let mut keys__ = None;
+                let mut values__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::IsNull => {
+                            if is_null__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("isNull"));
+                            }
+                            is_null__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::Field => {
+                            if field__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("field"));
+                            }
+                            field__ = map.next_value()?;
+                        }
+                        GeneratedField::KeysSorted => {
+                            if keys_sorted__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("keysSorted"));
+                            }
+                            keys_sorted__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::Keys => {
+                            if keys__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("keys"));
+                            }
+                            keys__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::Values => {
+                            if values__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("values"));
+                            }
+                            values__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(ScalarMapValue {
+                    is_null: is_null__.unwrap_or_default(),
+                    field: field__,
+                    keys_sorted: keys_sorted__.unwrap_or_default(),
+                    keys: keys__.unwrap_or_default(),
+                    values: values__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("datafusion.ScalarMapValue", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for ScalarTime32Value {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -19392,6 +19598,9 @@ impl serde::Serialize for ScalarValue {
                 scalar_value::Value::FixedSizeBinaryValue(v) => {
                     struct_ser.serialize_field("fixedSizeBinaryValue", v)?;
                 }
+                scalar_value::Value::MapValue(v) => {
+                    struct_ser.serialize_field("mapValue", v)?;
+                }
             }
         }
         struct_ser.end()
@@ -19472,6 +19681,8 @@ impl<'de> serde::Deserialize<'de> for ScalarValue {
             "structValue",
             "fixed_size_binary_value",
             "fixedSizeBinaryValue",
+            "map_value",
+            "mapValue",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -19510,6 +19721,7 @@ impl<'de> serde::Deserialize<'de> for ScalarValue {
             IntervalMonthDayNano,
             StructValue,
             FixedSizeBinaryValue,
+            MapValue,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -19565,6 +19777,7 @@ impl<'de> serde::Deserialize<'de> for ScalarValue {
                             "intervalMonthDayNano" | "interval_month_day_nano" => Ok(GeneratedField::IntervalMonthDayNano),
                             "structValue" | "struct_value" => Ok(GeneratedField::StructValue),
                             "fixedSizeBinaryValue" | "fixed_size_binary_value" => Ok(GeneratedField::FixedSizeBinaryValue),
+                            "mapValue" | "map_value" => Ok(GeneratedField::MapValue),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -19800,6 +20013,13 @@ impl<'de> serde::Deserialize<'de> for ScalarValue {
                                 return Err(serde::de::Error::duplicate_field("fixedSizeBinaryValue"));
                             }
                             value__ = map.next_value::<::std::option::Option<_>>()?.map(scalar_value::Value::FixedSizeBinaryValue)
+;
+                        }
+                        GeneratedField::MapValue => {
+                            if value__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("mapValue"));
+                            }
+                            value__ = map.next_value::<::std::option::Option<_>>()?.map(scalar_value::Value::MapValue)
 ;
                         }
                     }
@@ -22684,6 +22904,9 @@ impl serde::Serialize for WindowExprNode {
         if self.window_frame.is_some() {
             len += 1;
         }
+        if self.ignore_nulls {
+            len += 1;
+        }
         if self.window_function.is_some() {
             len += 1;
         }
@@ -22700,6 +22923,9 @@ impl serde::Serialize for WindowExprNode {
         if let Some(v) = self.window_frame.as_ref() {
             struct_ser.serialize_field("windowFrame", v)?;
         }
+        if self.ignore_nulls {
+            struct_ser.serialize_field("ignoreNulls", &self.ignore_nulls)?;
+        }
         if let Some(v) = self.window_function.as_ref() {
             match v {
                 window_expr_node::WindowFunction::AggrFunction(v) => {
@@ -22737,6 +22963,8 @@ impl<'de> serde::Deserialize<'de> for WindowExprNode {
             "orderBy",
             "window_frame",
             "windowFrame",
+            "ignore_nulls",
+            "ignoreNulls",
             "aggr_function",
             "aggrFunction",
             "built_in_function",
@@ -22751,6 +22979,7 @@ impl<'de> serde::Deserialize<'de> for WindowExprNode {
             PartitionBy,
             OrderBy,
             WindowFrame,
+            IgnoreNulls,
             AggrFunction,
             BuiltInFunction,
             Udaf,
@@ -22780,6 +23009,7 @@ impl<'de> serde::Deserialize<'de> for WindowExprNode {
                             "partitionBy" | "partition_by" => Ok(GeneratedField::PartitionBy),
                             "orderBy" | "order_by" => Ok(GeneratedField::OrderBy),
                             "windowFrame" | "window_frame" => Ok(GeneratedField::WindowFrame),
+                            "ignoreNulls" | "ignore_nulls" => Ok(GeneratedField::IgnoreNulls),
                             "aggrFunction" | "aggr_function" => Ok(GeneratedField::AggrFunction),
                             "builtInFunction" | "built_in_function" => Ok(GeneratedField::BuiltInFunction),
                             "udaf" => Ok(GeneratedField::Udaf),
@@ -22807,6 +23037,7 @@ impl<'de> serde::Deserialize<'de> for WindowExprNode {
                 let mut partition_by__ = None;
                 let mut order_by__ = None;
                 let mut window_frame__ = None;
+                let mut ignore_nulls__ = None;
                 let mut window_function__ = None;
                 while let Some(k) = map.next_key()? {
                     match k {
@@ -22834,6 +23065,12 @@ impl<'de> serde::Deserialize<'de> for WindowExprNode {
                             }
                             window_frame__ = map.next_value()?;
                         }
+                        GeneratedField::IgnoreNulls => {
+                            if ignore_nulls__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("ignoreNulls"));
+                            }
+                            ignore_nulls__ = Some(map.next_value()?);
+                        }
                         GeneratedField::AggrFunction => {
                             if window_function__.is_some() {
                                 return Err(serde::de::Error::duplicate_field("aggrFunction"));
@@ -22865,6 +23102,7 @@ impl<'de> serde::Deserialize<'de> for WindowExprNode {
                     partition_by: partition_by__.unwrap_or_default(),
                     order_by: order_by__.unwrap_or_default(),
                     window_frame: window_frame__,
+                    ignore_nulls: ignore_nulls__.unwrap_or_default(),
                     window_function: window_function__,
                 })
             }
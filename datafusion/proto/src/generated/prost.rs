@@ -760,6 +760,8 @@ pub struct WindowExprNode {
     /// repeated LogicalExprNode filter = 7;
     #[prost(message, optional, tag = "8")]
     pub window_frame: ::core::option::Option<WindowFrame>,
+    #[prost(bool, tag = "10")]
+    pub ignore_nulls: bool,
     #[prost(oneof = "window_expr_node::WindowFunction", tags = "1, 2, 3, 9")]
     pub window_function: ::core::option::Option<window_expr_node::WindowFunction>,
 }
@@ -1013,6 +1015,21 @@ pub struct ScalarListValue {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ScalarMapValue {
+    /// encode null explicitly to distinguish a null map from an empty map
+    #[prost(bool, tag = "1")]
+    pub is_null: bool,
+    #[prost(message, optional, tag = "2")]
+    pub field: ::core::option::Option<Field>,
+    #[prost(bool, tag = "3")]
+    pub keys_sorted: bool,
+    #[prost(message, repeated, tag = "4")]
+    pub keys: ::prost::alloc::vec::Vec<ScalarValue>,
+    #[prost(message, repeated, tag = "5")]
+    pub values: ::prost::alloc::vec::Vec<ScalarValue>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ScalarTime32Value {
     #[prost(oneof = "scalar_time32_value::Value", tags = "1, 2")]
     pub value: ::core::option::Option<scalar_time32_value::Value>,
@@ -1110,7 +1127,7 @@ pub struct ScalarFixedSizeBinary {
 pub struct ScalarValue {
     #[prost(
         oneof = "scalar_value::Value",
-        tags = "33, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 17, 20, 39, 21, 24, 25, 35, 36, 37, 38, 26, 27, 28, 29, 30, 31, 32, 34"
+        tags = "33, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 17, 20, 39, 21, 24, 25, 35, 36, 37, 38, 26, 27, 28, 29, 30, 31, 32, 34, 40"
     )]
     pub value: ::core::option::Option<scalar_value::Value>,
 }
@@ -1191,6 +1208,8 @@ pub mod scalar_value {
         StructValue(super::StructValue),
         #[prost(message, tag = "34")]
         FixedSizeBinaryValue(super::ScalarFixedSizeBinary),
+        #[prost(message, tag = "40")]
+        MapValue(super::ScalarMapValue),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -2138,6 +2157,7 @@ pub enum JoinType {
     Leftanti = 5,
     Rightsemi = 6,
     Rightanti = 7,
+    Asof = 8,
 }
 impl JoinType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -2154,6 +2174,7 @@ impl JoinType {
             JoinType::Leftanti => "LEFTANTI",
             JoinType::Rightsemi => "RIGHTSEMI",
             JoinType::Rightanti => "RIGHTANTI",
+            JoinType::Asof => "ASOF",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -2167,6 +2188,7 @@ impl JoinType {
             "LEFTANTI" => Some(Self::Leftanti),
             "RIGHTSEMI" => Some(Self::Rightsemi),
             "RIGHTANTI" => Some(Self::Rightanti),
+            "ASOF" => Some(Self::Asof),
             _ => None,
         }
     }
@@ -2310,6 +2332,16 @@ pub enum ScalarFunction {
     ArrayReplaceN = 108,
     ArrayRemoveAll = 109,
     ArrayReplaceAll = 110,
+    MakeMap = 111,
+    MapExtract = 112,
+    MapKeys = 113,
+    MapValues = 114,
+    JsonArrayLength = 115,
+    JsonAsStruct = 116,
+    JsonContains = 117,
+    JsonGet = 118,
+    JsonGetInt = 119,
+    JsonGetStr = 120,
 }
 impl ScalarFunction {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -2428,6 +2460,16 @@ impl ScalarFunction {
             ScalarFunction::ArrayReplaceN => "ArrayReplaceN",
             ScalarFunction::ArrayRemoveAll => "ArrayRemoveAll",
             ScalarFunction::ArrayReplaceAll => "ArrayReplaceAll",
+            ScalarFunction::MakeMap => "MakeMap",
+            ScalarFunction::MapExtract => "MapExtract",
+            ScalarFunction::MapKeys => "MapKeys",
+            ScalarFunction::MapValues => "MapValues",
+            ScalarFunction::JsonArrayLength => "JsonArrayLength",
+            ScalarFunction::JsonAsStruct => "JsonAsStruct",
+            ScalarFunction::JsonContains => "JsonContains",
+            ScalarFunction::JsonGet => "JsonGet",
+            ScalarFunction::JsonGetInt => "JsonGetInt",
+            ScalarFunction::JsonGetStr => "JsonGetStr",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -2543,6 +2585,16 @@ impl ScalarFunction {
             "ArrayReplaceN" => Some(Self::ArrayReplaceN),
             "ArrayRemoveAll" => Some(Self::ArrayRemoveAll),
             "ArrayReplaceAll" => Some(Self::ArrayReplaceAll),
+            "MakeMap" => Some(Self::MakeMap),
+            "MapExtract" => Some(Self::MapExtract),
+            "MapKeys" => Some(Self::MapKeys),
+            "MapValues" => Some(Self::MapValues),
+            "JsonArrayLength" => Some(Self::JsonArrayLength),
+            "JsonAsStruct" => Some(Self::JsonAsStruct),
+            "JsonContains" => Some(Self::JsonContains),
+            "JsonGet" => Some(Self::JsonGet),
+            "JsonGetInt" => Some(Self::JsonGetInt),
+            "JsonGetStr" => Some(Self::JsonGetStr),
             _ => None,
         }
     }
@@ -2578,6 +2630,10 @@ pub enum AggregateFunction {
     /// we append "_AGG" to obey name scoping rules.
     FirstValueAgg = 24,
     LastValueAgg = 25,
+    StringAgg = 26,
+    PercentileCont = 27,
+    PercentileDisc = 28,
+    Mode = 29,
 }
 impl AggregateFunction {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -2614,6 +2670,10 @@ impl AggregateFunction {
             AggregateFunction::BoolOr => "BOOL_OR",
             AggregateFunction::FirstValueAgg => "FIRST_VALUE_AGG",
             AggregateFunction::LastValueAgg => "LAST_VALUE_AGG",
+            AggregateFunction::StringAgg => "STRING_AGG",
+            AggregateFunction::PercentileCont => "PERCENTILE_CONT",
+            AggregateFunction::PercentileDisc => "PERCENTILE_DISC",
+            AggregateFunction::Mode => "MODE",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -2647,6 +2707,10 @@ impl AggregateFunction {
             "BOOL_OR" => Some(Self::BoolOr),
             "FIRST_VALUE_AGG" => Some(Self::FirstValueAgg),
             "LAST_VALUE_AGG" => Some(Self::LastValueAgg),
+            "STRING_AGG" => Some(Self::StringAgg),
+            "PERCENTILE_CONT" => Some(Self::PercentileCont),
+            "PERCENTILE_DISC" => Some(Self::PercentileDisc),
+            "MODE" => Some(Self::Mode),
             _ => None,
         }
     }
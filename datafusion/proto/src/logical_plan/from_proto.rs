@@ -44,14 +44,15 @@ use datafusion_expr::{
     character_length, chr, coalesce, concat_expr, concat_ws_expr, cos, cosh, cot,
     current_date, current_time, date_bin, date_part, date_trunc, degrees, digest, exp,
     expr::{self, InList, Sort, WindowFunction},
-    factorial, floor, from_unixtime, gcd, lcm, left, ln, log, log10, log2,
+    factorial, floor, from_unixtime, gcd, json_array_length, json_as_struct,
+    json_contains, json_get, json_get_int, json_get_str, lcm, left, ln, log, log10, log2,
     logical_plan::{PlanType, StringifiedPlan},
-    lower, lpad, ltrim, md5, now, nullif, octet_length, pi, power, radians, random,
-    regexp_match, regexp_replace, repeat, replace, reverse, right, round, rpad, rtrim,
-    sha224, sha256, sha384, sha512, signum, sin, sinh, split_part, sqrt, starts_with,
-    strpos, substr, substring, tan, tanh, to_hex, to_timestamp_micros,
-    to_timestamp_millis, to_timestamp_seconds, translate, trim, trim_array, trunc, upper,
-    uuid,
+    lower, lpad, ltrim, map, map_extract, map_keys, map_values, md5, now, nullif,
+    octet_length, pi, power, radians, random, regexp_match, regexp_replace, repeat,
+    replace, reverse, right, round, rpad, rtrim, sha224, sha256, sha384, sha512, signum,
+    sin, sinh, split_part, sqrt, starts_with, strpos, substr, substring, tan, tanh,
+    to_hex, to_timestamp_micros, to_timestamp_millis, to_timestamp_seconds, translate,
+    trim, trim_array, trunc, upper, uuid,
     window_frame::regularize,
     AggregateFunction, Between, BinaryExpr, BuiltInWindowFunction, BuiltinScalarFunction,
     Case, Cast, Expr, GetIndexedField, GroupingSet,
@@ -472,6 +473,16 @@ impl From<&protobuf::ScalarFunction> for BuiltinScalarFunction {
             ScalarFunction::Cardinality => Self::Cardinality,
             ScalarFunction::Array => Self::MakeArray,
             ScalarFunction::TrimArray => Self::TrimArray,
+            ScalarFunction::MakeMap => Self::MakeMap,
+            ScalarFunction::MapExtract => Self::MapExtract,
+            ScalarFunction::MapKeys => Self::MapKeys,
+            ScalarFunction::MapValues => Self::MapValues,
+            ScalarFunction::JsonArrayLength => Self::JsonArrayLength,
+            ScalarFunction::JsonAsStruct => Self::JsonAsStruct,
+            ScalarFunction::JsonContains => Self::JsonContains,
+            ScalarFunction::JsonGet => Self::JsonGet,
+            ScalarFunction::JsonGetInt => Self::JsonGetInt,
+            ScalarFunction::JsonGetStr => Self::JsonGetStr,
             ScalarFunction::NullIf => Self::NullIf,
             ScalarFunction::DatePart => Self::DatePart,
             ScalarFunction::DateTrunc => Self::DateTrunc,
@@ -542,6 +553,10 @@ impl From<protobuf::AggregateFunction> for AggregateFunction {
             protobuf::AggregateFunction::Count => Self::Count,
             protobuf::AggregateFunction::ApproxDistinct => Self::ApproxDistinct,
             protobuf::AggregateFunction::ArrayAgg => Self::ArrayAgg,
+            protobuf::AggregateFunction::StringAgg => Self::StringAgg,
+            protobuf::AggregateFunction::PercentileCont => Self::PercentileCont,
+            protobuf::AggregateFunction::PercentileDisc => Self::PercentileDisc,
+            protobuf::AggregateFunction::Mode => Self::Mode,
             protobuf::AggregateFunction::Variance => Self::Variance,
             protobuf::AggregateFunction::VariancePop => Self::VariancePop,
             protobuf::AggregateFunction::Covariance => Self::Covariance,
@@ -641,6 +656,30 @@ impl TryFrom<&protobuf::ScalarValue> for ScalarValue {
 
                 Self::List(values, field)
             }
+            Value::MapValue(scalar_map) => {
+                let protobuf::ScalarMapValue {
+                    is_null,
+                    field,
+                    keys_sorted,
+                    keys,
+                    values,
+                } = &scalar_map;
+
+                let field: Field = field.as_ref().required("field")?;
+
+                let entries = if *is_null {
+                    None
+                } else {
+                    let entries = keys
+                        .iter()
+                        .zip(values)
+                        .map(|(key, value)| Ok((key.try_into()?, value.try_into()?)))
+                        .collect::<Result<Vec<_>, Error>>()?;
+                    Some(entries)
+                };
+
+                Self::Map(entries, Arc::new(field), *keys_sorted)
+            }
             Value::NullValue(v) => {
                 let null_type: DataType = v.try_into()?;
                 null_type.try_into().map_err(Error::DataFusionError)?
@@ -847,6 +886,7 @@ impl From<protobuf::JoinType> for JoinType {
             protobuf::JoinType::Rightsemi => JoinType::RightSemi,
             protobuf::JoinType::Leftanti => JoinType::LeftAnti,
             protobuf::JoinType::Rightanti => JoinType::RightAnti,
+            protobuf::JoinType::Asof => JoinType::AsOf,
         }
     }
 }
@@ -989,6 +1029,7 @@ pub fn parse_expr(
                         partition_by,
                         order_by,
                         window_frame,
+                        expr.ignore_nulls,
                     )))
                 }
                 window_expr_node::WindowFunction::BuiltInFunction(i) => {
@@ -1008,6 +1049,7 @@ pub fn parse_expr(
                         partition_by,
                         order_by,
                         window_frame,
+                        expr.ignore_nulls,
                     )))
                 }
                 window_expr_node::WindowFunction::Udaf(udaf_name) => {
@@ -1023,6 +1065,7 @@ pub fn parse_expr(
                         partition_by,
                         order_by,
                         window_frame,
+                        expr.ignore_nulls,
                     )))
                 }
                 window_expr_node::WindowFunction::Udwf(udwf_name) => {
@@ -1038,6 +1081,7 @@ pub fn parse_expr(
                         partition_by,
                         order_by,
                         window_frame,
+                        expr.ignore_nulls,
                     )))
                 }
             }
@@ -1299,6 +1343,63 @@ pub fn parse_expr(
                     parse_expr(&args[0], registry)?,
                     parse_expr(&args[1], registry)?,
                 )),
+                ScalarFunction::MakeMap => Ok(map(
+                    parse_expr(&args[0], registry)?,
+                    parse_expr(&args[1], registry)?,
+                )),
+                ScalarFunction::MapExtract => Ok(map_extract(
+                    parse_expr(&args[0], registry)?,
+                    parse_expr(&args[1], registry)?,
+                )),
+                ScalarFunction::MapKeys => Ok(map_keys(parse_expr(&args[0], registry)?)),
+                ScalarFunction::MapValues => {
+                    Ok(map_values(parse_expr(&args[0], registry)?))
+                }
+                ScalarFunction::JsonArrayLength => Ok(json_array_length(
+                    parse_expr(&args[0], registry)?,
+                    args[1..]
+                        .iter()
+                        .map(|expr| parse_expr(expr, registry))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )),
+                ScalarFunction::JsonContains => Ok(json_contains(
+                    parse_expr(&args[0], registry)?,
+                    args[1..]
+                        .iter()
+                        .map(|expr| parse_expr(expr, registry))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )),
+                ScalarFunction::JsonGet => Ok(json_get(
+                    parse_expr(&args[0], registry)?,
+                    args[1..]
+                        .iter()
+                        .map(|expr| parse_expr(expr, registry))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )),
+                ScalarFunction::JsonGetInt => Ok(json_get_int(
+                    parse_expr(&args[0], registry)?,
+                    args[1..]
+                        .iter()
+                        .map(|expr| parse_expr(expr, registry))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )),
+                ScalarFunction::JsonGetStr => Ok(json_get_str(
+                    parse_expr(&args[0], registry)?,
+                    args[1..]
+                        .iter()
+                        .map(|expr| parse_expr(expr, registry))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )),
+                ScalarFunction::JsonAsStruct => {
+                    match parse_expr(&args[1], registry)? {
+                        Expr::Literal(ScalarValue::Struct(_, fields)) => {
+                            Ok(json_as_struct(parse_expr(&args[0], registry)?, fields))
+                        }
+                        other => Err(proto_error(format!(
+                            "json_as_struct expects a struct literal as its second argument, got {other}"
+                        ))),
+                    }
+                }
                 ScalarFunction::ArrayLength => Ok(array_length(
                     parse_expr(&args[0], registry)?,
                     parse_expr(&args[1], registry)?,
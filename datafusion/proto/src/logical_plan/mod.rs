@@ -1401,6 +1401,12 @@ impl AsLogicalPlan for LogicalPlanNode {
             LogicalPlan::Unnest(_) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for Unnest",
             )),
+            LogicalPlan::RecursiveQuery(_) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for RecursiveQuery",
+            )),
+            LogicalPlan::Sample(_) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for Sample",
+            )),
             LogicalPlan::Ddl(DdlStatement::CreateMemoryTable(_)) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for CreateMemoryTable",
             )),
@@ -1423,12 +1429,24 @@ impl AsLogicalPlan for LogicalPlanNode {
             LogicalPlan::Ddl(DdlStatement::DropCatalogSchema(_)) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for DropCatalogSchema",
             )),
+            LogicalPlan::Ddl(DdlStatement::AlterTable(_)) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for AlterTable",
+            )),
+            LogicalPlan::Ddl(DdlStatement::CreateFunction(_)) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for CreateFunction",
+            )),
+            LogicalPlan::Ddl(DdlStatement::DropFunction(_)) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for DropFunction",
+            )),
             LogicalPlan::Statement(_) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for Statement",
             )),
             LogicalPlan::Dml(_) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for Dml",
             )),
+            LogicalPlan::Copy(_) => Err(proto_error(
+                "LogicalPlan serde is not yet implemented for Copy",
+            )),
             LogicalPlan::DescribeTable(_) => Err(proto_error(
                 "LogicalPlan serde is not yet implemented for DescribeTable",
             )),
@@ -2110,6 +2128,16 @@ mod roundtrip_tests {
             ),
             ScalarValue::FixedSizeBinary(0, None),
             ScalarValue::FixedSizeBinary(5, None),
+            ScalarValue::new_map(
+                Some(vec![
+                    (ScalarValue::from("a"), ScalarValue::Int64(Some(1))),
+                    (ScalarValue::from("b"), ScalarValue::Int64(None)),
+                ]),
+                DataType::Utf8,
+                DataType::Int64,
+            ),
+            ScalarValue::new_map(Some(vec![]), DataType::Utf8, DataType::Int64),
+            ScalarValue::new_map(None, DataType::Int32, DataType::Utf8),
         ];
 
         for test_case in should_pass.into_iter() {
@@ -2780,6 +2808,27 @@ mod roundtrip_tests {
         roundtrip_expr_test(test_expr, ctx.clone());
         roundtrip_expr_test(test_expr_with_count, ctx);
     }
+
+    #[test]
+    fn roundtrip_json_functions() {
+        let ctx = SessionContext::new();
+        roundtrip_expr_test(
+            datafusion_expr::json_get(col("col"), vec![lit("a"), lit(1_i64)]),
+            ctx.clone(),
+        );
+        roundtrip_expr_test(
+            datafusion_expr::json_get_str(col("col"), vec![lit("a")]),
+            ctx.clone(),
+        );
+        roundtrip_expr_test(
+            datafusion_expr::json_array_length(col("col"), vec![]),
+            ctx.clone(),
+        );
+
+        let fields = Fields::from(vec![Field::new("a", DataType::Int64, true)]);
+        roundtrip_expr_test(datafusion_expr::json_as_struct(col("col"), fields), ctx);
+    }
+
     #[test]
     fn roundtrip_window() {
         let ctx = SessionContext::new();
@@ -2793,6 +2842,7 @@ mod roundtrip_tests {
             vec![col("col1")],
             vec![col("col2")],
             WindowFrame::new(true),
+            false,
         ));
 
         // 2. with default window_frame
@@ -2804,6 +2854,7 @@ mod roundtrip_tests {
             vec![col("col1")],
             vec![col("col2")],
             WindowFrame::new(true),
+            false,
         ));
 
         // 3. with window_frame with row numbers
@@ -2821,6 +2872,7 @@ mod roundtrip_tests {
             vec![col("col1")],
             vec![col("col2")],
             range_number_frame,
+            false,
         ));
 
         // 4. test with AggregateFunction
@@ -2836,6 +2888,7 @@ mod roundtrip_tests {
             vec![col("col1")],
             vec![col("col2")],
             row_number_frame.clone(),
+            false,
         ));
 
         // 5. test with AggregateUDF
@@ -2890,6 +2943,7 @@ mod roundtrip_tests {
             vec![col("col1")],
             vec![col("col2")],
             row_number_frame.clone(),
+            false,
         ));
         ctx.register_udaf(dummy_agg);
 
@@ -2939,15 +2993,29 @@ mod roundtrip_tests {
             vec![col("col1")],
             vec![col("col2")],
             row_number_frame,
+            false,
         ));
 
         ctx.register_udwf(dummy_window_udf);
 
+        // 7. with IGNORE NULLS
+        let test_expr7 = Expr::WindowFunction(expr::WindowFunction::new(
+            WindowFunction::BuiltInWindowFunction(
+                datafusion_expr::window_function::BuiltInWindowFunction::LastValue,
+            ),
+            vec![col("col1")],
+            vec![],
+            vec![col("col2")],
+            WindowFrame::new(true),
+            true,
+        ));
+
         roundtrip_expr_test(test_expr1, ctx.clone());
         roundtrip_expr_test(test_expr2, ctx.clone());
         roundtrip_expr_test(test_expr3, ctx.clone());
         roundtrip_expr_test(test_expr4, ctx.clone());
         roundtrip_expr_test(test_expr5, ctx.clone());
-        roundtrip_expr_test(test_expr6, ctx);
+        roundtrip_expr_test(test_expr6, ctx.clone());
+        roundtrip_expr_test(test_expr7, ctx);
     }
 }
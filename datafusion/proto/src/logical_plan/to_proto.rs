@@ -377,6 +377,10 @@ impl From<&AggregateFunction> for protobuf::AggregateFunction {
             AggregateFunction::Count => Self::Count,
             AggregateFunction::ApproxDistinct => Self::ApproxDistinct,
             AggregateFunction::ArrayAgg => Self::ArrayAgg,
+            AggregateFunction::StringAgg => Self::StringAgg,
+            AggregateFunction::PercentileCont => Self::PercentileCont,
+            AggregateFunction::PercentileDisc => Self::PercentileDisc,
+            AggregateFunction::Mode => Self::Mode,
             AggregateFunction::Variance => Self::Variance,
             AggregateFunction::VariancePop => Self::VariancePop,
             AggregateFunction::Covariance => Self::Covariance,
@@ -579,6 +583,7 @@ impl TryFrom<&Expr> for protobuf::LogicalExprNode {
                 ref partition_by,
                 ref order_by,
                 ref window_frame,
+                ref ignore_nulls,
             }) => {
                 let window_function = match fun {
                     WindowFunction::AggregateFunction(fun) => {
@@ -625,6 +630,7 @@ impl TryFrom<&Expr> for protobuf::LogicalExprNode {
                     partition_by,
                     order_by,
                     window_frame,
+                    ignore_nulls: *ignore_nulls,
                 });
                 Self {
                     expr_type: Some(ExprType::WindowExpr(window_expr)),
@@ -648,6 +654,16 @@ impl TryFrom<&Expr> for protobuf::LogicalExprNode {
                         protobuf::AggregateFunction::ApproxPercentileContWithWeight
                     }
                     AggregateFunction::ArrayAgg => protobuf::AggregateFunction::ArrayAgg,
+                    AggregateFunction::StringAgg => {
+                        protobuf::AggregateFunction::StringAgg
+                    }
+                    AggregateFunction::PercentileCont => {
+                        protobuf::AggregateFunction::PercentileCont
+                    }
+                    AggregateFunction::PercentileDisc => {
+                        protobuf::AggregateFunction::PercentileDisc
+                    }
+                    AggregateFunction::Mode => protobuf::AggregateFunction::Mode,
                     AggregateFunction::Min => protobuf::AggregateFunction::Min,
                     AggregateFunction::Max => protobuf::AggregateFunction::Max,
                     AggregateFunction::Sum => protobuf::AggregateFunction::Sum,
@@ -1103,6 +1119,31 @@ impl TryFrom<&ScalarValue> for protobuf::ScalarValue {
                     )),
                 })
             }
+            ScalarValue::Map(entries, field, keys_sorted) => {
+                let is_null = entries.is_none();
+
+                let (keys, values) = entries
+                    .iter()
+                    .flatten()
+                    .map(|(key, value)| Ok((key.try_into()?, value.try_into()?)))
+                    .collect::<Result<Vec<_>, Error>>()?
+                    .into_iter()
+                    .unzip();
+
+                let field = field.as_ref().try_into()?;
+
+                Ok(protobuf::ScalarValue {
+                    value: Some(protobuf::scalar_value::Value::MapValue(
+                        protobuf::ScalarMapValue {
+                            is_null,
+                            field: Some(field),
+                            keys_sorted: *keys_sorted,
+                            keys,
+                            values,
+                        },
+                    )),
+                })
+            }
             ScalarValue::Date32(val) => {
                 create_proto_scalar(val.as_ref(), &data_type, |s| Value::Date32Value(*s))
             }
@@ -1420,6 +1461,16 @@ impl TryFrom<&BuiltinScalarFunction> for protobuf::ScalarFunction {
             BuiltinScalarFunction::Cardinality => Self::Cardinality,
             BuiltinScalarFunction::MakeArray => Self::Array,
             BuiltinScalarFunction::TrimArray => Self::TrimArray,
+            BuiltinScalarFunction::MakeMap => Self::MakeMap,
+            BuiltinScalarFunction::MapExtract => Self::MapExtract,
+            BuiltinScalarFunction::MapKeys => Self::MapKeys,
+            BuiltinScalarFunction::MapValues => Self::MapValues,
+            BuiltinScalarFunction::JsonArrayLength => Self::JsonArrayLength,
+            BuiltinScalarFunction::JsonAsStruct => Self::JsonAsStruct,
+            BuiltinScalarFunction::JsonContains => Self::JsonContains,
+            BuiltinScalarFunction::JsonGet => Self::JsonGet,
+            BuiltinScalarFunction::JsonGetInt => Self::JsonGetInt,
+            BuiltinScalarFunction::JsonGetStr => Self::JsonGetStr,
             BuiltinScalarFunction::NullIf => Self::NullIf,
             BuiltinScalarFunction::DatePart => Self::DatePart,
             BuiltinScalarFunction::DateTrunc => Self::DateTrunc,
@@ -1541,6 +1592,7 @@ impl From<JoinType> for protobuf::JoinType {
             JoinType::RightSemi => protobuf::JoinType::Rightsemi,
             JoinType::LeftAnti => protobuf::JoinType::Leftanti,
             JoinType::RightAnti => protobuf::JoinType::Rightanti,
+            JoinType::AsOf => protobuf::JoinType::Asof,
         }
     }
 }
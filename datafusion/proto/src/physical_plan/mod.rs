@@ -329,6 +329,7 @@ impl AsExecutionPlan for PhysicalPlanNode {
                                     &[],
                                     Arc::new(WindowFrame::new(false)),
                                     &physical_schema,
+                                    false,
                                 )?)
                             }
                             _ => Err(DataFusionError::Internal(
@@ -1420,6 +1421,8 @@ mod roundtrip_tests {
             empty::EmptyExec,
             expressions::{binary, col, lit, NotExpr},
             expressions::{Avg, Column, DistinctCount, PhysicalSortExpr},
+            expressions::{DistinctStringAgg, OrderSensitiveStringAgg, StringAgg},
+            expressions::{Mode, Percentile, PercentileKind},
             filter::FilterExec,
             joins::{HashJoinExec, NestedLoopJoinExec, PartitionMode},
             limit::{GlobalLimitExec, LocalLimitExec},
@@ -1897,6 +1900,111 @@ mod roundtrip_tests {
         )?))
     }
 
+    #[test]
+    fn roundtrip_string_agg() -> Result<()> {
+        let field_a = Field::new("a", DataType::Int64, false);
+        let field_b = Field::new("b", DataType::Utf8, false);
+        let schema = Arc::new(Schema::new(vec![field_a, field_b]));
+
+        let ordering_req = vec![PhysicalSortExpr {
+            expr: col("b", &schema)?,
+            options: SortOptions {
+                descending: true,
+                nulls_first: false,
+            },
+        }];
+        let aggregates: Vec<Arc<dyn AggregateExpr>> = vec![
+            Arc::new(StringAgg::try_new(
+                col("b", &schema)?,
+                lit(","),
+                "STRING_AGG(b, ',')",
+                DataType::Utf8,
+            )?),
+            Arc::new(OrderSensitiveStringAgg::try_new(
+                col("b", &schema)?,
+                lit(","),
+                "STRING_AGG(b, ',' ORDER BY b DESC)",
+                DataType::Utf8,
+                vec![DataType::Utf8],
+                ordering_req.clone(),
+            )?),
+            Arc::new(DistinctStringAgg::try_new(
+                col("b", &schema)?,
+                lit(","),
+                "STRING_AGG(DISTINCT b, ',' ORDER BY b DESC)",
+                DataType::Utf8,
+                ordering_req.clone(),
+            )?),
+        ];
+
+        let groups: Vec<(Arc<dyn PhysicalExpr>, String)> =
+            vec![(col("a", &schema)?, "unused".to_string())];
+
+        roundtrip_test(Arc::new(AggregateExec::try_new(
+            AggregateMode::Partial,
+            PhysicalGroupBy::new_single(groups),
+            aggregates,
+            vec![None, None, None],
+            vec![None, Some(ordering_req.clone()), Some(ordering_req)],
+            Arc::new(EmptyExec::new(false, schema.clone())),
+            schema,
+        )?))
+    }
+
+    #[test]
+    fn roundtrip_ordered_set_aggregates() -> Result<()> {
+        let field_a = Field::new("a", DataType::Int64, false);
+        let field_b = Field::new("b", DataType::Float64, false);
+        let schema = Arc::new(Schema::new(vec![field_a, field_b]));
+
+        let aggregates: Vec<Arc<dyn AggregateExpr>> = vec![
+            Arc::new(Percentile::try_new(
+                col("b", &schema)?,
+                lit(0.5),
+                PercentileKind::Continuous,
+                "PERCENTILE_CONT(b, 0.5)",
+                DataType::Float64,
+                vec![],
+            )?),
+            Arc::new(Percentile::try_new(
+                col("a", &schema)?,
+                lit(ScalarValue::new_list(
+                    Some(vec![ScalarValue::from(0.25), ScalarValue::from(0.75)]),
+                    DataType::Float64,
+                )),
+                PercentileKind::Discrete,
+                "PERCENTILE_DISC(a, [0.25, 0.75])",
+                DataType::List(Arc::new(Field::new("item", DataType::Int64, true))),
+                vec![PhysicalSortExpr {
+                    expr: col("a", &schema)?,
+                    options: SortOptions {
+                        descending: true,
+                        nulls_first: true,
+                    },
+                }],
+            )?),
+            Arc::new(Mode::new(
+                col("a", &schema)?,
+                "MODE(a)",
+                DataType::Int64,
+                vec![],
+            )),
+        ];
+
+        let groups: Vec<(Arc<dyn PhysicalExpr>, String)> =
+            vec![(col("a", &schema)?, "unused".to_string())];
+
+        roundtrip_test(Arc::new(AggregateExec::try_new(
+            AggregateMode::Final,
+            PhysicalGroupBy::new_single(groups),
+            aggregates,
+            vec![None, None, None],
+            vec![None, None, None],
+            Arc::new(EmptyExec::new(false, schema.clone())),
+            schema,
+        )?))
+    }
+
     #[test]
     fn roundtrip_like() -> Result<()> {
         let schema = Schema::new(vec![
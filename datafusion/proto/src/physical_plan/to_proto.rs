@@ -106,6 +106,27 @@ impl TryFrom<Arc<dyn AggregateExpr>> for protobuf::PhysicalExprNode {
             Ok(AggregateFunction::ApproxDistinct.into())
         } else if a.as_any().downcast_ref::<expressions::ArrayAgg>().is_some() {
             Ok(AggregateFunction::ArrayAgg.into())
+        } else if a.as_any().is::<expressions::StringAgg>()
+            || a.as_any().is::<expressions::OrderSensitiveStringAgg>()
+        {
+            Ok(AggregateFunction::StringAgg.into())
+        } else if a.as_any().is::<expressions::DistinctStringAgg>() {
+            distinct = true;
+            Ok(AggregateFunction::StringAgg.into())
+        } else if let Some(percentile) =
+            a.as_any().downcast_ref::<expressions::Percentile>()
+        {
+            Ok(match percentile.kind() {
+                expressions::PercentileKind::Continuous => {
+                    AggregateFunction::PercentileCont
+                }
+                expressions::PercentileKind::Discrete => {
+                    AggregateFunction::PercentileDisc
+                }
+            }
+            .into())
+        } else if a.as_any().is::<expressions::Mode>() {
+            Ok(AggregateFunction::Mode.into())
         } else if a.as_any().downcast_ref::<expressions::Variance>().is_some() {
             Ok(AggregateFunction::Variance.into())
         } else if a
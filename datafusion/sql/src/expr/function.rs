@@ -16,7 +16,7 @@
 // under the License.
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
-use datafusion_common::{DFSchema, DataFusionError, Result};
+use datafusion_common::{DFSchema, DataFusionError, Result, ScalarValue};
 use datafusion_expr::expr::{ScalarFunction, ScalarUDF};
 use datafusion_expr::function::suggest_valid_function;
 use datafusion_expr::window_frame::regularize;
@@ -25,8 +25,12 @@ use datafusion_expr::{
     WindowFunction,
 };
 use sqlparser::ast::{
-    Expr as SQLExpr, Function as SQLFunction, FunctionArg, FunctionArgExpr, WindowType,
+    Expr as SQLExpr, Function as SQLFunction, FunctionArg, FunctionArgExpr,
+    NullTreatment, OrderByExpr, WindowType,
 };
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::Token;
 use std::str::FromStr;
 
 use super::arrow_cast::ARROW_CAST_NAME;
@@ -46,6 +50,48 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             crate::utils::normalize_ident(function.name.0[0].clone())
         };
 
+        if function.null_treatment.is_some() && function.over.is_none() {
+            return Err(DataFusionError::Plan(format!(
+                "Null treatment is only supported for window functions, got {name}"
+            )));
+        }
+        let ignore_nulls = function.null_treatment == Some(NullTreatment::IgnoreNulls);
+
+        let mut within_group = std::mem::take(&mut function.within_group);
+        if !within_group.is_empty()
+            && !matches!(
+                AggregateFunction::from_str(&name),
+                Ok(AggregateFunction::PercentileCont
+                    | AggregateFunction::PercentileDisc
+                    | AggregateFunction::Mode)
+            )
+        {
+            return Err(DataFusionError::Plan(format!(
+                "WITHIN GROUP is only supported for the ordered-set aggregate functions percentile_cont, percentile_disc and mode, got {name}"
+            )));
+        }
+        if within_group.len() > 1 {
+            return Err(DataFusionError::Plan(
+                "WITHIN GROUP only supports a single ORDER BY expression".to_string(),
+            ));
+        }
+        // the ordering expression of an ordered-set aggregate is its first
+        // argument. NULL values are never aggregated, so their ordering does
+        // not matter
+        let within_group_descending = match within_group.pop() {
+            Some(OrderByExpr { expr, asc, .. }) => {
+                let arg = FunctionArg::Unnamed(FunctionArgExpr::Expr(expr));
+                function.args.insert(0, arg);
+                asc == Some(false)
+            }
+            None => false,
+        };
+        if within_group_descending && function.over.is_some() {
+            return Err(DataFusionError::NotImplemented(format!(
+                "WITHIN GROUP (ORDER BY ... DESC) is not supported for window functions, got {name}"
+            )));
+        }
+
         // user-defined function (UDF) should have precedence in case it has the same name as a scalar built-in function
         if let Some(fm) = self.schema_provider.get_function_meta(&name) {
             let args =
@@ -55,8 +101,11 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
 
         // next, scalar built-in
         if let Ok(fun) = BuiltinScalarFunction::from_str(&name) {
-            let args =
+            let mut args =
                 self.function_args_to_expr(function.args, schema, planner_context)?;
+            if fun == BuiltinScalarFunction::JsonAsStruct && args.len() == 2 {
+                args[1] = self.json_struct_schema(&args[1])?;
+            }
             return Ok(Expr::ScalarFunction(ScalarFunction::new(fun, args)));
         };
 
@@ -93,6 +142,11 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 WindowFrame::new(!order_by.is_empty())
             };
             if let Ok(fun) = self.find_window_func(&name) {
+                if ignore_nulls && !fun.supports_ignore_nulls() {
+                    return Err(DataFusionError::Plan(format!(
+                        "IGNORE NULLS is not supported for window function {fun}"
+                    )));
+                }
                 let expr = match fun {
                     WindowFunction::AggregateFunction(aggregate_fun) => {
                         let args = self.function_args_to_expr(
@@ -107,6 +161,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                             partition_by,
                             order_by,
                             window_frame,
+                            ignore_nulls,
                         ))
                     }
                     _ => Expr::WindowFunction(expr::WindowFunction::new(
@@ -119,6 +174,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                         partition_by,
                         order_by,
                         window_frame,
+                        ignore_nulls,
                     )),
                 };
                 return Ok(expr);
@@ -132,9 +188,16 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     schema,
                     planner_context,
                 )?;
-                let order_by = (!order_by.is_empty()).then_some(order_by);
                 let args =
                     self.function_args_to_expr(function.args, schema, planner_context)?;
+                // the descending order of an ordered-set aggregate is the
+                // ordering of its aggregated argument, ascending is implied
+                let order_by = if within_group_descending {
+                    vec![args[0].clone().sort(false, true)]
+                } else {
+                    order_by
+                };
+                let order_by = (!order_by.is_empty()).then_some(order_by);
 
                 return Ok(Expr::AggregateFunction(expr::AggregateFunction::new(
                     fun, args, distinct, None, order_by,
@@ -156,6 +219,13 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     self.function_args_to_expr(function.args, schema, planner_context)?;
                 return super::arrow_cast::create_arrow_cast(args, schema);
             }
+
+            // last, functions created by `CREATE FUNCTION`, which are inlined
+            if let Some(scalar_macro) = self.schema_provider.get_scalar_macro(&name) {
+                let args =
+                    self.function_args_to_expr(function.args, schema, planner_context)?;
+                return scalar_macro.inline(args, schema);
+            }
         }
 
         // Could not find the relevant function, so return an error
@@ -165,6 +235,32 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         )))
     }
 
+    /// Plans the schema argument of `json_as_struct`, a string with column
+    /// definitions such as `'a INT, b VARCHAR'`, as a NULL struct literal of
+    /// the described type. The fields cannot be `NOT NULL`, as they are NULL
+    /// for missing keys and for values that are not JSON objects.
+    fn json_struct_schema(&self, schema: &Expr) -> Result<Expr> {
+        let schema = match schema {
+            Expr::Literal(ScalarValue::Utf8(Some(schema))) => schema,
+            other => {
+                return Err(DataFusionError::Plan(format!(
+                    "The json_as_struct function requires a string literal with the struct schema as its second argument, got {other}"
+                )))
+            }
+        };
+        let mut parser = Parser::new(&GenericDialect {}).try_with_sql(schema)?;
+        let columns = parser.parse_comma_separated(Parser::parse_column_def)?;
+        parser.expect_token(&Token::EOF)?;
+        let fields = self.build_schema(columns)?.fields;
+        if let Some(field) = fields.iter().find(|field| !field.is_nullable()) {
+            return Err(DataFusionError::Plan(format!(
+                "The json_as_struct schema cannot declare the field {} NOT NULL",
+                field.name()
+            )));
+        }
+        Ok(Expr::Literal(ScalarValue::Struct(None, fields)))
+    }
+
     pub(super) fn sql_named_function_to_expr(
         &self,
         expr: SQLExpr,
@@ -36,7 +36,10 @@ use datafusion_expr::{
     col, expr, lit, AggregateFunction, Between, BinaryExpr, BuiltinScalarFunction, Cast,
     Expr, ExprSchemable, GetIndexedField, Like, Operator, TryCast,
 };
-use sqlparser::ast::{ArrayAgg, Expr as SQLExpr, TrimWhereField, Value};
+use sqlparser::ast::{
+    ArrayAgg, Expr as SQLExpr, JsonOperator, ListAgg, ListAggOnOverflow, TrimWhereField,
+    Value,
+};
 use sqlparser::parser::ParserError::ParserError;
 
 impl<'a, S: ContextProvider> SqlToRel<'a, S> {
@@ -184,6 +187,8 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 plan_indexed(expr, indexes)
             }
 
+            SQLExpr::JsonAccess { left, operator, right } => self.sql_json_access_to_expr(*left, operator, *right, schema, planner_context),
+
             SQLExpr::CompoundIdentifier(ids) => self.sql_compound_identifier_to_expr(ids, schema, planner_context),
 
             SQLExpr::Case {
@@ -319,6 +324,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             SQLExpr::Subquery(subquery) => self.parse_scalar_subquery(*subquery, schema, planner_context),
 
             SQLExpr::ArrayAgg(array_agg) => self.parse_array_agg(array_agg, schema, planner_context),
+            SQLExpr::ListAgg(list_agg) => self.parse_list_agg(list_agg, schema, planner_context),
 
             _ => Err(DataFusionError::NotImplemented(format!(
                 "Unsupported ast node in sqltorel: {sql:?}"
@@ -326,6 +332,32 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         }
     }
 
+    /// Plans the json operators `->` and `->>` as `json_get` and
+    /// `json_get_str`
+    fn sql_json_access_to_expr(
+        &self,
+        left: SQLExpr,
+        operator: JsonOperator,
+        right: SQLExpr,
+        schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Expr> {
+        let fun = match operator {
+            JsonOperator::Arrow => BuiltinScalarFunction::JsonGet,
+            JsonOperator::LongArrow => BuiltinScalarFunction::JsonGetStr,
+            _ => {
+                return Err(DataFusionError::NotImplemented(format!(
+                    "Unsupported json operator {operator:?}"
+                )))
+            }
+        };
+        let args = vec![
+            self.sql_expr_to_logical_expr(left, schema, planner_context)?,
+            self.sql_expr_to_logical_expr(right, schema, planner_context)?,
+        ];
+        Ok(Expr::ScalarFunction(ScalarFunction::new(fun, args)))
+    }
+
     fn parse_array_agg(
         &self,
         array_agg: ArrayAgg,
@@ -369,6 +401,54 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         )))
     }
 
+    fn parse_list_agg(
+        &self,
+        list_agg: ListAgg,
+        input_schema: &DFSchema,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Expr> {
+        // LISTAGG is planned as STRING_AGG, ordered by its WITHIN GROUP clause
+        let ListAgg {
+            distinct,
+            expr,
+            separator,
+            on_overflow,
+            within_group,
+        } = list_agg;
+
+        if let Some(on_overflow @ ListAggOnOverflow::Truncate { .. }) = on_overflow {
+            return Err(DataFusionError::NotImplemented(format!(
+                "LISTAGG{on_overflow} is not supported"
+            )));
+        }
+
+        let order_by = if within_group.is_empty() {
+            None
+        } else {
+            Some(self.order_by_to_sort_expr(
+                &within_group,
+                input_schema,
+                planner_context,
+            )?)
+        };
+
+        let expr = self.sql_expr_to_logical_expr(*expr, input_schema, planner_context)?;
+        let separator = match separator {
+            Some(separator) => {
+                self.sql_expr_to_logical_expr(*separator, input_schema, planner_context)?
+            }
+            None => lit(""),
+        };
+
+        Ok(Expr::AggregateFunction(expr::AggregateFunction::new(
+            AggregateFunction::StringAgg,
+            vec![expr, separator],
+            distinct,
+            None,
+            order_by,
+        )))
+    }
+
     fn sql_in_list_to_expr(
         &self,
         expr: SQLExpr,
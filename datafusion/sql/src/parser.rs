@@ -18,7 +18,10 @@
 //! DataFusion SQL Parser based on [`sqlparser`]
 
 use datafusion_common::parsers::CompressionTypeVariant;
-use sqlparser::ast::{OrderByExpr, Query, Value};
+use sqlparser::ast::{
+    CreateFunctionBody, FunctionBehavior, FunctionDefinition, OperateFunctionArg,
+    OrderByExpr, Query, Value,
+};
 use sqlparser::tokenizer::Word;
 use sqlparser::{
     ast::{
@@ -52,7 +55,7 @@ fn parse_file_type(s: &str) -> Result<String, ParserError> {
 /// COPY <table_name | (<query>)>
 /// TO
 /// <destination_url>
-/// (key_value_list)
+/// [ [ WITH ] (key_value_list) ]
 /// ```
 ///
 /// # Examples
@@ -356,8 +359,10 @@ impl<'a> DFParser<'a> {
 
         let target = self.parser.parse_literal_string()?;
 
-        // check for options in parens
-        let options = if self.parser.peek_token().token == Token::LParen {
+        // check for options in parens, optionally preceded by `WITH`
+        let options = if self.parser.parse_keyword(Keyword::WITH)
+            || self.parser.peek_token().token == Token::LParen
+        {
             self.parse_value_options()?
         } else {
             HashMap::new()
@@ -413,17 +418,133 @@ impl<'a> DFParser<'a> {
     }
 
     /// Parse a SQL `CREATE` statement handling `CREATE EXTERNAL TABLE`
+    /// and `CREATE FUNCTION`
     pub fn parse_create(&mut self) -> Result<Statement, ParserError> {
         if self.parser.parse_keyword(Keyword::EXTERNAL) {
             self.parse_create_external_table(false)
         } else if self.parser.parse_keyword(Keyword::UNBOUNDED) {
             self.parser.expect_keyword(Keyword::EXTERNAL)?;
             self.parse_create_external_table(true)
+        } else if self.is_create_function() {
+            self.parse_create_function()
         } else {
             Ok(Statement::Statement(Box::from(self.parser.parse_create()?)))
         }
     }
 
+    /// Returns true if the tokens following `CREATE` start a
+    /// `CREATE [OR REPLACE] [TEMPORARY] FUNCTION` statement
+    fn is_create_function(&self) -> bool {
+        let mut n = 0;
+        loop {
+            match self.parser.peek_nth_token(n).token {
+                Token::Word(w)
+                    if matches!(
+                        w.keyword,
+                        Keyword::OR | Keyword::REPLACE | Keyword::TEMPORARY
+                    ) =>
+                {
+                    n += 1
+                }
+                Token::Word(w) => return w.keyword == Keyword::FUNCTION,
+                _ => return false,
+            }
+        }
+    }
+
+    /// Parse a SQL `CREATE FUNCTION` statement, which defines a function by
+    /// an expression of its arguments:
+    ///
+    /// ```sql
+    /// CREATE [OR REPLACE] [TEMPORARY] FUNCTION name([arg_name arg_type, ...])
+    ///   [RETURNS return_type]
+    ///   [LANGUAGE SQL]
+    ///   [IMMUTABLE | STABLE | VOLATILE]
+    ///   { AS 'expression' | RETURN expression }
+    /// ```
+    fn parse_create_function(&mut self) -> Result<Statement, ParserError> {
+        let or_replace = self.parser.parse_keywords(&[Keyword::OR, Keyword::REPLACE]);
+        let temporary = self.parser.parse_keyword(Keyword::TEMPORARY);
+        self.parser.expect_keyword(Keyword::FUNCTION)?;
+        let name = self.parser.parse_object_name()?;
+
+        self.parser.expect_token(&Token::LParen)?;
+        let args = if self.parser.consume_token(&Token::RParen) {
+            vec![]
+        } else {
+            let args = self.parser.parse_comma_separated(|parser| {
+                let name = parser.parse_identifier()?;
+                let data_type = parser.parse_data_type()?;
+                Ok(OperateFunctionArg {
+                    mode: None,
+                    name: Some(name),
+                    data_type,
+                    default_expr: None,
+                })
+            })?;
+            self.parser.expect_token(&Token::RParen)?;
+            args
+        };
+
+        let return_type = if self.parser.parse_keyword(Keyword::RETURNS) {
+            Some(self.parser.parse_data_type()?)
+        } else {
+            None
+        };
+
+        let mut params = CreateFunctionBody::default();
+        loop {
+            if params.language.is_none() && self.parser.parse_keyword(Keyword::LANGUAGE) {
+                params.language = Some(self.parser.parse_identifier()?);
+            } else if params.behavior.is_none()
+                && self.parser.parse_keyword(Keyword::IMMUTABLE)
+            {
+                params.behavior = Some(FunctionBehavior::Immutable);
+            } else if params.behavior.is_none()
+                && self.parser.parse_keyword(Keyword::STABLE)
+            {
+                params.behavior = Some(FunctionBehavior::Stable);
+            } else if params.behavior.is_none()
+                && self.parser.parse_keyword(Keyword::VOLATILE)
+            {
+                params.behavior = Some(FunctionBehavior::Volatile);
+            } else if params.as_.is_none()
+                && params.return_.is_none()
+                && self.parser.parse_keyword(Keyword::AS)
+            {
+                // `$$body$$` is accepted by all dialects
+                params.as_ = match self.parser.peek_token().token {
+                    Token::DollarQuotedString(body) => {
+                        self.parser.next_token();
+                        Some(FunctionDefinition::DoubleDollarDef(body.value))
+                    }
+                    _ => Some(self.parser.parse_function_definition()?),
+                };
+            } else if params.as_.is_none()
+                && params.return_.is_none()
+                && self.parser.parse_keyword(Keyword::RETURN)
+            {
+                params.return_ = Some(self.parser.parse_expr()?);
+            } else {
+                break;
+            }
+        }
+        if params.as_.is_none() && params.return_.is_none() {
+            return self.expected("AS or RETURN", self.parser.peek_token());
+        }
+
+        Ok(Statement::Statement(Box::new(
+            SQLStatement::CreateFunction {
+                or_replace,
+                temporary,
+                name,
+                args: Some(args),
+                return_type,
+                params,
+            },
+        )))
+    }
+
     fn parse_partitions(&mut self) -> Result<Vec<String>, ParserError> {
         let mut partitions: Vec<String> = vec![];
         if !self.parser.consume_token(&Token::LParen)
@@ -1325,6 +1446,22 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn copy_to_with_options() -> Result<(), ParserError> {
+        let sql = "COPY foo TO bar WITH (format csv)";
+        let canonical = "COPY foo TO bar (format csv)";
+        let expected = Statement::CopyTo(CopyToStatement {
+            source: object_name("foo"),
+            target: "bar".to_string(),
+            options: HashMap::from([(
+                "format".to_string(),
+                Value::UnQuotedString("csv".to_string()),
+            )]),
+        });
+        assert_eq!(one_statement_parses_to(sql, canonical), expected);
+        Ok(())
+    }
+
     #[test]
     fn copy_to_multi_options() -> Result<(), ParserError> {
         let sql =
@@ -1362,6 +1499,170 @@ mod tests {
 
     // For error cases, see: `copy.slt`
 
+    #[test]
+    fn create_function() {
+        verified_stmt(
+            "CREATE OR REPLACE FUNCTION add(a INT, b INT) RETURNS INT LANGUAGE sql IMMUTABLE AS 'a + b'",
+        );
+        verified_stmt("CREATE TEMPORARY FUNCTION one() RETURN 1");
+        one_statement_parses_to(
+            "CREATE FUNCTION f(a INT) AS $$a * 2$$ STABLE",
+            "CREATE FUNCTION f(a INT) STABLE AS $$a * 2$$",
+        );
+
+        // other objects are still created by the native parser
+        let statement = verified_stmt("CREATE OR REPLACE VIEW v AS SELECT 1");
+        assert!(matches!(
+            statement,
+            Statement::Statement(s) if matches!(*s, SQLStatement::CreateView { .. })
+        ));
+
+        expect_parse_error("CREATE FUNCTION f(a INT)", "Expected AS or RETURN");
+        expect_parse_error(
+            "CREATE FUNCTION f(a INT) AS 'a' AS 'a'",
+            "Expected end of statement, found: AS",
+        );
+    }
+
+    #[test]
+    fn table_sample() {
+        verified_stmt("SELECT * FROM t TABLESAMPLE BERNOULLI (1)");
+        one_statement_parses_to(
+            "SELECT * FROM t AS s tablesample system(5 rows) repeatable (42) WHERE x > 1",
+            "SELECT * FROM t AS s TABLESAMPLE system (5 ROWS) REPEATABLE (42) WHERE x > 1",
+        );
+        verified_stmt(
+            "SELECT * FROM t1 TABLESAMPLE SYSTEM (0.5 PERCENT) JOIN t2 TABLESAMPLE BERNOULLI ((1 + 2)) ON t1.a = t2.a",
+        );
+        // comments and whitespace around the clause are kept by the tokenizer
+        one_statement_parses_to(
+            "SELECT * FROM t -- sample\n TABLESAMPLE /* method */ BERNOULLI (1)",
+            "SELECT * FROM t TABLESAMPLE BERNOULLI (1)",
+        );
+        // identifiers are not parsed as the clause
+        verified_stmt("SELECT \"tablesample\" FROM t");
+        verified_stmt("SELECT tablesample FROM t");
+        verified_stmt("SELECT a, tablesample FROM t WHERE tablesample > 1");
+        one_statement_parses_to(
+            "CREATE TABLE t(tablesample INT) AS VALUES (1)",
+            "CREATE TABLE t (tablesample INT) AS VALUES (1)",
+        );
+
+        expect_parse_error(
+            "SELECT * FROM t TABLESAMPLE BERNOULLI (5) REPEATABLE (1",
+            "Expected ), found: EOF",
+        );
+    }
+
+    #[test]
+    fn unpivot() {
+        verified_stmt("SELECT * FROM t UNPIVOT(amount FOR month IN (jan, feb))");
+        one_statement_parses_to(
+            "SELECT * FROM t AS s unpivot include nulls (amount for \"Month\" in (jan)) AS u WHERE amount > 1",
+            "SELECT * FROM t AS s UNPIVOT INCLUDE NULLS(amount FOR \"Month\" IN (jan)) AS u WHERE amount > 1",
+        );
+        one_statement_parses_to(
+            "SELECT * FROM (SELECT * FROM t) UNPIVOT EXCLUDE NULLS (amount FOR month IN (jan)) u JOIN v ON u.id = v.id",
+            "SELECT * FROM (SELECT * FROM t) UNPIVOT EXCLUDE NULLS(amount FOR month IN (jan)) AS u JOIN v ON u.id = v.id",
+        );
+        // comments and whitespace around the clause are kept by the tokenizer
+        one_statement_parses_to(
+            "SELECT * FROM t /* sales */ UNPIVOT -- by month\n (amount FOR month IN (jan))",
+            "SELECT * FROM t UNPIVOT(amount FOR month IN (jan))",
+        );
+        // identifiers are not parsed as the clause
+        verified_stmt("SELECT unpivot FROM t");
+        verified_stmt("SELECT * FROM t AS \"unpivot\"");
+
+        expect_parse_error(
+            "SELECT * FROM t UNPIVOT (amount IN (jan))",
+            "Expected FOR, found: IN",
+        );
+        expect_parse_error(
+            "SELECT * FROM t UNPIVOT INCLUDE (amount FOR month IN (jan))",
+            "Expected (, found: INCLUDE",
+        );
+        expect_parse_error(
+            "SELECT * FROM t UNPIVOT (amount FOR month IN (jan) x)",
+            "Expected ), found: x",
+        );
+    }
+
+    #[test]
+    fn asof_join() {
+        verified_stmt("SELECT * FROM t ASOF JOIN q MATCH_CONDITION (t.ts >= q.ts)");
+        one_statement_parses_to(
+            "SELECT * FROM t asof join (SELECT * FROM q) AS q2 match_condition(t.ts < q2.ts) ON t.sym = q2.sym WHERE t.x > 1",
+            "SELECT * FROM t ASOF JOIN (SELECT * FROM q) AS q2 MATCH_CONDITION (t.ts < q2.ts) ON t.sym = q2.sym WHERE t.x > 1",
+        );
+        verified_stmt(
+            "SELECT * FROM t ASOF JOIN q TABLESAMPLE SYSTEM (10) MATCH_CONDITION (t.ts >= q.ts)",
+        );
+        // rejected by the planner
+        verified_stmt(
+            "SELECT * FROM t ASOF JOIN q MATCH_CONDITION (t.ts >= q.ts) USING(sym)",
+        );
+        verified_stmt("SELECT * FROM t AS \"asof\" JOIN q ON t.a = q.a");
+        // a match condition outside of an ASOF JOIN is an ordinary function call
+        verified_stmt("SELECT * FROM t JOIN q ON MATCH_CONDITION(t.ts >= q.ts)");
+
+        expect_parse_error(
+            "SELECT * FROM t ASOF JOIN q ON t.ts >= q.ts",
+            "Expected MATCH_CONDITION, found: ON",
+        );
+        expect_parse_error(
+            "SELECT * FROM t ASOF JOIN q MATCH_CONDITION t.ts >= q.ts",
+            "Expected (, found: t",
+        );
+    }
+
+    #[test]
+    fn null_treatment() {
+        verified_stmt("SELECT lag(a, 2) IGNORE NULLS OVER (ORDER BY b) FROM t");
+        one_statement_parses_to(
+            "SELECT first_value(a respect nulls) over (order by b) FROM t",
+            "SELECT first_value(a) RESPECT NULLS OVER (ORDER BY b) FROM t",
+        );
+        // rejected by the planner
+        verified_stmt("SELECT f() IGNORE NULLS FROM t");
+        verified_stmt("SELECT \"ignore\" AS nulls FROM t");
+        // comments are kept
+        one_statement_parses_to(
+            "SELECT lag(a) /* skip */ IGNORE NULLS OVER () -- nulls\nFROM t",
+            "SELECT lag(a) IGNORE NULLS OVER () FROM t",
+        );
+
+        expect_parse_error(
+            "SELECT a IGNORE NULLS FROM t",
+            "Expected end of statement, found: NULLS",
+        );
+    }
+
+    #[test]
+    fn within_group() {
+        verified_stmt("SELECT percentile_cont(0.5) WITHIN GROUP (ORDER BY a) FROM t");
+        one_statement_parses_to(
+            "SELECT percentile_disc(array[0.25, 0.75]) within group (order by a + 1 asc nulls last) FROM t",
+            "SELECT percentile_disc(ARRAY[0.25, 0.75]) WITHIN GROUP (ORDER BY a + 1 ASC NULLS LAST) FROM t",
+        );
+        verified_stmt(
+            "SELECT mode() WITHIN GROUP (ORDER BY f(a, b) DESC) OVER (PARTITION BY c) FROM t",
+        );
+        // rejected by the planner
+        verified_stmt("SELECT mode() WITHIN GROUP (ORDER BY a, b) FROM t");
+        // LISTAGG is parsed into its own expression
+        verified_stmt("SELECT LISTAGG(a, ',') WITHIN GROUP (ORDER BY b) FROM t");
+        // nested in other clauses
+        verified_stmt(
+            "SELECT mode() WITHIN GROUP (ORDER BY lag(a) IGNORE NULLS OVER ()) FROM t UNPIVOT(a FOR b IN (c))",
+        );
+
+        expect_parse_error(
+            "SELECT mode() WITHIN GROUP (a) FROM t",
+            "Expected ORDER, found: a",
+        );
+    }
+
     fn object_name(name: &str) -> CopyToSource {
         CopyToSource::Relation(ObjectName(vec![Ident::new(name)]))
     }
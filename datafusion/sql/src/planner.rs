@@ -29,12 +29,14 @@ use sqlparser::ast::{ColumnDef as SQLColumnDef, ColumnOption};
 use sqlparser::ast::{DataType as SQLDataType, Ident, ObjectName, TableAlias};
 
 use datafusion_common::config::ConfigOptions;
-use datafusion_common::{unqualified_field_not_found, DFSchema, DataFusionError, Result};
+use datafusion_common::{
+    unqualified_field_not_found, DFSchema, DFSchemaRef, DataFusionError, Result,
+};
 use datafusion_common::{OwnedTableReference, TableReference};
 use datafusion_expr::logical_plan::{LogicalPlan, LogicalPlanBuilder};
 use datafusion_expr::utils::find_column_exprs;
 use datafusion_expr::TableSource;
-use datafusion_expr::{col, AggregateUDF, Expr, ScalarUDF};
+use datafusion_expr::{col, AggregateUDF, Expr, ScalarMacro, ScalarUDF};
 
 use crate::utils::make_decimal_type;
 
@@ -49,11 +51,29 @@ pub trait ContextProvider {
     fn get_aggregate_meta(&self, name: &str) -> Option<Arc<AggregateUDF>>;
     /// Getter for a UDWF
     fn get_window_meta(&self, name: &str) -> Option<Arc<WindowUDF>>;
+    /// Getter for a function created by `CREATE FUNCTION`
+    fn get_scalar_macro(&self, _name: &str) -> Option<Arc<ScalarMacro>> {
+        None
+    }
     /// Getter for system/user-defined variable type
     fn get_variable_type(&self, variable_names: &[String]) -> Option<DataType>;
 
     /// Get configuration options
     fn options(&self) -> &ConfigOptions;
+
+    /// Create a source for the working table of the recursive CTE `name`.
+    ///
+    /// The returned source is scanned by the recursive term of the CTE and
+    /// yields the rows produced by the previous iteration.
+    fn create_cte_work_table(
+        &self,
+        _name: &str,
+        _schema: SchemaRef,
+    ) -> Result<Arc<dyn TableSource>> {
+        Err(DataFusionError::NotImplemented(
+            "Recursive CTEs are not supported by this context provider".to_string(),
+        ))
+    }
 }
 
 /// SQL parser options
@@ -113,6 +133,9 @@ pub struct PlannerContext {
     ctes: HashMap<String, Arc<LogicalPlan>>,
     /// The query schema of the outer query plan, used to resolve the columns in subquery
     outer_query_schema: Option<DFSchema>,
+    /// The joined schemas of the relations planned so far in the current FROM clause,
+    /// used to resolve the columns of the preceding relations in `LATERAL` subqueries
+    outer_from_schema: Option<DFSchemaRef>,
 }
 
 impl Default for PlannerContext {
@@ -128,6 +151,7 @@ impl PlannerContext {
             prepare_param_data_types: vec![],
             ctes: HashMap::new(),
             outer_query_schema: None,
+            outer_from_schema: None,
         }
     }
 
@@ -155,6 +179,29 @@ impl PlannerContext {
         schema
     }
 
+    /// return the schema of the relations preceding the one being planned in
+    /// the current FROM clause, if any
+    pub fn outer_from_schema(&self) -> Option<DFSchemaRef> {
+        self.outer_from_schema.clone()
+    }
+
+    /// sets the outer FROM schema, returning the existing one, if any
+    pub fn set_outer_from_schema(
+        &mut self,
+        mut schema: Option<DFSchemaRef>,
+    ) -> Option<DFSchemaRef> {
+        std::mem::swap(&mut self.outer_from_schema, &mut schema);
+        schema
+    }
+
+    /// extends the outer FROM schema with the fields of `schema`
+    pub fn extend_outer_from_schema(&mut self, schema: &DFSchemaRef) {
+        match self.outer_from_schema.as_mut() {
+            Some(from_schema) => Arc::make_mut(from_schema).merge(schema),
+            None => self.outer_from_schema = Some(schema.clone()),
+        }
+    }
+
     /// Return the types of parameters (`$1`, `$2`, etc) if known
     pub fn prepare_param_data_types(&self) -> &[DataType] {
         &self.prepare_param_data_types
@@ -17,14 +17,18 @@
 
 use std::sync::Arc;
 
+use arrow_schema::Schema;
+
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
 
+use datafusion_common::tree_node::{TreeNode, VisitRecursion};
 use datafusion_common::{Constraints, DataFusionError, Result, ScalarValue};
 use datafusion_expr::{
     CreateMemoryTable, DdlStatement, Expr, LogicalPlan, LogicalPlanBuilder,
 };
 use sqlparser::ast::{
-    Expr as SQLExpr, Offset as SQLOffset, OrderByExpr, Query, SetExpr, Value,
+    Expr as SQLExpr, Offset as SQLOffset, OrderByExpr, Query, SetExpr, SetOperator,
+    SetQuantifier, TableAlias, Value,
 };
 
 use sqlparser::parser::ParserError::ParserError;
@@ -50,13 +54,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         let set_expr = query.body;
         if let Some(with) = query.with {
             // Process CTEs from top to bottom
-            // do not allow self-references
-            if with.recursive {
-                return Err(DataFusionError::NotImplemented(
-                    "Recursive CTEs are not supported".to_string(),
-                ));
-            }
-
+            // self-references are only allowed in `WITH RECURSIVE`
             for cte in with.cte_tables {
                 // A `WITH` block can't use the same name more than once
                 let cte_name = self.normalizer.normalize(cte.alias.name.clone());
@@ -65,14 +63,23 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                         "WITH query name {cte_name:?} specified more than once"
                     ))));
                 }
-                // create logical plan & pass backreferencing CTEs
-                // CTE expr don't need extend outer_query_schema
-                let logical_plan =
-                    self.query_to_plan(*cte.query, &mut planner_context.clone())?;
+                let logical_plan = if with.recursive {
+                    self.recursive_cte_to_plan(
+                        &cte_name,
+                        *cte.query,
+                        cte.alias,
+                        planner_context,
+                    )?
+                } else {
+                    // create logical plan & pass backreferencing CTEs
+                    // CTE expr don't need extend outer_query_schema
+                    let logical_plan =
+                        self.query_to_plan(*cte.query, &mut planner_context.clone())?;
 
-                // Each `WITH` block can change the column names in the last
-                // projection (e.g. "WITH table(t1, t2) AS SELECT 1, 2").
-                let logical_plan = self.apply_table_alias(logical_plan, cte.alias)?;
+                    // Each `WITH` block can change the column names in the last
+                    // projection (e.g. "WITH table(t1, t2) AS SELECT 1, 2").
+                    self.apply_table_alias(logical_plan, cte.alias)?
+                };
 
                 planner_context.insert_cte(cte_name, logical_plan);
             }
@@ -98,6 +105,116 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         Ok(plan)
     }
 
+    /// Generate a logical plan for a common table expression of a
+    /// `WITH RECURSIVE` clause.
+    ///
+    /// A recursive CTE has the form `static_term UNION [ALL] recursive_term`,
+    /// where only the recursive term may reference the CTE itself. Such
+    /// references are planned as scans of a working table that holds the rows
+    /// produced by the previous iteration. A CTE that does not reference
+    /// itself is planned like a regular CTE.
+    fn recursive_cte_to_plan(
+        &self,
+        cte_name: &str,
+        query: Query,
+        alias: TableAlias,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let is_union = matches!(
+            query.body.as_ref(),
+            SetExpr::SetOperation {
+                op: SetOperator::Union,
+                ..
+            }
+        );
+        if !is_union || query.with.is_some() {
+            let logical_plan = self.query_to_plan(query, &mut planner_context.clone())?;
+            return self.apply_table_alias(logical_plan, alias);
+        }
+
+        let (left, right, set_quantifier) = match *query.body {
+            SetExpr::SetOperation {
+                left,
+                right,
+                set_quantifier,
+                ..
+            } => (left, right, set_quantifier),
+            _ => {
+                return Err(DataFusionError::Internal(
+                    "Expected a UNION in recursive CTE".to_string(),
+                ))
+            }
+        };
+        let distinct = match set_quantifier {
+            SetQuantifier::All => false,
+            SetQuantifier::Distinct | SetQuantifier::None => true,
+            SetQuantifier::ByName | SetQuantifier::AllByName => {
+                return Err(DataFusionError::NotImplemented(
+                    "UNION BY NAME is not supported in recursive CTEs".to_string(),
+                ))
+            }
+        };
+
+        // The static term can not reference the CTE itself, so it is planned
+        // before the working table is registered under the CTE name.
+        let static_plan = self.set_expr_to_plan(*left, &mut planner_context.clone())?;
+        let static_plan = self.apply_expr_alias(static_plan, alias.columns)?;
+
+        // The recursive term may produce nulls in columns that are not
+        // nullable in the static term, so all columns of the work table are
+        // nullable
+        let work_table_fields = static_plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.field().as_ref().clone().with_nullable(true))
+            .collect::<Vec<_>>();
+        let work_table_source = self
+            .schema_provider
+            .create_cte_work_table(cte_name, Arc::new(Schema::new(work_table_fields)))?;
+        let work_table_plan = LogicalPlanBuilder::scan(
+            cte_name.to_string(),
+            work_table_source.clone(),
+            None,
+        )?
+        .build()?;
+
+        let mut recursive_context = planner_context.clone();
+        recursive_context.insert_cte(cte_name, work_table_plan);
+        let recursive_plan = self.set_expr_to_plan(*right, &mut recursive_context)?;
+
+        let mut is_recursive = false;
+        recursive_plan.apply(&mut |plan| {
+            if let LogicalPlan::TableScan(scan) = plan {
+                if Arc::ptr_eq(&scan.source, &work_table_source) {
+                    is_recursive = true;
+                    return Ok(VisitRecursion::Stop);
+                }
+            }
+            Ok(VisitRecursion::Continue)
+        })?;
+
+        let builder = LogicalPlanBuilder::from(static_plan);
+        let plan = if !is_recursive {
+            // No self-reference: this is a plain UNION
+            if distinct {
+                builder.union_distinct(recursive_plan)?.build()?
+            } else {
+                builder.union(recursive_plan)?.build()?
+            }
+        } else {
+            builder
+                .to_recursive_query(cte_name.to_string(), recursive_plan, distinct)?
+                .build()?
+        };
+
+        let plan = self.order_by(plan, query.order_by, planner_context)?;
+        let plan = self.limit(plan, query.offset, query.limit)?;
+        LogicalPlanBuilder::from(plan)
+            .alias(self.normalizer.normalize(alias.name))?
+            .build()
+    }
+
     /// Wrap a plan in a limit
     fn limit(
         &self,
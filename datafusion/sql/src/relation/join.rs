@@ -17,8 +17,14 @@
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
 use datafusion_common::{Column, DataFusionError, Result};
-use datafusion_expr::{JoinType, LogicalPlan, LogicalPlanBuilder};
-use sqlparser::ast::{Join, JoinConstraint, JoinOperator, TableWithJoins};
+use datafusion_expr::expr_rewriter::normalize_col_with_schemas_and_ambiguity_check;
+use datafusion_expr::utils::find_valid_equijoin_key_pair;
+use datafusion_expr::{
+    BinaryExpr, Expr, JoinType, LogicalPlan, LogicalPlanBuilder, Operator,
+};
+use sqlparser::ast::{
+    BinaryOperator, Expr as SQLExpr, Join, JoinConstraint, JoinOperator, TableWithJoins,
+};
 use std::collections::HashSet;
 
 impl<'a, S: ContextProvider> SqlToRel<'a, S> {
@@ -63,7 +69,27 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         join: Join,
         planner_context: &mut PlannerContext,
     ) -> Result<LogicalPlan> {
-        let right = self.create_relation(join.relation, planner_context)?;
+        // the left side of the join can be referenced by a LATERAL subquery
+        let old_outer_from_schema = planner_context.outer_from_schema();
+        planner_context.extend_outer_from_schema(left.schema());
+        let right = self.create_relation(join.relation, planner_context);
+        planner_context.set_outer_from_schema(old_outer_from_schema);
+        let right = right?;
+
+        if matches!(right, LogicalPlan::Subquery(_))
+            && !matches!(
+                join.join_operator,
+                JoinOperator::Inner(_)
+                    | JoinOperator::LeftOuter(_)
+                    | JoinOperator::CrossJoin
+            )
+        {
+            return Err(DataFusionError::Plan(
+                "The combining JOIN type must be INNER or LEFT for a LATERAL reference"
+                    .to_string(),
+            ));
+        }
+
         match join.join_operator {
             JoinOperator::LeftOuter(constraint) => {
                 self.parse_join(left, right, constraint, JoinType::Left, planner_context)
@@ -106,6 +132,16 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 self.parse_join(left, right, constraint, JoinType::Full, planner_context)
             }
             JoinOperator::CrossJoin => self.parse_cross_join(left, right),
+            JoinOperator::AsOf {
+                match_condition,
+                constraint,
+            } => self.parse_asof_join(
+                left,
+                right,
+                match_condition,
+                constraint,
+                planner_context,
+            ),
             other => Err(DataFusionError::NotImplemented(format!(
                 "Unsupported JOIN operator {other:?}"
             ))),
@@ -179,4 +215,82 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             )),
         }
     }
+    /// Plans an ASOF join from its match condition and the equality
+    /// predicates of its ON clause
+    fn parse_asof_join(
+        &self,
+        left: LogicalPlan,
+        right: LogicalPlan,
+        condition: SQLExpr,
+        constraint: JoinConstraint,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let join_schema = left.schema().join(right.schema())?;
+        let schemas = [left.schema().as_ref(), right.schema().as_ref()];
+        let mut plan_expr = |sql_expr| {
+            let expr = self.sql_to_expr(sql_expr, &join_schema, planner_context)?;
+            normalize_col_with_schemas_and_ambiguity_check(expr, &[&schemas], &[])
+        };
+        let match_condition = plan_expr(condition)?;
+        let mut predicates = vec![];
+        match constraint {
+            JoinConstraint::On(sql_expr) => conjuncts(sql_expr, &mut predicates),
+            JoinConstraint::None => {}
+            JoinConstraint::Using(_) | JoinConstraint::Natural => {
+                return Err(DataFusionError::Plan(
+                    "ASOF JOIN does not support USING, use ON instead".to_string(),
+                ))
+            }
+        }
+
+        let mut left_keys = vec![];
+        let mut right_keys = vec![];
+        for predicate in predicates {
+            let expr = plan_expr(predicate)?;
+            let key_pair = match &expr {
+                Expr::BinaryExpr(BinaryExpr {
+                    left: l,
+                    op: Operator::Eq,
+                    right: r,
+                }) => find_valid_equijoin_key_pair(
+                    l,
+                    r,
+                    left.schema().clone(),
+                    right.schema().clone(),
+                )?,
+                _ => None,
+            };
+            let Some((left_key, right_key)) = key_pair else {
+                return Err(DataFusionError::Plan(format!(
+                    "ASOF JOIN ON clause only supports equality predicates between the joined relations, got {expr}"
+                )));
+            };
+            left_keys.push(left_key);
+            right_keys.push(right_key);
+        }
+
+        LogicalPlanBuilder::from(left)
+            .join_with_expr_keys(
+                right,
+                JoinType::AsOf,
+                (left_keys, right_keys),
+                Some(match_condition),
+            )?
+            .build()
+    }
+}
+
+/// Appends the conjuncts of `expr` to `exprs`
+fn conjuncts(expr: SQLExpr, exprs: &mut Vec<SQLExpr>) {
+    match expr {
+        SQLExpr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            conjuncts(*left, exprs);
+            conjuncts(*right, exprs);
+        }
+        _ => exprs.push(expr),
+    }
 }
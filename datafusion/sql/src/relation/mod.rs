@@ -16,9 +16,16 @@
 // under the License.
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
-use datafusion_common::{DataFusionError, Result};
-use datafusion_expr::{LogicalPlan, LogicalPlanBuilder};
-use sqlparser::ast::TableFactor;
+use std::sync::Arc;
+
+use datafusion_common::{Column, DFSchema, DataFusionError, Result, ScalarValue};
+use datafusion_expr::{
+    Expr, LogicalPlan, LogicalPlanBuilder, SampleMethod, SampleSize, Subquery,
+};
+use sqlparser::ast::{
+    Expr as SQLExpr, Ident, NullInclusion, Query, TableAlias, TableFactor, TableSample,
+    TableSampleUnit, Value,
+};
 
 mod join;
 
@@ -30,30 +37,45 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         planner_context: &mut PlannerContext,
     ) -> Result<LogicalPlan> {
         let (plan, alias) = match relation {
-            TableFactor::Table { name, alias, .. } => {
+            TableFactor::Table {
+                name,
+                alias,
+                sample,
+                ..
+            } => {
                 // normalize name and alias
                 let table_ref = self.object_name_to_table_reference(name)?;
                 let table_name = table_ref.to_string();
                 let cte = planner_context.get_cte(&table_name);
-                (
-                    match (
-                        cte,
-                        self.schema_provider.get_table_provider(table_ref.clone()),
-                    ) {
-                        (Some(cte_plan), _) => Ok(cte_plan.clone()),
-                        (_, Ok(provider)) => {
-                            LogicalPlanBuilder::scan(table_ref, provider, None)?.build()
-                        }
-                        (None, Err(e)) => Err(e),
-                    }?,
-                    alias,
-                )
+                let plan = match (
+                    cte,
+                    self.schema_provider.get_table_provider(table_ref.clone()),
+                ) {
+                    (Some(cte_plan), _) => Ok(cte_plan.clone()),
+                    (_, Ok(provider)) => {
+                        LogicalPlanBuilder::scan(table_ref, provider, None)?.build()
+                    }
+                    (None, Err(e)) => Err(e),
+                }?;
+                let plan = match sample {
+                    Some(sample) => self.table_sample(plan, sample)?,
+                    None => plan,
+                };
+                (plan, alias)
             }
+            TableFactor::Derived {
+                lateral: true,
+                subquery,
+                alias,
+            } => return self.create_lateral_relation(*subquery, alias, planner_context),
             TableFactor::Derived {
                 subquery, alias, ..
             } => {
-                let logical_plan = self.query_to_plan(*subquery, planner_context)?;
-                (logical_plan, alias)
+                // a derived table can't reference the preceding relations unless it is LATERAL
+                let old_outer_from_schema = planner_context.set_outer_from_schema(None);
+                let logical_plan = self.query_to_plan(*subquery, planner_context);
+                planner_context.set_outer_from_schema(old_outer_from_schema);
+                (logical_plan?, alias)
             }
             TableFactor::NestedJoin {
                 table_with_joins,
@@ -62,6 +84,56 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 self.plan_table_with_joins(*table_with_joins, planner_context)?,
                 alias,
             ),
+            TableFactor::Pivot {
+                name,
+                table_alias,
+                aggregate_function,
+                value_column,
+                pivot_values,
+                pivot_alias,
+            } => {
+                let table = TableFactor::Table {
+                    name,
+                    alias: table_alias,
+                    args: None,
+                    with_hints: vec![],
+                    sample: None,
+                };
+                let plan = self.create_relation(table, planner_context)?;
+                (
+                    self.pivot_to_plan(
+                        plan,
+                        aggregate_function,
+                        value_column,
+                        pivot_values,
+                        planner_context,
+                    )?,
+                    pivot_alias,
+                )
+            }
+            TableFactor::Unpivot {
+                table,
+                null_inclusion,
+                value,
+                name,
+                columns,
+                alias,
+            } => {
+                let plan = self.create_relation(*table, planner_context)?;
+                let columns: Vec<_> = columns
+                    .into_iter()
+                    .map(|column| Column::from_name(self.normalizer.normalize(column)))
+                    .collect();
+                let plan = LogicalPlanBuilder::from(plan)
+                    .unpivot(
+                        self.normalizer.normalize(value),
+                        self.normalizer.normalize(name),
+                        columns,
+                        null_inclusion == Some(NullInclusion::IncludeNulls),
+                    )?
+                    .build()?;
+                (plan, alias)
+            }
             // @todo Support TableFactory::TableFunction?
             _ => {
                 return Err(DataFusionError::NotImplemented(format!(
@@ -75,4 +147,160 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             Ok(plan)
         }
     }
+
+    /// Wrap `plan` in the sample of a `TABLESAMPLE` clause
+    fn table_sample(
+        &self,
+        plan: LogicalPlan,
+        sample: TableSample,
+    ) -> Result<LogicalPlan> {
+        let literal = |expr: SQLExpr| match self.sql_to_expr(
+            expr,
+            &DFSchema::empty(),
+            &mut PlannerContext::new(),
+        )? {
+            Expr::Literal(value) => Ok(value),
+            other => Err(DataFusionError::Plan(format!(
+                "TABLESAMPLE arguments must be literals, got {other}"
+            ))),
+        };
+
+        let method = match sample.method.value.to_uppercase().as_str() {
+            "BERNOULLI" => SampleMethod::Bernoulli,
+            "SYSTEM" => SampleMethod::System,
+            _ => {
+                return Err(DataFusionError::Plan(format!(
+                    "Unsupported TABLESAMPLE method {}, expected BERNOULLI or SYSTEM",
+                    sample.method.value.to_uppercase()
+                )))
+            }
+        };
+        let size = literal(sample.size)?;
+        let size = match sample.unit {
+            Some(TableSampleUnit::Rows) => match size {
+                ScalarValue::Int64(Some(rows)) if rows >= 0 => {
+                    SampleSize::Rows(rows as u64)
+                }
+                _ => {
+                    return Err(DataFusionError::Plan(
+                        "TABLESAMPLE row count must be a non-negative integer"
+                            .to_string(),
+                    ))
+                }
+            },
+            Some(TableSampleUnit::Percent) | None => match size {
+                ScalarValue::Int64(Some(p)) => SampleSize::Percent(p as f64),
+                ScalarValue::Float64(Some(p)) => SampleSize::Percent(p),
+                ScalarValue::Decimal128(Some(p), _, scale) => {
+                    SampleSize::Percent(p as f64 / 10_f64.powi(scale as i32))
+                }
+                _ => {
+                    return Err(DataFusionError::Plan(
+                        "TABLESAMPLE percentage must be a number".to_string(),
+                    ))
+                }
+            },
+        };
+        let seed = match sample.seed.map(literal).transpose()? {
+            None => None,
+            Some(ScalarValue::Int64(Some(seed))) => Some(seed as u64),
+            Some(_) => {
+                return Err(DataFusionError::Plan(
+                    "REPEATABLE seed must be an integer".to_string(),
+                ))
+            }
+        };
+
+        LogicalPlanBuilder::from(plan)
+            .sample(method, size, seed)?
+            .build()
+    }
+
+    /// Create a `LogicalPlan` for `PIVOT(aggregate_function FOR value_column IN (pivot_values))`
+    /// over `plan`
+    fn pivot_to_plan(
+        &self,
+        plan: LogicalPlan,
+        aggregate_function: SQLExpr,
+        value_column: Vec<Ident>,
+        pivot_values: Vec<Value>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let schema = plan.schema().clone();
+        let aggr_expr = self.sql_to_expr(aggregate_function, &schema, planner_context)?;
+        let value_column = match value_column.len() {
+            1 => SQLExpr::Identifier(value_column[0].clone()),
+            _ => SQLExpr::CompoundIdentifier(value_column),
+        };
+        let value_column =
+            match self.sql_to_expr(value_column, &schema, planner_context)? {
+                Expr::Column(column) => column,
+                expr => {
+                    return Err(DataFusionError::Plan(format!(
+                        "PIVOT expects a column, got {expr}"
+                    )))
+                }
+            };
+        let pivot_values = pivot_values
+            .into_iter()
+            .map(|value| {
+                match self.sql_to_expr(SQLExpr::Value(value), &schema, planner_context)? {
+                    Expr::Literal(value) => Ok(value),
+                    expr => Err(DataFusionError::Plan(format!(
+                        "PIVOT expects literal values, got {expr}"
+                    ))),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        LogicalPlanBuilder::from(plan)
+            .pivot(aggr_expr, value_column, pivot_values)?
+            .build()
+    }
+
+    /// Create a `LogicalPlan` for a `LATERAL` subquery, which can reference the
+    /// columns of the relations preceding it in the FROM clause.
+    ///
+    /// If the subquery references any of those columns, the returned plan is a
+    /// [`LogicalPlan::Subquery`] that has to be the right input of the join with
+    /// the preceding relations.
+    fn create_lateral_relation(
+        &self,
+        subquery: Query,
+        alias: Option<TableAlias>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let outer_query_schema = match (
+            planner_context.outer_from_schema(),
+            planner_context.outer_query_schema(),
+        ) {
+            (Some(from_schema), Some(query_schema)) => {
+                let mut schema = from_schema.as_ref().clone();
+                schema.merge(query_schema);
+                Some(schema)
+            }
+            (Some(from_schema), None) => Some(from_schema.as_ref().clone()),
+            (None, query_schema) => query_schema.cloned(),
+        };
+        let old_outer_query_schema =
+            planner_context.set_outer_query_schema(outer_query_schema);
+        let old_outer_from_schema = planner_context.set_outer_from_schema(None);
+        let plan = self.query_to_plan(subquery, planner_context);
+        planner_context.set_outer_from_schema(old_outer_from_schema);
+        planner_context.set_outer_query_schema(old_outer_query_schema);
+
+        let plan = match alias {
+            Some(alias) => self.apply_table_alias(plan?, alias)?,
+            None => plan?,
+        };
+        let outer_ref_columns = plan.all_out_ref_exprs();
+        if outer_ref_columns.is_empty() {
+            Ok(plan)
+        } else {
+            Ok(LogicalPlan::Subquery(Subquery {
+                subquery: Arc::new(plan),
+                outer_ref_columns,
+            }))
+        }
+    }
 }
@@ -42,6 +42,10 @@ use datafusion_expr::{
 use sqlparser::ast::{Distinct, Expr as SQLExpr, WildcardAdditionalOptions, WindowType};
 use sqlparser::ast::{NamedWindowDefinition, Select, SelectItem, TableWithJoins};
 
+/// The aggregate plan along with the SELECT, HAVING and QUALIFY expressions
+/// rewritten to reference its output, see [`SqlToRel::aggregate`]
+type AggregatePlanResult = (LogicalPlan, Vec<Expr>, Option<Expr>, Option<Expr>);
+
 impl<'a, S: ContextProvider> SqlToRel<'a, S> {
     /// Generate a logic plan from an SQL select
     pub(super) fn select_to_plan(
@@ -56,9 +60,6 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         if !select.lateral_views.is_empty() {
             return Err(DataFusionError::NotImplemented("LATERAL VIEWS".to_string()));
         }
-        if select.qualify.is_some() {
-            return Err(DataFusionError::NotImplemented("QUALIFY".to_string()));
-        }
         if select.top.is_some() {
             return Err(DataFusionError::NotImplemented("TOP".to_string()));
         }
@@ -120,13 +121,32 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             })
             .transpose()?;
 
+        // Optionally the QUALIFY expression, which filters on the results of
+        // window functions. Like HAVING, it may refer to aliased columns.
+        let qualify_expr_opt = select
+            .qualify
+            .map::<Result<Expr>, _>(|qualify_expr| {
+                let qualify_expr = self.sql_expr_to_logical_expr(
+                    qualify_expr,
+                    &combined_schema,
+                    planner_context,
+                )?;
+                let qualify_expr = resolve_aliases_to_exprs(&qualify_expr, &alias_map)?;
+                normalize_col(qualify_expr, &projected_plan)
+            })
+            .transpose()?;
+
         // The outer expressions we will search through for
         // aggregates. Aggregates may be sourced from the SELECT...
         let mut aggr_expr_haystack = select_exprs.clone();
-        // ... or from the HAVING.
+        // ... or from the HAVING ...
         if let Some(having_expr) = &having_expr_opt {
             aggr_expr_haystack.push(having_expr.clone());
         }
+        // ... or from the QUALIFY.
+        if let Some(qualify_expr) = &qualify_expr_opt {
+            aggr_expr_haystack.push(qualify_expr.clone());
+        }
 
         // All of the aggregate expressions (deduplicated).
         let aggr_exprs = find_aggregate_exprs(&aggr_expr_haystack);
@@ -157,14 +177,17 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             .collect::<Result<Vec<Expr>>>()?;
 
         // process group by, aggregation or having
-        let (plan, mut select_exprs_post_aggr, having_expr_post_aggr) = if !group_by_exprs
-            .is_empty()
-            || !aggr_exprs.is_empty()
-        {
+        let (
+            plan,
+            mut select_exprs_post_aggr,
+            having_expr_post_aggr,
+            qualify_expr_post_aggr,
+        ) = if !group_by_exprs.is_empty() || !aggr_exprs.is_empty() {
             self.aggregate(
                 plan,
                 &select_exprs,
                 having_expr_opt.as_ref(),
+                qualify_expr_opt.as_ref(),
                 group_by_exprs,
                 aggr_exprs,
             )?
@@ -172,7 +195,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             match having_expr_opt {
                 Some(having_expr) => return Err(DataFusionError::Plan(
                     format!("HAVING clause references: {having_expr} must appear in the GROUP BY clause or be used in an aggregate function"))),
-                None => (plan, select_exprs, having_expr_opt)
+                None => (plan, select_exprs, having_expr_opt, qualify_expr_opt)
             }
         };
 
@@ -184,8 +207,15 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             plan
         };
 
-        // process window function
-        let window_func_exprs = find_window_exprs(&select_exprs_post_aggr);
+        // process window function, including the ones only used by QUALIFY
+        let mut window_func_exprs = find_window_exprs(&select_exprs_post_aggr);
+        if let Some(qualify_expr) = &qualify_expr_post_aggr {
+            for expr in find_window_exprs(std::slice::from_ref(qualify_expr)) {
+                if !window_func_exprs.contains(&expr) {
+                    window_func_exprs.push(expr);
+                }
+            }
+        }
 
         let plan = if window_func_exprs.is_empty() {
             plan
@@ -201,6 +231,21 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             plan
         };
 
+        // process the QUALIFY clause, filtering on the window function results
+        let plan = if let Some(qualify_expr) = qualify_expr_post_aggr {
+            if window_func_exprs.is_empty() {
+                return Err(DataFusionError::Plan(format!(
+                    "QUALIFY clause references: {qualify_expr} requires a window function in the SELECT list or the QUALIFY clause"
+                )));
+            }
+            let qualify_expr = rebase_expr(&qualify_expr, &window_func_exprs, &plan)?;
+            LogicalPlanBuilder::from(plan)
+                .filter(qualify_expr)?
+                .build()?
+        } else {
+            plan
+        };
+
         // final projection
         let plan = project(plan, select_exprs_post_aggr)?;
 
@@ -291,16 +336,20 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 self.plan_table_with_joins(from, planner_context)
             }
             _ => {
-                let mut plans = from
-                    .into_iter()
-                    .map(|t| self.plan_table_with_joins(t, planner_context));
-
-                let mut left = LogicalPlanBuilder::from(plans.next().unwrap()?);
-
-                for right in plans {
-                    left = left.cross_join(right?)?;
-                }
-                Ok(left.build()?)
+                let mut from = from.into_iter();
+                let left = LogicalPlanBuilder::from(
+                    self.plan_table_with_joins(from.next().unwrap(), planner_context)?,
+                );
+
+                let old_outer_from_schema = planner_context.outer_from_schema();
+                let plan = from.try_fold(left, |left, t| {
+                    // the relations planned so far can be referenced by LATERAL subqueries
+                    planner_context.extend_outer_from_schema(left.schema());
+                    let right = self.plan_table_with_joins(t, planner_context)?;
+                    left.cross_join(right)
+                });
+                planner_context.set_outer_from_schema(old_outer_from_schema);
+                Ok(plan?.build()?)
             }
         }
     }
@@ -413,27 +462,31 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
     ///                       "having" expressions must all be resolvable from this plan.
     /// * `select_exprs`    - The projection expressions from the SELECT clause.
     /// * `having_expr_opt` - Optional HAVING clause.
+    /// * `qualify_expr_opt` - Optional QUALIFY clause.
     /// * `group_by_exprs`  - Grouping expressions from the GROUP BY clause. These can be column
     ///                       references or more complex expressions.
     /// * `aggr_exprs`      - Aggregate expressions, such as `SUM(a)` or `COUNT(1)`.
     ///
     /// # Return
     ///
-    /// The return value is a triplet of the following items:
+    /// The return value is a tuple of the following items:
     ///
     /// * `plan`                   - A [LogicalPlan::Aggregate] plan for the newly created aggregate.
     /// * `select_exprs_post_aggr` - The projection expressions rewritten to reference columns from
-    ///                              the aggregate
+    ///   the aggregate
     /// * `having_expr_post_aggr`  - The "having" expression rewritten to reference a column from
-    ///                              the aggregate
+    ///   the aggregate
+    /// * `qualify_expr_post_aggr` - The "qualify" expression rewritten to reference a column from
+    ///   the aggregate
     fn aggregate(
         &self,
         input: LogicalPlan,
         select_exprs: &[Expr],
         having_expr_opt: Option<&Expr>,
+        qualify_expr_opt: Option<&Expr>,
         group_by_exprs: Vec<Expr>,
         aggr_exprs: Vec<Expr>,
-    ) -> Result<(LogicalPlan, Vec<Expr>, Option<Expr>)> {
+    ) -> Result<AggregatePlanResult> {
         let group_by_exprs =
             get_updated_group_by_exprs(&group_by_exprs, select_exprs, input.schema())?;
 
@@ -512,7 +565,29 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             None
         };
 
-        Ok((plan, select_exprs_post_aggr, having_expr_post_aggr))
+        // Rewrite the QUALIFY expression to use the columns produced by the
+        // aggregation.
+        let qualify_expr_post_aggr = if let Some(qualify_expr) = qualify_expr_opt {
+            let qualify_expr_post_aggr =
+                rebase_expr(qualify_expr, &aggr_projection_exprs, &input)?;
+
+            check_columns_satisfy_exprs(
+                &column_exprs_post_aggr,
+                std::slice::from_ref(&qualify_expr_post_aggr),
+                "QUALIFY clause references non-aggregate values",
+            )?;
+
+            Some(qualify_expr_post_aggr)
+        } else {
+            None
+        };
+
+        Ok((
+            plan,
+            select_exprs_post_aggr,
+            having_expr_post_aggr,
+            qualify_expr_post_aggr,
+        ))
     }
 }
 
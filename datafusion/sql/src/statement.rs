@@ -15,51 +15,85 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::expr::arrow_cast::ARROW_CAST_NAME;
 use crate::parser::{
-    CopyToStatement, CreateExternalTable, DFParser, DescribeTableStmt, LexOrdering,
-    Statement as DFStatement,
+    CopyToSource, CopyToStatement, CreateExternalTable, DFParser, DescribeTableStmt,
+    LexOrdering, Statement as DFStatement,
 };
 use crate::planner::{
     object_name_to_qualifier, ContextProvider, PlannerContext, SqlToRel,
 };
 use crate::utils::normalize_ident;
 
+use arrow::compute::can_cast_types;
 use arrow_schema::DataType;
 use datafusion_common::parsers::CompressionTypeVariant;
 use datafusion_common::{
     unqualified_field_not_found, Column, Constraints, DFField, DFSchema, DFSchemaRef,
-    DataFusionError, ExprSchema, OwnedTableReference, Result, SchemaReference,
-    TableReference, ToDFSchema,
+    DataFusionError, ExprSchema, OwnedTableReference, Result, ScalarValue,
+    SchemaReference, TableReference, ToDFSchema,
 };
-use datafusion_expr::expr::Placeholder;
+use datafusion_expr::expr::{Case, Placeholder};
 use datafusion_expr::expr_rewriter::normalize_col_with_schemas_and_ambiguity_check;
 use datafusion_expr::logical_plan::builder::project;
 use datafusion_expr::logical_plan::DdlStatement;
 use datafusion_expr::utils::expr_to_columns;
+use datafusion_expr::window_function::find_df_window_func;
 use datafusion_expr::{
-    cast, col, Analyze, CreateCatalog, CreateCatalogSchema,
-    CreateExternalTable as PlanCreateExternalTable, CreateMemoryTable, CreateView,
-    DescribeTable, DmlStatement, DropCatalogSchema, DropTable, DropView, EmptyRelation,
-    Explain, ExprSchemable, Filter, LogicalPlan, LogicalPlanBuilder, PlanType, Prepare,
-    SetVariable, Statement as PlanStatement, ToStringifiedPlan, TransactionAccessMode,
-    TransactionConclusion, TransactionEnd, TransactionIsolationLevel, TransactionStart,
-    WriteOp,
+    cast, col, lit, AlterTable, AlterTableOperation, Analyze, BuiltinScalarFunction,
+    CopyTo, CreateCatalog, CreateCatalogSchema,
+    CreateExternalTable as PlanCreateExternalTable, CreateFunction, CreateMemoryTable,
+    CreateView, Deallocate, DescribeTable, DmlStatement, DropCatalogSchema, DropFunction,
+    DropTable, DropView, EmptyRelation, Execute, Explain, ExprSchemable, Filter,
+    JoinType, LogicalPlan, LogicalPlanBuilder, MergeAction, OutputFileFormat, PlanType,
+    Prepare, ScalarMacro, SetVariable, Statement as PlanStatement, ToStringifiedPlan,
+    TransactionAccessMode, TransactionConclusion, TransactionEnd,
+    TransactionIsolationLevel, TransactionStart, WriteOp, MERGE_ACTION_COLUMN,
 };
 use sqlparser::ast;
 use sqlparser::ast::{
-    Assignment, Expr as SQLExpr, Expr, Ident, ObjectName, ObjectType, Query, SchemaName,
-    SetExpr, ShowCreateObject, ShowStatementFilter, Statement, TableFactor,
-    TableWithJoins, TransactionMode, UnaryOperator, Value,
+    Assignment, ColumnOption, CreateFunctionBody, Expr as SQLExpr, Expr,
+    FunctionDefinition, Ident, MergeClause, ObjectName, ObjectType, OperateFunctionArg,
+    Query, SchemaName, SetExpr, ShowCreateObject, ShowStatementFilter, Statement,
+    TableFactor, TableWithJoins, TransactionMode, UnaryOperator, Value,
 };
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::Parser;
 use sqlparser::parser::ParserError::ParserError;
+use sqlparser::tokenizer::Token;
 
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::str::FromStr;
 use std::sync::Arc;
 
+/// The names of the columns that mark the rows of the table and of the
+/// source relation of a `MERGE` statement, and that number the clause
+/// that applies to each row
+const MERGE_TARGET_MARKER: &str = "__merge_target";
+const MERGE_SOURCE_MARKER: &str = "__merge_source";
+const MERGE_CLAUSE: &str = "__merge_clause";
+/// The prefix of the names of the columns with the previous values of the
+/// rows of the table in the input of a `MERGE`
+const MERGE_PREVIOUS_PREFIX: &str = "__merge_previous_";
+/// The prefix of the names of the columns with the previous values of the
+/// updated rows in the input of an `UPDATE`
+const UPDATE_PREVIOUS_PREFIX: &str = "__update_previous_";
+
 fn ident_to_string(ident: &Ident) -> String {
     normalize_ident(ident.to_owned())
 }
 
+/// Returns the name of a function, which can't be qualified, as function
+/// calls are resolved by their name only
+fn function_name(object_name: &ObjectName) -> Result<String> {
+    match object_name.0.as_slice() {
+        [name] => Ok(normalize_ident(name.clone())),
+        _ => Err(DataFusionError::NotImplemented(format!(
+            "Unsupported qualified function name: {object_name}"
+        ))),
+    }
+}
+
 fn object_name_to_string(object_name: &ObjectName) -> String {
     object_name
         .0
@@ -69,6 +103,20 @@ fn object_name_to_string(object_name: &ObjectName) -> String {
         .join(".")
 }
 
+/// Converts the value of a `COPY TO` option to its string representation
+fn copy_option_value_to_string(key: &str, value: &Value) -> Result<String> {
+    match value {
+        Value::SingleQuotedString(s)
+        | Value::DoubleQuotedString(s)
+        | Value::UnQuotedString(s)
+        | Value::EscapedStringLiteral(s) => Ok(s.to_string()),
+        Value::Number(_, _) | Value::Boolean(_) => Ok(value.to_string()),
+        _ => Err(DataFusionError::Plan(format!(
+            "Unsupported value {value} for COPY option {key}"
+        ))),
+    }
+}
+
 fn get_schema_name(schema_name: &SchemaName) -> String {
     match schema_name {
         SchemaName::Simple(schema_name) => object_name_to_string(schema_name),
@@ -247,6 +295,96 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     schema: Arc::new(DFSchema::empty()),
                 },
             ))),
+            Statement::AlterTable { name, operation } => {
+                let name = self.object_name_to_table_reference(name)?;
+                let operation = match operation {
+                    ast::AlterTableOperation::AddColumn {
+                        if_not_exists,
+                        column_def,
+                        ..
+                    } => {
+                        if column_def.options.iter().any(|option| {
+                            !matches!(
+                                option.option,
+                                ColumnOption::Null | ColumnOption::NotNull
+                            )
+                        }) {
+                            return Err(DataFusionError::NotImplemented(format!(
+                                "Unsupported option of added column: {column_def}"
+                            )));
+                        }
+                        let schema = self.build_schema(vec![column_def])?;
+                        AlterTableOperation::AddColumn {
+                            field: schema.field(0).clone(),
+                            if_not_exists,
+                        }
+                    }
+                    ast::AlterTableOperation::DropColumn {
+                        column_name,
+                        if_exists,
+                        ..
+                    } => AlterTableOperation::DropColumn {
+                        name: self.normalizer.normalize(column_name),
+                        if_exists,
+                    },
+                    ast::AlterTableOperation::RenameColumn {
+                        old_column_name,
+                        new_column_name,
+                    } => AlterTableOperation::RenameColumn {
+                        old_name: self.normalizer.normalize(old_column_name),
+                        new_name: self.normalizer.normalize(new_column_name),
+                    },
+                    ast::AlterTableOperation::RenameTable { table_name } => {
+                        AlterTableOperation::RenameTable {
+                            new_name: self.object_name_to_table_reference(table_name)?,
+                        }
+                    }
+                    operation => {
+                        return Err(DataFusionError::NotImplemented(format!(
+                            "Unsupported ALTER TABLE operation: {operation}"
+                        )))
+                    }
+                };
+                Ok(LogicalPlan::Ddl(DdlStatement::AlterTable(AlterTable {
+                    name,
+                    operation,
+                    schema: DFSchemaRef::new(DFSchema::empty()),
+                })))
+            }
+
+            Statement::CreateFunction {
+                or_replace,
+                temporary: _,
+                name,
+                args,
+                return_type,
+                params,
+            } => self.create_function_to_plan(
+                or_replace,
+                name,
+                args.unwrap_or_default(),
+                return_type,
+                params,
+            ),
+            Statement::DropFunction {
+                if_exists,
+                mut func_desc,
+                option: _,
+            } => {
+                // the arguments are ignored, as functions can't be overloaded
+                if func_desc.len() != 1 {
+                    return Err(DataFusionError::NotImplemented(
+                        "Dropping multiple functions at once is not supported"
+                            .to_string(),
+                    ));
+                }
+                let desc = func_desc.pop().unwrap();
+                Ok(LogicalPlan::Ddl(DdlStatement::DropFunction(DropFunction {
+                    name: function_name(&desc.name)?,
+                    if_exists,
+                    schema: DFSchemaRef::new(DFSchema::empty()),
+                })))
+            }
             Statement::Drop {
                 object_type,
                 if_exists,
@@ -327,6 +465,27 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     input: Arc::new(plan),
                 }))
             }
+            Statement::Execute { name, parameters } => {
+                // The parameters are constant expressions, evaluated when the
+                // prepared statement is executed
+                let empty_schema = DFSchema::empty();
+                let parameters = parameters
+                    .into_iter()
+                    .map(|e| self.sql_to_expr(e, &empty_schema, planner_context))
+                    .collect::<Result<Vec<_>>>()?;
+
+                Ok(LogicalPlan::Statement(PlanStatement::Execute(Execute {
+                    name: ident_to_string(&name),
+                    parameters,
+                    schema: DFSchemaRef::new(empty_schema),
+                })))
+            }
+            Statement::Deallocate { name, .. } => Ok(LogicalPlan::Statement(
+                PlanStatement::Deallocate(Deallocate {
+                    name: ident_to_string(&name),
+                    schema: DFSchemaRef::new(DFSchema::empty()),
+                }),
+            )),
 
             Statement::ShowTables {
                 extended,
@@ -409,6 +568,17 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 self.update_to_plan(table, assignments, from, selection)
             }
 
+            Statement::Merge {
+                into,
+                table,
+                source,
+                on,
+                clauses,
+            } => {
+                let _ = into; // optional keyword doesn't change behavior
+                self.merge_to_plan(table, source, *on, clauses)
+            }
+
             Statement::Delete {
                 tables,
                 using,
@@ -520,10 +690,10 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 "DELETE FROM only supports single table, got: joins".to_string(),
             ));
         }
-        let TableFactor::Table{name, ..} = table_factor.relation else {
+        let TableFactor::Table { name, .. } = table_factor.relation else {
             return Err(DataFusionError::NotImplemented(format!(
                 "DELETE FROM only supports single table, got: {table_factor:?}"
-            )))
+            )));
         };
 
         Ok(name)
@@ -575,11 +745,80 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         }))
     }
 
-    fn copy_to_plan(&self, _statement: CopyToStatement) -> Result<LogicalPlan> {
-        // TODO: implement as part of https://github.com/apache/arrow-datafusion/issues/5654
-        Err(DataFusionError::NotImplemented(
-            "`COPY .. TO ..` statement is not yet supported".to_string(),
-        ))
+    fn copy_to_plan(&self, statement: CopyToStatement) -> Result<LogicalPlan> {
+        let CopyToStatement {
+            source,
+            target,
+            options,
+        } = statement;
+
+        // determine if source is table or query and handle accordingly
+        let input = match source {
+            CopyToSource::Relation(object_name) => {
+                let table_ref =
+                    self.object_name_to_table_reference(object_name.clone())?;
+                let table_source = self.schema_provider.get_table_provider(table_ref)?;
+                LogicalPlanBuilder::scan(
+                    object_name_to_string(&object_name),
+                    table_source,
+                    None,
+                )?
+                .build()?
+            }
+            CopyToSource::Query(query) => {
+                self.query_to_plan(query, &mut PlannerContext::new())?
+            }
+        };
+
+        let mut file_format = None;
+        let mut single_file_output = None;
+        let mut format_options = vec![];
+        for (key, value) in options {
+            let key = key.to_lowercase();
+            let value = copy_option_value_to_string(&key, &value)?;
+            match key.as_str() {
+                "format" => file_format = Some(value.parse::<OutputFileFormat>()?),
+                "single_file_output" => {
+                    single_file_output = Some(value.parse::<bool>().map_err(|_| {
+                        DataFusionError::Plan(format!(
+                            "Invalid value for single_file_output: {value}"
+                        ))
+                    })?)
+                }
+                _ => format_options.push((key, value)),
+            }
+        }
+        // options are kept in a deterministic order so that plans are stable
+        format_options.sort_unstable();
+
+        // A target ending with a path separator is a directory, anything else is a file
+        let single_file_output =
+            single_file_output.unwrap_or_else(|| !target.ends_with('/'));
+
+        // Infer the output format from the file extension if not given explicitly
+        let file_format = match file_format {
+            Some(file_format) => file_format,
+            None => {
+                let extension = std::path::Path::new(&target)
+                    .extension()
+                    .filter(|_| single_file_output)
+                    .ok_or_else(|| {
+                        DataFusionError::Plan(
+                            "Format not explicitly set and unable to get file extension!"
+                                .to_string(),
+                        )
+                    })?;
+                extension.to_string_lossy().parse::<OutputFileFormat>()?
+            }
+        };
+
+        Ok(LogicalPlan::Copy(CopyTo {
+            input: Arc::new(input),
+            output_url: target,
+            file_format,
+            single_file_output,
+            options: format_options,
+        }))
     }
 
     fn build_order_by(
@@ -617,6 +856,117 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         Ok(all_results)
     }
 
+    /// Generate a logical plan from a CREATE FUNCTION statement, whose body
+    /// is planned as an expression of columns named after the arguments
+    fn create_function_to_plan(
+        &self,
+        or_replace: bool,
+        name: ObjectName,
+        args: Vec<OperateFunctionArg>,
+        return_type: Option<ast::DataType>,
+        params: CreateFunctionBody,
+    ) -> Result<LogicalPlan> {
+        let name = function_name(&name)?;
+        if BuiltinScalarFunction::from_str(&name).is_ok()
+            || find_df_window_func(&name).is_some()
+            || name == ARROW_CAST_NAME
+        {
+            return Err(DataFusionError::Plan(format!(
+                "Function {name} cannot be created, as there is a built-in function with the same name"
+            )));
+        }
+        if let Some(language) = &params.language {
+            if !language.value.eq_ignore_ascii_case("sql") {
+                return Err(DataFusionError::NotImplemented(format!(
+                    "Unsupported language of function {name}: {language}"
+                )));
+            }
+        }
+        if params.using.is_some() {
+            return Err(DataFusionError::NotImplemented(format!(
+                "Unsupported USING clause of function {name}"
+            )));
+        }
+
+        let mut fields: Vec<DFField> = Vec::with_capacity(args.len());
+        for arg in args {
+            if arg.mode.is_some() || arg.default_expr.is_some() {
+                return Err(DataFusionError::NotImplemented(format!(
+                    "Unsupported argument of function {name}: {arg}"
+                )));
+            }
+            let Some(arg_name) = arg.name else {
+                return Err(DataFusionError::Plan(format!(
+                    "The arguments of function {name} must be named"
+                )));
+            };
+            let arg_name = self.normalizer.normalize(arg_name);
+            if fields.iter().any(|f| f.name() == &arg_name) {
+                return Err(DataFusionError::Plan(format!(
+                    "Function {name} has more than one argument named {arg_name}"
+                )));
+            }
+            let data_type = self.convert_data_type(&arg.data_type)?;
+            fields.push(DFField::new_unqualified(&arg_name, data_type, true));
+        }
+        let schema = DFSchema::new_with_metadata(fields, HashMap::new())?;
+
+        let (definition, sql_body) = match (params.as_, params.return_) {
+            (Some(definition), None) => {
+                let definition = match definition {
+                    FunctionDefinition::SingleQuotedDef(s)
+                    | FunctionDefinition::DoubleDollarDef(s) => s,
+                };
+                let mut parser =
+                    Parser::new(&GenericDialect {}).try_with_sql(&definition)?;
+                let sql_body = parser.parse_expr()?;
+                if parser.peek_token() != Token::EOF {
+                    return Err(DataFusionError::Plan(format!(
+                        "The body of function {name} must be a single expression"
+                    )));
+                }
+                (definition, sql_body)
+            }
+            (None, Some(sql_body)) => (sql_body.to_string(), sql_body),
+            _ => {
+                return Err(DataFusionError::Plan(format!(
+                    "Function {name} must have either an AS or a RETURN clause"
+                )))
+            }
+        };
+        let body = self.sql_to_expr(sql_body, &schema, &mut PlannerContext::new())?;
+
+        let body_type = body.get_type(&schema)?;
+        let (body, return_type) = match return_type {
+            Some(return_type) => {
+                let return_type = self.convert_data_type(&return_type)?;
+                if body_type == return_type {
+                    (body, return_type)
+                } else if can_cast_types(&body_type, &return_type) {
+                    (cast(body, return_type.clone()), return_type)
+                } else {
+                    return Err(DataFusionError::Plan(format!(
+                        "Function {name} returns {return_type}, but its body has type {body_type}"
+                    )));
+                }
+            }
+            None => (body, body_type),
+        };
+
+        let args = schema
+            .fields()
+            .iter()
+            .map(|f| (f.name().clone(), f.data_type().clone()))
+            .collect();
+        Ok(LogicalPlan::Ddl(DdlStatement::CreateFunction(
+            CreateFunction {
+                function: ScalarMacro::new(name, args, return_type, body, definition),
+                or_replace,
+                schema: DFSchemaRef::new(DFSchema::empty()),
+            },
+        )))
+    }
+
     /// Generate a logical plan from a CREATE EXTERNAL TABLE statement
     fn external_table_to_plan(
         &self,
@@ -639,12 +989,6 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         } = statement;
 
         // semantic checks
-        if file_type == "PARQUET" && !columns.is_empty() {
-            Err(DataFusionError::Plan(
-                "Column definitions can not be specified for PARQUET files.".into(),
-            ))?;
-        }
-
         if file_type != "CSV"
             && file_type != "JSON"
             && file_compression_type != CompressionTypeVariant::UNCOMPRESSED
@@ -842,17 +1186,29 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         let source = match predicate_expr {
             None => scan,
             Some(predicate_expr) => {
+                // Resolve the predicate against the scan, so that it refers to
+                // the qualified columns of the table
+                let scan_schema = scan.schema().clone();
                 let filter_expr =
-                    self.sql_to_expr(predicate_expr, &schema, &mut planner_context)?;
-                let schema = Arc::new(schema.clone());
+                    self.sql_to_expr(predicate_expr, &scan_schema, &mut planner_context)?;
                 let mut using_columns = HashSet::new();
                 expr_to_columns(&filter_expr, &mut using_columns)?;
                 let filter_expr = normalize_col_with_schemas_and_ambiguity_check(
                     filter_expr,
-                    &[&[&schema]],
+                    &[&[&scan_schema]],
                     &[using_columns],
                 )?;
-                LogicalPlan::Filter(Filter::try_new(filter_expr, Arc::new(scan))?)
+                // Decorrelating subqueries in the predicate turns the filter
+                // into a join, so select the columns of the table explicitly
+                let columns = scan_schema
+                    .fields()
+                    .iter()
+                    .map(|field| col(field.qualified_column()))
+                    .collect::<Vec<_>>();
+                LogicalPlanBuilder::from(scan)
+                    .filter(filter_expr)?
+                    .project(columns)?
+                    .build()?
             }
         };
 
@@ -923,26 +1279,28 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         let source = match predicate_expr {
             None => scan,
             Some(predicate_expr) => {
-                let filter_expr = self.sql_to_expr(
-                    predicate_expr,
-                    &table_schema,
-                    &mut planner_context,
-                )?;
+                // Resolve the predicate against the scan, so that it refers to
+                // the qualified columns of the table
+                let scan_schema = scan.schema().clone();
+                let filter_expr =
+                    self.sql_to_expr(predicate_expr, &scan_schema, &mut planner_context)?;
                 let mut using_columns = HashSet::new();
                 expr_to_columns(&filter_expr, &mut using_columns)?;
                 let filter_expr = normalize_col_with_schemas_and_ambiguity_check(
                     filter_expr,
-                    &[&[&table_schema]],
+                    &[&[&scan_schema]],
                     &[using_columns],
                 )?;
                 LogicalPlan::Filter(Filter::try_new(filter_expr, Arc::new(scan))?)
             }
         };
 
-        // Projection
+        // Projection, resolved against the source so that subqueries can
+        // refer to the qualified columns of the table
+        let source_schema = source.schema().clone();
         let mut exprs = vec![];
         for (col_name, expr) in values.into_iter() {
-            let expr = self.sql_to_expr(expr, &table_schema, &mut planner_context)?;
+            let expr = self.sql_to_expr(expr, &source_schema, &mut planner_context)?;
             let expr = match expr {
                 datafusion_expr::Expr::Placeholder(Placeholder {
                     ref id,
@@ -959,9 +1317,15 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 },
                 _ => expr,
             };
-            let expr = expr.alias(col_name);
+            let data_type = table_schema.data_type(&Column::from_name(&col_name))?;
+            let expr = expr.cast_to(data_type, &source_schema)?.alias(col_name);
             exprs.push(expr);
         }
+        // The previous values of the updated rows, to find them in the table
+        for field in table_schema.fields() {
+            let column = datafusion_expr::Expr::Column(Column::from_name(field.name()));
+            exprs.push(column.alias(format!("{UPDATE_PREVIOUS_PREFIX}{}", field.name())));
+        }
         let source = project(source, exprs)?;
 
         let plan = LogicalPlan::Dml(DmlStatement {
@@ -973,6 +1337,268 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         Ok(plan)
     }
 
+    /// Plans a `MERGE` statement as a [`WriteOp::Merge`] of the outer join
+    /// of the table with the source relation, which computes the new
+    /// values and the [`MergeAction`] of the rows of the table, and the
+    /// inserted rows
+    fn merge_to_plan(
+        &self,
+        table: TableFactor,
+        source: TableFactor,
+        on: SQLExpr,
+        clauses: Vec<MergeClause>,
+    ) -> Result<LogicalPlan> {
+        let table_name = match &table {
+            TableFactor::Table { name, .. } => name.clone(),
+            _ => Err(DataFusionError::Plan(
+                "Cannot merge into non-table relation!".to_string(),
+            ))?,
+        };
+
+        // Do a table lookup to verify the table exists
+        let table_name = self.object_name_to_table_reference(table_name)?;
+        let provider = self
+            .schema_provider
+            .get_table_provider(table_name.clone())?;
+        let arrow_schema = (*provider.schema()).clone();
+        let table_schema = Arc::new(DFSchema::try_from(arrow_schema)?);
+
+        // Mark the rows of both sides, to tell them apart from the nulls
+        // produced by the outer join
+        let mut planner_context = PlannerContext::new();
+        let mark = |plan: LogicalPlan, marker: &str| {
+            let exprs = plan
+                .schema()
+                .fields()
+                .iter()
+                .map(|f| datafusion_expr::Expr::Column(f.qualified_column()))
+                .chain(std::iter::once(lit(true).alias(marker)))
+                .collect::<Vec<_>>();
+            LogicalPlanBuilder::from(plan).project(exprs)?.build()
+        };
+        let target = self.plan_table_with_joins(
+            TableWithJoins {
+                relation: table,
+                joins: vec![],
+            },
+            &mut planner_context,
+        )?;
+        let target_columns = target
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| datafusion_expr::Expr::Column(f.qualified_column()))
+            .collect::<Vec<_>>();
+        let target = mark(target, MERGE_TARGET_MARKER)?;
+        let source = self.plan_table_with_joins(
+            TableWithJoins {
+                relation: source,
+                joins: vec![],
+            },
+            &mut planner_context,
+        )?;
+        let source = mark(source, MERGE_SOURCE_MARKER)?;
+
+        // Source rows without a matching row are only needed to insert them
+        let has_insert = clauses
+            .iter()
+            .any(|clause| matches!(clause, MergeClause::NotMatched { .. }));
+        let join_type = if has_insert {
+            JoinType::Full
+        } else {
+            JoinType::Left
+        };
+        let join_schema = target.schema().join(source.schema())?;
+        let on = self.sql_to_expr(on, &join_schema, &mut planner_context)?;
+        let join = LogicalPlanBuilder::from(target)
+            .join(
+                source,
+                join_type,
+                (Vec::<Column>::new(), Vec::<Column>::new()),
+                Some(on),
+            )?
+            .build()?;
+        let join_schema = join.schema().clone();
+
+        let matched = col(MERGE_TARGET_MARKER)
+            .is_not_null()
+            .and(col(MERGE_SOURCE_MARKER).is_not_null());
+        let not_matched = col(MERGE_TARGET_MARKER).is_null();
+
+        // The condition, action and new values of the columns of each
+        // clause, the first clause whose condition holds applies to a row
+        let mut when_then = vec![];
+        for clause in clauses {
+            let (base, predicate, action) = match &clause {
+                MergeClause::MatchedUpdate { predicate, .. } => {
+                    (matched.clone(), predicate, MergeAction::Update)
+                }
+                MergeClause::MatchedDelete(predicate) => {
+                    (matched.clone(), predicate, MergeAction::Delete)
+                }
+                MergeClause::NotMatched { predicate, .. } => {
+                    (not_matched.clone(), predicate, MergeAction::Insert)
+                }
+            };
+            let condition = match predicate {
+                Some(predicate) => base.and(self.sql_to_expr(
+                    predicate.clone(),
+                    &join_schema,
+                    &mut planner_context,
+                )?),
+                None => base,
+            };
+
+            let mut values = HashMap::new();
+            match clause {
+                MergeClause::MatchedUpdate { assignments, .. } => {
+                    for assign in assignments {
+                        let col_name: &Ident =
+                            assign.id.iter().last().ok_or_else(|| {
+                                DataFusionError::Plan("Empty column id".to_string())
+                            })?;
+                        let col_name = self.normalizer.normalize(col_name.clone());
+                        // Validate that the assignment target column exists
+                        table_schema.field_with_unqualified_name(&col_name)?;
+                        let value = self.sql_to_expr(
+                            assign.value,
+                            &join_schema,
+                            &mut planner_context,
+                        )?;
+                        values.insert(col_name, value);
+                    }
+                }
+                MergeClause::MatchedDelete(_) => {}
+                MergeClause::NotMatched {
+                    columns,
+                    values: mut rows,
+                    ..
+                } => {
+                    let columns = if columns.is_empty() {
+                        table_schema
+                            .fields()
+                            .iter()
+                            .map(|f| f.name().clone())
+                            .collect()
+                    } else {
+                        columns
+                            .into_iter()
+                            .map(|c| self.normalizer.normalize(c))
+                            .collect::<Vec<_>>()
+                    };
+                    let row = match (rows.rows.pop(), rows.rows.is_empty()) {
+                        (Some(row), true) if row.len() == columns.len() => row,
+                        _ => {
+                            return Err(DataFusionError::Plan(
+                                "MERGE INSERT must have a single row of values for the inserted columns"
+                                    .to_string(),
+                            ))
+                        }
+                    };
+                    for (col_name, value) in columns.into_iter().zip(row) {
+                        table_schema.field_with_unqualified_name(&col_name)?;
+                        let value =
+                            self.sql_to_expr(value, &join_schema, &mut planner_context)?;
+                        if values.insert(col_name.clone(), value).is_some() {
+                            return Err(DataFusionError::Plan(format!(
+                                "Column {col_name} is inserted more than once"
+                            )));
+                        }
+                    }
+                }
+            }
+            when_then.push((condition, action, values));
+        }
+
+        // Number the clause that applies to each row, starting from 1. The
+        // source rows that no clause applies to are not inserted
+        let clause = datafusion_expr::Expr::Case(Case::new(
+            None,
+            when_then
+                .iter()
+                .enumerate()
+                .map(|(i, (condition, _, _))| {
+                    (Box::new(condition.clone()), Box::new(lit(i as i64 + 1)))
+                })
+                .collect(),
+            None,
+        ));
+        let exprs = join_schema
+            .fields()
+            .iter()
+            .map(|f| datafusion_expr::Expr::Column(f.qualified_column()))
+            .chain(std::iter::once(clause.alias(MERGE_CLAUSE)))
+            .collect::<Vec<_>>();
+        let mut source = LogicalPlanBuilder::from(join).project(exprs)?;
+        if has_insert {
+            source = source.filter(
+                col(MERGE_CLAUSE)
+                    .is_not_null()
+                    .or(col(MERGE_TARGET_MARKER).is_not_null()),
+            )?;
+        }
+
+        // The rows of the table that no clause applies to are kept
+        let case_clause = |branches: Vec<(usize, datafusion_expr::Expr)>,
+                           otherwise: datafusion_expr::Expr| {
+            datafusion_expr::Expr::Case(Case::new(
+                Some(Box::new(col(MERGE_CLAUSE))),
+                branches
+                    .into_iter()
+                    .map(|(i, value)| (Box::new(lit(i as i64 + 1)), Box::new(value)))
+                    .collect(),
+                Some(Box::new(otherwise)),
+            ))
+        };
+        let mut exprs = vec![];
+        for (field, target_column) in table_schema.fields().iter().zip(&target_columns) {
+            let mut branches = vec![];
+            for (i, (_, action, values)) in when_then.iter().enumerate() {
+                let value = match values.get(field.name()) {
+                    Some(value) => value.clone(),
+                    None if *action == MergeAction::Insert => {
+                        datafusion_expr::Expr::Literal(ScalarValue::try_from(
+                            field.data_type(),
+                        )?)
+                    }
+                    None => continue,
+                };
+                branches.push((i, value.cast_to(field.data_type(), &join_schema)?));
+            }
+            let expr = if branches.is_empty() {
+                target_column.clone()
+            } else {
+                case_clause(branches, target_column.clone())
+            };
+            exprs.push(expr.alias(field.name()));
+        }
+        let actions = when_then
+            .iter()
+            .enumerate()
+            .map(|(i, (_, action, _))| (i, lit(action.as_str())))
+            .collect();
+        exprs.push(
+            case_clause(actions, lit(MergeAction::Keep.as_str()))
+                .alias(MERGE_ACTION_COLUMN),
+        );
+        // The previous values of the rows of the table, which are null for
+        // the inserted rows
+        for (field, target_column) in table_schema.fields().iter().zip(target_columns) {
+            exprs.push(
+                target_column.alias(format!("{MERGE_PREVIOUS_PREFIX}{}", field.name())),
+            );
+        }
+        let source = source.project(exprs)?;
+
+        let plan = LogicalPlan::Dml(DmlStatement {
+            table_name,
+            table_schema,
+            op: WriteOp::Merge,
+            input: Arc::new(source.build()?),
+        });
+        Ok(plan)
+    }
+
     fn insert_to_plan(
         &self,
         table_name: ObjectName,
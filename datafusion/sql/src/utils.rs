@@ -182,6 +182,7 @@ where
                 partition_by,
                 order_by,
                 window_frame,
+                ignore_nulls,
             }) => Ok(Expr::WindowFunction(WindowFunction::new(
                 fun.clone(),
                 args.iter()
@@ -196,6 +197,7 @@ where
                     .map(|e| clone_with_replacement(e, replacement_fn))
                     .collect::<Result<Vec<_>>>()?,
                 window_frame.clone(),
+                *ignore_nulls,
             ))),
             Expr::AggregateUDF(AggregateUDF {
                 fun,
@@ -324,6 +324,41 @@ fn plan_rollback_transaction_chained() {
     quick_test(sql, plan);
 }
 
+#[test]
+fn plan_copy_to() {
+    let sql = "COPY test_decimal to 'output.csv'";
+    let plan = r#"
+CopyTo: format=csv output_url=output.csv single_file_output=true options: ()
+  TableScan: test_decimal
+    "#
+    .trim();
+    quick_test(sql, plan);
+}
+
+#[test]
+fn plan_copy_to_query_with_options() {
+    let sql = "COPY (select * from test_decimal limit 10) to 'output/' \
+               WITH (FORMAT parquet, ROW_GROUP_SIZE 55, compression 'zstd(10)')";
+    let plan = r#"
+CopyTo: format=parquet output_url=output/ single_file_output=false options: (compression zstd(10), row_group_size 55)
+  Limit: skip=0, fetch=10
+    Projection: test_decimal.id, test_decimal.price
+      TableScan: test_decimal
+    "#
+    .trim();
+    quick_test(sql, plan);
+}
+
+#[test]
+fn plan_copy_to_without_format() {
+    let sql = "COPY test_decimal to 'output/'";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "Plan(\"Format not explicitly set and unable to get file extension!\")",
+        format!("{err:?}")
+    );
+}
+
 #[test]
 fn plan_insert() {
     let sql =
@@ -389,8 +424,8 @@ fn plan_update() {
     let sql = "update person set last_name='Kay' where id=1";
     let plan = r#"
 Dml: op=[Update] table=[person]
-  Projection: person.id AS id, person.first_name AS first_name, Utf8("Kay") AS last_name, person.age AS age, person.state AS state, person.salary AS salary, person.birth_date AS birth_date, person.😀 AS 😀
-    Filter: id = Int64(1)
+  Projection: person.id AS id, person.first_name AS first_name, Utf8("Kay") AS last_name, person.age AS age, person.state AS state, person.salary AS salary, person.birth_date AS birth_date, person.😀 AS 😀, person.id AS __update_previous_id, person.first_name AS __update_previous_first_name, person.last_name AS __update_previous_last_name, person.age AS __update_previous_age, person.state AS __update_previous_state, person.salary AS __update_previous_salary, person.birth_date AS __update_previous_birth_date, person.😀 AS __update_previous_😀
+    Filter: person.id = Int64(1)
       TableScan: person
       "#
     .trim();
@@ -414,13 +449,51 @@ fn plan_delete() {
     let sql = "delete from person where id=1";
     let plan = r#"
 Dml: op=[Delete] table=[person]
-  Filter: id = Int64(1)
-    TableScan: person
+  Projection: person.id, person.first_name, person.last_name, person.age, person.state, person.salary, person.birth_date, person.😀
+    Filter: person.id = Int64(1)
+      TableScan: person
     "#
     .trim();
     quick_test(sql, plan);
 }
 
+#[test]
+fn plan_merge() {
+    let sql = "merge into j1 using j2 on j1_id = j2_id \
+               when matched and j2_string = 'x' then delete \
+               when matched then update set j1_string = j2_string \
+               when not matched then insert values (j2_id, j2_string)";
+    let plan = r#"
+Dml: op=[Merge] table=[j1]
+  Projection: CASE __merge_clause WHEN Int64(3) THEN j2.j2_id ELSE j1.j1_id END AS j1_id, CASE __merge_clause WHEN Int64(2) THEN j2.j2_string WHEN Int64(3) THEN j2.j2_string ELSE j1.j1_string END AS j1_string, CASE __merge_clause WHEN Int64(1) THEN Utf8("delete") WHEN Int64(2) THEN Utf8("update") WHEN Int64(3) THEN Utf8("insert") ELSE Utf8("keep") END AS __merge_action, j1.j1_id AS __merge_previous_j1_id, j1.j1_string AS __merge_previous_j1_string
+    Filter: __merge_clause IS NOT NULL OR __merge_target IS NOT NULL
+      Projection: j1.j1_id, j1.j1_string, __merge_target, j2.j2_id, j2.j2_string, __merge_source, CASE WHEN __merge_target IS NOT NULL AND __merge_source IS NOT NULL AND j2.j2_string = Utf8("x") THEN Int64(1) WHEN __merge_target IS NOT NULL AND __merge_source IS NOT NULL THEN Int64(2) WHEN __merge_target IS NULL THEN Int64(3) END AS __merge_clause
+        Full Join:  Filter: j1.j1_id = j2.j2_id
+          Projection: j1.j1_id, j1.j1_string, Boolean(true) AS __merge_target
+            TableScan: j1
+          Projection: j2.j2_id, j2.j2_string, Boolean(true) AS __merge_source
+            TableScan: j2
+    "#
+    .trim();
+    quick_test(sql, plan);
+}
+
+#[rstest]
+#[case::missing_assignement_target(
+    "MERGE INTO j1 USING j2 ON j1_id = j2_id WHEN MATCHED THEN UPDATE SET doesnotexist = 1"
+)]
+#[case::missing_insert_column(
+    "MERGE INTO j1 USING j2 ON j1_id = j2_id WHEN NOT MATCHED THEN INSERT (doesnotexist) VALUES (1)"
+)]
+#[case::missing_on_expression(
+    "MERGE INTO j1 USING j2 ON j1_id = doesnotexist WHEN MATCHED THEN DELETE"
+)]
+#[test]
+fn merge_column_does_not_exist(#[case] sql: &str) {
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_field_not_found(err, "doesnotexist");
+}
+
 #[test]
 fn select_column_does_not_exist() {
     let sql = "SELECT doesnotexist FROM person";
@@ -466,6 +539,61 @@ fn select_scalar_func_with_literal_no_relation() {
     );
 }
 
+#[test]
+fn select_json_operators() {
+    quick_test(
+        "SELECT first_name -> 'a' -> 0, first_name ->> 'b' FROM person",
+        "Projection: json_get(json_get(person.first_name, Utf8(\"a\")), Int64(0)), json_get_str(person.first_name, Utf8(\"b\"))\
+             \n  TableScan: person",
+    );
+}
+
+#[test]
+fn select_json_operators_precedence() {
+    quick_test(
+        "SELECT first_name FROM person WHERE first_name ->> 'a' = 'b' AND first_name -> 0 IS NOT NULL",
+        "Projection: person.first_name\
+             \n  Filter: json_get_str(person.first_name, Utf8(\"a\")) = Utf8(\"b\") AND json_get(person.first_name, Int64(0)) IS NOT NULL\
+             \n    TableScan: person",
+    );
+    // left associative, and binding more tightly than comparisons and casts
+    quick_test(
+        "SELECT first_name -> 'x' ->> 'y' = 'z', first_name ->> 'n'::INT FROM person",
+        "Projection: json_get_str(json_get(person.first_name, Utf8(\"x\")), Utf8(\"y\")) = Utf8(\"z\"), CAST(json_get_str(person.first_name, Utf8(\"n\")) AS Int32)\
+             \n  TableScan: person",
+    );
+}
+
+#[test]
+fn select_json_as_struct() {
+    let plan =
+        logical_plan("SELECT json_as_struct(first_name, 'a INT, b VARCHAR') FROM person")
+            .unwrap();
+    assert_eq!(
+        plan.schema().field(0).data_type(),
+        &DataType::Struct(Fields::from(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Utf8, true),
+        ]))
+    );
+
+    let err = logical_plan(
+        "SELECT json_as_struct(first_name, 'a INT, b VARCHAR NOT NULL') FROM person",
+    )
+    .expect_err("query should have failed");
+    assert_eq!(
+        "Error during planning: The json_as_struct schema cannot declare the field b NOT NULL",
+        err.to_string()
+    );
+
+    let err = logical_plan("SELECT json_as_struct(first_name, 'a') FROM person")
+        .expect_err("query should have failed");
+    assert_eq!(
+        "SQL(ParserError(\"Expected a data type name, found: EOF\"))",
+        format!("{err:?}")
+    );
+}
+
 #[test]
 fn select_simple_filter() {
     let sql = "SELECT id, first_name, last_name \
@@ -1212,6 +1340,34 @@ fn select_simple_aggregate_with_groupby() {
     );
 }
 
+#[test]
+fn select_string_agg() {
+    quick_test(
+        "SELECT state, STRING_AGG(first_name, ', ' ORDER BY first_name) FROM person GROUP BY state",
+        "Projection: person.state, STRING_AGG(person.first_name,Utf8(\", \")) ORDER BY [person.first_name ASC NULLS LAST]\
+            \n  Aggregate: groupBy=[[person.state]], aggr=[[STRING_AGG(person.first_name, Utf8(\", \")) ORDER BY [person.first_name ASC NULLS LAST]]]\
+            \n    TableScan: person",
+    );
+}
+
+#[test]
+fn select_listagg() {
+    quick_test(
+        "SELECT LISTAGG(DISTINCT first_name, '|') WITHIN GROUP (ORDER BY first_name DESC), LISTAGG(last_name) FROM person",
+        "Projection: STRING_AGG(DISTINCT person.first_name,Utf8(\"|\")) ORDER BY [person.first_name DESC NULLS FIRST], STRING_AGG(person.last_name,Utf8(\"\"))\
+            \n  Aggregate: groupBy=[[]], aggr=[[STRING_AGG(DISTINCT person.first_name, Utf8(\"|\")) ORDER BY [person.first_name DESC NULLS FIRST], STRING_AGG(person.last_name, Utf8(\"\"))]]\
+            \n    TableScan: person",
+    );
+
+    let sql =
+        "SELECT LISTAGG(first_name, ',' ON OVERFLOW TRUNCATE WITH COUNT) FROM person";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "This feature is not implemented: LISTAGG ON OVERFLOW TRUNCATE WITH COUNT is not supported",
+        err.to_string()
+    );
+}
+
 #[test]
 fn select_simple_aggregate_with_groupby_with_aliases() {
     quick_test(
@@ -1289,10 +1445,74 @@ fn recursive_ctes() {
               select n + 1 FROM numbers WHERE N < 10
         )
         select * from numbers;";
+    let expected = "Projection: numbers.n\
+        \n  SubqueryAlias: numbers\
+        \n    RecursiveQuery: name=numbers, is_distinct=false\
+        \n      Projection: Int64(1) AS n\
+        \n        EmptyRelation\
+        \n      Projection: numbers.n + Int64(1)\
+        \n        Filter: numbers.n < Int64(10)\
+        \n          TableScan: numbers";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn recursive_ctes_distinct_with_column_names() {
+    let sql = "
+        WITH RECURSIVE reports(id, manager) AS (
+              select id, age from person where age = 1
+            UNION
+              select p.id, p.age from person p JOIN reports r ON p.age = r.id
+        )
+        select id from reports;";
+    let expected = "Projection: reports.id\
+        \n  SubqueryAlias: reports\
+        \n    RecursiveQuery: name=reports, is_distinct=true\
+        \n      Projection: person.id AS id, person.age AS manager\
+        \n        Projection: person.id, person.age\
+        \n          Filter: person.age = Int64(1)\
+        \n            TableScan: person\
+        \n      Projection: p.id, p.age\
+        \n        Inner Join:  Filter: p.age = r.id\
+        \n          SubqueryAlias: p\
+        \n            TableScan: person\
+        \n          SubqueryAlias: r\
+        \n            TableScan: reports";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn recursive_ctes_without_self_reference() {
+    let sql = "
+        WITH RECURSIVE numbers AS (
+              select 1 as n
+            UNION ALL
+              select 2
+        )
+        select * from numbers;";
+    let expected = "Projection: numbers.n\
+        \n  SubqueryAlias: numbers\
+        \n    Union\
+        \n      Projection: Int64(1) AS n\
+        \n        EmptyRelation\
+        \n      Projection: Int64(2) AS n\
+        \n        EmptyRelation";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn recursive_ctes_unbalanced_number_of_columns() {
+    let sql = "
+        WITH RECURSIVE numbers AS (
+              select 1 as n
+            UNION ALL
+              select n + 1, n FROM numbers
+        )
+        select * from numbers;";
     let err = logical_plan(sql).expect_err("query should have failed");
     assert_eq!(
-        r#"NotImplemented("Recursive CTEs are not supported")"#,
-        format!("{err:?}")
+        "Error during planning: Non-recursive term and recursive term must have the same number of columns (1 != 2)",
+        err.to_string()
     );
 }
 
@@ -1779,11 +1999,8 @@ fn create_external_table_with_compression_type() {
 #[test]
 fn create_external_table_parquet() {
     let sql = "CREATE EXTERNAL TABLE t(c1 int) STORED AS PARQUET LOCATION 'foo.parquet'";
-    let err = logical_plan(sql).expect_err("query should have failed");
-    assert_eq!(
-        "Plan(\"Column definitions can not be specified for PARQUET files.\")",
-        format!("{err:?}")
-    );
+    let expected = "CreateExternalTable: Bare { table: \"t\" }";
+    quick_test(sql, expected);
 }
 
 #[test]
@@ -1793,6 +2010,174 @@ fn create_external_table_parquet_no_schema() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn create_function() {
+    let sql = "CREATE FUNCTION discount(Price DOUBLE, pct DOUBLE) RETURNS DOUBLE AS 'price * (1 - pct)'";
+    let expected = "CreateFunction: discount(price Float64, pct Float64) -> Float64 or replace:=false";
+    quick_test(sql, expected);
+
+    // the return type defaults to the type of the body
+    let sql = "CREATE OR REPLACE FUNCTION \"Inc\"(x INT) RETURN x + 1";
+    let expected = "CreateFunction: Inc(x Int32) -> Int64 or replace:=true";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn create_function_invalid_return_type() {
+    let sql = "CREATE FUNCTION f(x VARCHAR) RETURNS DATE AS 'x IS NULL'";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "Error during planning: Function f returns Date32, but its body has type Boolean",
+        err.to_string()
+    );
+}
+
+#[test]
+fn drop_function() {
+    let sql = "DROP FUNCTION IF EXISTS Discount(DOUBLE, DOUBLE)";
+    let expected = "DropFunction: discount if exist:=true";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn alter_table_add_column() {
+    let sql = "ALTER TABLE person ADD COLUMN IF NOT EXISTS nickname VARCHAR NOT NULL";
+    let expected = "AlterTable: Bare { table: \"person\" } add column nickname Utf8 not null if not exist:=true";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn alter_table_drop_column() {
+    let sql = "ALTER TABLE person DROP COLUMN \"First_Name\"";
+    let expected =
+        "AlterTable: Bare { table: \"person\" } drop column First_Name if exist:=false";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn alter_table_rename() {
+    let sql = "ALTER TABLE person RENAME COLUMN Age TO years";
+    let expected = "AlterTable: Bare { table: \"person\" } rename column age to years";
+    quick_test(sql, expected);
+
+    let sql = "ALTER TABLE person RENAME TO public.people";
+    let expected = "AlterTable: Bare { table: \"person\" } rename to Partial { schema: \"public\", table: \"people\" }";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn alter_table_unsupported_operation() {
+    let sql = "ALTER TABLE person ALTER COLUMN age SET DEFAULT 0";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert!(
+        err.to_string().contains(
+            "Unsupported ALTER TABLE operation: ALTER COLUMN age SET DEFAULT 0"
+        ),
+        "{err}"
+    );
+}
+
+#[test]
+fn table_sample() {
+    let sql = "SELECT id FROM person TABLESAMPLE BERNOULLI (10) WHERE age > 20";
+    let expected = "Projection: person.id\
+                        \n  Filter: person.age > Int64(20)\
+                        \n    Sample: method=bernoulli, size=10%\
+                        \n      TableScan: person";
+    quick_test(sql, expected);
+
+    let sql = "SELECT p.id FROM person AS p TABLESAMPLE SYSTEM (5 ROWS) REPEATABLE (42)";
+    let expected = "Projection: p.id\
+                        \n  SubqueryAlias: p\
+                        \n    Sample: method=system, size=5 rows, seed=42\
+                        \n      TableScan: person";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn table_sample_invalid() {
+    for (sql, error) in [
+        (
+            "SELECT * FROM person TABLESAMPLE BLOCK (10)",
+            "Unsupported TABLESAMPLE method BLOCK, expected BERNOULLI or SYSTEM",
+        ),
+        (
+            "SELECT * FROM person TABLESAMPLE SYSTEM (150 PERCENT)",
+            "Sample percentage must be between 0 and 100, got 150",
+        ),
+        (
+            "SELECT * FROM person TABLESAMPLE SYSTEM (1.5 ROWS)",
+            "TABLESAMPLE row count must be a non-negative integer",
+        ),
+        (
+            "SELECT * FROM person TABLESAMPLE SYSTEM (1 + 2)",
+            "TABLESAMPLE arguments must be literals, got Int64(1) + Int64(2)",
+        ),
+    ] {
+        let err = logical_plan(sql).expect_err("query should have failed");
+        assert!(err.to_string().contains(error), "{err}");
+    }
+}
+
+#[test]
+fn asof_join() {
+    let sql = "SELECT id, order_id \
+            FROM person \
+            ASOF JOIN orders \
+            MATCH_CONDITION (age >= qty) \
+            ON id = customer_id";
+    let expected = "Projection: person.id, orders.order_id\
+            \n  AsOf Join: person.id = orders.customer_id Filter: person.age >= orders.qty\
+            \n    TableScan: person\
+            \n    TableScan: orders";
+    quick_test(sql, expected);
+
+    let sql = "SELECT p.id, o.order_id \
+            FROM person p \
+            ASOF JOIN orders o \
+            MATCH_CONDITION (o.qty < p.age)";
+    let expected = "Projection: p.id, o.order_id\
+            \n  AsOf Join:  Filter: o.qty < p.age\
+            \n    SubqueryAlias: p\
+            \n      TableScan: person\
+            \n    SubqueryAlias: o\
+            \n      TableScan: orders";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn asof_join_invalid() {
+    for (sql, error) in [
+        (
+            "SELECT * FROM person ASOF JOIN orders ON id = customer_id",
+            "Expected MATCH_CONDITION, found: ON",
+        ),
+        (
+            "SELECT * FROM person ASOF JOIN orders MATCH_CONDITION (age >= qty) USING (id)",
+            "ASOF JOIN does not support USING, use ON instead",
+        ),
+        (
+            "SELECT * FROM person ASOF JOIN orders MATCH_CONDITION (age >= qty) ON id > customer_id",
+            "ASOF JOIN ON clause only supports equality predicates between the joined relations, got person.id > orders.customer_id",
+        ),
+        (
+            "SELECT * FROM person ASOF JOIN orders MATCH_CONDITION (age >= qty) ON id = customer_id OR id = order_id",
+            "ASOF JOIN ON clause only supports equality predicates between the joined relations, got person.id = orders.customer_id OR person.id = orders.order_id",
+        ),
+        (
+            "SELECT * FROM person ASOF JOIN orders MATCH_CONDITION (age = qty)",
+            "ASOF join requires a match condition comparing an expression on each input with one of >=, >, <=, <, got person.age = orders.qty",
+        ),
+        (
+            "SELECT * FROM person LEFT JOIN orders ON MATCH_CONDITION(age >= qty)",
+            "Invalid function 'match_condition'",
+        ),
+    ] {
+        let err = logical_plan(sql).expect_err("query should have failed");
+        assert!(err.to_string().contains(error), "{err}");
+    }
+}
+
 #[test]
 fn equijoin_explicit_syntax() {
     let sql = "SELECT id, order_id \
@@ -2213,6 +2598,150 @@ fn over_order_by() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn select_qualify() {
+    let sql = "SELECT order_id, qty FROM orders \
+               QUALIFY ROW_NUMBER() OVER (PARTITION BY customer_id ORDER BY qty DESC) = 1";
+    let expected = "\
+        Projection: orders.order_id, orders.qty\
+        \n  Filter: ROW_NUMBER() PARTITION BY [orders.customer_id] ORDER BY [orders.qty DESC NULLS FIRST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW = Int64(1)\
+        \n    WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [orders.customer_id] ORDER BY [orders.qty DESC NULLS FIRST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW]]\
+        \n      TableScan: orders";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_qualify_with_alias() {
+    let sql =
+        "SELECT order_id, RANK() OVER (ORDER BY qty) AS rk FROM orders QUALIFY rk < 3";
+    let expected = "\
+        Projection: orders.order_id, RANK() ORDER BY [orders.qty ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW AS rk\
+        \n  Filter: RANK() ORDER BY [orders.qty ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW < Int64(3)\
+        \n    WindowAggr: windowExpr=[[RANK() ORDER BY [orders.qty ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW]]\
+        \n      TableScan: orders";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_qualify_with_aggregate() {
+    let sql = "SELECT customer_id, SUM(qty) FROM orders GROUP BY customer_id \
+               QUALIFY RANK() OVER (ORDER BY SUM(qty) DESC) = 1";
+    let expected = "\
+        Projection: orders.customer_id, SUM(orders.qty)\
+        \n  Filter: RANK() ORDER BY [SUM(orders.qty) DESC NULLS FIRST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW = Int64(1)\
+        \n    WindowAggr: windowExpr=[[RANK() ORDER BY [SUM(orders.qty) DESC NULLS FIRST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW]]\
+        \n      Aggregate: groupBy=[[orders.customer_id]], aggr=[[SUM(orders.qty)]]\
+        \n        TableScan: orders";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_qualify_without_window_function() {
+    let sql = "SELECT order_id, qty FROM orders QUALIFY qty > 1";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "Plan(\"QUALIFY clause references: orders.qty > Int64(1) requires a window function in the SELECT list or the QUALIFY clause\")",
+        format!("{err:?}")
+    );
+}
+
+#[test]
+fn over_order_by_ignore_nulls() {
+    let sql = "SELECT order_id, LAG(qty) IGNORE NULLS OVER (ORDER BY order_id), FIRST_VALUE(qty RESPECT NULLS) OVER (ORDER BY order_id) from orders";
+    let expected = "\
+        Projection: orders.order_id, LAG(orders.qty) IGNORE NULLS ORDER BY [orders.order_id ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW, FIRST_VALUE(orders.qty) ORDER BY [orders.order_id ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW\
+        \n  WindowAggr: windowExpr=[[LAG(orders.qty) IGNORE NULLS ORDER BY [orders.order_id ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW, FIRST_VALUE(orders.qty) ORDER BY [orders.order_id ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW]]\
+        \n    TableScan: orders";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn ignore_nulls_without_window_function() {
+    let sql = "SELECT MAX(qty) IGNORE NULLS FROM orders";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "Plan(\"Null treatment is only supported for window functions, got max\")",
+        format!("{err:?}")
+    );
+}
+
+#[test]
+fn null_treatment_written_as_argument() {
+    // only the parser can add the null treatment argument
+    let sql = "SELECT LAG(qty, NULL_TREATMENT('IGNORE NULLS')) \
+               OVER (ORDER BY order_id) FROM orders";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    let error = "Invalid function 'null_treatment'";
+    assert!(err.to_string().contains(error), "{err}");
+}
+
+#[test]
+fn select_ordered_set_aggregates() {
+    let sql = "SELECT state, \
+               percentile_cont(0.5) WITHIN GROUP (ORDER BY age), \
+               percentile_disc([0.25, 0.75]) WITHIN GROUP (ORDER BY age ASC), \
+               mode() WITHIN GROUP (ORDER BY first_name) \
+               FROM person GROUP BY state";
+    let expected = "\
+        Projection: person.state, PERCENTILE_CONT(person.age,Float64(0.5)), PERCENTILE_DISC(person.age,List([0.25,0.75])), MODE(person.first_name)\
+        \n  Aggregate: groupBy=[[person.state]], aggr=[[PERCENTILE_CONT(person.age, Float64(0.5)), PERCENTILE_DISC(person.age, List([0.25,0.75])), MODE(person.first_name)]]\
+        \n    TableScan: person";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn ordered_set_aggregate_over_window() {
+    let sql = "SELECT percentile_cont(0.5) WITHIN GROUP (ORDER BY qty) \
+               OVER (ORDER BY order_id ROWS BETWEEN 2 PRECEDING AND CURRENT ROW) FROM orders";
+    let expected = "\
+        Projection: PERCENTILE_CONT(orders.qty,Float64(0.5)) ORDER BY [orders.order_id ASC NULLS LAST] ROWS BETWEEN 2 PRECEDING AND CURRENT ROW\
+        \n  WindowAggr: windowExpr=[[PERCENTILE_CONT(orders.qty, Float64(0.5)) ORDER BY [orders.order_id ASC NULLS LAST] ROWS BETWEEN 2 PRECEDING AND CURRENT ROW]]\
+        \n    TableScan: orders";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn ordered_set_aggregates_descending() {
+    let sql = "SELECT percentile_cont(0.25) WITHIN GROUP (ORDER BY age DESC), \
+               mode() WITHIN GROUP (ORDER BY first_name DESC NULLS LAST) \
+               FROM person";
+    let expected = "\
+        Projection: PERCENTILE_CONT(person.age,Float64(0.25)) ORDER BY [person.age DESC NULLS FIRST], MODE(person.first_name) ORDER BY [person.first_name DESC NULLS FIRST]\
+        \n  Aggregate: groupBy=[[]], aggr=[[PERCENTILE_CONT(person.age, Float64(0.25)) ORDER BY [person.age DESC NULLS FIRST], MODE(person.first_name) ORDER BY [person.first_name DESC NULLS FIRST]]]\
+        \n    TableScan: person";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn ordered_set_aggregate_descending_over_window() {
+    let sql = "SELECT percentile_disc(0.5) WITHIN GROUP (ORDER BY qty DESC) \
+               OVER (ORDER BY order_id) FROM orders";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "NotImplemented(\"WITHIN GROUP (ORDER BY ... DESC) is not supported for window functions, got percentile_disc\")",
+        format!("{err:?}")
+    );
+}
+
+#[test]
+fn within_group_written_as_argument() {
+    // only the parser can add the WITHIN GROUP argument
+    let sql = "SELECT percentile_cont(WITHIN_GROUP(qty), 0.5) FROM orders";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    let error = "Invalid function 'within_group'";
+    assert!(err.to_string().contains(error), "{err}");
+}
+
+#[test]
+fn within_group_unsupported_function() {
+    let sql = "SELECT MAX(1) WITHIN GROUP (ORDER BY qty) FROM orders";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "Plan(\"WITHIN GROUP is only supported for the ordered-set aggregate functions percentile_cont, percentile_disc and mode, got max\")",
+        format!("{err:?}")
+    );
+}
+
 #[test]
 fn over_order_by_with_window_frame_double_end() {
     let sql = "SELECT order_id, MAX(qty) OVER (ORDER BY order_id ROWS BETWEEN 3 PRECEDING and 3 FOLLOWING), MIN(qty) OVER (ORDER BY order_id DESC) from orders";
@@ -2698,6 +3227,14 @@ impl ContextProvider for MockContextProvider {
     fn options(&self) -> &ConfigOptions {
         &self.options
     }
+
+    fn create_cte_work_table(
+        &self,
+        _name: &str,
+        schema: SchemaRef,
+    ) -> Result<Arc<dyn TableSource>> {
+        Ok(Arc::new(EmptyTable::new(schema)))
+    }
 }
 
 #[test]
@@ -2722,6 +3259,132 @@ fn cross_join_not_to_inner_join() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn lateral_subquery() {
+    let sql = "SELECT j1_string, j2_string FROM j1, \
+               LATERAL (SELECT * FROM j2 WHERE j1_id < j2_id) AS j2";
+    let expected = "Projection: j1.j1_string, j2.j2_string\
+        \n  CrossJoin:\
+        \n    TableScan: j1\
+        \n    Subquery:\
+        \n      SubqueryAlias: j2\
+        \n        Projection: j2.j2_id, j2.j2_string\
+        \n          Filter: outer_ref(j1.j1_id) < j2.j2_id\
+        \n            TableScan: j2";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn lateral_join_with_limit() {
+    let sql = "SELECT j1_id, j2_id FROM j1 LEFT JOIN \
+               LATERAL (SELECT j2_id FROM j2 WHERE j1_id = j2_id ORDER BY j2_id LIMIT 2) ON true";
+    let expected = "Projection: j1.j1_id, j2.j2_id\
+        \n  Left Join:  Filter: Boolean(true)\
+        \n    TableScan: j1\
+        \n    Subquery:\
+        \n      Limit: skip=0, fetch=2\
+        \n        Sort: j2.j2_id ASC NULLS LAST\
+        \n          Projection: j2.j2_id\
+        \n            Filter: outer_ref(j1.j1_id) = j2.j2_id\
+        \n              TableScan: j2";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn lateral_nested_join() {
+    let sql = "SELECT j1_id, j2_id, j3_id FROM j1, j2 JOIN \
+               LATERAL (SELECT j3_id FROM j3 WHERE j3_id = j1_id + j2_id) ON true";
+    let expected = "Projection: j1.j1_id, j2.j2_id, j3.j3_id\
+        \n  CrossJoin:\
+        \n    TableScan: j1\
+        \n    Inner Join:  Filter: Boolean(true)\
+        \n      TableScan: j2\
+        \n      Subquery:\
+        \n        Projection: j3.j3_id\
+        \n          Filter: j3.j3_id = outer_ref(j1.j1_id) + outer_ref(j2.j2_id)\
+        \n            TableScan: j3";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn lateral_uncorrelated_subquery() {
+    let sql = "SELECT j1_id, j2_id FROM j1, LATERAL (SELECT j2_id FROM j2)";
+    let expected = "Projection: j1.j1_id, j2.j2_id\
+        \n  CrossJoin:\
+        \n    TableScan: j1\
+        \n    Projection: j2.j2_id\
+        \n      TableScan: j2";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn lateral_unsupported_join_type() {
+    let sql = "SELECT j1_id, j2_id FROM j1 RIGHT JOIN \
+               LATERAL (SELECT j2_id FROM j2 WHERE j1_id = j2_id) ON true";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "Plan(\"The combining JOIN type must be INNER or LEFT for a LATERAL reference\")",
+        format!("{err:?}")
+    );
+}
+
+#[test]
+fn derived_table_cannot_reference_preceding_relation() {
+    let sql = "SELECT j1_id, j2_id FROM j1, (SELECT j2_id FROM j2 WHERE j1_id = j2_id)";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "SchemaError(FieldNotFound { field: Column { relation: None, name: \"j1_id\" }, valid_fields: [Column { relation: Some(Bare { table: \"j2\" }), name: \"j2_id\" }, Column { relation: Some(Bare { table: \"j2\" }), name: \"j2_string\" }] })",
+        format!("{err:?}")
+    );
+}
+
+#[test]
+fn pivot_table() {
+    let sql =
+        "SELECT * FROM lineitem PIVOT(SUM(price) FOR l_description IN ('a', 'b')) AS p";
+    let expected = "Projection: p.l_item_id, p.a, p.b\
+        \n  SubqueryAlias: p\
+        \n    Aggregate: groupBy=[[lineitem.l_item_id]], aggr=[[SUM(lineitem.price) FILTER (WHERE lineitem.l_description = Utf8(\"a\")) AS a, SUM(lineitem.price) FILTER (WHERE lineitem.l_description = Utf8(\"b\")) AS b]]\
+        \n      TableScan: lineitem";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn pivot_table_with_table_alias() {
+    let sql = "SELECT p.l_item_id, p.\"1\" FROM lineitem AS l PIVOT(COUNT(l.l_description) FOR l.price IN (1, 2.5)) AS p";
+    let expected = "Projection: p.l_item_id, p.1\
+        \n  SubqueryAlias: p\
+        \n    Aggregate: groupBy=[[l.l_item_id]], aggr=[[COUNT(l.l_description) FILTER (WHERE l.price = Int64(1)) AS 1, COUNT(l.l_description) FILTER (WHERE l.price = Float64(2.5)) AS 2.5]]\
+        \n      SubqueryAlias: l\
+        \n        TableScan: lineitem";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn pivot_table_requires_aggregate_function() {
+    let sql = "SELECT * FROM lineitem PIVOT(abs(price) FOR l_description IN ('a'))";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "Plan(\"PIVOT requires an aggregate function, got abs(lineitem.price)\")",
+        format!("{err:?}")
+    );
+}
+
+#[test]
+fn unpivot_table() {
+    let sql = "SELECT * FROM lineitem UNPIVOT (value FOR column_name IN (l_description, l_item_id)) AS u";
+    let expected = "Projection: u.price, u.column_name, u.value\
+        \n  SubqueryAlias: u\
+        \n    Union\
+        \n      Projection: lineitem.price, Utf8(\"l_description\") AS column_name, lineitem.l_description AS value\
+        \n        Filter: lineitem.l_description IS NOT NULL\
+        \n          TableScan: lineitem\
+        \n      Projection: lineitem.price, Utf8(\"l_item_id\") AS column_name, CAST(lineitem.l_item_id AS Utf8) AS value\
+        \n        Filter: lineitem.l_item_id IS NOT NULL\
+        \n          TableScan: lineitem";
+    quick_test(sql, expected);
+}
+
 #[test]
 fn join_with_aliases() {
     let sql = "select peeps.id, folks.first_name from person as peeps join person as folks on peeps.id = folks.id";
@@ -3419,10 +4082,6 @@ fn test_select_distinct_order_by() {
     "SELECT id, number FROM person LATERAL VIEW explode(numbers) exploded_table AS number",
     "This feature is not implemented: LATERAL VIEWS"
 )]
-#[case::select_qualify_unsupported(
-    "SELECT i, p, o FROM person QUALIFY ROW_NUMBER() OVER (PARTITION BY p ORDER BY o) = 1",
-    "This feature is not implemented: QUALIFY"
-)]
 #[case::select_top_unsupported(
     "SELECT TOP (5) * FROM person",
     "This feature is not implemented: TOP"
@@ -3819,8 +4478,8 @@ fn test_prepare_statement_update_infer() {
 
     let expected_plan = r#"
 Dml: op=[Update] table=[person]
-  Projection: person.id AS id, person.first_name AS first_name, person.last_name AS last_name, $1 AS age, person.state AS state, person.salary AS salary, person.birth_date AS birth_date, person.😀 AS 😀
-    Filter: id = $2
+  Projection: person.id AS id, person.first_name AS first_name, person.last_name AS last_name, $1 AS age, person.state AS state, person.salary AS salary, person.birth_date AS birth_date, person.😀 AS 😀, person.id AS __update_previous_id, person.first_name AS __update_previous_first_name, person.last_name AS __update_previous_last_name, person.age AS __update_previous_age, person.state AS __update_previous_state, person.salary AS __update_previous_salary, person.birth_date AS __update_previous_birth_date, person.😀 AS __update_previous_😀
+    Filter: person.id = $2
       TableScan: person
         "#
         .trim();
@@ -3839,8 +4498,8 @@ Dml: op=[Update] table=[person]
     let param_values = vec![ScalarValue::Int32(Some(42)), ScalarValue::UInt32(Some(1))];
     let expected_plan = r#"
 Dml: op=[Update] table=[person]
-  Projection: person.id AS id, person.first_name AS first_name, person.last_name AS last_name, Int32(42) AS age, person.state AS state, person.salary AS salary, person.birth_date AS birth_date, person.😀 AS 😀
-    Filter: id = UInt32(1)
+  Projection: person.id AS id, person.first_name AS first_name, person.last_name AS last_name, Int32(42) AS age, person.state AS state, person.salary AS salary, person.birth_date AS birth_date, person.😀 AS 😀, person.id AS __update_previous_id, person.first_name AS __update_previous_first_name, person.last_name AS __update_previous_last_name, person.age AS __update_previous_age, person.state AS __update_previous_state, person.salary AS __update_previous_salary, person.birth_date AS __update_previous_birth_date, person.😀 AS __update_previous_😀
+    Filter: person.id = UInt32(1)
       TableScan: person
         "#
         .trim();
@@ -4037,6 +4696,23 @@ fn test_prepare_statement_to_plan_value_list() {
     prepare_stmt_replace_params_quick_test(plan, param_values, expected_plan);
 }
 
+#[test]
+fn test_execute_statement_to_plan() {
+    let sql = "EXECUTE my_plan(1, 'a', 1 + 2)";
+    let expected = "Execute: \"my_plan\" [Int64(1), Utf8(\"a\"), Int64(1) + Int64(2)]";
+    quick_test(sql, expected);
+
+    let sql = "EXECUTE my_plan";
+    let expected = "Execute: \"my_plan\" []";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn test_deallocate_statement_to_plan() {
+    quick_test("DEALLOCATE my_plan", "Deallocate: \"my_plan\"");
+    quick_test("DEALLOCATE PREPARE my_plan", "Deallocate: \"my_plan\"");
+}
+
 #[test]
 fn test_table_alias() {
     let sql = "select * from (\
@@ -52,8 +52,8 @@ use substrait::proto::{
 };
 use substrait::proto::{FunctionArgument, SortField};
 
-use datafusion::logical_expr::expr::{InList, Sort};
-use std::collections::HashMap;
+use datafusion::logical_expr::expr::{GroupingSet, InList, Sort};
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -262,21 +262,24 @@ pub async fn from_substrait_rel(
                 let input = LogicalPlanBuilder::from(
                     from_substrait_rel(ctx, input, extensions).await?,
                 );
-                let mut group_expr = vec![];
                 let mut aggr_expr = vec![];
 
-                let groupings = match agg.groupings.len() {
-                    1 => Ok(&agg.groupings[0]),
-                    _ => Err(DataFusionError::NotImplemented(
-                        "Aggregate with multiple grouping sets is not supported"
-                            .to_string(),
-                    )),
-                };
-
-                for e in &groupings?.grouping_expressions {
-                    let x = from_substrait_rex(e, input.schema(), extensions).await?;
-                    group_expr.push(x.as_ref().clone());
+                let mut grouping_sets = vec![];
+                for grouping in &agg.groupings {
+                    let mut grouping_set = vec![];
+                    for e in &grouping.grouping_expressions {
+                        let x = from_substrait_rex(e, input.schema(), extensions).await?;
+                        grouping_set.push(x.as_ref().clone());
+                    }
+                    grouping_sets.push(grouping_set);
                 }
+                let group_expr = match grouping_sets.len() {
+                    0 => vec![],
+                    1 => grouping_sets.pop().unwrap(),
+                    _ => vec![Expr::GroupingSet(from_substrait_grouping_sets(
+                        grouping_sets,
+                    ))],
+                };
 
                 for m in &agg.measures {
                     let filter = match &m.filter {
@@ -635,6 +638,38 @@ pub async fn from_substriat_func_args(
     Ok(args)
 }
 
+/// Convert the grouping sets of a Substrait aggregate to a [`GroupingSet`]
+///
+/// Grouping sets that start with all grouping expressions followed by every
+/// prefix of them are a `ROLLUP`, and grouping sets made of every subset of
+/// the grouping expressions are a `CUBE`.
+fn from_substrait_grouping_sets(grouping_sets: Vec<Vec<Expr>>) -> GroupingSet {
+    let all = &grouping_sets[0];
+
+    let is_rollup = grouping_sets.len() == all.len() + 1
+        && grouping_sets
+            .iter()
+            .enumerate()
+            .all(|(i, set)| set[..] == all[..all.len() - i]);
+    if is_rollup {
+        return GroupingSet::Rollup(all.clone());
+    }
+
+    let is_subset = |set: &Vec<Expr>| {
+        let mut exprs = all.iter();
+        set.iter().all(|e| exprs.any(|x| x == e))
+    };
+    let distinct_sets: HashSet<&Vec<Expr>> = grouping_sets.iter().collect();
+    let is_cube = 1usize.checked_shl(all.len() as u32) == Some(grouping_sets.len())
+        && distinct_sets.len() == grouping_sets.len()
+        && grouping_sets.iter().all(is_subset);
+    if is_cube {
+        return GroupingSet::Cube(all.clone());
+    }
+
+    GroupingSet::GroupingSets(grouping_sets)
+}
+
 /// Convert Substrait AggregateFunction to DataFusion Expr
 pub async fn from_substrait_agg_func(
     f: &AggregateFunction,
@@ -933,6 +968,7 @@ pub async fn from_substrait_rex(
                     start_bound: from_substrait_bound(&window.lower_bound, true)?,
                     end_bound: from_substrait_bound(&window.upper_bound, false)?,
                 },
+                ignore_nulls: false,
             })))
         }
         _ => Err(DataFusionError::NotImplemented(
@@ -32,8 +32,8 @@ use datafusion::common::DFSchemaRef;
 #[allow(unused_imports)]
 use datafusion::logical_expr::aggregate_function;
 use datafusion::logical_expr::expr::{
-    Alias, BinaryExpr, Case, Cast, InList, ScalarFunction as DFScalarFunction, Sort,
-    WindowFunction,
+    Alias, BinaryExpr, Case, Cast, GroupingSet, InList,
+    ScalarFunction as DFScalarFunction, Sort, WindowFunction,
 };
 use datafusion::logical_expr::{expr, Between, JoinConstraint, LogicalPlan, Operator};
 use datafusion::prelude::Expr;
@@ -219,12 +219,11 @@ pub fn to_substrait_rel(
         }
         LogicalPlan::Aggregate(agg) => {
             let input = to_substrait_rel(agg.input.as_ref(), ctx, extension_info)?;
-            // Translate aggregate expression to Substrait's groupings (repeated repeated Expression)
-            let grouping = agg
-                .group_expr
-                .iter()
-                .map(|e| to_substrait_rex(e, agg.input.schema(), 0, extension_info))
-                .collect::<Result<Vec<_>>>()?;
+            let groupings = to_substrait_groupings(
+                &agg.group_expr,
+                agg.input.schema(),
+                extension_info,
+            )?;
             let measures = agg
                 .aggr_expr
                 .iter()
@@ -235,9 +234,7 @@ pub fn to_substrait_rel(
                 rel_type: Some(RelType::Aggregate(Box::new(AggregateRel {
                     common: None,
                     input: Some(input),
-                    groupings: vec![Grouping {
-                        grouping_expressions: grouping,
-                    }], //groupings,
+                    groupings,
                     measures,
                     advanced_extension: None,
                 }))),
@@ -264,6 +261,11 @@ pub fn to_substrait_rel(
             }))
         }
         LogicalPlan::Join(join) => {
+            if join.join_type == JoinType::AsOf {
+                return Err(DataFusionError::NotImplemented(
+                    "ASOF joins are not supported in Substrait".to_string(),
+                ));
+            }
             let left = to_substrait_rel(join.left.as_ref(), ctx, extension_info)?;
             let right = to_substrait_rel(join.right.as_ref(), ctx, extension_info)?;
             let join_type = to_substrait_jointype(join.join_type);
@@ -440,6 +442,8 @@ fn to_substrait_jointype(join_type: JoinType) -> join_rel::JoinType {
         JoinType::LeftAnti => join_rel::JoinType::Anti,
         JoinType::LeftSemi => join_rel::JoinType::Semi,
         JoinType::RightAnti | JoinType::RightSemi => unimplemented!(),
+        // rejected by `to_substrait_rel`
+        JoinType::AsOf => unreachable!(),
     }
 }
 
@@ -473,6 +477,57 @@ pub fn operator_to_name(op: Operator) -> &'static str {
     }
 }
 
+/// Translate group by expressions to Substrait's groupings (repeated repeated Expression),
+/// with one grouping for every grouping set of `GROUPING SETS`, `ROLLUP` or `CUBE`
+fn to_substrait_groupings(
+    exprs: &[Expr],
+    schema: &DFSchemaRef,
+    extension_info: &mut (
+        Vec<extensions::SimpleExtensionDeclaration>,
+        HashMap<String, u32>,
+    ),
+) -> Result<Vec<Grouping>> {
+    let grouping_sets = match exprs {
+        [Expr::GroupingSet(GroupingSet::GroupingSets(sets))] => sets.clone(),
+        // (a, b), (a), ()
+        [Expr::GroupingSet(GroupingSet::Rollup(exprs))] => (0..=exprs.len())
+            .rev()
+            .map(|n| exprs[..n].to_vec())
+            .collect(),
+        // every subset of the expressions, starting with all of them
+        [Expr::GroupingSet(GroupingSet::Cube(exprs))] => (0..1usize << exprs.len())
+            .rev()
+            .map(|mask| {
+                exprs
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| mask & (1 << i) != 0)
+                    .map(|(_, e)| e.clone())
+                    .collect()
+            })
+            .collect(),
+        _ if exprs.iter().any(|e| matches!(e, Expr::GroupingSet(_))) => {
+            return Err(DataFusionError::NotImplemented(
+                "Aggregate with grouping sets and other group by expressions is not supported"
+                    .to_string(),
+            ))
+        }
+        _ => vec![exprs.to_vec()],
+    };
+
+    grouping_sets
+        .iter()
+        .map(|set| {
+            Ok(Grouping {
+                grouping_expressions: set
+                    .iter()
+                    .map(|e| to_substrait_rex(e, schema, 0, extension_info))
+                    .collect::<Result<Vec<_>>>()?,
+            })
+        })
+        .collect()
+}
+
 #[allow(deprecated)]
 pub fn to_substrait_agg_measure(
     expr: &Expr,
@@ -878,7 +933,13 @@ pub fn to_substrait_rex(
             partition_by,
             order_by,
             window_frame,
+            ignore_nulls,
         }) => {
+            if *ignore_nulls {
+                return Err(DataFusionError::NotImplemented(
+                    "IGNORE NULLS is not supported in Substrait plans".to_string(),
+                ));
+            }
             // function reference
             let function_name = fun.to_string().to_lowercase();
             let function_anchor = _register_function(function_name, extension_info);
@@ -209,6 +209,32 @@ async fn aggregate_multiple_keys() -> Result<()> {
     roundtrip("SELECT a, c, avg(b) FROM data GROUP BY a, c").await
 }
 
+#[tokio::test]
+async fn aggregate_grouping_sets() -> Result<()> {
+    roundtrip(
+        "SELECT a, c, d, avg(b) FROM data GROUP BY GROUPING SETS ((a, c), (c), (d), ())",
+    )
+    .await
+}
+
+#[tokio::test]
+async fn aggregate_rollup() -> Result<()> {
+    roundtrip("SELECT a, c, e, avg(b) FROM data GROUP BY ROLLUP (a, c, e)").await
+}
+
+#[tokio::test]
+async fn aggregate_cube() -> Result<()> {
+    roundtrip("SELECT a, c, e, count(b) FROM data GROUP BY CUBE (a, c, e)").await
+}
+
+#[tokio::test]
+async fn aggregate_grouping_function() -> Result<()> {
+    roundtrip(
+        "SELECT a, c, grouping(a), grouping(c), sum(e) FROM data GROUP BY ROLLUP (a, c)",
+    )
+    .await
+}
+
 #[tokio::test]
 async fn decimal_literal() -> Result<()> {
     roundtrip("SELECT * FROM data WHERE b > 2.5").await
@@ -410,6 +436,23 @@ async fn roundtrip_outer_join() -> Result<()> {
     roundtrip("SELECT data.a FROM data FULL OUTER JOIN data2 ON data.a = data2.a").await
 }
 
+#[tokio::test]
+async fn asof_join_is_not_supported() -> Result<()> {
+    let ctx = create_context().await?;
+    let df = ctx
+        .sql(
+            "SELECT data.a FROM data ASOF JOIN data2 MATCH_CONDITION (data.a >= data2.a)",
+        )
+        .await?;
+    let plan = df.into_optimized_plan()?;
+    let err = to_substrait_plan(&plan, &ctx).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "This feature is not implemented: ASOF joins are not supported in Substrait"
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn roundtrip_like() -> Result<()> {
     roundtrip("SELECT f FROM data WHERE f LIKE 'a%b'").await